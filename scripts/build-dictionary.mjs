@@ -0,0 +1,146 @@
+#!/usr/bin/env node
+/**
+ * Build the reader's dictionary asset from a jmdict-simplified release.
+ *
+ * Runs before every `npm run build` (the `prebuild` script), so deployed builds
+ * always ship static/dictionary/jmdict-eng.json. The release it is built from
+ * is pinned in scripts/jmdict-release.json (tag, asset URL and SHA-256); the
+ * download is checked against that hash and skipped when the output is already
+ * built from it.
+ *
+ *   node scripts/build-dictionary.mjs            build from the pinned release
+ *   node scripts/build-dictionary.mjs --update   pin the latest release, then build
+ *   node scripts/build-dictionary.mjs file.json  build from a local jmdict-eng-*.json
+ *
+ * Builds never pick a release on their own: without a committed
+ * scripts/jmdict-release.json they fail, and only --update moves the pin.
+ *
+ * Writes the packed format read by src/lib/dictionary/db.ts:
+ * [kanji[], readings[], [pos[], glosses[]][]], plus version.json, which the
+ * app checks to replace a stored copy of an older release.
+ */
+
+import { createHash } from 'node:crypto';
+import { existsSync, mkdirSync, readFileSync, writeFileSync } from 'node:fs';
+import { dirname, join } from 'node:path';
+import { fileURLToPath } from 'node:url';
+import { gunzipSync } from 'node:zlib';
+
+const ROOT = join(dirname(fileURLToPath(import.meta.url)), '..');
+const OUTPUT_DIR = join(ROOT, 'static', 'dictionary');
+const OUTPUT = join(OUTPUT_DIR, 'jmdict-eng.json');
+const OUTPUT_VERSION = join(OUTPUT_DIR, 'version.json');
+/** SHA-256 of the release asset the current output was built from */
+const OUTPUT_SOURCE = join(OUTPUT_DIR, 'SOURCE');
+const LOCK = join(ROOT, 'scripts', 'jmdict-release.json');
+const LATEST_RELEASE = 'https://api.github.com/repos/scriptin/jmdict-simplified/releases/latest';
+/** The full English dictionary, not the "common" subset */
+const ASSET_PATTERN = /^jmdict-eng-\d[^/]*\.json\.tgz$/;
+
+function pack(source) {
+  return source.words.map((word) => [
+    word.kanji.map((k) => k.text),
+    word.kana.map((k) => k.text),
+    word.sense.map((sense) => [
+      sense.partOfSpeech,
+      sense.gloss.filter((g) => !g.lang || g.lang === 'eng').map((g) => g.text)
+    ])
+  ]);
+}
+
+function write(source, version, sourceHash) {
+  const entries = pack(source);
+  mkdirSync(OUTPUT_DIR, { recursive: true });
+  writeFileSync(OUTPUT, JSON.stringify({ version, entries }));
+  // Checked by the app on each load so a new pin replaces the stored copy
+  writeFileSync(OUTPUT_VERSION, JSON.stringify({ version }));
+  writeFileSync(OUTPUT_SOURCE, sourceHash ?? '');
+  console.log(`Wrote ${entries.length} entries to ${OUTPUT}`);
+}
+
+/** The first .json file in a tar archive */
+function extractJson(tar) {
+  for (let offset = 0; offset + 512 <= tar.length; ) {
+    const header = tar.subarray(offset, offset + 512);
+    const field = (start, end) => header.toString('utf8', start, end).replace(/\0.*$/s, '');
+    const name = field(0, 100);
+    if (!name) break;
+    const size = parseInt(field(124, 136).trim(), 8) || 0;
+    const start = offset + 512;
+    if (name.endsWith('.json')) return tar.toString('utf8', start, start + size);
+    offset = start + Math.ceil(size / 512) * 512;
+  }
+  throw new Error('No .json file found in the dictionary archive');
+}
+
+async function download(url) {
+  const response = await fetch(url, { headers: { 'User-Agent': 'mokuro-reader-build' } });
+  if (!response.ok) throw new Error(`GET ${url} failed: HTTP ${response.status}`);
+  return response;
+}
+
+async function pinLatestRelease() {
+  const release = await (await download(LATEST_RELEASE)).json();
+  const asset = release.assets.find((a) => ASSET_PATTERN.test(a.name));
+  if (!asset) throw new Error(`Release ${release.tag_name} has no jmdict-eng .json.tgz asset`);
+
+  const archive = Buffer.from(await (await download(asset.browser_download_url)).arrayBuffer());
+  const lock = {
+    tag: release.tag_name,
+    url: asset.browser_download_url,
+    sha256: createHash('sha256').update(archive).digest('hex')
+  };
+  writeFileSync(LOCK, JSON.stringify(lock, null, 2) + '\n');
+  console.log(`Pinned jmdict-simplified ${lock.tag} in ${LOCK}`);
+  return { lock, archive };
+}
+
+function readLock() {
+  if (!existsSync(LOCK)) {
+    throw new Error(
+      `${LOCK} is missing; run \`node scripts/build-dictionary.mjs --update\` and commit it`
+    );
+  }
+  return JSON.parse(readFileSync(LOCK, 'utf8'));
+}
+
+async function buildFromRelease(update) {
+  let lock;
+  let archive;
+
+  if (update) {
+    ({ lock, archive } = await pinLatestRelease());
+  } else {
+    lock = readLock();
+    if (
+      existsSync(OUTPUT) &&
+      existsSync(OUTPUT_VERSION) &&
+      existsSync(OUTPUT_SOURCE) &&
+      readFileSync(OUTPUT_SOURCE, 'utf8') === lock.sha256
+    ) {
+      console.log(`Dictionary is up to date (jmdict-simplified ${lock.tag})`);
+      return;
+    }
+    archive = Buffer.from(await (await download(lock.url)).arrayBuffer());
+    const hash = createHash('sha256').update(archive).digest('hex');
+    if (hash !== lock.sha256) {
+      throw new Error(`${lock.url} does not match the pinned SHA-256 (got ${hash})`);
+    }
+  }
+
+  // The release tag changes with every release, unlike the data's own version field
+  write(JSON.parse(extractJson(gunzipSync(archive))), lock.tag, lock.sha256);
+}
+
+const arg = process.argv[2];
+try {
+  if (arg && arg !== '--update') {
+    const source = JSON.parse(readFileSync(arg, 'utf8'));
+    write(source, `${source.version ?? 'unknown'}+${source.dictDate ?? 'local'}`);
+  } else {
+    await buildFromRelease(arg === '--update');
+  }
+} catch (error) {
+  console.error(`Dictionary build failed: ${error.message}`);
+  process.exit(1);
+}
@@ -15,16 +15,25 @@ function normalizeSeriesTitle(title: string): string {
   return title.trim().replace(/\s+/g, ' ').toLowerCase();
 }
 
+/**
+ * Title a volume is grouped under in the catalog: the manual override when set,
+ * otherwise its own series title.
+ */
+export function getSeriesGroupTitle(volume: VolumeMetadata): string {
+  return volume.series_override?.trim() || volume.series_title;
+}
+
 export function deriveSeriesFromVolumes(volumeEntries: Array<VolumeMetadata>) {
   // Group volumes by normalized series title (user-visible identity)
   const titleMap = new Map<string, Series>();
 
   for (const entry of volumeEntries) {
-    const key = normalizeSeriesTitle(entry.series_title);
+    const groupTitle = getSeriesGroupTitle(entry);
+    const key = normalizeSeriesTitle(groupTitle);
     let volumes = titleMap.get(key);
     if (volumes === undefined) {
       volumes = {
-        title: entry.series_title,
+        title: groupTitle,
         series_uuid: entry.series_uuid,
         volumes: []
       };
@@ -54,7 +54,7 @@ function buildPageCharCounts(pages: unknown[]): { totalChars: number; cumulative
   return { totalChars, cumulative };
 }
 
-async function decodeMokuroSidecar(sidecarPath: string, blob: Blob): Promise<File | null> {
+export async function decodeMokuroSidecar(sidecarPath: string, blob: Blob): Promise<File | null> {
   if (sidecarPath.toLowerCase().endsWith('.mokuro')) {
     console.log('[Cloud OCR Upgrade] Decoding plain mokuro sidecar:', sidecarPath, blob.size);
     return new File([blob], sidecarPath.split('/').pop() || sidecarPath, {
@@ -1,5 +1,7 @@
 import type { VolumeMetadata } from '$lib/types';
+import { get } from 'svelte/store';
 import { unifiedCloudManager } from '$lib/util/sync/unified-cloud-manager';
+import { isMetered } from '$lib/util/metered';
 
 export interface CloudThumbnailResult {
   file: File;
@@ -84,6 +86,8 @@ export async function fetchCloudThumbnail(
 ): Promise<CloudThumbnailResult | null> {
   if (!volume.cloudThumbnailFileId) return null;
   if (!volume.cloudProvider) return null;
+  // Covers are images; on mobile data placeholders keep the generic cover
+  if (get(isMetered)) return null;
 
   const activeProvider = unifiedCloudManager.getActiveProvider();
   if (!activeProvider || activeProvider.type !== volume.cloudProvider) {
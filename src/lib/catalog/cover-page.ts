@@ -0,0 +1,132 @@
+/**
+ * Which page image a volume's generated thumbnail is made from.
+ *
+ * Shared by import, "reset cover" and background thumbnail (re)generation so
+ * they all pick the same page.
+ */
+
+import type { VolumeMetadata } from '$lib/types';
+import { isImageExtension } from '$lib/import/types';
+import { naturalSort } from '$lib/util/natural-sort';
+import { normalizeFilename } from '$lib/util/misc';
+
+/**
+ * Version of generated thumbnails. Bump it when the cover choice below or
+ * generateThumbnail's output changes: volumes stamped with an older version
+ * get their thumbnails rebuilt in the background.
+ */
+export const THUMBNAIL_VERSION = 1;
+
+/**
+ * Whether a volume's thumbnail should be (re)generated: it's missing, or it
+ * was generated by an older version (thumbnails from before versioning count
+ * as version 0). Custom and sidecar covers are never replaced.
+ */
+export function needsThumbnail(volume: VolumeMetadata): boolean {
+  if (volume.isPlaceholder) return false;
+  if (!volume.thumbnail || !volume.thumbnail_width || !volume.thumbnail_height) return true;
+  if (volume.thumbnail_custom) return false;
+  return (volume.thumbnail_version ?? 0) < THUMBNAIL_VERSION;
+}
+
+function isLikelyGeneratedMissingPlaceholder(path: string, file: File): boolean {
+  if (!file?.type || file.type.toLowerCase() !== 'image/png') {
+    return false;
+  }
+
+  // Our generated missing placeholders can keep original filename extensions (e.g. .jpg)
+  // while always being PNG blobs. Use this mismatch as a conservative legacy-data filter.
+  const lowerPath = path.toLowerCase();
+  return (
+    lowerPath.endsWith('.jpg') ||
+    lowerPath.endsWith('.jpeg') ||
+    lowerPath.endsWith('.webp') ||
+    lowerPath.endsWith('.avif') ||
+    lowerPath.endsWith('.gif') ||
+    lowerPath.endsWith('.bmp') ||
+    lowerPath.endsWith('.tif') ||
+    lowerPath.endsWith('.tiff')
+  );
+}
+
+function isCoverCandidateImage(path: string, file: File): boolean {
+  if (file.type?.toLowerCase().startsWith('image/')) {
+    return true;
+  }
+  const ext = path.split('.').pop()?.toLowerCase() || '';
+  return isImageExtension(ext);
+}
+
+function basename(path: string): string {
+  return path.split('/').pop() || path;
+}
+
+/** Sort by position in the OCR page list, then by natural filename order */
+export function pageOrderComparator(
+  pages: { img_path: string }[]
+): (a: string, b: string) => number {
+  const pageIndexByPath = new Map<string, number>();
+  const pageIndexByBasename = new Map<string, number>();
+
+  pages.forEach((page, index) => {
+    const normalizedPath = normalizeFilename(page.img_path).toLowerCase();
+    const normalizedBase = basename(normalizedPath);
+
+    if (!pageIndexByPath.has(normalizedPath)) {
+      pageIndexByPath.set(normalizedPath, index);
+    }
+    if (!pageIndexByBasename.has(normalizedBase)) {
+      pageIndexByBasename.set(normalizedBase, index);
+    }
+  });
+
+  return (a: string, b: string) => {
+    const normalizedA = normalizeFilename(a).toLowerCase();
+    const normalizedB = normalizeFilename(b).toLowerCase();
+
+    const aIndex =
+      pageIndexByPath.get(normalizedA) ??
+      pageIndexByBasename.get(basename(normalizedA)) ??
+      Infinity;
+    const bIndex =
+      pageIndexByPath.get(normalizedB) ??
+      pageIndexByBasename.get(basename(normalizedB)) ??
+      Infinity;
+
+    if (aIndex !== bIndex) {
+      return aIndex - bIndex;
+    }
+
+    return naturalSort(a, b);
+  };
+}
+
+/**
+ * Image paths that can serve as a cover, in page order. Placeholders generated
+ * for missing pages are left out.
+ */
+export function coverCandidates(
+  files: Record<string, File>,
+  pages: { img_path: string }[],
+  placeholderPaths: string[] = []
+): string[] {
+  const placeholders = new Set(placeholderPaths);
+  return Object.keys(files)
+    .filter((path) => {
+      if (placeholders.has(path)) return false;
+      const file = files[path];
+      if (!isCoverCandidateImage(path, file)) return false;
+      return !isLikelyGeneratedMissingPlaceholder(path, file);
+    })
+    .sort(pageOrderComparator(pages));
+}
+
+/** An image named like a cover, otherwise the first page */
+export function pickCoverPath(
+  files: Record<string, File>,
+  pages: { img_path: string }[],
+  placeholderPaths: string[] = []
+): string | null {
+  const candidates = coverCandidates(files, pages, placeholderPaths);
+  return candidates.find((path) => path.toLowerCase().includes('cover')) ?? candidates[0] ?? null;
+}
@@ -3,13 +3,16 @@ import Dexie, { type Table } from 'dexie';
 import { generateThumbnail } from '$lib/catalog/thumbnails';
 import { browser } from '$app/environment';
 import { progressTrackerStore } from '$lib/util/progress-tracker';
-import { naturalSort } from '$lib/util/natural-sort';
+import { THUMBNAIL_VERSION, needsThumbnail, pickCoverPath } from './cover-page';
 
 export class CatalogDexieV3 extends Dexie {
   volumes!: Table<VolumeMetadata>;
   volume_ocr!: Table<VolumeOCR>;
   volume_files!: Table<VolumeFiles>;
 
+  private thumbnailRun: Promise<void> | null = null;
+  private thumbnailRerun = false;
+
   constructor(dbName: string = 'mokuro_v3') {
     super(dbName);
 
@@ -19,16 +22,55 @@ export class CatalogDexieV3 extends Dexie {
       volume_ocr: 'volume_uuid',
       volume_files: 'volume_uuid'
     });
+    // Indexed for the same-source check on import
+    this.version(2).stores({
+      volumes: 'volume_uuid, series_uuid, series_title, original_filename'
+    });
   }
 
-  async processThumbnails(batchSize: number = 5): Promise<void> {
+  /**
+   * Generate missing thumbnails and rebuild ones stamped with an older
+   * THUMBNAIL_VERSION. Each volume is stamped as it completes, so an
+   * interrupted run resumes where it stopped the next time this is called.
+   * Calls made while a run is in progress queue one more pass.
+   */
+  processThumbnails(batchSize: number = 5): Promise<void> {
+    if (this.thumbnailRun) {
+      this.thumbnailRerun = true;
+      return this.thumbnailRun;
+    }
+    this.thumbnailRun = (async () => {
+      try {
+        do {
+          this.thumbnailRerun = false;
+          await this.runThumbnailPass(batchSize);
+        } while (this.thumbnailRerun);
+      } finally {
+        this.thumbnailRun = null;
+      }
+    })();
+    return this.thumbnailRun;
+  }
+
+  /**
+   * Mark every generated thumbnail as out of date and rebuild them in the
+   * background. Custom and sidecar covers (both flagged thumbnail_custom) are
+   * kept. Returns how many were marked.
+   */
+  async invalidateThumbnails(): Promise<number> {
+    const marked = await this.volumes
+      .filter((vol) => !vol.isPlaceholder && !vol.thumbnail_custom)
+      .modify({ thumbnail_version: 0 });
+    this.processThumbnails().catch((error) => {
+      console.error('Error in thumbnail processing:', error);
+    });
+    return marked;
+  }
+
+  private async runThumbnailPass(batchSize: number): Promise<void> {
     const processId = 'thumbnail-generation';
 
-    // Get volumes that need thumbnail generation/regeneration
-    // Missing any of thumbnail, width, or height indicates need for (re)generation
-    const volumesNeedingThumbnails = await this.volumes
-      .filter((vol) => !vol.thumbnail || !vol.thumbnail_width || !vol.thumbnail_height)
-      .primaryKeys();
+    const volumesNeedingThumbnails = await this.volumes.filter(needsThumbnail).primaryKeys();
 
     if (volumesNeedingThumbnails.length === 0) return;
 
@@ -51,22 +93,33 @@ export class CatalogDexieV3 extends Dexie {
         await Promise.all(
           batch.map(async (volumeUuid) => {
             try {
+              const volume = await this.volumes.get(volumeUuid as string);
+              // Re-check: the volume may have been deleted or given a custom cover meanwhile
+              if (!volume || !needsThumbnail(volume)) return;
+
               const files = await this.volume_files.get(volumeUuid as string);
-              if (files && files.files) {
-                // Get the first image file when sorted naturally
-                const fileNames = Object.keys(files.files).sort(naturalSort);
-                const firstImageFile = fileNames.length > 0 ? files.files[fileNames[0]] : null;
-
-                if (firstImageFile) {
-                  const thumbnailResult = await generateThumbnail(firstImageFile);
-                  // Store thumbnail and dimensions directly in volumes table
-                  await this.volumes.update(volumeUuid as string, {
-                    thumbnail: thumbnailResult.file,
-                    thumbnail_width: thumbnailResult.width,
-                    thumbnail_height: thumbnailResult.height
-                  });
-                }
-              }
+              if (!files?.files) return;
+
+              const ocr = await this.volume_ocr.get(volumeUuid as string);
+              const coverPath = pickCoverPath(
+                files.files,
+                ocr?.pages || [],
+                volume.missing_page_paths
+              );
+              if (!coverPath) return;
+
+              const thumbnailResult = await generateThumbnail(files.files[coverPath]);
+              // Replacing an existing thumbnail: drop its decoded bitmap as soon as
+              // the row changes, before the catalog redraws from the old one
+              const cache = volume.thumbnail ? await loadThumbnailCache() : null;
+              // Store thumbnail and dimensions directly in volumes table
+              await this.volumes.update(volumeUuid as string, {
+                thumbnail: thumbnailResult.file,
+                thumbnail_width: thumbnailResult.width,
+                thumbnail_height: thumbnailResult.height,
+                thumbnail_version: THUMBNAIL_VERSION
+              });
+              cache?.invalidate(volumeUuid as string);
             } catch (error) {
               console.error('Failed to generate thumbnail for volume:', volumeUuid, error);
             }
@@ -88,6 +141,12 @@ export class CatalogDexieV3 extends Dexie {
   }
 }
 
+// Imported lazily: the cache starts decode workers when loaded
+async function loadThumbnailCache() {
+  const { thumbnailCache } = await import('./thumbnail-cache');
+  return thumbnailCache;
+}
+
 // Singleton instance - will be initialized after migration check
 let dbInstance: CatalogDexieV3 | null = null;
 
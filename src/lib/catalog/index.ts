@@ -7,6 +7,8 @@ import { unifiedCloudManager } from '$lib/util/sync/unified-cloud-manager';
 import { generatePlaceholders } from '$lib/catalog/placeholders';
 import { routeParams } from '$lib/util/hash-router';
 import { getLegacyImageOnlyVolumeUuid } from '$lib/util/download-volume-repair';
+import { volumes as volumeDataStore } from '$lib/settings/volume-data';
+import { withoutSkippedPages } from '$lib/import/duplicate-pages';
 
 async function loadCurrentVolumeData(volume: VolumeMetadata): Promise<VolumeData | undefined> {
   let [ocr, files] = await Promise.all([
@@ -49,7 +51,7 @@ async function loadCurrentVolumeData(volume: VolumeMetadata): Promise<VolumeData
 
   return {
     volume_uuid: volume.volume_uuid,
-    pages: ocr.pages,
+    pages: withoutSkippedPages(ocr.pages, volume.skipped_page_paths),
     files: files?.files
   };
 }
@@ -76,8 +78,8 @@ export const volumes = readable<Record<string, VolumeMetadata>>({}, (set) => {
 
 // Merge local volumes with cloud placeholders
 export const volumesWithPlaceholders = derived(
-  [volumes, unifiedCloudManager.cloudFiles],
-  ([$volumes, $cloudFiles]) => {
+  [volumes, unifiedCloudManager.cloudFiles, volumeDataStore],
+  ([$volumes, $cloudFiles, $volumeData]) => {
     const combined = { ...$volumes };
     const localVolumes = Object.values($volumes);
 
@@ -85,7 +87,17 @@ export const volumesWithPlaceholders = derived(
     if ($cloudFiles.size > 0) {
       const cloudPlaceholders = generatePlaceholders($cloudFiles, localVolumes);
       for (const placeholder of cloudPlaceholders) {
-        combined[placeholder.volume_uuid] = placeholder;
+        // Placeholders have no catalog row, so take manual grouping, order and
+        // series cover from synced volume data
+        const data = $volumeData[placeholder.volume_uuid];
+        combined[placeholder.volume_uuid] = data
+          ? {
+              ...placeholder,
+              series_override: data.series_override,
+              sort_index: data.sort_index,
+              series_cover: data.series_cover
+            }
+          : placeholder;
       }
     }
 
@@ -0,0 +1,37 @@
+import type { VolumeMetadata } from '$lib/types';
+import { countMokuroPages, type QuickScan } from '$lib/import/archive-extraction';
+import { getCloudProvider, getCloudSize } from '$lib/util/cloud-fields';
+import { unifiedCloudManager } from '$lib/util/sync/unified-cloud-manager';
+import { decodeMokuroSidecar } from './cloud-ocr-upgrade';
+
+/**
+ * Quick scan of a cloud placeholder before downloading it: the stored file
+ * size plus the page count from its .mokuro sidecar. Placeholders without a
+ * sidecar report no page count (0). Returns null when the size is unknown.
+ */
+export async function scanPlaceholderVolume(volume: VolumeMetadata): Promise<QuickScan | null> {
+  const size = getCloudSize(volume);
+  if (!size) return null;
+
+  const provider = getCloudProvider(volume);
+  let pageCount = 0;
+  if (volume.cloudMokuroFileId && volume.cloudMokuroPath && provider) {
+    try {
+      const blob = await unifiedCloudManager.downloadFile({
+        provider,
+        fileId: volume.cloudMokuroFileId,
+        path: volume.cloudMokuroPath,
+        modifiedTime: '',
+        size: 0
+      });
+      const mokuroFile = await decodeMokuroSidecar(volume.cloudMokuroPath, blob);
+      pageCount = (mokuroFile && countMokuroPages(await mokuroFile.text())) || 0;
+    } catch (error) {
+      console.warn('Failed to read .mokuro sidecar for quick scan:', error);
+    }
+  }
+
+  // Archives of already-compressed images barely shrink, so the stored size
+  // is a close estimate of the extracted size
+  return { pageCount, decompressedSize: size };
+}
@@ -185,6 +185,11 @@ export function generatePlaceholders(
         placeholder.cloudThumbnailFileId = thumbnailInfo.fileId;
         placeholder.cloudThumbnailPath = thumbnailInfo.path;
       }
+      const mokuroInfo = mokuroMap.get(basePath);
+      if (mokuroInfo) {
+        placeholder.cloudMokuroFileId = mokuroInfo.fileId;
+        placeholder.cloudMokuroPath = mokuroInfo.path;
+      }
       placeholders.push(placeholder);
     }
   }
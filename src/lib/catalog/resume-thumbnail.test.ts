@@ -0,0 +1,81 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest';
+import type { VolumeMetadata } from '$lib/types';
+
+const volumesById = new Map<string, Partial<VolumeMetadata>>();
+const pages = Array.from({ length: 60 }, (_, i) => ({
+  img_path: `${String(i + 1).padStart(3, '0')}.jpg`,
+  img_width: 800,
+  img_height: 1200,
+  blocks: []
+}));
+const files = Object.fromEntries(pages.map((page) => [page.img_path, new File([], page.img_path)]));
+
+vi.mock('./db', () => ({
+  db: {
+    volumes: { get: vi.fn((uuid: string) => Promise.resolve(volumesById.get(uuid))) },
+    volume_ocr: {
+      get: vi.fn((uuid: string) =>
+        Promise.resolve(volumesById.has(uuid) ? { volume_uuid: uuid, pages } : undefined)
+      )
+    },
+    volume_files: {
+      get: vi.fn((uuid: string) =>
+        Promise.resolve(
+          volumesById.get(uuid)?.isPlaceholder ? undefined : { volume_uuid: uuid, files }
+        )
+      )
+    }
+  }
+}));
+
+import {
+  hasPartialProgress,
+  loadResumePageFile,
+  resumePreviewPage,
+  MIN_RESUME_PAGE_CHANGE
+} from './resume-thumbnail';
+
+describe('loadResumePageFile', () => {
+  beforeEach(() => {
+    volumesById.clear();
+  });
+
+  it('returns the image for the last-read page', async () => {
+    volumesById.set('vol', { volume_uuid: 'vol' });
+
+    const file = await loadResumePageFile('vol', 40);
+
+    expect(file?.name).toBe('040.jpg');
+  });
+
+  it('returns nothing for cloud placeholders', async () => {
+    volumesById.set('placeholder', { volume_uuid: 'placeholder', isPlaceholder: true });
+
+    expect(await loadResumePageFile('placeholder', 40)).toBeUndefined();
+  });
+
+  it('returns nothing for a missing-page placeholder', async () => {
+    volumesById.set('vol', { volume_uuid: 'vol', missing_page_paths: ['040.jpg'] });
+
+    expect(await loadResumePageFile('vol', 40)).toBeUndefined();
+  });
+});
+
+describe('resumePreviewPage', () => {
+  it('follows progress once it moves far enough', () => {
+    expect(resumePreviewPage('moving', 40)).toBe(40);
+    expect(resumePreviewPage('moving', 40 + MIN_RESUME_PAGE_CHANGE - 1)).toBe(40);
+    expect(resumePreviewPage('moving', 40 + MIN_RESUME_PAGE_CHANGE)).toBe(
+      40 + MIN_RESUME_PAGE_CHANGE
+    );
+  });
+});
+
+describe('hasPartialProgress', () => {
+  it('is false for unstarted and finished volumes', () => {
+    expect(hasPartialProgress(1, 200)).toBe(false);
+    expect(hasPartialProgress(40, 200)).toBe(true);
+    expect(hasPartialProgress(199, 200)).toBe(false);
+    expect(hasPartialProgress(200, 200)).toBe(false);
+  });
+});
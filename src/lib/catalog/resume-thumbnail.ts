@@ -0,0 +1,59 @@
+import { db } from './db';
+import { matchFilesToPages } from '$lib/reader/image-cache';
+import { withoutSkippedPages } from '$lib/import/duplicate-pages';
+
+/**
+ * "Resume" previews of the last-read page on partially read volume tiles.
+ *
+ * Previews decode through the shared thumbnail cache under page preview keys
+ * (see `pagePreviewKey`) like the reader's page strip, so a page seen at the
+ * same size in either place is decoded once.
+ */
+
+/** Pages the reader must move before a tile swaps to a newer preview */
+export const MIN_RESUME_PAGE_CHANGE = 3;
+
+// The page each tile currently previews, for this session
+const shownPages = new Map<string, number>();
+
+/**
+ * The 1-based page to preview for a volume read up to `currentPage`.
+ * Small moves keep the previous preview so tiles don't re-decode a new page
+ * after every page turn.
+ */
+export function resumePreviewPage(volumeUuid: string, currentPage: number): number {
+  const shown = shownPages.get(volumeUuid);
+  if (shown !== undefined && Math.abs(currentPage - shown) < MIN_RESUME_PAGE_CHANGE) {
+    return shown;
+  }
+  shownPages.set(volumeUuid, currentPage);
+  return currentPage;
+}
+
+/** Whether a volume has been started but not finished */
+export function hasPartialProgress(currentPage: number, pageCount: number): boolean {
+  return currentPage > 1 && currentPage < pageCount - 1;
+}
+
+/**
+ * The stored image for a 1-based page, or undefined if the volume has no
+ * local files (cloud placeholders) or the page is a missing-page placeholder.
+ */
+export async function loadResumePageFile(
+  volumeUuid: string,
+  page: number
+): Promise<File | undefined> {
+  const [volume, ocr, files] = await Promise.all([
+    db.volumes.get(volumeUuid),
+    db.volume_ocr.get(volumeUuid),
+    db.volume_files.get(volumeUuid)
+  ]);
+  if (!volume || volume.isPlaceholder || !ocr || !files) return undefined;
+
+  const pages = withoutSkippedPages(ocr.pages, volume.skipped_page_paths);
+  const index = page - 1;
+  const target = pages[index];
+  if (!target || volume.missing_page_paths?.includes(target.img_path)) return undefined;
+
+  return matchFilesToPages(files.files, pages)[index];
+}
@@ -1,6 +1,6 @@
 import { describe, it, expect } from 'vitest';
 import type { VolumeMetadata } from '$lib/types';
-import { sortVolumes } from './sort-volumes';
+import { compareVolumeTitles, sortVolumes } from './sort-volumes';
 
 // Helper function to create mock volume metadata
 function createMockVolume(title: string): VolumeMetadata {
@@ -128,4 +128,53 @@ describe('sortVolumes', () => {
       'Series B Vol 1'
     ]);
   });
+
+  it('should follow a manual order when one is set', () => {
+    const volumes = [
+      createMockVolume('Series 01'),
+      createMockVolume('Series 02'),
+      createMockVolume('Series Extra - Vol 0 prologue')
+    ];
+    expect([...volumes].sort(sortVolumes).map((v) => v.volume_title)).toEqual([
+      'Series 01',
+      'Series 02',
+      'Series Extra - Vol 0 prologue'
+    ]);
+
+    // Drag the prologue before Vol 1
+    volumes[2].sort_index = 0;
+    volumes[0].sort_index = 1;
+    volumes[1].sort_index = 2;
+
+    expect([...volumes].sort(sortVolumes).map((v) => v.volume_title)).toEqual([
+      'Series Extra - Vol 0 prologue',
+      'Series 01',
+      'Series 02'
+    ]);
+  });
+
+  it('should put volumes without a manual position after ordered ones', () => {
+    const ordered = createMockVolume('Vol 2');
+    ordered.sort_index = 0;
+    const added = createMockVolume('Vol 1');
+
+    expect([added, ordered].sort(sortVolumes).map((v) => v.volume_title)).toEqual([
+      'Vol 2',
+      'Vol 1'
+    ]);
+  });
+});
+
+describe('compareVolumeTitles', () => {
+  it('ignores the manual order, so reversing it gives reverse title order', () => {
+    const volumes = [
+      { ...createMockVolume('Volume 2'), sort_index: 0 },
+      { ...createMockVolume('Volume 10'), sort_index: 1 },
+      { ...createMockVolume('Volume 1'), sort_index: 2 }
+    ];
+
+    const sorted = volumes.sort((a, b) => compareVolumeTitles(b, a));
+
+    expect(sorted.map((v) => v.volume_title)).toEqual(['Volume 10', 'Volume 2', 'Volume 1']);
+  });
 });
@@ -6,6 +6,22 @@ const naturalSort = new Intl.Collator(undefined, {
   sensitivity: 'base'
 });
 
-export function sortVolumes(a: VolumeMetadata, b: VolumeMetadata) {
+/** Natural title order ("Vol 2" before "Vol 10"), ignoring any manual order */
+export function compareVolumeTitles(a: VolumeMetadata, b: VolumeMetadata) {
   return naturalSort.compare(a.volume_title, b.volume_title);
 }
+
+/**
+ * Series order: manually ordered volumes first (by sort_index), then the rest
+ * by natural title sort, so volumes added after a manual reorder land at the end.
+ */
+export function sortVolumes(a: VolumeMetadata, b: VolumeMetadata) {
+  const aIndex = a.sort_index;
+  const bIndex = b.sort_index;
+  if (aIndex !== undefined && bIndex !== undefined && aIndex !== bIndex) {
+    return aIndex - bIndex;
+  }
+  if (aIndex !== undefined && bIndex === undefined) return -1;
+  if (aIndex === undefined && bIndex !== undefined) return 1;
+  return compareVolumeTitles(a, b);
+}
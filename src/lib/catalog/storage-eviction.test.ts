@@ -0,0 +1,101 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest';
+import type { VolumeMetadata } from '$lib/types';
+
+const localVolumes: VolumeMetadata[] = [];
+const cloudPaths = new Set<string>();
+
+vi.mock('./db', () => ({
+  db: {
+    volumes: { toArray: vi.fn(() => Promise.resolve(localVolumes)) },
+    volume_files: {
+      get: vi.fn((uuid: string) =>
+        Promise.resolve({
+          volume_uuid: uuid,
+          files: { 'page.jpg': { size: 400 } }
+        })
+      )
+    }
+  }
+}));
+
+vi.mock('$lib/import/database', () => ({
+  deleteVolume: vi.fn(() => Promise.resolve())
+}));
+
+vi.mock('$lib/util/sync/unified-cloud-manager', () => ({
+  unifiedCloudManager: {
+    existsInCloud: (series: string, volume: string) => cloudPaths.has(`${series}/${volume}`)
+  }
+}));
+
+import { planEviction, enforceStorageCap } from './storage-eviction';
+import { deleteVolume } from '$lib/import/database';
+import { miscSettings } from '$lib/settings/misc';
+import { volumesWithTrash, VolumeData } from '$lib/settings/volume-data';
+
+function createVolume(uuid: string): VolumeMetadata {
+  return {
+    mokuro_version: '0.2.0',
+    series_title: 'Series',
+    series_uuid: 'series-uuid',
+    volume_title: uuid,
+    volume_uuid: uuid,
+    page_count: 1,
+    character_count: 0,
+    page_char_counts: [0]
+  };
+}
+
+describe('planEviction', () => {
+  it('returns nothing when under the cap', () => {
+    expect(planEviction([{ volume_uuid: 'a', lastRead: '2024', bytes: 10 }], 0)).toEqual([]);
+  });
+
+  it('evicts least-recently-read volumes first until enough is freed', () => {
+    const plan = planEviction(
+      [
+        { volume_uuid: 'recent', lastRead: '2024-06-01T00:00:00.000Z', bytes: 100 },
+        { volume_uuid: 'oldest', lastRead: '2023-01-01T00:00:00.000Z', bytes: 100 },
+        { volume_uuid: 'middle', lastRead: '2024-01-01T00:00:00.000Z', bytes: 100 }
+      ],
+      150
+    );
+    expect(plan).toEqual(['oldest', 'middle']);
+  });
+});
+
+describe('enforceStorageCap', () => {
+  beforeEach(() => {
+    vi.mocked(deleteVolume).mockClear();
+    localVolumes.length = 0;
+    cloudPaths.clear();
+    Object.defineProperty(navigator, 'storage', {
+      configurable: true,
+      value: {
+        estimate: () => Promise.resolve({ usage: 1024 * 1024 * 1024, quota: 10 * 1024 ** 3 }),
+        persisted: () => Promise.resolve(false)
+      }
+    });
+  });
+
+  it('does nothing when eviction is disabled', async () => {
+    miscSettings.update((s) => ({ ...s, storageEvictionEnabled: false, storageCapGB: 1 }));
+    expect(await enforceStorageCap(500)).toEqual([]);
+  });
+
+  it('evicts the oldest-read cloud-backed volume when a download would exceed the cap', async () => {
+    miscSettings.update((s) => ({ ...s, storageEvictionEnabled: true, storageCapGB: 1 }));
+    localVolumes.push(createVolume('old'), createVolume('new'), createVolume('local-only'));
+    cloudPaths.add('Series/old').add('Series/new');
+    volumesWithTrash.set({
+      old: new VolumeData({ progress: 5, lastProgressUpdate: '2023-01-01T00:00:00.000Z' }),
+      new: new VolumeData({ progress: 5, lastProgressUpdate: '2024-01-01T00:00:00.000Z' }),
+      'local-only': new VolumeData({ lastProgressUpdate: '2020-01-01T00:00:00.000Z' })
+    });
+
+    const evicted = await enforceStorageCap(300);
+
+    expect(evicted).toEqual(['old']);
+    expect(deleteVolume).toHaveBeenCalledWith('old');
+  });
+});
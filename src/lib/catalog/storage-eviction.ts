@@ -0,0 +1,125 @@
+/**
+ * Storage quota monitoring and LRU eviction of cloud-backed volumes.
+ *
+ * Browsers may silently evict a whole origin's IndexedDB under storage
+ * pressure. Rather than risk losing everything, users can opt into a cap:
+ * before a cloud download, if usage would exceed the cap, the least-recently
+ * read downloaded volumes that also exist in the cloud are removed locally.
+ * They reappear as placeholders via the normal cloud reconciliation, and
+ * their reading progress (kept in the volume-data store) is untouched.
+ */
+
+import { get } from 'svelte/store';
+import type { VolumeMetadata } from '$lib/types';
+import { db } from './db';
+import { deleteVolume as deleteStoredVolume } from '$lib/import/database';
+import { miscSettings } from '$lib/settings/misc';
+import { volumes as volumeProgress } from '$lib/settings/volume-data';
+import { unifiedCloudManager } from '$lib/util/sync/unified-cloud-manager';
+import { routeParams } from '$lib/util/hash-router';
+
+export interface StorageEstimate {
+  usage: number;
+  quota: number;
+  persisted: boolean;
+}
+
+export interface EvictionCandidate {
+  volume_uuid: string;
+  /** ISO timestamp of the last progress update; epoch if never read */
+  lastRead: string;
+  bytes: number;
+}
+
+const GB = 1024 * 1024 * 1024;
+
+export async function getStorageEstimate(): Promise<StorageEstimate | null> {
+  if (!navigator.storage?.estimate) return null;
+  try {
+    const estimate = await navigator.storage.estimate();
+    const persisted = navigator.storage.persisted ? await navigator.storage.persisted() : false;
+    return { usage: estimate.usage ?? 0, quota: estimate.quota ?? 0, persisted };
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Pick the least-recently-read candidates until at least `bytesToFree` is covered.
+ * Returns an empty list when nothing needs freeing.
+ */
+export function planEviction(candidates: EvictionCandidate[], bytesToFree: number): string[] {
+  if (bytesToFree <= 0) return [];
+
+  const ordered = [...candidates].sort((a, b) => a.lastRead.localeCompare(b.lastRead));
+  const selected: string[] = [];
+  let freed = 0;
+  for (const candidate of ordered) {
+    if (freed >= bytesToFree) break;
+    selected.push(candidate.volume_uuid);
+    freed += candidate.bytes;
+  }
+  return selected;
+}
+
+async function getLocalVolumeBytes(volumeUuid: string): Promise<number> {
+  const files = await db.volume_files.get(volumeUuid);
+  if (!files) return 0;
+  return Object.values(files.files).reduce((sum, file) => sum + (file?.size ?? 0), 0);
+}
+
+async function collectCandidates(exclude: Set<string>): Promise<EvictionCandidate[]> {
+  const localVolumes: VolumeMetadata[] = await db.volumes.toArray();
+  const progress = get(volumeProgress);
+  const candidates: EvictionCandidate[] = [];
+
+  for (const volume of localVolumes) {
+    if (volume.isPlaceholder || exclude.has(volume.volume_uuid)) continue;
+    // Only volumes that can be restored from the cloud are safe to evict
+    if (!unifiedCloudManager.existsInCloud(volume.series_title, volume.volume_title)) continue;
+
+    candidates.push({
+      volume_uuid: volume.volume_uuid,
+      lastRead: progress[volume.volume_uuid]?.lastProgressUpdate ?? new Date(0).toISOString(),
+      bytes: await getLocalVolumeBytes(volume.volume_uuid)
+    });
+  }
+
+  return candidates;
+}
+
+/**
+ * Make room for an incoming download when the user has enabled the storage cap.
+ *
+ * @param incomingBytes - Expected size of the data about to be written
+ * @returns UUIDs of volumes that were evicted
+ */
+export async function enforceStorageCap(incomingBytes: number): Promise<string[]> {
+  const { storageEvictionEnabled, storageCapGB } = get(miscSettings);
+  if (!storageEvictionEnabled || !storageCapGB) return [];
+
+  const estimate = await getStorageEstimate();
+  if (!estimate) return [];
+
+  const capBytes = storageCapGB * GB;
+  const bytesToFree = estimate.usage + incomingBytes - capBytes;
+  if (bytesToFree <= 0) return [];
+
+  // Never evict the volume that is open in the reader
+  const exclude = new Set<string>();
+  const openVolume = get(routeParams).volume;
+  if (openVolume) exclude.add(openVolume);
+
+  const candidates = await collectCandidates(exclude);
+  const toEvict = planEviction(candidates, bytesToFree);
+
+  for (const volumeUuid of toEvict) {
+    await deleteStoredVolume(volumeUuid);
+  }
+
+  if (toEvict.length > 0) {
+    console.log(`[Storage] Evicted ${toEvict.length} volume(s) to stay under ${storageCapGB} GB`);
+  }
+
+  return toEvict;
+}
@@ -0,0 +1,106 @@
+import { describe, it, expect, beforeEach } from 'vitest';
+import 'fake-indexeddb/auto';
+import type { VolumeMetadata } from '$lib/types';
+import { CatalogDexieV3 } from './db-v3';
+import { scanStorage, deleteOrphanedData } from './storage-maintenance';
+
+function createVolume(uuid: string, overrides: Partial<VolumeMetadata> = {}): VolumeMetadata {
+  return {
+    mokuro_version: '0.2.0',
+    series_title: 'Series',
+    series_uuid: 'series-uuid',
+    volume_title: `Volume ${uuid}`,
+    volume_uuid: uuid,
+    page_count: 2,
+    character_count: 0,
+    page_char_counts: [0, 0],
+    ...overrides
+  };
+}
+
+function page(img_path: string) {
+  return { version: '0.2.0', img_width: 100, img_height: 100, blocks: [], img_path };
+}
+
+describe('storage maintenance', () => {
+  let database: CatalogDexieV3;
+
+  beforeEach(async () => {
+    const { IDBFactory } = await import('fake-indexeddb');
+    globalThis.indexedDB = new IDBFactory();
+    database = new CatalogDexieV3('storage-maintenance-test');
+  });
+
+  it('finds and frees blobs left behind by a killed import', async () => {
+    // A killed import wrote OCR and files but never committed the volume row
+    await database.volume_ocr.add({ volume_uuid: 'orphan', pages: [page('a.jpg')] });
+    await database.volume_files.add({
+      volume_uuid: 'orphan',
+      files: { 'a.jpg': new File(['x'.repeat(1000)], 'a.jpg') }
+    });
+
+    // A healthy volume alongside it
+    await database.volumes.add(createVolume('ok'));
+    await database.volume_ocr.add({ volume_uuid: 'ok', pages: [page('1.jpg'), page('2.jpg')] });
+    await database.volume_files.add({
+      volume_uuid: 'ok',
+      files: { '1.jpg': new File(['1'], '1.jpg'), '2.jpg': new File(['2'], '2.jpg') }
+    });
+
+    const scan = await scanStorage(database);
+    expect(scan.orphans).toHaveLength(1);
+    expect(scan.orphans[0].volume_uuid).toBe('orphan');
+    expect(scan.orphans[0].tables.sort()).toEqual(['volume_files', 'volume_ocr']);
+    expect(scan.reclaimableBytes).toBeGreaterThanOrEqual(1000);
+    expect(scan.corrupted).toEqual([]);
+
+    const removed = await deleteOrphanedData(scan.orphans, database);
+    expect(removed).toBe(1);
+    expect(await database.volume_files.get('orphan')).toBeUndefined();
+    expect(await database.volume_ocr.get('orphan')).toBeUndefined();
+    expect(await database.volume_files.get('ok')).toBeDefined();
+
+    const rescan = await scanStorage(database);
+    expect(rescan.orphans).toEqual([]);
+  });
+
+  it('flags volumes whose metadata references missing images', async () => {
+    await database.volumes.add(createVolume('no-files'));
+    await database.volume_ocr.add({ volume_uuid: 'no-files', pages: [page('1.jpg')] });
+
+    await database.volumes.add(createVolume('partial'));
+    await database.volume_ocr.add({
+      volume_uuid: 'partial',
+      pages: [page('1.jpg'), page('2.jpg')]
+    });
+    await database.volume_files.add({
+      volume_uuid: 'partial',
+      files: { '1.jpg': new File(['1'], '1.jpg') }
+    });
+
+    const scan = await scanStorage(database);
+    const byUuid = Object.fromEntries(scan.corrupted.map((c) => [c.volume_uuid, c]));
+    expect(byUuid['no-files'].reason).toBe('missing-files');
+    expect(byUuid['partial'].reason).toBe('missing-pages');
+    expect(byUuid['partial'].missingCount).toBe(1);
+  });
+
+  it('ignores cloud placeholders', async () => {
+    await database.volumes.add(createVolume('placeholder', { isPlaceholder: true }));
+    const scan = await scanStorage(database);
+    expect(scan.corrupted).toEqual([]);
+  });
+
+  it('does not delete data for a volume that finished importing after the scan', async () => {
+    await database.volume_files.add({
+      volume_uuid: 'late',
+      files: { '1.jpg': new File(['1'], '1.jpg') }
+    });
+    const scan = await scanStorage(database);
+    await database.volumes.add(createVolume('late'));
+
+    const removed = await deleteOrphanedData(scan.orphans, database);
+    expect(removed).toBe(0);
+    expect(await database.volume_files.get('late')).toBeDefined();
+  });
+});
@@ -0,0 +1,181 @@
+/**
+ * Storage maintenance for the V3 catalog.
+ *
+ * Interrupted imports (tab closed, crash, quota error mid-write) and
+ * interrupted deletes can leave `volume_ocr` / `volume_files` rows with no
+ * owning `volumes` row. Those rows are invisible in the catalog but still
+ * consume IndexedDB quota. The inverse also happens: a `volumes` row whose
+ * image or OCR rows are gone opens as a blank reader.
+ *
+ * `scanStorage()` finds both cases; `deleteOrphanedData()` reclaims the former.
+ */
+
+import type { VolumeMetadata } from '$lib/types';
+import { db as defaultDb, type CatalogDexieV3 } from './db';
+
+export interface OrphanedEntry {
+  volume_uuid: string;
+  /** Which tables still hold data for this UUID */
+  tables: Array<'volume_ocr' | 'volume_files'>;
+  fileCount: number;
+  bytes: number;
+}
+
+export interface CorruptedVolume {
+  volume_uuid: string;
+  series_title: string;
+  volume_title: string;
+  reason: 'missing-files' | 'missing-ocr' | 'missing-pages';
+  /** Number of referenced images that are absent (for 'missing-pages') */
+  missingCount?: number;
+}
+
+export interface StorageScanResult {
+  orphans: OrphanedEntry[];
+  corrupted: CorruptedVolume[];
+  reclaimableBytes: number;
+}
+
+function estimateOcrBytes(pages: unknown): number {
+  try {
+    return new Blob([JSON.stringify(pages)]).size;
+  } catch {
+    return 0;
+  }
+}
+
+function fileBytes(files: Record<string, File> | undefined): number {
+  if (!files) return 0;
+  let total = 0;
+  for (const file of Object.values(files)) {
+    total += file?.size ?? 0;
+  }
+  return total;
+}
+
+/**
+ * Check a local volume's OCR and file rows for consistency.
+ * Image-only volumes have OCR pages that reference images too, so the same
+ * check applies regardless of mokuro_version.
+ */
+function checkVolume(
+  volume: VolumeMetadata,
+  ocrPages: { img_path: string }[] | undefined,
+  files: Record<string, File> | undefined
+): CorruptedVolume | null {
+  const base = {
+    volume_uuid: volume.volume_uuid,
+    series_title: volume.series_title,
+    volume_title: volume.volume_title
+  };
+
+  if (!files) return { ...base, reason: 'missing-files' };
+  if (!ocrPages) return { ...base, reason: 'missing-ocr' };
+
+  const available = new Set(Object.keys(files));
+  const basenames = new Set(Object.keys(files).map((path) => path.split('/').pop()));
+  let missing = 0;
+  for (const page of ocrPages) {
+    if (available.has(page.img_path)) continue;
+    if (basenames.has(page.img_path.split('/').pop())) continue;
+    missing++;
+  }
+
+  if (missing > 0) return { ...base, reason: 'missing-pages', missingCount: missing };
+  return null;
+}
+
+/**
+ * Scan the catalog for orphaned OCR/image rows and corrupted volumes.
+ * Placeholders are skipped: they intentionally have no local data.
+ */
+export async function scanStorage(
+  database: CatalogDexieV3 = defaultDb
+): Promise<StorageScanResult> {
+  const volumes = await database.volumes.toArray();
+  const volumeMap = new Map(volumes.map((v) => [v.volume_uuid, v]));
+
+  const orphanMap = new Map<string, OrphanedEntry>();
+  const ocrPagesByUuid = new Map<string, { img_path: string }[]>();
+  const filesByUuid = new Map<string, Record<string, File>>();
+
+  await database.volume_ocr.each((row) => {
+    if (volumeMap.has(row.volume_uuid)) {
+      ocrPagesByUuid.set(row.volume_uuid, row.pages);
+      return;
+    }
+    const entry = orphanMap.get(row.volume_uuid) ?? {
+      volume_uuid: row.volume_uuid,
+      tables: [],
+      fileCount: 0,
+      bytes: 0
+    };
+    entry.tables.push('volume_ocr');
+    entry.bytes += estimateOcrBytes(row.pages);
+    orphanMap.set(row.volume_uuid, entry);
+  });
+
+  await database.volume_files.each((row) => {
+    if (volumeMap.has(row.volume_uuid)) {
+      filesByUuid.set(row.volume_uuid, row.files);
+      return;
+    }
+    const entry = orphanMap.get(row.volume_uuid) ?? {
+      volume_uuid: row.volume_uuid,
+      tables: [],
+      fileCount: 0,
+      bytes: 0
+    };
+    entry.tables.push('volume_files');
+    entry.fileCount += Object.keys(row.files ?? {}).length;
+    entry.bytes += fileBytes(row.files);
+    orphanMap.set(row.volume_uuid, entry);
+  });
+
+  const corrupted: CorruptedVolume[] = [];
+  for (const volume of volumes) {
+    if (volume.isPlaceholder) continue;
+    const issue = checkVolume(
+      volume,
+      ocrPagesByUuid.get(volume.volume_uuid),
+      filesByUuid.get(volume.volume_uuid)
+    );
+    if (issue) corrupted.push(issue);
+  }
+
+  const orphans = Array.from(orphanMap.values());
+  const reclaimableBytes = orphans.reduce((sum, o) => sum + o.bytes, 0);
+
+  return { orphans, corrupted, reclaimableBytes };
+}
+
+/**
+ * Delete OCR/image rows that have no owning volume.
+ *
+ * Each UUID is re-checked inside the transaction so a volume that finished
+ * importing between scan and cleanup is never touched.
+ *
+ * @returns Number of orphaned volume UUIDs removed
+ */
+export async function deleteOrphanedData(
+  orphans: OrphanedEntry[],
+  database: CatalogDexieV3 = defaultDb
+): Promise<number> {
+  let removed = 0;
+
+  await database.transaction(
+    'rw',
+    [database.volumes, database.volume_ocr, database.volume_files],
+    async () => {
+      for (const orphan of orphans) {
+        const owner = await database.volumes.get(orphan.volume_uuid);
+        if (owner) continue;
+        await database.volume_ocr.delete(orphan.volume_uuid);
+        await database.volume_files.delete(orphan.volume_uuid);
+        removed++;
+      }
+    }
+  );
+
+  return removed;
+}
@@ -0,0 +1,92 @@
+import { describe, it, expect, vi } from 'vitest';
+import type { VolumeMetadata } from '$lib/types';
+
+const { update } = vi.hoisted(() => ({ update: vi.fn(() => Promise.resolve(1)) }));
+
+vi.mock('./db', () => ({
+  db: { volumes: { update } }
+}));
+
+vi.mock('$lib/import/database', () => ({
+  deleteVolume: vi.fn(() => Promise.resolve())
+}));
+
+import { volumeSyncState, markVolumeSynced, REMOTE_NEWER_TOLERANCE_MS } from './sync-status';
+
+function createVolume(overrides: Partial<VolumeMetadata> = {}): VolumeMetadata {
+  return {
+    mokuro_version: '0.2.0',
+    series_title: 'Series',
+    series_uuid: 'series-uuid',
+    volume_title: 'Volume 1',
+    volume_uuid: 'volume-uuid',
+    page_count: 10,
+    character_count: 100,
+    page_char_counts: [],
+    ...overrides
+  };
+}
+
+const SYNCED_AT = '2026-03-01T12:00:00.000Z';
+
+function cloudFileAt(iso: string) {
+  return { modifiedTime: iso };
+}
+
+describe('volumeSyncState', () => {
+  it('reports placeholders as cloud-only', () => {
+    expect(volumeSyncState(createVolume({ isPlaceholder: true }), undefined)).toBe('cloud-only');
+  });
+
+  it('reports volumes missing from the cloud as local-only', () => {
+    expect(volumeSyncState(createVolume(), undefined)).toBe('local-only');
+  });
+
+  it('reports untouched volumes in both places as synced', () => {
+    const volume = createVolume({ cloud_synced_at: SYNCED_AT });
+    expect(volumeSyncState(volume, cloudFileAt(SYNCED_AT))).toBe('synced');
+  });
+
+  it('treats volumes without sync history as synced', () => {
+    expect(volumeSyncState(createVolume(), cloudFileAt(SYNCED_AT))).toBe('synced');
+  });
+
+  it('flags a volume changed locally since it was uploaded', () => {
+    const volume = createVolume({
+      cloud_synced_at: SYNCED_AT,
+      content_modified_at: '2026-03-02T08:00:00.000Z'
+    });
+    expect(volumeSyncState(volume, cloudFileAt(SYNCED_AT))).toBe('needs-upload');
+  });
+
+  it('flags a volume changed locally that was never synced from this device', () => {
+    const volume = createVolume({ content_modified_at: '2026-03-02T08:00:00.000Z' });
+    expect(volumeSyncState(volume, cloudFileAt(SYNCED_AT))).toBe('needs-upload');
+  });
+
+  it('flags a cloud archive uploaded after the last sync', () => {
+    const volume = createVolume({ cloud_synced_at: SYNCED_AT });
+    expect(volumeSyncState(volume, cloudFileAt('2026-03-05T00:00:00.000Z'))).toBe('remote-newer');
+  });
+
+  it('ignores clock skew between the provider and this device', () => {
+    const volume = createVolume({ cloud_synced_at: SYNCED_AT });
+    const skewed = new Date(Date.parse(SYNCED_AT) + REMOTE_NEWER_TOLERANCE_MS / 2).toISOString();
+    expect(volumeSyncState(volume, cloudFileAt(skewed))).toBe('synced');
+  });
+
+  it('clears needs-upload once the volume is synced again', () => {
+    const volume = createVolume({
+      content_modified_at: '2026-03-02T08:00:00.000Z',
+      cloud_synced_at: '2026-03-02T09:00:00.000Z'
+    });
+    expect(volumeSyncState(volume, cloudFileAt('2026-03-02T09:00:30.000Z'))).toBe('synced');
+  });
+});
+
+describe('markVolumeSynced', () => {
+  it('stores the given sync time', async () => {
+    await markVolumeSynced('volume-uuid', SYNCED_AT);
+    expect(update).toHaveBeenCalledWith('volume-uuid', { cloud_synced_at: SYNCED_AT });
+  });
+});
@@ -0,0 +1,132 @@
+/**
+ * Per-volume sync state for catalog badges.
+ *
+ * Derived from the local volume row and the cloud file listing:
+ * - local-only: no archive in the cloud
+ * - cloud-only: a placeholder, not downloaded on this device
+ * - synced: both copies match as far as this device knows
+ * - needs-upload: the local pages or text were replaced since the last upload/download
+ * - remote-newer: the cloud archive changed after this device last synced it
+ *
+ * Volumes that predate sync tracking have no `cloud_synced_at`; if they exist
+ * in both places they count as synced rather than flagging the whole library.
+ */
+
+import type { VolumeMetadata } from '$lib/types';
+import type { CloudFileMetadata } from '$lib/util/sync/provider-interface';
+import { db } from './db';
+
+export type VolumeSyncState =
+  | 'local-only'
+  | 'cloud-only'
+  | 'synced'
+  | 'needs-upload'
+  | 'remote-newer';
+
+export interface SyncStateInfo {
+  label: string;
+  description: string;
+  color: 'gray' | 'blue' | 'green' | 'yellow' | 'purple';
+}
+
+export const SYNC_STATE_INFO: Record<VolumeSyncState, SyncStateInfo> = {
+  'local-only': {
+    label: 'Local only',
+    description: 'Only on this device, not backed up',
+    color: 'gray'
+  },
+  'cloud-only': {
+    label: 'In cloud',
+    description: 'In the cloud, not downloaded on this device',
+    color: 'blue'
+  },
+  synced: {
+    label: 'Synced',
+    description: 'On this device and in the cloud',
+    color: 'green'
+  },
+  'needs-upload': {
+    label: 'Needs upload',
+    description: 'Changed on this device since it was last uploaded',
+    color: 'yellow'
+  },
+  'remote-newer': {
+    label: 'Cloud newer',
+    description: 'The cloud copy changed after this device last synced it',
+    color: 'purple'
+  }
+};
+
+/** Display order for the legend */
+export const SYNC_STATES: VolumeSyncState[] = [
+  'synced',
+  'needs-upload',
+  'remote-newer',
+  'local-only',
+  'cloud-only'
+];
+
+/**
+ * Cloud modified times come from the provider's clock, sync times from this
+ * device's, so small differences are skew rather than a newer upload.
+ */
+export const REMOTE_NEWER_TOLERANCE_MS = 5 * 60 * 1000;
+
+function time(iso: string | undefined): number | null {
+  if (!iso) return null;
+  const value = Date.parse(iso);
+  return Number.isNaN(value) ? null : value;
+}
+
+export function volumeSyncState(
+  volume: VolumeMetadata,
+  cloudFile: Pick<CloudFileMetadata, 'modifiedTime'> | undefined
+): VolumeSyncState {
+  if (volume.isPlaceholder) return 'cloud-only';
+  if (!cloudFile) return 'local-only';
+
+  const syncedAt = time(volume.cloud_synced_at);
+  const modifiedAt = time(volume.content_modified_at);
+  const cloudModifiedAt = time(cloudFile.modifiedTime);
+
+  if (syncedAt !== null && cloudModifiedAt !== null) {
+    if (cloudModifiedAt > syncedAt + REMOTE_NEWER_TOLERANCE_MS) return 'remote-newer';
+  }
+  if (modifiedAt !== null && (syncedAt === null || modifiedAt > syncedAt)) {
+    return 'needs-upload';
+  }
+  return 'synced';
+}
+
+/**
+ * Record that this device's copy now matches the cloud archive.
+ * `at` defaults to now; downloads pass the cloud file's modified time.
+ */
+export async function markVolumeSynced(volumeUuid: string, at?: string): Promise<void> {
+  await db.volumes.update(volumeUuid, { cloud_synced_at: at ?? new Date().toISOString() });
+}
+
+/**
+ * Download the cloud archive and put its content in place of the local copy.
+ * The local copy is only replaced once the download is in, so a failed
+ * download leaves it untouched. Reading progress is kept.
+ */
+export async function replaceWithCloudCopy(
+  volume: VolumeMetadata,
+  cloudFile: CloudFileMetadata
+): Promise<void> {
+  // Imported lazily: the download queue records sync times through this module
+  const { downloadQueue } = await import('$lib/util/download-queue');
+  downloadQueue.queueVolume(
+    {
+      ...volume,
+      isPlaceholder: true,
+      cloudProvider: cloudFile.provider,
+      cloudFileId: cloudFile.fileId,
+      cloudModifiedTime: cloudFile.modifiedTime,
+      cloudSize: cloudFile.size,
+      cloudPath: cloudFile.path
+    },
+    { replaceLocal: true }
+  );
+}
@@ -30,6 +30,7 @@ interface QueuedLoad {
   priority: number; // Stack position: 0 = front (highest), 1, 2... = behind
   timestamp: number; // For FILO within same priority
   element: HTMLElement | null; // For visibility check before dispatch
+  resizeHeight?: number;
   resolve: (entry: CacheEntry) => void;
   reject: (error: Error) => void;
 }
@@ -39,6 +40,26 @@ interface PendingDecode {
   reject: (error: Error) => void;
 }
 
+/** Main-thread decode, mirroring what the worker does */
+function decodeImage(file: File, resizeHeight?: number): Promise<ImageBitmap> {
+  return resizeHeight
+    ? createImageBitmap(file, { resizeHeight, resizeQuality: 'medium' })
+    : createImageBitmap(file);
+}
+
+/**
+ * Cache key for a page preview decoded at `height` px. Page previews are
+ * decoded at different heights by the page strip and resume tiles, so the
+ * height is part of the key.
+ */
+export function pagePreviewKey(volumeUuid: string, pageIndex: number, height: number): string {
+  return `${pagePreviewPrefix(volumeUuid, pageIndex)}${Math.round(height)}`;
+}
+
+function pagePreviewPrefix(volumeUuid: string, pageIndex: number): string {
+  return `${volumeUuid}:page:${pageIndex}@`;
+}
+
 class ThumbnailCache {
   private cache = new Map<string, CacheEntry>(); // volume_uuid -> entry
   private pending = new Map<string, Promise<CacheEntry>>(); // coalesce concurrent requests
@@ -116,11 +137,11 @@ class ThumbnailCache {
   /**
    * Decode an image using a worker (off-main-thread)
    */
-  private decodeInWorker(file: File): Promise<ImageBitmap> {
+  private decodeInWorker(file: File, resizeHeight?: number): Promise<ImageBitmap> {
     return new Promise((resolve, reject) => {
       // Fallback to main thread if workers not available
       if (!this.workersReady || this.workers.length === 0) {
-        createImageBitmap(file).then(resolve).catch(reject);
+        decodeImage(file, resizeHeight).then(resolve).catch(reject);
         return;
       }
 
@@ -131,7 +152,7 @@ class ThumbnailCache {
       const worker = this.workers[this.workerIndex];
       this.workerIndex = (this.workerIndex + 1) % this.workers.length;
 
-      worker.postMessage({ id, file } satisfies DecodeRequest);
+      worker.postMessage({ id, file, resizeHeight } satisfies DecodeRequest);
     });
   }
 
@@ -140,12 +161,15 @@ class ThumbnailCache {
    * Coalesces concurrent requests for the same thumbnail
    * @param priority Stack position (0 = front/top, higher = further back)
    * @param element Canvas element for visibility check before dispatch
+   * @param resizeHeight Decode scaled down to this height (page previews);
+   *   callers must use a distinct key per size
    */
   async get(
     volumeUuid: string,
     file: File,
     priority: number = 0,
-    element: HTMLElement | null = null
+    element: HTMLElement | null = null,
+    resizeHeight?: number
   ): Promise<CacheEntry> {
     // Check cache first
     const existing = this.cache.get(volumeUuid);
@@ -168,6 +192,7 @@ class ThumbnailCache {
         priority,
         timestamp: Date.now(),
         element,
+        resizeHeight,
         resolve,
         reject
       });
@@ -249,7 +274,7 @@ class ThumbnailCache {
       const item = this.queue.splice(itemIndex, 1)[0];
       this.activeLoads++;
 
-      this.load(item.volumeUuid, item.file)
+      this.load(item.volumeUuid, item.file, item.resizeHeight)
         .then(item.resolve)
         .catch(item.reject)
         .finally(() => {
@@ -277,6 +302,38 @@ class ThumbnailCache {
     return entry;
   }
 
+  /**
+   * Get the largest cached preview of a page, whatever height it was decoded at
+   */
+  getPagePreviewSync(volumeUuid: string, pageIndex: number): CacheEntry | undefined {
+    const prefix = pagePreviewPrefix(volumeUuid, pageIndex);
+    let bestKey: string | undefined;
+    let best: CacheEntry | undefined;
+    for (const [key, entry] of this.cache) {
+      if (key.startsWith(prefix) && (!best || entry.height > best.height)) {
+        bestKey = key;
+        best = entry;
+      }
+    }
+    if (bestKey) {
+      this.touch(bestKey);
+    }
+    return best;
+  }
+
+  /**
+   * Invalidate every cached preview of a page (e.g., when its image is replaced)
+   */
+  invalidatePagePreviews(volumeUuid: string, pageIndex: number): void {
+    const prefix = pagePreviewPrefix(volumeUuid, pageIndex);
+    const keys = new Set([...this.cache.keys(), ...this.pending.keys()]);
+    for (const key of keys) {
+      if (key.startsWith(prefix)) {
+        this.invalidate(key);
+      }
+    }
+  }
+
   /**
    * Invalidate a specific cache entry (e.g., when cover is edited)
    * Does not close bitmap - components may still hold references.
@@ -317,12 +374,12 @@ class ThumbnailCache {
   /**
    * Load and decode a thumbnail using worker
    */
-  private async load(volumeUuid: string, file: File): Promise<CacheEntry> {
+  private async load(volumeUuid: string, file: File, resizeHeight?: number): Promise<CacheEntry> {
     const useMainThreadWarmup =
       this.mainThreadWarmupDecodesRemaining > 0 || Date.now() < this.workerWarmupUntil;
     const bitmap = useMainThreadWarmup
-      ? await createImageBitmap(file)
-      : await this.decodeInWorker(file);
+      ? await decodeImage(file, resizeHeight)
+      : await this.decodeInWorker(file, resizeHeight);
     if (useMainThreadWarmup && this.mainThreadWarmupDecodesRemaining > 0) {
       this.mainThreadWarmupDecodesRemaining--;
     }
@@ -0,0 +1,116 @@
+import { describe, it, expect, beforeEach, vi } from 'vitest';
+import 'fake-indexeddb/auto';
+import type { VolumeMetadata } from '$lib/types';
+
+const { generateThumbnail, invalidate } = vi.hoisted(() => ({
+  generateThumbnail: vi.fn(async (file: File) => ({
+    file: new File([`thumb:${file.name}`], `thumb-${file.name}`),
+    width: 100,
+    height: 150
+  })),
+  invalidate: vi.fn()
+}));
+
+vi.mock('$lib/catalog/thumbnails', () => ({ generateThumbnail }));
+vi.mock('./thumbnail-cache', () => ({ thumbnailCache: { invalidate } }));
+
+import { CatalogDexieV3 } from './db-v3';
+import { THUMBNAIL_VERSION, needsThumbnail } from './cover-page';
+
+function createVolume(uuid: string, overrides: Partial<VolumeMetadata> = {}): VolumeMetadata {
+  return {
+    mokuro_version: '0.2.0',
+    series_title: 'Series',
+    series_uuid: 'series-uuid',
+    volume_title: `Volume ${uuid}`,
+    volume_uuid: uuid,
+    page_count: 2,
+    character_count: 0,
+    page_char_counts: [0, 0],
+    thumbnail: new File(['old'], 'old.jpg'),
+    thumbnail_width: 50,
+    thumbnail_height: 75,
+    thumbnail_version: THUMBNAIL_VERSION,
+    ...overrides
+  };
+}
+
+function page(img_path: string) {
+  return { version: '0.2.0', img_width: 100, img_height: 100, blocks: [], img_path };
+}
+
+describe('thumbnail regeneration', () => {
+  let database: CatalogDexieV3;
+
+  async function addVolume(uuid: string, overrides: Partial<VolumeMetadata> = {}) {
+    await database.volumes.add(createVolume(uuid, overrides));
+    await database.volume_ocr.add({ volume_uuid: uuid, pages: [page('1.jpg'), page('2.jpg')] });
+    await database.volume_files.add({
+      volume_uuid: uuid,
+      files: { '2.jpg': new File(['2'], '2.jpg'), '1.jpg': new File(['1'], '1.jpg') }
+    });
+  }
+
+  beforeEach(async () => {
+    vi.clearAllMocks();
+    const { IDBFactory } = await import('fake-indexeddb');
+    globalThis.indexedDB = new IDBFactory();
+    database = new CatalogDexieV3('thumbnail-regeneration-test');
+  });
+
+  it('rebuilds only out-of-date, pre-versioning and missing thumbnails', async () => {
+    await addVolume('current');
+    await addVolume('outdated', { thumbnail_version: THUMBNAIL_VERSION - 1 });
+    await addVolume('missing', { thumbnail: undefined, thumbnail_version: undefined });
+    await addVolume('legacy', { thumbnail_version: undefined });
+    await addVolume('custom', { thumbnail_version: undefined, thumbnail_custom: true });
+
+    await database.processThumbnails();
+
+    expect(generateThumbnail).toHaveBeenCalledTimes(3);
+    expect(generateThumbnail.mock.calls.every(([file]) => file.name === '1.jpg')).toBe(true);
+
+    const outdated = await database.volumes.get('outdated');
+    expect(outdated?.thumbnail?.name).toBe('thumb-1.jpg');
+    expect(outdated?.thumbnail_version).toBe(THUMBNAIL_VERSION);
+    expect((await database.volumes.get('missing'))?.thumbnail_version).toBe(THUMBNAIL_VERSION);
+    expect((await database.volumes.get('current'))?.thumbnail?.name).toBe('old.jpg');
+    expect((await database.volumes.get('legacy'))?.thumbnail_version).toBe(THUMBNAIL_VERSION);
+    expect((await database.volumes.get('custom'))?.thumbnail?.name).toBe('old.jpg');
+
+    // Only the replaced thumbnails had a cached bitmap to drop
+    expect(invalidate.mock.calls.map(([id]) => id).sort()).toEqual(['legacy', 'outdated']);
+  });
+
+  it('skips volumes already at the current version when resumed', async () => {
+    await addVolume('a', { thumbnail_version: 0 });
+    await addVolume('b', { thumbnail_version: 0 });
+
+    // Simulate a run that was interrupted after the first volume
+    await database.volumes.update('a', { thumbnail_version: THUMBNAIL_VERSION });
+    await database.processThumbnails();
+
+    expect(generateThumbnail).toHaveBeenCalledTimes(1);
+    expect(invalidate.mock.calls).toEqual([['b']]);
+    const volumes = await database.volumes.toArray();
+    expect(volumes.filter(needsThumbnail)).toEqual([]);
+  });
+
+  it('invalidates every generated thumbnail but keeps custom covers', async () => {
+    await addVolume('a');
+    await addVolume('b', { thumbnail_version: undefined });
+    await addVolume('custom', { thumbnail_version: undefined, thumbnail_custom: true });
+    await database.volumes.add(createVolume('placeholder', { isPlaceholder: true }));
+
+    const marked = await database.invalidateThumbnails();
+    expect(marked).toBe(2);
+
+    // Joins the run started by invalidateThumbnails
+    await database.processThumbnails();
+
+    expect(generateThumbnail).toHaveBeenCalledTimes(2);
+    expect((await database.volumes.get('a'))?.thumbnail_version).toBe(THUMBNAIL_VERSION);
+    expect((await database.volumes.get('b'))?.thumbnail_version).toBe(THUMBNAIL_VERSION);
+    expect((await database.volumes.get('custom'))?.thumbnail?.name).toBe('old.jpg');
+  });
+});
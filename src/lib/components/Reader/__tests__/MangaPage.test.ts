@@ -0,0 +1,117 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+import { render } from '@testing-library/svelte';
+import { tick } from 'svelte';
+import MangaPage from '../MangaPage.svelte';
+import type { Page } from '$lib/types';
+
+vi.mock('$lib/settings', async () => {
+  const { writable } = await import('svelte/store');
+  return {
+    settings: writable({
+      fontSize: 'auto',
+      boldFont: false,
+      displayOCR: true,
+      alwaysShowOCR: true,
+      textBoxBorders: false,
+      textEditable: false,
+      ankiConnectSettings: { triggerMethod: 'doubleTap', tags: [], cardMode: 'single' }
+    }),
+    volumes: writable({}),
+    pageToneFilter: writable('none')
+  };
+});
+
+vi.mock('$lib/catalog/db', () => ({
+  db: { volumes: { get: vi.fn() } }
+}));
+
+vi.mock('$lib/anki-connect', () => ({
+  showCropper: vi.fn(),
+  openCreateModal: vi.fn(),
+  openUpdateModal: vi.fn(),
+  expandTextBoxBounds: vi.fn(),
+  sendQuickCapture: vi.fn(),
+  getLastCardInfo: vi.fn(),
+  getCardAgeInMin: vi.fn(),
+  extractFieldValues: vi.fn(),
+  getModelConfig: vi.fn(),
+  blobToBase64: vi.fn()
+}));
+
+// The page strip already decoded a preview of page 5
+const preview = { bitmap: { width: 70, height: 100 }, width: 70, height: 100, size: 28000 };
+vi.mock('$lib/catalog/thumbnail-cache', () => ({
+  thumbnailCache: {
+    getPagePreviewSync: (volumeUuid: string, pageIndex: number) =>
+      volumeUuid === 'vol' && pageIndex === 4 ? preview : undefined
+  }
+}));
+
+const page = {
+  version: '0.2.2',
+  img_width: 700,
+  img_height: 1000,
+  img_path: 'page_005.jpg',
+  blocks: [{ box: [100, 100, 200, 300], vertical: true, font_size: 30, lines: ['結界'] }]
+} as unknown as Page;
+
+let finishDecode: () => void;
+
+beforeEach(() => {
+  // jsdom neither decodes images nor draws on canvases
+  vi.stubGlobal(
+    'Image',
+    class {
+      src = '';
+      decode() {
+        return new Promise<void>((resolve) => (finishDecode = resolve));
+      }
+    }
+  );
+  URL.createObjectURL = vi.fn(() => 'blob:page');
+  URL.revokeObjectURL = vi.fn();
+  HTMLCanvasElement.prototype.getContext = vi.fn(() => ({
+    drawImage: vi.fn()
+  })) as unknown as typeof HTMLCanvasElement.prototype.getContext;
+});
+
+afterEach(() => {
+  vi.unstubAllGlobals();
+});
+
+describe('MangaPage', () => {
+  it('shows a blurred preview that gives way to the full image', async () => {
+    const { container, queryByTestId } = render(MangaPage, {
+      page,
+      src: new File(['x'], 'page_005.jpg'),
+      volumeUuid: 'vol',
+      pageIndex: 4
+    });
+    const pageEl = container.querySelector<HTMLElement>('[data-page-index="4"]')!;
+
+    expect(queryByTestId('page-preview')).not.toBeNull();
+    expect(pageEl.style.backgroundImage).toBe('');
+    // No OCR boxes over the preview
+    expect(container.querySelector('.textBox')).toBeNull();
+
+    finishDecode();
+    await tick();
+    await tick();
+
+    expect(queryByTestId('page-preview')).toBeNull();
+    expect(pageEl.style.backgroundImage).toContain('blob:page');
+    expect(container.querySelector('.textBox')).not.toBeNull();
+  });
+
+  it('shows a pre-decoded page straight away', () => {
+    const { container, queryByTestId } = render(MangaPage, {
+      page,
+      cachedUrl: 'blob:cached',
+      volumeUuid: 'vol',
+      pageIndex: 4
+    });
+
+    expect(queryByTestId('page-preview')).toBeNull();
+    expect(container.querySelector('.textBox')).not.toBeNull();
+  });
+});
@@ -0,0 +1,80 @@
+import { beforeEach, describe, expect, it, vi } from 'vitest';
+import { fireEvent, render } from '@testing-library/svelte';
+import { tick } from 'svelte';
+import PageThumbnailStrip from '../PageThumbnailStrip.svelte';
+import type { Page } from '$lib/types';
+
+const get = vi.fn((..._args: unknown[]) => new Promise(() => {}));
+
+vi.mock('$lib/catalog/thumbnail-cache', () => ({
+  pagePreviewKey: (uuid: string, index: number, height: number) => `${uuid}:${index}@${height}`,
+  thumbnailCache: { get: (...args: unknown[]) => get(...args), getSync: () => undefined }
+}));
+
+const page = (n: number): Page =>
+  ({ img_path: `p${n}.jpg`, img_width: 700, img_height: 1000, blocks: [] }) as unknown as Page;
+
+const pages = [page(1), page(2), page(3)];
+const files = Object.fromEntries(pages.map((p) => [p.img_path, new File([''], p.img_path)]));
+
+const scrollIntoView = vi.fn();
+const observed: Element[] = [];
+
+beforeEach(() => {
+  vi.clearAllMocks();
+  observed.length = 0;
+  Element.prototype.scrollIntoView = scrollIntoView;
+  vi.stubGlobal(
+    'IntersectionObserver',
+    class {
+      observe(el: Element) {
+        observed.push(el);
+      }
+      disconnect() {}
+    }
+  );
+});
+
+function renderStrip(props: Partial<Record<string, unknown>> = {}) {
+  const onJump = vi.fn();
+  const result = render(PageThumbnailStrip, {
+    volumeUuid: 'vol',
+    pages,
+    files,
+    currentPage: 1,
+    missingPagePaths: new Set<string>(),
+    onJump,
+    ...props
+  });
+  return { ...result, onJump };
+}
+
+describe('PageThumbnailStrip', () => {
+  it('jumps to the clicked page', async () => {
+    const { getByLabelText, onJump } = renderStrip();
+    await fireEvent.click(getByLabelText('Go to page 3'));
+    expect(onJump).toHaveBeenCalledWith(3);
+  });
+
+  it('marks the current page and scrolls it into view when it changes', async () => {
+    const { getByLabelText, rerender } = renderStrip();
+    expect(getByLabelText('Go to page 1').getAttribute('aria-current')).toBe('page');
+
+    scrollIntoView.mockClear();
+    await rerender({ currentPage: 2 });
+    await tick();
+
+    expect(getByLabelText('Go to page 2').getAttribute('aria-current')).toBe('page');
+    expect(getByLabelText('Go to page 1').getAttribute('aria-current')).toBeNull();
+    expect(scrollIntoView).toHaveBeenCalled();
+    expect(scrollIntoView.mock.contexts.at(-1)).toBe(getByLabelText('Go to page 2'));
+  });
+
+  it('only lazy-loads previews for downloaded pages', () => {
+    const { getByLabelText } = renderStrip({ missingPagePaths: new Set(['p2.jpg']) });
+    expect(getByLabelText('Go to page 2').querySelector('canvas')).toBeNull();
+    expect(observed).toHaveLength(2);
+    // Nothing decodes until a thumbnail scrolls near view
+    expect(get).not.toHaveBeenCalled();
+  });
+});
@@ -0,0 +1,57 @@
+import { writable } from 'svelte/store';
+import { ensureDictionaryLoaded, findEntries } from '$lib/dictionary/db';
+import { lookupText, type LookupResult } from '$lib/dictionary/lookup';
+
+interface AnchorRect {
+  left: number;
+  top: number;
+  right: number;
+  bottom: number;
+}
+
+interface DictionaryPopupState {
+  open: boolean;
+  loading?: boolean;
+  error?: string;
+  anchor?: AnchorRect;
+  result?: LookupResult | null;
+}
+
+export const dictionaryPopupStore = writable<DictionaryPopupState>({ open: false });
+
+// Hovering quickly across a line starts many lookups; only the latest may show
+let lookupId = 0;
+
+/**
+ * Look up the word starting at the beginning of `text` and show the popup
+ * next to `anchor` (the first character's screen rect).
+ */
+export async function showDictionaryLookup(text: string, anchor: AnchorRect) {
+  const id = ++lookupId;
+  dictionaryPopupStore.set({ open: true, loading: true, anchor });
+
+  try {
+    await ensureDictionaryLoaded();
+    const result = await lookupText(text, findEntries);
+    if (id !== lookupId) return;
+    if (!result) {
+      // Nothing to show for punctuation, names the dictionary lacks, etc.
+      dictionaryPopupStore.set({ open: false });
+      return;
+    }
+    dictionaryPopupStore.set({ open: true, anchor, result });
+  } catch (error) {
+    if (id !== lookupId) return;
+    console.error('Dictionary lookup failed:', error);
+    dictionaryPopupStore.set({
+      open: true,
+      anchor,
+      error: error instanceof Error ? error.message : 'Dictionary lookup failed'
+    });
+  }
+}
+
+export function closeDictionaryPopup() {
+  lookupId++;
+  dictionaryPopupStore.set({ open: false });
+}
@@ -0,0 +1,61 @@
+import 'fake-indexeddb/auto';
+import { beforeEach, describe, expect, it, vi } from 'vitest';
+import { DICTIONARY_URL, DICTIONARY_VERSION_URL } from './db';
+
+function dictionaryFetch(version: string | null, word: string) {
+  return vi.fn(async (url: string) => {
+    if (url === DICTIONARY_VERSION_URL) {
+      return version === null
+        ? new Response('', { status: 404 })
+        : new Response(JSON.stringify({ version }));
+    }
+    if (url === DICTIONARY_URL) {
+      const entries = [[[word], ['よみ'], [[['n'], ['gloss']]]]];
+      return new Response(JSON.stringify({ version, entries }));
+    }
+    throw new Error(`Unexpected fetch ${url}`);
+  }) as unknown as typeof fetch & ReturnType<typeof vi.fn>;
+}
+
+/** A fresh module, so its shared import promise doesn't carry over */
+async function loadDb() {
+  vi.resetModules();
+  return import('./db');
+}
+
+describe('ensureDictionaryLoaded', () => {
+  beforeEach(async () => {
+    const { IDBFactory } = await import('fake-indexeddb');
+    globalThis.indexedDB = new IDBFactory();
+  });
+
+  it('keeps the stored dictionary while the bundled version matches', async () => {
+    await (await loadDb()).ensureDictionaryLoaded(dictionaryFetch('r1', '本'));
+
+    const fetchImpl = dictionaryFetch('r1', '本');
+    const db = await loadDb();
+    await db.ensureDictionaryLoaded(fetchImpl);
+
+    expect(fetchImpl).toHaveBeenCalledTimes(1);
+    expect(fetchImpl).toHaveBeenCalledWith(DICTIONARY_VERSION_URL);
+  });
+
+  it('replaces the stored dictionary when a new release is bundled', async () => {
+    await (await loadDb()).ensureDictionaryLoaded(dictionaryFetch('r1', '本'));
+
+    const db = await loadDb();
+    await db.ensureDictionaryLoaded(dictionaryFetch('r2', '猫'));
+
+    expect(await db.findEntries('本')).toEqual([]);
+    expect((await db.findEntries('猫')).map((entry) => entry.kanji)).toEqual([['猫']]);
+  });
+
+  it('keeps the stored dictionary when the version cannot be checked', async () => {
+    await (await loadDb()).ensureDictionaryLoaded(dictionaryFetch('r1', '本'));
+
+    const db = await loadDb();
+    await db.ensureDictionaryLoaded(dictionaryFetch(null, '猫'));
+
+    expect(await db.findEntries('本')).toHaveLength(1);
+  });
+});
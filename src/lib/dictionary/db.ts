@@ -0,0 +1,151 @@
+/**
+ * Local JMdict store for in-reader lookups.
+ *
+ * The dataset ships as a static asset, generated during `npm run build` by
+ * scripts/build-dictionary.mjs from a pinned jmdict-simplified release. It is
+ * only downloaded the first time a lookup is made, then kept in its own
+ * IndexedDB database so the catalog schema is untouched. A build pinning a
+ * newer release replaces the stored copy on the next lookup.
+ */
+
+import Dexie, { type Table } from 'dexie';
+
+export const DICTIONARY_URL = '/dictionary/jmdict-eng.json';
+/** `{ version }` of the bundled dictionary, small enough to check on every load */
+export const DICTIONARY_VERSION_URL = '/dictionary/version.json';
+
+export interface DictionarySense {
+  /** JMdict part-of-speech tags, e.g. 'v5k', 'adj-i', 'n' */
+  pos: string[];
+  glosses: string[];
+}
+
+export interface DictionaryEntry {
+  id?: number;
+  kanji: string[];
+  readings: string[];
+  senses: DictionarySense[];
+}
+
+/** Compact on-disk entry: [kanji, readings, [pos, glosses][]] */
+type PackedEntry = [string[], string[], [string[], string[]][]];
+
+export interface DictionaryFile {
+  version: string;
+  entries: PackedEntry[];
+}
+
+interface DictionaryMeta {
+  key: 'version';
+  value: string;
+}
+
+/** One row of an imported frequency list; lower rank = more common */
+export interface FrequencyRow {
+  term: string;
+  rank: number;
+}
+
+class DictionaryDexie extends Dexie {
+  entries!: Table<DictionaryEntry, number>;
+  meta!: Table<DictionaryMeta, string>;
+  frequency!: Table<FrequencyRow, string>;
+
+  constructor() {
+    super('mokuro_dictionary');
+    this.version(1).stores({
+      entries: '++id, *kanji, *readings',
+      meta: 'key'
+    });
+    this.version(2).stores({
+      frequency: 'term'
+    });
+  }
+}
+
+let dictionaryDb: DictionaryDexie | null = null;
+
+export function getDictionaryDb(): DictionaryDexie {
+  if (!dictionaryDb) {
+    dictionaryDb = new DictionaryDexie();
+  }
+  return dictionaryDb;
+}
+
+const IMPORT_CHUNK_SIZE = 5000;
+
+let loadPromise: Promise<void> | null = null;
+
+/** Version of the dictionary this build ships, or null when it can't be checked */
+async function bundledVersion(fetchImpl: typeof fetch): Promise<string | null> {
+  try {
+    const response = await fetchImpl(DICTIONARY_VERSION_URL);
+    if (!response.ok) return null;
+    const { version } = await response.json();
+    return typeof version === 'string' ? version : null;
+  } catch {
+    return null;
+  }
+}
+
+async function importDictionary(fetchImpl: typeof fetch): Promise<void> {
+  const db = getDictionaryDb();
+  const stored = (await db.meta.get('version'))?.value;
+  if (stored) {
+    // Offline, the stored copy is still good enough
+    const bundled = await bundledVersion(fetchImpl);
+    if (bundled === null || bundled === stored) return;
+  }
+
+  const response = await fetchImpl(DICTIONARY_URL);
+  if (!response.ok) {
+    throw new Error(`Dictionary data is not available (HTTP ${response.status})`);
+  }
+  const file: DictionaryFile = await response.json();
+
+  await db.transaction('rw', db.entries, db.meta, async () => {
+    await db.entries.clear();
+    for (let i = 0; i < file.entries.length; i += IMPORT_CHUNK_SIZE) {
+      const chunk = file.entries.slice(i, i + IMPORT_CHUNK_SIZE);
+      await db.entries.bulkAdd(
+        chunk.map(([kanji, readings, senses]) => ({
+          kanji,
+          readings,
+          senses: senses.map(([pos, glosses]) => ({ pos, glosses }))
+        }))
+      );
+    }
+    // Written last so an interrupted import is retried on the next lookup
+    await db.meta.put({ key: 'version', value: file.version });
+  });
+}
+
+/**
+ * Download and store the dictionary if it isn't already, or if this build
+ * ships a different version. Concurrent callers share one import; a failed
+ * import can be retried.
+ */
+export function ensureDictionaryLoaded(fetchImpl: typeof fetch = fetch): Promise<void> {
+  if (!loadPromise) {
+    loadPromise = importDictionary(fetchImpl).catch((error) => {
+      loadPromise = null;
+      throw error;
+    });
+  }
+  return loadPromise;
+}
+
+/** Entries whose kanji or kana spelling is exactly `term` */
+export async function findEntries(term: string): Promise<DictionaryEntry[]> {
+  const db = getDictionaryDb();
+  const [byKanji, byReading] = await Promise.all([
+    db.entries.where('kanji').equals(term).toArray(),
+    db.entries.where('readings').equals(term).toArray()
+  ]);
+  const seen = new Set<number | undefined>();
+  return [...byKanji, ...byReading].filter((entry) => {
+    if (seen.has(entry.id)) return false;
+    seen.add(entry.id);
+    return true;
+  });
+}
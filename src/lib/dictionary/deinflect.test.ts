@@ -0,0 +1,40 @@
+import { describe, it, expect } from 'vitest';
+import { deinflect, matchesWordType, WordType } from './deinflect';
+
+const terms = (word: string) => deinflect(word).map((candidate) => candidate.term);
+
+describe('deinflect', () => {
+  it('keeps the original text as the first candidate', () => {
+    expect(deinflect('食べる')[0]).toMatchObject({ term: '食べる', reasons: [] });
+  });
+
+  it('undoes polite, past and negative verb forms', () => {
+    expect(terms('食べました')).toContain('食べる');
+    expect(terms('行かなかった')).toContain('行く');
+    expect(terms('泳いで')).toContain('泳ぐ');
+    expect(terms('勉強しました')).toContain('勉強する');
+    expect(terms('来なかった')).toContain('来る');
+  });
+
+  it('chains auxiliaries back to the dictionary form', () => {
+    const reading = deinflect('読んでいた').find((c) => c.term === '読む');
+    expect(reading?.type).toBe(WordType.V5);
+    expect(reading?.reasons).toEqual(['past', 'progressive', 'te']);
+
+    expect(terms('食べさせられた')).toContain('食べる');
+    expect(terms('書かれた')).toContain('書く');
+  });
+
+  it('handles i-adjectives', () => {
+    expect(terms('高くなかった')).toContain('高い');
+    expect(terms('飲みたくない')).toContain('飲む');
+  });
+});
+
+describe('matchesWordType', () => {
+  it('requires the word class a deinflection implies', () => {
+    expect(matchesWordType(['v5k', 'vi'], WordType.V5)).toBe(true);
+    expect(matchesWordType(['v1'], WordType.V5)).toBe(false);
+    expect(matchesWordType(['adj-i'], WordType.ADJ_I)).toBe(true);
+  });
+});
@@ -0,0 +1,210 @@
+/**
+ * Rule-based deinflection for dictionary lookups.
+ *
+ * Works backwards from a conjugated form (食べました, 行かなかった, 読んでいる)
+ * to candidate dictionary forms. Each candidate carries the word class it
+ * must have in the dictionary, so 書かれる → 書く only matches a godan verb.
+ * This covers the common verb and i-adjective conjugations, not every
+ * colloquial contraction.
+ */
+
+export const WordType = {
+  V1: 1 << 0, // ichidan verb
+  V5: 1 << 1, // godan verb
+  VS: 1 << 2, // する verb
+  VK: 1 << 3, // 来る
+  ADJ_I: 1 << 4,
+  /** Internal: a て-form waiting for its own deinflection */
+  TE: 1 << 5
+} as const;
+
+const ANY = 0xff;
+const DICTIONARY_TYPES = WordType.V1 | WordType.V5 | WordType.VS | WordType.VK | WordType.ADJ_I;
+
+interface Rule {
+  from: string;
+  to: string;
+  /** Word classes the inflected form may have for the rule to apply */
+  typeIn: number;
+  typeOut: number;
+  reason: string;
+}
+
+export interface Deinflection {
+  term: string;
+  /** Word classes the dictionary entry must have; ANY for the original text */
+  type: number;
+  /** Conjugations undone, outermost first */
+  reasons: string[];
+}
+
+// Godan endings by row: dictionary (u) form, then the i/a/e/o-row stems
+const GODAN = [
+  { u: 'う', i: 'い', a: 'わ', e: 'え', o: 'お' },
+  { u: 'く', i: 'き', a: 'か', e: 'け', o: 'こ' },
+  { u: 'ぐ', i: 'ぎ', a: 'が', e: 'げ', o: 'ご' },
+  { u: 'す', i: 'し', a: 'さ', e: 'せ', o: 'そ' },
+  { u: 'つ', i: 'ち', a: 'た', e: 'て', o: 'と' },
+  { u: 'ぬ', i: 'に', a: 'な', e: 'ね', o: 'の' },
+  { u: 'ぶ', i: 'び', a: 'ば', e: 'べ', o: 'ぼ' },
+  { u: 'む', i: 'み', a: 'ま', e: 'め', o: 'も' },
+  { u: 'る', i: 'り', a: 'ら', e: 'れ', o: 'ろ' }
+];
+
+// Past and て-form endings for godan verbs, with the ending they come from
+const GODAN_PAST: [string, string][] = [
+  ['いた', 'く'],
+  ['いだ', 'ぐ'],
+  ['した', 'す'],
+  ['った', 'う'],
+  ['った', 'つ'],
+  ['った', 'る'],
+  ['んだ', 'ぬ'],
+  ['んだ', 'ぶ'],
+  ['んだ', 'む']
+];
+
+function buildRules(): Rule[] {
+  const { V1, V5, VS, VK, ADJ_I, TE } = WordType;
+  const rules: Rule[] = [];
+  const add = (from: string, to: string, typeIn: number, typeOut: number, reason: string) =>
+    rules.push({ from, to, typeIn, typeOut, reason });
+
+  // Forms built on the masu stem; たい itself conjugates as an i-adjective
+  const stemForms: [string, number, string][] = [
+    ['ます', ANY, 'polite'],
+    ['ました', ANY, 'polite past'],
+    ['ません', ANY, 'polite negative'],
+    ['ませんでした', ANY, 'polite past negative'],
+    ['ましょう', ANY, 'polite volitional'],
+    ['たい', ADJ_I, 'want']
+  ];
+  for (const [suffix, typeIn, reason] of stemForms) {
+    for (const row of GODAN) add(row.i + suffix, row.u, typeIn, V5, reason);
+    add(suffix, 'る', typeIn, V1, reason);
+    add('し' + suffix, 'する', typeIn, VS, reason);
+    add('き' + suffix, 'くる', typeIn, VK, reason);
+    add('来' + suffix, '来る', typeIn, VK, reason);
+  }
+
+  // Negative ない conjugates like an i-adjective (なかった, なくて)
+  for (const row of GODAN) add(row.a + 'ない', row.u, ADJ_I, V5, 'negative');
+  add('ない', 'る', ADJ_I, V1, 'negative');
+  add('しない', 'する', ADJ_I, VS, 'negative');
+  add('こない', 'くる', ADJ_I, VK, 'negative');
+  add('来ない', '来る', ADJ_I, VK, 'negative');
+
+  // Past and て-form
+  for (const [past, u] of GODAN_PAST) {
+    const te = past.slice(0, -1) + (past.endsWith('だ') ? 'で' : 'て');
+    add(past, u, ANY, V5, 'past');
+    add(te, u, ANY, V5, 'te');
+    add(past + 'ら', u, ANY, V5, 'conditional');
+    add(past + 'り', u, ANY, V5, 'tari');
+  }
+  for (const [past, dict, type] of [
+    ['た', 'る', V1],
+    ['した', 'する', VS],
+    ['きた', 'くる', VK],
+    ['来た', '来る', VK],
+    ['行った', '行く', V5],
+    ['いった', 'いく', V5]
+  ] as [string, string, number][]) {
+    const te = past.slice(0, -1) + 'て';
+    add(past, dict, ANY, type, 'past');
+    add(te, dict, ANY, type, 'te');
+    add(past + 'ら', dict, ANY, type, 'conditional');
+    add(past + 'り', dict, ANY, type, 'tari');
+  }
+
+  // Auxiliaries attached to the て-form. いる is itself ichidan, so ていた
+  // reaches ている through た → る.
+  for (const [aux, reason] of [
+    ['いる', 'progressive'],
+    ['る', 'progressive'],
+    ['しまう', 'completion'],
+    ['おく', 'preparation'],
+    ['ある', 'resultative'],
+    ['ください', 'request']
+  ]) {
+    add('て' + aux, 'て', ANY, TE, reason);
+    add('で' + aux, 'で', ANY, TE, reason);
+  }
+  add('ちゃう', 'て', ANY, TE, 'completion');
+  add('じゃう', 'で', ANY, TE, 'completion');
+
+  // Passive, potential and causative all conjugate as ichidan verbs
+  for (const row of GODAN) {
+    add(row.a + 'れる', row.u, V1, V5, 'passive');
+    add(row.e + 'る', row.u, V1, V5, 'potential');
+    add(row.a + 'せる', row.u, V1, V5, 'causative');
+    add(row.o + 'う', row.u, ANY, V5, 'volitional');
+    add(row.e, row.u, ANY, V5, 'imperative');
+    add(row.e + 'ば', row.u, ANY, V5, 'conditional');
+  }
+  add('られる', 'る', V1, V1, 'passive/potential');
+  add('させる', 'る', V1, V1, 'causative');
+  add('よう', 'る', ANY, V1, 'volitional');
+  add('ろ', 'る', ANY, V1, 'imperative');
+  add('れば', 'る', ANY, V1 | V5, 'conditional');
+  add('される', 'する', V1, VS, 'passive');
+  add('させる', 'する', V1, VS, 'causative');
+  add('できる', 'する', V1, VS, 'potential');
+  add('しよう', 'する', ANY, VS, 'volitional');
+  add('すれば', 'する', ANY, VS, 'conditional');
+  add('こられる', 'くる', V1, VK, 'passive/potential');
+  add('来られる', '来る', V1, VK, 'passive/potential');
+  add('こよう', 'くる', ANY, VK, 'volitional');
+  add('くれば', 'くる', ANY, VK, 'conditional');
+
+  // i-adjectives
+  add('かった', 'い', ANY, ADJ_I, 'past');
+  add('くない', 'い', ADJ_I, ADJ_I, 'negative');
+  add('くて', 'い', ANY, ADJ_I, 'te');
+  add('ければ', 'い', ANY, ADJ_I, 'conditional');
+  add('く', 'い', ANY, ADJ_I, 'adverb');
+  add('さ', 'い', ANY, ADJ_I, 'noun');
+
+  return rules;
+}
+
+const RULES = buildRules();
+
+/**
+ * All candidate dictionary forms of `word`, the word itself first.
+ * Candidates whose only class is the internal て-form are dropped.
+ */
+export function deinflect(word: string): Deinflection[] {
+  const results: Deinflection[] = [{ term: word, type: ANY, reasons: [] }];
+  const seen = new Map<string, number>([[word, ANY]]);
+
+  for (let i = 0; i < results.length; i++) {
+    const { term, type, reasons } = results[i];
+    for (const rule of RULES) {
+      if (!(type & rule.typeIn) || !term.endsWith(rule.from)) continue;
+      const next = term.slice(0, term.length - rule.from.length) + rule.to;
+      if (!next) continue;
+
+      // Only revisit a term when it gains a word class it didn't have
+      const known = seen.get(next) ?? 0;
+      if ((known & rule.typeOut) === rule.typeOut) continue;
+      seen.set(next, known | rule.typeOut);
+      results.push({ term: next, type: rule.typeOut, reasons: [...reasons, rule.reason] });
+    }
+  }
+
+  return results.filter((candidate) => candidate.type & DICTIONARY_TYPES);
+}
+
+/** Whether a dictionary part-of-speech tag satisfies a deinflection's word class */
+export function matchesWordType(partsOfSpeech: string[], type: number): boolean {
+  if (type === ANY) return true;
+  return partsOfSpeech.some(
+    (pos) =>
+      (type & WordType.V1 && pos.startsWith('v1')) ||
+      (type & WordType.V5 && pos.startsWith('v5')) ||
+      (type & WordType.VS && pos.startsWith('vs')) ||
+      (type & WordType.VK && pos === 'vk') ||
+      (type & WordType.ADJ_I && pos === 'adj-i')
+  );
+}
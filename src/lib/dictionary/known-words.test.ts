@@ -0,0 +1,74 @@
+import { describe, it, expect, beforeEach } from 'vitest';
+import { get } from 'svelte/store';
+import {
+  importKnownWords,
+  knownWordSet,
+  knownWordsWithTrash,
+  mergeKnownWords,
+  parseFrequencyList,
+  parseWordList,
+  setWordKnown
+} from './known-words';
+import { highlightPage } from './unknown-words';
+
+describe('known words store', () => {
+  beforeEach(() => knownWordsWithTrash.set({}));
+
+  it('removes a word marked known from highlights on later pages', () => {
+    const highlightedWords = (lines: string[][]) =>
+      highlightPage(lines, { known: get(knownWordSet), ranks: null, threshold: 0 })
+        .flat(2)
+        .filter((run) => run.unknown)
+        .map((run) => run.text);
+
+    expect(highlightedWords([['寿司を食べた']])).toEqual(['寿司', '食べた']);
+    setWordKnown('寿司');
+    expect(highlightedWords([['寿司と刺身']])).toEqual(['刺身']);
+  });
+
+  it('keeps un-marked words as entries so the change syncs', () => {
+    setWordKnown('寿司');
+    setWordKnown('寿司', false);
+    expect(get(knownWordSet).has('寿司')).toBe(false);
+    expect(get(knownWordsWithTrash)['寿司'].known).toBe(false);
+  });
+
+  it('counts only newly known words on import', () => {
+    setWordKnown('本');
+    expect(importKnownWords(['本', '寿司', '猫'])).toBe(2);
+    expect([...get(knownWordSet)].sort()).toEqual(['寿司', '本', '猫'].sort());
+  });
+});
+
+describe('mergeKnownWords', () => {
+  it('keeps the newest change for each word', () => {
+    const merged = mergeKnownWords(
+      {
+        本: { known: true, updatedOn: '2024-01-02T00:00:00Z' },
+        猫: { known: true, updatedOn: '2024-01-01T00:00:00Z' }
+      },
+      {
+        本: { known: false, updatedOn: '2024-01-01T00:00:00Z' },
+        猫: { known: false, updatedOn: '2024-01-03T00:00:00Z' },
+        犬: { known: true, updatedOn: '2024-01-01T00:00:00Z' }
+      }
+    );
+    expect(merged['本'].known).toBe(true);
+    expect(merged['猫'].known).toBe(false);
+    expect(merged['犬'].known).toBe(true);
+  });
+});
+
+describe('list parsing', () => {
+  it('reads the first column of a word list and skips comments', () => {
+    expect(parseWordList('# known\n本\r\n猫\t2\n\n犬,x')).toEqual(['本', '猫', '犬']);
+  });
+
+  it('ranks by line order unless a rank column is given', () => {
+    expect([...parseFrequencyList('の\nに\nの')]).toEqual([
+      ['の', 1],
+      ['に', 2]
+    ]);
+    expect(parseFrequencyList('本\t120\n猫\t3').get('猫')).toBe(3);
+  });
+});
@@ -0,0 +1,146 @@
+/**
+ * Words the reader already knows, for unknown-word highlighting.
+ *
+ * Known words are keyed by dictionary form (食べる, not 食べました) and kept in
+ * localStorage like profiles. Un-marking a word keeps an entry with
+ * `known: false` so the change wins over an older "known" on other devices;
+ * the whole map syncs as known-words.json.
+ *
+ * Frequency ranks come from a user-imported list and stay on this device in
+ * the dictionary database — they're large and identical everywhere.
+ */
+
+import { browser } from '$app/environment';
+import { derived, get, writable } from 'svelte/store';
+import { getDictionaryDb } from './db';
+
+export interface KnownWordEntry {
+  known: boolean;
+  /** ISO 8601 timestamp of the last change, for newest-wins merging */
+  updatedOn: string;
+}
+
+export type KnownWords = Record<string, KnownWordEntry>;
+
+const STORAGE_KEY = 'knownWords';
+
+const storedKnownWords = browser ? window.localStorage.getItem(STORAGE_KEY) : null;
+
+/** Every entry including un-marked ones; sync reads and writes this */
+export const knownWordsWithTrash = writable<KnownWords>(
+  storedKnownWords ? JSON.parse(storedKnownWords) : {}
+);
+
+knownWordsWithTrash.subscribe((words) => {
+  if (browser) {
+    window.localStorage.setItem(STORAGE_KEY, JSON.stringify(words));
+  }
+});
+
+export const knownWordSet = derived(
+  knownWordsWithTrash,
+  ($words) =>
+    new Set(
+      Object.entries($words)
+        .filter(([, entry]) => entry.known)
+        .map(([word]) => word)
+    )
+);
+
+export function setWordKnown(word: string, known = true) {
+  const updatedOn = new Date().toISOString();
+  knownWordsWithTrash.update((words) => ({ ...words, [word]: { known, updatedOn } }));
+}
+
+/** Mark every word as known; returns how many weren't known before */
+export function importKnownWords(words: string[]): number {
+  const updatedOn = new Date().toISOString();
+  const current = get(knownWordsWithTrash);
+  const next = { ...current };
+  let added = 0;
+  for (const word of words) {
+    if (current[word]?.known) continue;
+    next[word] = { known: true, updatedOn };
+    added++;
+  }
+  knownWordsWithTrash.set(next);
+  return added;
+}
+
+/** Per word, the most recent change wins */
+export function mergeKnownWords(local: KnownWords, cloud: KnownWords): KnownWords {
+  const merged: KnownWords = { ...local };
+  for (const [word, cloudEntry] of Object.entries(cloud)) {
+    const localEntry = local[word];
+    if (!localEntry || new Date(cloudEntry.updatedOn) > new Date(localEntry.updatedOn)) {
+      merged[word] = cloudEntry;
+    }
+  }
+  return merged;
+}
+
+/**
+ * Words from a plain-text list, one per line. Only the first tab- or
+ * comma-separated column is used, so exported spreadsheets work too.
+ */
+export function parseWordList(text: string): string[] {
+  return text
+    .split(/\r?\n/)
+    .map((line) => line.split(/[\t,]/)[0].trim())
+    .filter((word) => word && !word.startsWith('#'));
+}
+
+/**
+ * Frequency ranks from a list ordered most common first. A numeric second
+ * column is used as the rank when present; otherwise the line order is.
+ * The first (most common) rank seen for a word is kept.
+ */
+export function parseFrequencyList(text: string): Map<string, number> {
+  const ranks = new Map<string, number>();
+  let position = 0;
+  for (const line of text.split(/\r?\n/)) {
+    const [rawWord, rawRank] = line.split(/[\t,]/);
+    const word = rawWord?.trim();
+    if (!word || word.startsWith('#')) continue;
+    position++;
+    const rank = Number(rawRank);
+    if (!ranks.has(word)) {
+      ranks.set(word, rawRank !== undefined && rank > 0 ? rank : position);
+    }
+  }
+  return ranks;
+}
+
+/** Loaded frequency ranks; null until loaded or when no list was imported */
+export const frequencyRanks = writable<Map<string, number> | null>(null);
+
+let ranksLoad: Promise<void> | null = null;
+
+/** Read the imported frequency list into `frequencyRanks` once per session */
+export function loadFrequencyRanks(): Promise<void> {
+  if (!ranksLoad) {
+    ranksLoad = getDictionaryDb()
+      .frequency.toArray()
+      .then((rows) => {
+        frequencyRanks.set(
+          rows.length > 0 ? new Map(rows.map(({ term, rank }) => [term, rank])) : null
+        );
+      })
+      .catch((error) => {
+        ranksLoad = null;
+        console.error('Failed to load word frequencies:', error);
+      });
+  }
+  return ranksLoad;
+}
+
+/** Replace the stored frequency list */
+export async function importFrequencyList(ranks: Map<string, number>): Promise<void> {
+  const db = getDictionaryDb();
+  await db.transaction('rw', db.frequency, async () => {
+    await db.frequency.clear();
+    await db.frequency.bulkPut([...ranks].map(([term, rank]) => ({ term, rank })));
+  });
+  frequencyRanks.set(ranks.size > 0 ? ranks : null);
+  ranksLoad = Promise.resolve();
+}
@@ -0,0 +1,70 @@
+import { describe, it, expect, vi } from 'vitest';
+import type { DictionaryEntry } from './db';
+import { lookupText, placePopup } from './lookup';
+
+const dictionary: DictionaryEntry[] = [
+  {
+    kanji: ['食べる'],
+    readings: ['たべる'],
+    senses: [{ pos: ['v1', 'vt'], glosses: ['to eat'] }]
+  },
+  { kanji: ['食'], readings: ['しょく'], senses: [{ pos: ['n'], glosses: ['food'] }] },
+  {
+    kanji: ['書く'],
+    readings: ['かく'],
+    senses: [{ pos: ['v5k', 'vt'], glosses: ['to write'] }]
+  },
+  { kanji: [], readings: ['かる'], senses: [{ pos: ['v5r'], glosses: ['to cut'] }] }
+];
+
+const findEntries = vi.fn(async (term: string) =>
+  dictionary.filter((entry) => entry.kanji.includes(term) || entry.readings.includes(term))
+);
+
+describe('lookupText', () => {
+  it('finds the dictionary entry for a conjugated verb', async () => {
+    const result = await lookupText('食べましたか？', findEntries);
+    expect(result?.matched).toBe('食べました');
+    expect(result?.term).toBe('食べる');
+    expect(result?.entries[0].senses[0].glosses).toEqual(['to eat']);
+  });
+
+  it('prefers the longest match over a shorter word', async () => {
+    expect((await lookupText('書かれた本', findEntries))?.term).toBe('書く');
+  });
+
+  it('ignores line breaks inside a word', async () => {
+    expect((await lookupText('食べ\nました', findEntries))?.matched).toBe('食べました');
+  });
+
+  it('rejects entries of the wrong word class', async () => {
+    // 食べれ only deinflects to 食べる as a godan imperative; 食べる is ichidan
+    const result = await lookupText('食べれ', findEntries);
+    expect(result?.term).toBe('食');
+  });
+
+  it('returns null when nothing matches', async () => {
+    expect(await lookupText('？！', findEntries)).toBeNull();
+  });
+});
+
+describe('placePopup', () => {
+  const viewport = { width: 400, height: 800 };
+  const popup = { width: 300, height: 200 };
+
+  it('opens below the word when there is room', () => {
+    const anchor = { left: 20, top: 100, right: 40, bottom: 120 };
+    expect(placePopup(anchor, popup, viewport)).toEqual({ x: 20, y: 128 });
+  });
+
+  it('flips above near the bottom and stays inside the right edge', () => {
+    const anchor = { left: 380, top: 700, right: 395, bottom: 720 };
+    expect(placePopup(anchor, popup, viewport)).toEqual({ x: 92, y: 492 });
+  });
+
+  it('clamps to the viewport when neither side has room', () => {
+    const anchor = { left: 0, top: 100, right: 20, bottom: 120 };
+    const pos = placePopup(anchor, popup, { width: 400, height: 250 });
+    expect(pos).toEqual({ x: 8, y: 42 });
+  });
+});
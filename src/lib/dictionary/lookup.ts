@@ -0,0 +1,79 @@
+/**
+ * Find the dictionary word that starts at a position in OCR text.
+ *
+ * Scans prefixes from longest to shortest, deinflecting each, and returns the
+ * first one that has a dictionary entry of a matching word class — the same
+ * longest-match approach popup dictionaries like Yomitan use.
+ */
+
+import type { DictionaryEntry } from './db';
+import { deinflect, matchesWordType } from './deinflect';
+
+/** Longest prefix tried; compound words and conjugations rarely exceed this */
+const MAX_SCAN_LENGTH = 16;
+
+export interface LookupResult {
+  /** The text as it appears on the page, e.g. 食べました */
+  matched: string;
+  /** Dictionary form that was found, e.g. 食べる */
+  term: string;
+  /** Conjugations undone to get from matched to term, outermost first */
+  reasons: string[];
+  entries: DictionaryEntry[];
+}
+
+export type FindEntries = (term: string) => Promise<DictionaryEntry[]>;
+
+export async function lookupText(
+  text: string,
+  findEntries: FindEntries
+): Promise<LookupResult | null> {
+  const scan = text.replace(/\s+/g, '').slice(0, MAX_SCAN_LENGTH);
+
+  for (let length = scan.length; length > 0; length--) {
+    const matched = scan.slice(0, length);
+    for (const candidate of deinflect(matched)) {
+      const entries = (await findEntries(candidate.term)).filter((entry) =>
+        entry.senses.some((sense) => matchesWordType(sense.pos, candidate.type))
+      );
+      if (entries.length > 0) {
+        return { matched, term: candidate.term, reasons: candidate.reasons, entries };
+      }
+    }
+  }
+  return null;
+}
+
+interface Rect {
+  left: number;
+  top: number;
+  right: number;
+  bottom: number;
+}
+
+interface Size {
+  width: number;
+  height: number;
+}
+
+const POPUP_MARGIN = 8;
+
+/**
+ * Where to put a popup of `popup` size next to the word at `anchor`: below it
+ * when there's room, otherwise above, and always fully inside the viewport.
+ */
+export function placePopup(anchor: Rect, popup: Size, viewport: Size): { x: number; y: number } {
+  const maxX = viewport.width - popup.width - POPUP_MARGIN;
+  const maxY = viewport.height - popup.height - POPUP_MARGIN;
+
+  let y = anchor.bottom + POPUP_MARGIN;
+  if (y > maxY) {
+    const above = anchor.top - popup.height - POPUP_MARGIN;
+    y = above >= POPUP_MARGIN ? above : maxY;
+  }
+
+  return {
+    x: Math.max(POPUP_MARGIN, Math.min(anchor.left, maxX)),
+    y: Math.max(POPUP_MARGIN, y)
+  };
+}
@@ -0,0 +1,47 @@
+import { describe, it, expect } from 'vitest';
+import { highlightLine, highlightPage, type TextRun } from './unknown-words';
+
+const show = (runs: TextRun[]) =>
+  runs.map((run) => (run.unknown ? `[${run.text}]` : run.text)).join('');
+
+const options = (known: string[], ranks: [string, number][] | null = null, threshold = 0) => ({
+  known: new Set(known),
+  ranks: ranks && new Map(ranks),
+  threshold
+});
+
+describe('highlightLine', () => {
+  it('highlights unknown words but not particles or punctuation', () => {
+    expect(show(highlightLine('昨日は寿司を食べました。', options([])))).toBe(
+      '[昨日]は[寿司]を[食べました]。'
+    );
+  });
+
+  it('treats conjugated forms of a known dictionary form as known', () => {
+    expect(show(highlightLine('昨日は寿司を食べました。', options(['昨日', '食べる'])))).toBe(
+      '昨日は[寿司]を食べました。'
+    );
+  });
+
+  it('keeps unknown words more common than the threshold plain', () => {
+    const ranks: [string, number][] = [
+      ['問題', 100],
+      ['難しい', 5000]
+    ];
+    expect(show(highlightLine('難しい問題', options([], ranks, 1000)))).toBe('[難しい]問題');
+    expect(show(highlightLine('難しい問題', options([], ranks, 0)))).toBe('[難しい][問題]');
+  });
+
+  it('preserves the original text', () => {
+    const line = '「彼は本を読んでいる…」';
+    const runs = highlightLine(line, options(['彼']));
+    expect(runs.map((run) => run.text).join('')).toBe(line);
+  });
+});
+
+describe('highlightPage', () => {
+  it('returns runs per text box and line', () => {
+    const page = highlightPage([['寿司'], ['本', 'を読む']], options(['本']));
+    expect(page.map((box) => box.map(show))).toEqual([['[寿司]'], ['本', 'を[読む]']]);
+  });
+});
@@ -0,0 +1,163 @@
+/**
+ * Split OCR lines into runs of plain and highlighted (unknown) text.
+ *
+ * Lines are word-segmented with Intl.Segmenter, then neighbouring segments
+ * are joined while the result deinflects to a known or ranked word, so
+ * 食べ + まし + た is checked as 食べる rather than three fragments. Runs
+ * once per page when the page, known words or settings change — never per
+ * frame.
+ */
+
+import { deinflect } from './deinflect';
+
+export interface TextRun {
+  text: string;
+  unknown: boolean;
+}
+
+export interface HighlightOptions {
+  known: Set<string>;
+  /** Frequency ranks (1 = most common); null when no list is imported */
+  ranks: Map<string, number> | null;
+  /** Unknown words ranked at or above this stay plain; 0 highlights all */
+  threshold: number;
+}
+
+/** Segments joined at most; ICU splits 食べました into 食|べ|ま|した */
+const MAX_JOINED_SEGMENTS = 6;
+
+const JAPANESE_RE = /[぀-ヿ㐀-鿿]/;
+const SHORT_HIRAGANA_RE = /^[぀-ゟ]{1,2}$/;
+const KANA_RE = /^[぀-ヿ]+$/;
+
+let segmenter: Intl.Segmenter | null = null;
+
+interface Segment {
+  text: string;
+  wordLike: boolean;
+}
+
+function segmentWords(line: string): Segment[] {
+  segmenter ??= new Intl.Segmenter('ja', { granularity: 'word' });
+  return Array.from(segmenter.segment(line), ({ segment, isWordLike }) => ({
+    text: segment,
+    wordLike: !!isWordLike
+  }));
+}
+
+interface Forms {
+  /** The text itself and every dictionary form it could deinflect to */
+  terms: string[];
+  /** Whether any conjugation rule applies, i.e. the text looks inflected */
+  inflected: boolean;
+}
+
+export type FormsCache = Map<string, Forms>;
+
+function dictionaryForms(text: string, cache: FormsCache): Forms {
+  let forms = cache.get(text);
+  if (!forms) {
+    const candidates = deinflect(text).filter((candidate) => candidate.reasons.length > 0);
+    forms = {
+      terms: [text, ...candidates.map((candidate) => candidate.term)],
+      inflected: candidates.length > 0
+    };
+    cache.set(text, forms);
+  }
+  return forms;
+}
+
+function joinSegments(segments: { text: string }[], start: number, stop: number): string {
+  return segments
+    .slice(start, stop)
+    .map((segment) => segment.text)
+    .join('');
+}
+
+interface WordMatch {
+  length: number;
+  known: boolean;
+  rank?: number;
+}
+
+/**
+ * The longest run of segments from `start` that is a known or ranked word.
+ * Failing that, the longest run that looks like a conjugation, so an unknown
+ * verb is highlighted whole rather than just its kanji.
+ */
+function matchWord(
+  segments: Segment[],
+  start: number,
+  options: HighlightOptions,
+  cache: FormsCache
+): WordMatch {
+  // Words never span punctuation
+  let end = start + 1;
+  while (end < segments.length && end - start < MAX_JOINED_SEGMENTS && segments[end].wordLike) {
+    end++;
+  }
+
+  for (let stop = end; stop > start; stop--) {
+    let rank: number | undefined;
+    for (const term of dictionaryForms(joinSegments(segments, start, stop), cache).terms) {
+      if (options.known.has(term)) return { length: stop - start, known: true };
+      const termRank = options.ranks?.get(term);
+      if (termRank !== undefined && (rank === undefined || termRank < rank)) rank = termRank;
+    }
+    if (rank !== undefined) return { length: stop - start, known: false, rank };
+  }
+  // Only kana can be a conjugated ending, so 寿司を食べた isn't taken as one word
+  let kanaEnd = start + 1;
+  while (kanaEnd < end && KANA_RE.test(segments[kanaEnd].text)) kanaEnd++;
+  for (let stop = kanaEnd; stop > start + 1; stop--) {
+    if (dictionaryForms(joinSegments(segments, start, stop), cache).inflected) {
+      return { length: stop - start, known: false };
+    }
+  }
+  return { length: 1, known: false };
+}
+
+function isHighlightable(text: string): boolean {
+  // Particles and short function words would otherwise light up every line
+  return JAPANESE_RE.test(text) && !SHORT_HIRAGANA_RE.test(text);
+}
+
+export function highlightLine(
+  line: string,
+  options: HighlightOptions,
+  cache: FormsCache = new Map()
+): TextRun[] {
+  const segments = segmentWords(line);
+  const runs: TextRun[] = [];
+  const push = (text: string, unknown: boolean) => {
+    const last = runs[runs.length - 1];
+    if (last && last.unknown === unknown && !unknown) last.text += text;
+    else runs.push({ text, unknown });
+  };
+
+  for (let i = 0; i < segments.length; ) {
+    const segment = segments[i];
+    if (!segment.wordLike) {
+      push(segment.text, false);
+      i++;
+      continue;
+    }
+
+    const match = matchWord(segments, i, options, cache);
+    const text = joinSegments(segments, i, i + match.length);
+    const unknown =
+      !match.known &&
+      isHighlightable(text) &&
+      (match.rank === undefined || match.rank > options.threshold);
+    push(text, unknown);
+    i += match.length;
+  }
+
+  return runs;
+}
+
+/** Highlight runs for every line of every text box on a page */
+export function highlightPage(boxes: string[][], options: HighlightOptions): TextRun[][][] {
+  const cache: FormsCache = new Map();
+  return boxes.map((lines) => lines.map((line) => highlightLine(line, options, cache)));
+}
@@ -0,0 +1,147 @@
+/**
+ * Tests for animated page detection
+ *
+ * Animated WebP, GIF and APNG pages are detected from their headers, and their
+ * canvas size is used as the page size.
+ */
+
+import { describe, it, expect, vi, afterEach } from 'vitest';
+import { detectAnimatedImage, readAnimatedImageInfo } from '../animated-images';
+import { processVolume } from '../processing';
+import { miscSettings } from '$lib/settings/misc';
+
+vi.mock('$lib/catalog/thumbnails', () => ({
+  generateThumbnail: vi.fn().mockResolvedValue({
+    file: new File([], 'thumbnail.jpg'),
+    width: 200,
+    height: 300
+  })
+}));
+
+const ascii = (text: string) => [...text].map((c) => c.charCodeAt(0));
+const uint24 = (value: number) => [value & 0xff, (value >> 8) & 0xff, (value >> 16) & 0xff];
+const uint32be = (value: number) => [
+  value >>> 24,
+  (value >> 16) & 0xff,
+  (value >> 8) & 0xff,
+  value & 0xff
+];
+
+function webp(width: number, height: number, animated: boolean): Uint8Array {
+  return new Uint8Array([
+    ...ascii('RIFF'),
+    ...[0, 0, 0, 0],
+    ...ascii('WEBP'),
+    ...ascii('VP8X'),
+    ...[10, 0, 0, 0],
+    animated ? 0x02 : 0x00,
+    ...[0, 0, 0],
+    ...uint24(width - 1),
+    ...uint24(height - 1)
+  ]);
+}
+
+function gif(width: number, height: number, frameCount: number): Uint8Array {
+  const frame = [0x2c, 0, 0, 0, 0, width & 0xff, width >> 8, height & 0xff, height >> 8, 0x00];
+  const frames = Array.from({ length: frameCount }, () => [...frame, 0x02, 0x01, 0x00, 0x00]);
+  return new Uint8Array([
+    ...ascii('GIF89a'),
+    ...[width & 0xff, width >> 8, height & 0xff, height >> 8, 0x00, 0, 0],
+    // Graphic control extension, as animated GIFs carry before each frame
+    ...[0x21, 0xf9, 0x04, 0x00, 0x0a, 0x00, 0x00, 0x00],
+    ...frames.flat(),
+    0x3b
+  ]);
+}
+
+function png(width: number, height: number, animated: boolean): Uint8Array {
+  const chunk = (type: string, data: number[]) => [
+    ...uint32be(data.length),
+    ...ascii(type),
+    ...data,
+    ...[0, 0, 0, 0]
+  ];
+  return new Uint8Array([
+    ...[0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a],
+    ...chunk('IHDR', [...uint32be(width), ...uint32be(height), 8, 6, 0, 0, 0]),
+    ...(animated ? chunk('acTL', [...uint32be(2), ...uint32be(0)]) : []),
+    ...chunk('IDAT', [0])
+  ]);
+}
+
+describe('detectAnimatedImage', () => {
+  it('reads the canvas size of animated WebP', () => {
+    expect(detectAnimatedImage(webp(800, 1200, true))).toEqual({ width: 800, height: 1200 });
+    expect(detectAnimatedImage(webp(800, 1200, false))).toBeNull();
+  });
+
+  it('tells multi-frame GIFs from still ones', () => {
+    expect(detectAnimatedImage(gif(640, 960, 3))).toEqual({ width: 640, height: 960 });
+    expect(detectAnimatedImage(gif(640, 960, 1))).toBeNull();
+  });
+
+  it('detects APNG', () => {
+    expect(detectAnimatedImage(png(500, 700, true))).toEqual({ width: 500, height: 700 });
+    expect(detectAnimatedImage(png(500, 700, false))).toBeNull();
+  });
+
+  it('ignores JPEGs', () => {
+    expect(detectAnimatedImage(new Uint8Array([0xff, 0xd8, 0xff, 0xe0, 0, 0]))).toBeNull();
+  });
+});
+
+describe('readAnimatedImageInfo', () => {
+  it('reads files of each format', async () => {
+    expect(await readAnimatedImageInfo(new Blob([gif(10, 20, 2)]))).toEqual({
+      width: 10,
+      height: 20
+    });
+    expect(await readAnimatedImageInfo(new Blob([webp(30, 40, true)]))).toEqual({
+      width: 30,
+      height: 40
+    });
+  });
+});
+
+describe('importing animated pages', () => {
+  afterEach(() => {
+    miscSettings.update((settings) => ({ ...settings, animatedPages: 'play' }));
+  });
+
+  it('sizes an animated WebP page from its canvas', async () => {
+    const imageFiles = new Map<string, File>([
+      ['001.webp', new File([webp(800, 1200, true)], '001.webp', { type: 'image/webp' })]
+    ]);
+
+    const processed = await processVolume({
+      mokuroFile: null,
+      thumbnailSidecar: null,
+      imageFiles,
+      basePath: 'Animated/Animated v01',
+      sourceType: 'local',
+      nestedArchives: []
+    });
+
+    const page = processed.ocrData.pages[0];
+    expect([page.img_width, page.img_height]).toEqual([800, 1200]);
+    expect(processed.fileData.files['001.webp']).toBe(imageFiles.get('001.webp'));
+  });
+
+  it('keeps the page when flattening is unavailable', async () => {
+    miscSettings.update((settings) => ({ ...settings, animatedPages: 'flatten' }));
+    const file = new File([gif(64, 96, 2)], '001.gif', { type: 'image/gif' });
+
+    const processed = await processVolume({
+      mokuroFile: null,
+      thumbnailSidecar: null,
+      imageFiles: new Map([['001.gif', file]]),
+      basePath: 'Animated/Animated v02',
+      sourceType: 'local',
+      nestedArchives: []
+    });
+
+    // No canvas in jsdom, so the original stays rather than a broken page
+    expect(processed.fileData.files['001.gif']).toBe(file);
+    expect(processed.ocrData.pages[0].img_width).toBe(64);
+  });
+});
@@ -0,0 +1,115 @@
+/**
+ * Tests for all-or-nothing imports
+ *
+ * A volume only becomes visible once every page, its OCR and its metadata are
+ * stored. A corrupt page fails the volume before anything is written, and a
+ * failed write rolls back the ones before it.
+ */
+
+import { describe, it, expect, vi, beforeEach } from 'vitest';
+import 'fake-indexeddb/auto';
+import { db } from '$lib/catalog/db';
+import { ImportEntryError, hasImageSignature, processVolume } from '../processing';
+import { saveVolume } from '../database';
+import type { DecompressedVolume } from '../types';
+
+vi.mock('$lib/catalog/thumbnails', () => ({
+  generateThumbnail: vi.fn().mockResolvedValue({
+    file: new File([], 'thumbnail.jpg'),
+    width: 200,
+    height: 300
+  })
+}));
+
+const PNG_HEADER = [0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a];
+
+function pageName(index: number): string {
+  return `${String(index).padStart(3, '0')}.png`;
+}
+
+function createVolume(pageCount: number, corruptPage?: number): DecompressedVolume {
+  const imageFiles = new Map<string, File>();
+  for (let i = 1; i <= pageCount; i++) {
+    const bytes = i === corruptPage ? [0x00, 0x13, 0x37, 0x00] : PNG_HEADER;
+    imageFiles.set(pageName(i), new File([new Uint8Array([...bytes, 0, 0, 0, 0])], pageName(i)));
+  }
+  return {
+    mokuroFile: null,
+    thumbnailSidecar: null,
+    imageFiles,
+    basePath: 'Atomic Series/Atomic Series v01',
+    sourceType: 'local',
+    nestedArchives: []
+  };
+}
+
+describe('atomic import', () => {
+  beforeEach(async () => {
+    await Promise.all([db.volumes.clear(), db.volume_ocr.clear(), db.volume_files.clear()]);
+  });
+
+  it('fails on a corrupt page and names the entry', async () => {
+    const volume = createVolume(50, 20);
+
+    const error = await processVolume(volume).catch((e) => e);
+
+    expect(error).toBeInstanceOf(ImportEntryError);
+    expect(error.entry).toBe('020.png');
+    expect(error.message).toContain('page 20');
+    expect(await db.volumes.count()).toBe(0);
+  });
+
+  it('decodes every page and names a truncated entry with a valid header', async () => {
+    // The header and size probe pass; only the full decode fails
+    let probed33 = false;
+    const decode = vi.fn(async (file: File) => {
+      if (file.name === pageName(33)) {
+        if (probed33) throw new DOMException('Broken image', 'InvalidStateError');
+        probed33 = true;
+      }
+      return { width: 800, height: 1200, close: vi.fn() };
+    });
+    vi.stubGlobal('createImageBitmap', decode);
+
+    const error = await processVolume(createVolume(50)).catch((e) => e);
+    vi.unstubAllGlobals();
+
+    expect(new Set(decode.mock.calls.map(([file]) => file.name)).size).toBe(50);
+    expect(error).toBeInstanceOf(ImportEntryError);
+    expect(error.entry).toBe('033.png');
+    expect(error.message).toContain('page 33');
+  });
+
+  it('rolls back every table when a write fails partway', async () => {
+    const processed = await processVolume(createVolume(50));
+    const uuid = processed.metadata.volumeUuid;
+    const add = vi
+      .spyOn(db.volume_files, 'add')
+      .mockRejectedValueOnce(new Error('QuotaExceededError'));
+
+    await expect(saveVolume(processed)).rejects.toThrow('QuotaExceededError');
+    add.mockRestore();
+
+    expect(await db.volumes.get(uuid)).toBeUndefined();
+    expect(await db.volume_ocr.get(uuid)).toBeUndefined();
+    expect(await db.volume_files.get(uuid)).toBeUndefined();
+  });
+
+  it('saves a volume whose pages all read', async () => {
+    const processed = await processVolume(createVolume(50));
+
+    await saveVolume(processed);
+
+    const stored = await db.volume_files.get(processed.metadata.volumeUuid);
+    expect(Object.keys(stored?.files ?? {})).toHaveLength(50);
+  });
+});
+
+describe('hasImageSignature', () => {
+  it('recognizes common page formats', () => {
+    expect(hasImageSignature(new Uint8Array([0xff, 0xd8, 0xff, 0xe0]))).toBe(true);
+    expect(hasImageSignature(new Uint8Array(PNG_HEADER))).toBe(true);
+    expect(hasImageSignature(new TextEncoder().encode('RIFF\0\0\0\0WEBP'))).toBe(true);
+    expect(hasImageSignature(new TextEncoder().encode('<html>'))).toBe(false);
+  });
+});
@@ -8,25 +8,38 @@
  */
 
 import { describe, it, expect, vi, beforeEach } from 'vitest';
-import { saveVolume, volumeExists, deleteVolume } from '../database';
+import {
+  saveVolume,
+  volumeExists,
+  deleteVolume,
+  replaceVolumeContent,
+  pageCountWarning,
+  isFromSameSource
+} from '../database';
+import { get } from 'svelte/store';
+import { VolumeData, volumes, volumesWithTrash } from '$lib/settings/volume-data';
 import type { ProcessedVolume, ProcessedMetadata, ProcessedPage } from '../types';
+import type { VolumeMetadata } from '$lib/types';
 
 // Mock the db module to use our test database
 vi.mock('$lib/catalog/db', () => ({
   db: {
     volumes: {
       add: vi.fn(),
+      put: vi.fn(),
       get: vi.fn(),
       where: vi.fn(),
       delete: vi.fn()
     },
     volume_ocr: {
       add: vi.fn(),
+      put: vi.fn(),
       get: vi.fn(),
       delete: vi.fn()
     },
     volume_files: {
       add: vi.fn(),
+      put: vi.fn(),
       get: vi.fn(),
       delete: vi.fn()
     },
@@ -131,6 +144,24 @@ describe('saveVolume', () => {
     expect(addCall.character_count).toBe(500);
   });
 
+  it('records the file the volume was imported from', async () => {
+    const volume = createProcessedVolume();
+    volume.metadata.origin = {
+      originalFilename: 'Volume 01.cbz',
+      sourcePath: 'Manga/Volume 01.cbz',
+      provider: 'google-drive',
+      fileId: 'drive-file-id'
+    };
+
+    await saveVolume(volume);
+
+    const addCall = (db.volumes.add as any).mock.calls[0][0];
+    expect(addCall.original_filename).toBe('Volume 01.cbz');
+    expect(addCall.source_path).toBe('Manga/Volume 01.cbz');
+    expect(addCall.source_provider).toBe('google-drive');
+    expect(addCall.source_file_id).toBe('drive-file-id');
+  });
+
   it('sanitizes filesystem-illegal characters in series and volume titles', async () => {
     const volume = createProcessedVolume({
       metadata: { series: 'A/B: C', volume: 'Vol?1' } as ProcessedMetadata
@@ -292,6 +323,79 @@ describe('saveVolume', () => {
   });
 });
 
+describe('saveVolume reading direction', () => {
+  const verticalPages = (vertical: boolean): ProcessedPage[] => [
+    {
+      img_path: 'page001.jpg',
+      blocks: Array.from({ length: 12 }, () => ({ vertical, lines: ['セリフ'] })),
+      cumulativeChars: 36
+    }
+  ];
+
+  beforeEach(() => {
+    vi.clearAllMocks();
+    (db.transaction as any).mockImplementation(
+      async (_mode: string, _tables: any[], callback: () => Promise<void>) => {
+        await callback();
+      }
+    );
+    (db.volumes.get as any).mockResolvedValue(undefined);
+    volumesWithTrash.set({});
+  });
+
+  it('sets right to left for a volume with vertical text', async () => {
+    await saveVolume(
+      createProcessedVolume({
+        ocrData: { volume_uuid: 'test-volume-uuid', pages: verticalPages(true) }
+      })
+    );
+
+    expect(get(volumes)['test-volume-uuid'].settings).toEqual({
+      rightToLeft: true,
+      autoDirection: true
+    });
+  });
+
+  it('keeps a direction the user chose when the volume is imported again', async () => {
+    volumesWithTrash.set({
+      'test-volume-uuid': new VolumeData({ progress: 4, settings: { rightToLeft: false } })
+    });
+
+    await saveVolume(
+      createProcessedVolume({
+        ocrData: { volume_uuid: 'test-volume-uuid', pages: verticalPages(true) }
+      })
+    );
+
+    const data = get(volumes)['test-volume-uuid'];
+    expect(data.settings).toEqual({ rightToLeft: false });
+    expect(data.progress).toBe(4);
+  });
+
+  it("follows the direction chosen for the series' other volumes", async () => {
+    volumesWithTrash.set({
+      'volume-1': new VolumeData({
+        series_uuid: 'test-series-uuid',
+        settings: { rightToLeft: false }
+      })
+    });
+
+    await saveVolume(
+      createProcessedVolume({
+        ocrData: { volume_uuid: 'test-volume-uuid', pages: verticalPages(true) }
+      })
+    );
+
+    expect(get(volumes)['test-volume-uuid'].settings.rightToLeft).toBe(false);
+  });
+
+  it('leaves volumes without enough text on the global default', async () => {
+    await saveVolume(createProcessedVolume());
+
+    expect(get(volumes)['test-volume-uuid']).toBeUndefined();
+  });
+});
+
 describe('volumeExists', () => {
   beforeEach(() => {
     vi.clearAllMocks();
@@ -342,3 +446,147 @@ describe('deleteVolume', () => {
     );
   });
 });
+
+describe('replaceVolumeContent', () => {
+  const existing = {
+    volume_uuid: 'existing-uuid',
+    series_uuid: 'existing-series',
+    series_title: 'Kept Series',
+    volume_title: 'Kept Volume',
+    mokuro_version: '0.1.0',
+    page_count: 2,
+    character_count: 40,
+    page_char_counts: [20, 40],
+    sort_index: 3
+  };
+
+  beforeEach(() => {
+    vi.clearAllMocks();
+    (db.transaction as any).mockImplementation(
+      async (_mode: string, _tables: any[], callback: () => Promise<void>) => {
+        await callback();
+      }
+    );
+    (db.volumes.get as any).mockResolvedValue(existing);
+    volumesWithTrash.set({
+      'existing-uuid': new VolumeData({
+        progress: 2,
+        chars: 40,
+        series_uuid: 'existing-series',
+        timeReadInMinutes: 15
+      })
+    });
+  });
+
+  it('swaps in new pages and OCR while keeping UUID, titles and overrides', async () => {
+    const volume = createProcessedVolume({
+      metadata: {
+        volumeUuid: 'new-mokuro-uuid',
+        seriesUuid: 'new-series',
+        series: 'New Series',
+        volume: 'New Volume',
+        mokuroVersion: '0.2.1',
+        pageCount: 2,
+        chars: 100,
+        thumbnail: null,
+        thumbnailWidth: 0,
+        thumbnailHeight: 0
+      }
+    });
+
+    await replaceVolumeContent('existing-uuid', volume);
+
+    const putCall = (db.volumes.put as any).mock.calls[0][0];
+    expect(putCall.volume_uuid).toBe('existing-uuid');
+    expect(putCall.series_uuid).toBe('existing-series');
+    expect(putCall.series_title).toBe('Kept Series');
+    expect(putCall.volume_title).toBe('Kept Volume');
+    expect(putCall.sort_index).toBe(3);
+    expect(putCall.mokuro_version).toBe('0.2.1');
+    expect(putCall.character_count).toBe(100);
+    expect(putCall.page_char_counts).toEqual([50, 100]);
+
+    const ocrCall = (db.volume_ocr.put as any).mock.calls[0][0];
+    expect(ocrCall.volume_uuid).toBe('existing-uuid');
+    expect(ocrCall.pages[0]).not.toHaveProperty('cumulativeChars');
+    expect((db.volume_files.put as any).mock.calls[0][0].volume_uuid).toBe('existing-uuid');
+    expect(db.volumes.add).not.toHaveBeenCalled();
+  });
+
+  it('keeps the reading position and time read', async () => {
+    await replaceVolumeContent('existing-uuid', createProcessedVolume());
+
+    const data = get(volumes)['existing-uuid'];
+    expect(data.progress).toBe(2);
+    expect(data.timeReadInMinutes).toBe(15);
+  });
+
+  it('clamps progress past the new last page', async () => {
+    volumesWithTrash.set({
+      'existing-uuid': new VolumeData({ progress: 5, series_uuid: 'existing-series' })
+    });
+
+    await replaceVolumeContent('existing-uuid', createProcessedVolume());
+
+    expect(get(volumes)['existing-uuid'].progress).toBe(2);
+  });
+
+  it('records the replacement files as the source', async () => {
+    const volume = createProcessedVolume();
+    volume.metadata.origin = { originalFilename: 'Rescan', sourcePath: 'Downloads/Rescan' };
+
+    await replaceVolumeContent('existing-uuid', volume);
+
+    const putCall = (db.volumes.put as any).mock.calls[0][0];
+    expect(putCall.original_filename).toBe('Rescan');
+    expect(putCall.source_path).toBe('Downloads/Rescan');
+  });
+
+  it('throws if the volume does not exist', async () => {
+    (db.volumes.get as any).mockResolvedValue(undefined);
+
+    await expect(replaceVolumeContent('missing', createProcessedVolume())).rejects.toThrow(
+      'does not exist'
+    );
+    expect(db.volume_ocr.put).not.toHaveBeenCalled();
+  });
+});
+
+describe('isFromSameSource', () => {
+  const volume = {
+    original_filename: 'Volume 01.cbz',
+    source_path: 'Series/Volume 01.cbz',
+    page_count: 180
+  } as VolumeMetadata;
+
+  it('matches the same file name, path and page count', () => {
+    const origin = { originalFilename: 'volume 01.CBZ', sourcePath: 'series/volume 01.CBZ' };
+
+    expect(isFromSameSource(volume, origin, 180)).toBe(true);
+    expect(isFromSameSource(volume, origin, 181)).toBe(false);
+  });
+
+  it('keeps same-named folders from different series apart', () => {
+    const images = { original_filename: 'images', source_path: 'Series A/images', page_count: 40 };
+    const origin = { originalFilename: 'images', sourcePath: 'Series B/images' };
+
+    expect(isFromSameSource(images as VolumeMetadata, origin, 40)).toBe(false);
+  });
+
+  it('never matches volumes without a recorded source', () => {
+    const origin = { originalFilename: 'Volume 01.cbz', sourcePath: 'Volume 01.cbz' };
+
+    expect(isFromSameSource({ page_count: 180 } as VolumeMetadata, origin, 180)).toBe(false);
+  });
+});
+
+describe('pageCountWarning', () => {
+  it('returns null when the page counts match', () => {
+    expect(pageCountWarning(120, 120)).toBeNull();
+  });
+
+  it('describes the difference when they do not', () => {
+    expect(pageCountWarning(120, 118)).toContain('2 fewer pages (118 instead of 120)');
+    expect(pageCountWarning(120, 121)).toContain('1 more page (121 instead of 120)');
+  });
+});
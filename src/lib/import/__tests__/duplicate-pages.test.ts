@@ -0,0 +1,136 @@
+/**
+ * Tests for duplicate page detection
+ *
+ * Pages are compared by difference hash of a 9x8 grayscale thumbnail;
+ * the tests feed grayscale grids directly instead of decoding images.
+ */
+
+import { describe, it, expect } from 'vitest';
+import {
+  differenceHash,
+  hashDistance,
+  findDuplicateAdjacentPages,
+  skipPages,
+  visiblePageCharCounts,
+  withoutSkippedPages,
+  DUPLICATE_MAX_DISTANCE
+} from '../duplicate-pages';
+import type { ProcessedVolume } from '../types';
+
+// A 9x8 grayscale "cover", a slightly brighter, noisier re-scan of it, and a different page
+const cover = Array.from({ length: 72 }, (_, i) => ((i * 37) % 101) * 2);
+const coverRescan = cover.map((value, i) => value + 10 + (i % 5 === 0 ? 3 : -2));
+const otherPage = Array.from({ length: 72 }, (_, i) => ((i * 53) % 97) * 2);
+
+function createVolume(): ProcessedVolume {
+  const paths = ['cover.jpg', 'cover2.jpg', '001.jpg', '002.jpg'];
+  return {
+    metadata: {
+      volumeUuid: 'volume-uuid',
+      seriesUuid: 'series-uuid',
+      series: 'Series',
+      volume: 'Volume 01',
+      pageCount: 4,
+      chars: 30,
+      thumbnail: null,
+      thumbnailWidth: 0,
+      thumbnailHeight: 0
+    },
+    ocrData: {
+      volume_uuid: 'volume-uuid',
+      pages: [
+        { img_path: 'cover.jpg', blocks: [], cumulativeChars: 0 },
+        { img_path: 'cover2.jpg', blocks: [], cumulativeChars: 0 },
+        { img_path: '001.jpg', blocks: [], cumulativeChars: 10 },
+        { img_path: '002.jpg', blocks: [], cumulativeChars: 30 }
+      ]
+    },
+    fileData: {
+      volume_uuid: 'volume-uuid',
+      files: Object.fromEntries(paths.map((path) => [path, new File([], path)]))
+    },
+    nestedSources: []
+  };
+}
+
+describe('differenceHash', () => {
+  it('produces a 64-bit hash as 16 hex digits', () => {
+    expect(differenceHash(cover)).toMatch(/^[0-9a-f]{16}$/);
+  });
+
+  it('gives near-identical scans nearby hashes and different pages distant ones', () => {
+    const coverHash = differenceHash(cover);
+
+    expect(hashDistance(coverHash, differenceHash(coverRescan))).toBeLessThanOrEqual(
+      DUPLICATE_MAX_DISTANCE
+    );
+    expect(hashDistance(coverHash, differenceHash(otherPage))).toBeGreaterThan(
+      DUPLICATE_MAX_DISTANCE
+    );
+  });
+});
+
+describe('hashDistance', () => {
+  it('counts differing bits', () => {
+    expect(hashDistance('0000000000000000', '0000000000000000')).toBe(0);
+    expect(hashDistance('0000000000000000', '000000000000000f')).toBe(4);
+    expect(hashDistance('8000000000000001', '0000000000000000')).toBe(2);
+  });
+});
+
+describe('findDuplicateAdjacentPages', () => {
+  it('flags the second of two near-identical cover scans', () => {
+    const hashes = [cover, coverRescan, otherPage].map(differenceHash);
+
+    expect(findDuplicateAdjacentPages(hashes)).toEqual([1]);
+  });
+
+  it('only compares neighbors and skips pages that could not be hashed', () => {
+    const [a, b] = [cover, otherPage].map(differenceHash);
+
+    expect(findDuplicateAdjacentPages([a, b, a])).toEqual([]);
+    expect(findDuplicateAdjacentPages([a, null, a])).toEqual([]);
+  });
+});
+
+describe('skipPages', () => {
+  it('keeps the duplicate page and its OCR, and counts only the pages shown', () => {
+    const volume = createVolume();
+    const result = skipPages(volume, [1]);
+
+    expect(result.ocrData).toBe(volume.ocrData);
+    expect(result.metadata.pageCount).toBe(3);
+    expect(result.metadata.chars).toBe(30);
+    expect(result.metadata.skippedPagePaths).toEqual(['cover2.jpg']);
+  });
+
+  it('keeps the skipped image so the change is non-destructive', () => {
+    const result = skipPages(createVolume(), [1]);
+
+    expect(result.fileData.files['cover2.jpg']).toBeDefined();
+  });
+
+  it('recomputes cumulative characters without the skipped page', () => {
+    const result = skipPages(createVolume(), [2]);
+
+    const { pages } = result.ocrData;
+    expect(visiblePageCharCounts(pages, result.metadata.skippedPagePaths)).toEqual([0, 0, 20]);
+    expect(result.metadata.chars).toBe(20);
+  });
+
+  it('returns the volume unchanged when nothing is skipped', () => {
+    const volume = createVolume();
+
+    expect(skipPages(volume, [])).toBe(volume);
+  });
+});
+
+describe('withoutSkippedPages', () => {
+  it('hides skipped pages when the volume is shown', () => {
+    const { ocrData } = createVolume();
+
+    const shown = withoutSkippedPages(ocrData.pages, ['cover2.jpg']);
+    expect(shown.map((page) => page.img_path)).toEqual(['cover.jpg', '001.jpg', '002.jpg']);
+    expect(withoutSkippedPages(ocrData.pages, undefined)).toBe(ocrData.pages);
+  });
+});
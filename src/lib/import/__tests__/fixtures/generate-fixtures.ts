@@ -669,6 +669,58 @@ createFixture(
   ])
 );
 
+// 11. mokuro-with-named-image-subfolder
+// Pattern: [Volume]/[Volume].mokuro + [Volume]/images/*.jpg (image folder not named after the mokuro)
+createFixture(
+  'edge-cases',
+  'mokuro-with-named-image-subfolder',
+  {
+    'volume/volume.mokuro': createMokuro('Test Manga', 'Volume 1', 2, 'images'),
+    'volume/images/page001.jpg': TINY_PNG,
+    'volume/images/page002.jpg': TINY_PNG
+  },
+  createExpected(1, [
+    {
+      sourceType: 'directory',
+      hasMokuro: true,
+      basePathContains: 'volume/images',
+      imageOnly: false,
+      fileCount: 2
+    }
+  ])
+);
+
+// 12. nested-mokuro-with-same-name-dir
+// Pattern: [Wrapper]/[Series]/[Vol].mokuro + [Wrapper]/[Series]/[Vol]/ (mokuro below the root)
+createFixture(
+  'edge-cases',
+  'nested-mokuro-with-same-name-dir',
+  {
+    'library/series/vol1.mokuro': createMokuro('Test Series', 'Volume 1', 2),
+    'library/series/vol1/page001.jpg': TINY_PNG,
+    'library/series/vol1/page002.jpg': TINY_PNG,
+    'library/series/vol2.mokuro': createMokuro('Test Series', 'Volume 2', 2),
+    'library/series/vol2/page001.jpg': TINY_PNG,
+    'library/series/vol2/page002.jpg': TINY_PNG
+  },
+  createExpected(2, [
+    {
+      sourceType: 'directory',
+      hasMokuro: true,
+      basePathContains: 'library/series/vol1',
+      imageOnly: false,
+      fileCount: 2
+    },
+    {
+      sourceType: 'directory',
+      hasMokuro: true,
+      basePathContains: 'library/series/vol2',
+      imageOnly: false,
+      fileCount: 2
+    }
+  ])
+);
+
 // ============================================
 // MULTI-VOLUME SAME-NAME FIXTURES
 // ============================================
@@ -1214,25 +1266,31 @@ async function createAsyncFixtures(): Promise<void> {
     pageCount: number;
     mokuro?: string; // mokuro JSON content, omit for image-only
     thumbnail?: Buffer; // thumbnail sidecar, omit for no thumbnail
+    wrapperFolder?: string; // put everything under this folder instead of the ZIP root
+    mokuroInFolder?: boolean; // put the mokuro next to the images instead of beside the folder
   }): Promise<Buffer> {
     const blobWriter = new BlobWriter('application/zip');
     const zipWriter = new ZipWriter(blobWriter);
+    const root = options.wrapperFolder ? `${options.wrapperFolder}/` : '';
 
     // Add images inside subfolder
     for (let i = 1; i <= options.pageCount; i++) {
-      const filename = `${options.folderName}/page${String(i).padStart(3, '0')}.jpg`;
+      const filename = `${root}${options.folderName}/page${String(i).padStart(3, '0')}.jpg`;
       await zipWriter.add(filename, new Uint8ArrayReader(new Uint8Array(TINY_PNG)));
     }
 
     // Add mokuro JSON at root if provided
     if (options.mokuro) {
-      await zipWriter.add(`${options.folderName}.mokuro`, new TextReader(options.mokuro));
+      const mokuroPath = options.mokuroInFolder
+        ? `${root}${options.folderName}/${options.folderName}.mokuro`
+        : `${root}${options.folderName}.mokuro`;
+      await zipWriter.add(mokuroPath, new TextReader(options.mokuro));
     }
 
     // Add thumbnail sidecar at root if provided
     if (options.thumbnail) {
       await zipWriter.add(
-        `${options.folderName}.webp`,
+        `${root}${options.folderName}.webp`,
         new Uint8ArrayReader(new Uint8Array(options.thumbnail))
       );
     }
@@ -1255,7 +1313,7 @@ async function createAsyncFixtures(): Promise<void> {
           img_path: 'page001.jpg',
           img_width: 100,
           img_height: 100,
-          blocks: [{ lines: ['テスト'] }]
+          blocks: [{ box: [10, 10, 40, 70], vertical: true, font_size: 20, lines: ['テスト'] }]
         },
         { img_path: 'page002.jpg', img_width: 100, img_height: 100, blocks: [] }
       ],
@@ -1332,6 +1390,52 @@ async function createAsyncFixtures(): Promise<void> {
     ])
   );
 
+  // 4. cbz-in-wrapper-folder: exported layout nested under a top-level folder
+  const exportedCbzInWrapper = await createExportedCbz({
+    folderName: 'manga',
+    pageCount: 2,
+    mokuro: exportedMokuroJson,
+    wrapperFolder: 'Test Series'
+  });
+  createFixture(
+    'exported-format',
+    'cbz-in-wrapper-folder',
+    {
+      'manga.cbz': exportedCbzInWrapper
+    },
+    createExpected(1, [
+      {
+        sourceType: 'archive',
+        hasMokuro: false,
+        basePathContains: 'manga',
+        imageOnly: false
+      }
+    ])
+  );
+
+  // 5. cbz-single-subfolder: mokuro and images together in one subfolder
+  const exportedCbzSingleSubfolder = await createExportedCbz({
+    folderName: 'manga',
+    pageCount: 2,
+    mokuro: exportedMokuroJson,
+    mokuroInFolder: true
+  });
+  createFixture(
+    'exported-format',
+    'cbz-single-subfolder',
+    {
+      'manga.cbz': exportedCbzSingleSubfolder
+    },
+    createExpected(1, [
+      {
+        sourceType: 'archive',
+        hasMokuro: false,
+        basePathContains: 'manga',
+        imageOnly: false
+      }
+    ])
+  );
+
   console.log('Created async fixtures (CBZ with internal mokuro)');
   console.log('Created exported-format fixtures');
 }
@@ -37,6 +37,9 @@ vi.mock('$lib/catalog/db', () => ({
         return Promise.resolve();
       }),
       get: vi.fn(),
+      filter: vi.fn().mockImplementation((predicate) => ({
+        first: () => Promise.resolve(savedVolumes.map((v) => v.metadata).find(predicate))
+      })),
       where: vi.fn().mockReturnValue({
         equals: vi.fn().mockReturnValue({
           first: vi.fn().mockImplementation((uuid) => {
@@ -96,6 +99,8 @@ vi.mock('$lib/util/progress-tracker', () => ({
   progressTrackerStore: {
     addProcess: vi.fn(),
     updateProcess: vi.fn(),
+    completeProcess: vi.fn(),
+    failProcess: vi.fn(),
     removeProcess: vi.fn()
   }
 }));
@@ -125,8 +130,15 @@ vi.mock('$lib/util/file-processing-pool', () => ({
 }));
 
 // Import after mocks are set up
-import { importFiles, importQueue, isImporting, clearCompletedImports } from '../import-service';
+import {
+  importFiles,
+  importQueue,
+  isImporting,
+  skippedImports,
+  clearCompletedImports
+} from '../import-service';
 import { showSnackbar } from '$lib/util/snackbar';
+import { onLibraryEvent } from '$lib/util/library-events';
 
 // ============================================
 // TEST HELPERS
@@ -349,6 +361,24 @@ describe('importFiles integration', () => {
       expect(showSnackbar).toHaveBeenCalledWith('No importable volumes found');
       expect(result.imported).toBe(0);
     });
+
+    it('imports valid files from a mixed drop and reports the rest as skipped', async () => {
+      const fixture = await loadFixture('basic', 'mokuro-inside-dir');
+      const files = [
+        ...fixtureToFiles(fixture),
+        new File(['%PDF-1.4'], 'notes.pdf', { type: 'application/pdf' })
+      ];
+
+      const result = await importFiles(files);
+
+      expect(result.success).toBe(true);
+      expect(result.imported).toBe(1);
+      expect(result.skipped).toBe(1);
+      expect(savedVolumes).toHaveLength(1);
+      expect(get(skippedImports).map((s) => s.name)).toEqual(['notes.pdf']);
+      // Kept listed so the import panel can show what was left out
+      expect(get(importQueue).map((item) => item.status)).toEqual(['done']);
+    });
   });
 
   describe('stores and state', () => {
@@ -421,6 +451,25 @@ describe('importFiles integration', () => {
       expect(metadata.thumbnail_height).toBeGreaterThan(0);
     });
 
+    it('fires volume-imported with the new volume', async () => {
+      const fixture = await loadFixture('basic', 'mokuro-inside-dir');
+      const files = fixtureToFiles(fixture);
+      const listener = vi.fn();
+      const unsubscribe = onLibraryEvent('volume-imported', listener);
+
+      await importFiles(files);
+      unsubscribe();
+
+      const metadata = savedVolumes[0].metadata;
+      expect(listener).toHaveBeenCalledTimes(1);
+      expect(listener).toHaveBeenCalledWith({
+        volumeUuid: metadata.volume_uuid,
+        seriesUuid: metadata.series_uuid,
+        seriesTitle: metadata.series_title,
+        volumeTitle: metadata.volume_title
+      });
+    });
+
     it('calculates page character counts', async () => {
       const fixture = await loadFixture('basic', 'mokuro-inside-dir');
       const files = fixtureToFiles(fixture);
@@ -455,6 +504,17 @@ describe('importFiles with archives', () => {
     expect(savedVolumes).toHaveLength(1);
   });
 
+  it('records the archive each volume was imported from', async () => {
+    const fixture = await loadFixture('internal-mokuro', 'standalone-archive');
+    const files = fixtureToFiles(fixture);
+    const archive = files.find((file) => /\.(zip|cbz)$/i.test(file.name))!;
+
+    await importFiles(files);
+
+    expect(savedVolumes[0].metadata.original_filename).toBe(archive.name);
+    expect(savedVolumes[0].metadata.source_path).toBe(archive.webkitRelativePath || archive.name);
+  });
+
   it('handles standalone archive (internal mokuro)', async () => {
     const fixture = await loadFixture('internal-mokuro', 'standalone-archive');
     const files = fixtureToFiles(fixture);
@@ -564,6 +624,30 @@ describe('importFiles with exported format archives', () => {
     expect(savedVolumes[0].metadata.mokuro_version).not.toBe('');
   });
 
+  it.each(['cbz-in-wrapper-folder', 'cbz-single-subfolder'])(
+    'imports %s with overlays aligned to its pages',
+    async (fixtureName) => {
+      const fixture = await loadFixture('exported-format', fixtureName);
+      const files = fixtureToFiles(fixture);
+
+      const result = await importFiles(files);
+
+      expect(result.success).toBe(true);
+      expect(result.imported).toBe(1);
+      expect(savedVolumes).toHaveLength(1);
+
+      // Every page's image was found, so no placeholders stand in for it
+      const [volume] = savedVolumes;
+      expect(volume.metadata.missing_pages ?? 0).toBe(0);
+      const pages = volume.ocr.pages as Array<{ img_path: string; blocks: unknown[] }>;
+      expect(pages.map((page) => page.img_path)).toEqual(['page001.jpg', 'page002.jpg']);
+      for (const page of pages) {
+        expect(volume.files.files[page.img_path]?.size).toBeGreaterThan(0);
+      }
+      expect(pages[0].blocks).toHaveLength(1);
+    }
+  );
+
   it('imports exported image-only CBZ with thumbnail sidecar without spurious volume', async () => {
     const fixture = await loadFixture('exported-format', 'cbz-image-only-with-thumbnail');
     const files = fixtureToFiles(fixture);
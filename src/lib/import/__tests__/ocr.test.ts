@@ -0,0 +1,249 @@
+import { afterEach, describe, it, expect, vi } from 'vitest';
+import {
+  runOcrOnVolume,
+  createCloudVisionOcrEngine,
+  OcrCancelledError,
+  type OcrEngine
+} from '../ocr';
+import { processVolume } from '../processing';
+import type { DecompressedVolume } from '../types';
+
+// Mock thumbnail generation since we can't use canvas in tests
+vi.mock('$lib/catalog/thumbnails', () => ({
+  generateThumbnail: vi.fn().mockResolvedValue({
+    file: new File([], 'thumbnail.jpg'),
+    width: 200,
+    height: 300
+  })
+}));
+
+function createImageOnlyVolume(): DecompressedVolume {
+  const imageFiles = new Map<string, File>();
+  for (const name of ['page10.jpg', 'page2.jpg', 'page1.jpg']) {
+    imageFiles.set(name, new File([], name, { type: 'image/jpeg' }));
+  }
+  return {
+    mokuroFile: null,
+    imageFiles,
+    basePath: 'My Manga/Volume 01',
+    sourceType: 'local',
+    nestedArchives: []
+  };
+}
+
+/** Engine that finds one line of text on every page, named after the image */
+function createFakeEngine(): OcrEngine & { calls: string[] } {
+  const calls: string[] = [];
+  return {
+    calls,
+    async recognizePage(image) {
+      calls.push(image.name);
+      return {
+        version: '0.2.0',
+        img_width: 800,
+        img_height: 1200,
+        blocks: [{ box: [10, 10, 40, 70], vertical: true, font_size: 20, lines: ['テスト'] }]
+      };
+    }
+  };
+}
+
+describe('runOcrOnVolume', () => {
+  it('turns an image-only volume into a text-overlaid volume', async () => {
+    const volume = createImageOnlyVolume();
+    const engine = createFakeEngine();
+    const onPage = vi.fn();
+
+    volume.mokuroFile = await runOcrOnVolume(volume, engine, { onPage });
+    const processed = await processVolume(volume);
+
+    // Pages are OCR'd in reading order with progress per page
+    expect(engine.calls).toEqual(['page1.jpg', 'page2.jpg', 'page10.jpg']);
+    expect(onPage).toHaveBeenLastCalledWith(3, 3);
+
+    expect(processed.metadata.mokuroVersion).toBe('0.2.0');
+    expect(processed.metadata.series).toBe('My Manga');
+    expect(processed.metadata.chars).toBe(9);
+    expect(processed.ocrData.pages.map((p) => p.img_path)).toEqual([
+      'page1.jpg',
+      'page2.jpg',
+      'page10.jpg'
+    ]);
+    expect(processed.ocrData.pages[0].blocks[0]).toMatchObject({ lines: ['テスト'] });
+  });
+
+  it('keeps the UUIDs an image-only import of the same volume would get', async () => {
+    const withOcr = createImageOnlyVolume();
+    withOcr.mokuroFile = await runOcrOnVolume(withOcr, createFakeEngine());
+
+    const [ocrResult, plainResult] = await Promise.all([
+      processVolume(withOcr),
+      processVolume(createImageOnlyVolume())
+    ]);
+
+    expect(ocrResult.metadata.volumeUuid).toBe(plainResult.metadata.volumeUuid);
+    expect(ocrResult.metadata.seriesUuid).toBe(plainResult.metadata.seriesUuid);
+  });
+
+  it('stops with OcrCancelledError when cancelled mid-volume', async () => {
+    const controller = new AbortController();
+    const engine = createFakeEngine();
+
+    await expect(
+      runOcrOnVolume(createImageOnlyVolume(), engine, {
+        signal: controller.signal,
+        onPage: (done) => {
+          if (done === 1) controller.abort();
+        }
+      })
+    ).rejects.toBeInstanceOf(OcrCancelledError);
+    expect(engine.calls).toHaveLength(1);
+  });
+
+  it('rejects malformed pages returned by the OCR engine', async () => {
+    const engine: OcrEngine = {
+      recognizePage: async () => ({ img_width: 800, img_height: 1200, blocks: [{ lines: [] }] })
+    };
+
+    await expect(runOcrOnVolume(createImageOnlyVolume(), engine)).rejects.toThrow(
+      /page 1 \(page1\.jpg\) block 1 has no valid bounding box/
+    );
+  });
+});
+
+describe('createCloudVisionOcrEngine', () => {
+  /** Vision symbols for `text`, the last one ending in `lastBreak` */
+  function symbols(text: string, lastBreak?: string) {
+    return [...text].map((char, i) =>
+      lastBreak && i === text.length - 1
+        ? { text: char, property: { detectedBreak: { type: lastBreak } } }
+        : { text: char }
+    );
+  }
+
+  it('sends the page to document text detection and converts the blocks', async () => {
+    const annotation = {
+      pages: [
+        {
+          width: 800,
+          height: 1200,
+          blocks: [
+            {
+              // Vision omits zero coordinates
+              boundingBox: {
+                vertices: [{ x: 10 }, { x: 50 }, { x: 50, y: 200 }, { x: 10, y: 200 }]
+              },
+              paragraphs: [
+                { words: [{ symbols: [...symbols('今日は', 'LINE_BREAK'), ...symbols('晴れ')] }] }
+              ]
+            }
+          ]
+        }
+      ]
+    };
+    const fetchImpl = vi
+      .fn()
+      .mockResolvedValue(
+        new Response(JSON.stringify({ responses: [{ fullTextAnnotation: annotation }] }))
+      );
+    const engine = createCloudVisionOcrEngine('KEY', fetchImpl);
+    const image = new File([new Uint8Array([1, 2, 3])], 'page1.jpg', { type: 'image/jpeg' });
+
+    expect(await engine.recognizePage(image)).toEqual({
+      img_width: 800,
+      img_height: 1200,
+      blocks: [{ box: [10, 0, 50, 200], vertical: true, lines: ['今日は', '晴れ'] }]
+    });
+
+    const [url, init] = fetchImpl.mock.calls[0];
+    expect(url).toBe('https://vision.googleapis.com/v1/images:annotate?key=KEY');
+    const request = JSON.parse(init.body).requests[0];
+    expect(request.image.content).toBe('AQID');
+    expect(request.features).toEqual([{ type: 'DOCUMENT_TEXT_DETECTION' }]);
+  });
+
+  it('returns an empty page when Vision finds no text', async () => {
+    const fetchImpl = vi.fn().mockResolvedValue(new Response(JSON.stringify({ responses: [{}] })));
+    const engine = createCloudVisionOcrEngine('KEY', fetchImpl);
+
+    expect(await engine.recognizePage(new File([], 'blank.jpg'))).toEqual({
+      img_width: undefined,
+      img_height: undefined,
+      blocks: []
+    });
+  });
+
+  describe('pages over the size limit', () => {
+    // 8 MB is about 10.7 MB once base64-encoded
+    const largePage = () => new File([new Uint8Array(8_000_000)], 'scan.png');
+
+    afterEach(() => {
+      vi.unstubAllGlobals();
+    });
+
+    it('sends a downscaled JPEG and maps the results back to the page', async () => {
+      const canvasSizes: number[][] = [];
+      // The full-size JPEG is still too big; the downscaled one fits
+      const encodedSizes = [9_000_000, 3];
+      vi.stubGlobal('createImageBitmap', async () => ({ width: 4000, height: 6000, close() {} }));
+      vi.stubGlobal(
+        'OffscreenCanvas',
+        class {
+          constructor(width: number, height: number) {
+            canvasSizes.push([width, height]);
+          }
+          getContext() {
+            return { drawImage() {} };
+          }
+          async convertToBlob() {
+            return new Blob([new Uint8Array(encodedSizes.shift()!)]);
+          }
+        }
+      );
+
+      const fetchImpl = vi.fn(async (_url: string, init: RequestInit) => {
+        // Vision reports coordinates on the image it was sent
+        const [width, height] = canvasSizes[1];
+        const vertices = [{}, { x: width }, { x: width, y: height }, { y: height }];
+        const paragraphs = [{ words: [{ symbols: [{ text: '字' }] }] }];
+        const page = { width, height, blocks: [{ boundingBox: { vertices }, paragraphs }] };
+        expect(JSON.parse(init.body as string).requests[0].image.content).toBe('AAAA');
+        return new Response(
+          JSON.stringify({ responses: [{ fullTextAnnotation: { pages: [page] } }] })
+        );
+      });
+      const engine = createCloudVisionOcrEngine('KEY', fetchImpl as unknown as typeof fetch);
+
+      expect(await engine.recognizePage(largePage())).toEqual({
+        img_width: 4000,
+        img_height: 6000,
+        blocks: [{ box: [0, 0, 4000, 6000], vertical: true, lines: ['字'] }]
+      });
+      expect(canvasSizes[0]).toEqual([4000, 6000]);
+      expect(canvasSizes[1][0]).toBeLessThan(4000);
+    });
+
+    it('fails with the page name when the page cannot be re-encoded', async () => {
+      // No canvas encoding available (as in this test environment)
+      const fetchImpl = vi.fn();
+      const engine = createCloudVisionOcrEngine('KEY', fetchImpl);
+
+      await expect(engine.recognizePage(largePage())).rejects.toThrow(
+        /Cloud Vision failed for scan\.png: the page is over Vision's 10 MB limit/
+      );
+      expect(fetchImpl).not.toHaveBeenCalled();
+    });
+  });
+
+  it('reports API errors with the page name', async () => {
+    const body = { error: { code: 403, message: 'API key not valid' } };
+    const fetchImpl = vi
+      .fn()
+      .mockResolvedValue(new Response(JSON.stringify(body), { status: 403 }));
+    const engine = createCloudVisionOcrEngine('KEY', fetchImpl);
+
+    await expect(engine.recognizePage(new File([], 'page1.jpg'))).rejects.toThrow(
+      'Cloud Vision failed for page1.jpg: API key not valid'
+    );
+  });
+});
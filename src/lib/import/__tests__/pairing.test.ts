@@ -207,7 +207,9 @@ describe('pairMokuroWithSources', () => {
       'stem-matching-with-special-chars',
       'prefers-directory-over-archive',
       'case-insensitive-matching',
-      'unicode-names'
+      'unicode-names',
+      'mokuro-with-named-image-subfolder',
+      'nested-mokuro-with-same-name-dir'
     ])('%s', async (fixtureName) => {
       try {
         const fixture = await loadFixture('edge-cases', fixtureName);
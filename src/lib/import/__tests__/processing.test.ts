@@ -13,6 +13,7 @@ import {
   extractVolumeInfo
 } from '../processing';
 import type { DecompressedVolume } from '../types';
+import { miscSettings } from '$lib/settings/misc';
 
 // Mock thumbnail generation since we can't use canvas in tests
 vi.mock('$lib/catalog/thumbnails', () => ({
@@ -141,6 +142,69 @@ describe('parseMokuroFile', () => {
 
     await expect(parseMokuroFile(badFile)).rejects.toThrow(/missing required/i);
   });
+
+  it('rejects unsupported mokuro versions with the version in the message', async () => {
+    const mokuroFile = createMokuroFile({ version: '1.0.0' });
+
+    await expect(parseMokuroFile(mokuroFile)).rejects.toThrow(/version 1\.0\.0/);
+  });
+
+  it('rejects blocks without a bounding box', async () => {
+    const mokuroFile = createMokuroFile({
+      pages: [{ img_path: 'page001.jpg', blocks: [{ vertical: true, lines: ['テスト'] }] }]
+    });
+
+    await expect(parseMokuroFile(mokuroFile)).rejects.toThrow(
+      /page 1 \(page001\.jpg\) block 1 has no valid bounding box/
+    );
+  });
+
+  it('migrates older layouts to the current page shape', async () => {
+    // Early output: version only on pages, single-line blocks as strings,
+    // no box (only line quads) and no blocks list on empty pages
+    const oldData = {
+      title: 'Old Series',
+      title_uuid: 'series-old',
+      volume: 'Volume 01',
+      volume_uuid: 'volume-old',
+      pages: [
+        {
+          version: '0.1.6',
+          img_path: 'page001.jpg',
+          blocks: [
+            {
+              lines: 'テスト',
+              lines_coords: [
+                [
+                  [10, 20],
+                  [30, 20],
+                  [30, 80],
+                  [10, 80]
+                ]
+              ]
+            }
+          ]
+        },
+        { version: '0.1.6', img_path: 'page002.jpg' }
+      ]
+    };
+    const blob = new Blob([JSON.stringify(oldData)], { type: 'application/json' });
+    const oldFile = Object.assign(blob, {
+      name: 'old.mokuro',
+      lastModified: Date.now()
+    }) as unknown as File;
+
+    const result = await parseMokuroFile(oldFile);
+
+    expect(result.version).toBe('0.1.6');
+    expect(result.pages[0].blocks[0]).toMatchObject({
+      box: [10, 20, 30, 80],
+      vertical: false,
+      font_size: 60,
+      lines: ['テスト']
+    });
+    expect(result.pages[1].blocks).toEqual([]);
+  });
 });
 
 describe('matchImagesToPages', () => {
@@ -238,6 +302,18 @@ describe('matchImagesToPages', () => {
     expect(result.remapped.get('003.png')).toBe('003_result.webp');
   });
 
+  it('pairs renamed unpadded images with pages in reading order', () => {
+    const pages = ['1', '2', '10', '11'].map((name) => ({ img_path: `${name}.png`, blocks: [] }));
+    const files = createImageFiles(['p10.webp', 'p1.webp', 'p11.webp', 'p2.webp']);
+
+    const result = matchImagesToPages(pages, files);
+
+    expect(result.remapped.get('1.png')).toBe('p1.webp');
+    expect(result.remapped.get('2.png')).toBe('p2.webp');
+    expect(result.remapped.get('10.png')).toBe('p10.webp');
+    expect(result.remapped.get('11.png')).toBe('p11.webp');
+  });
+
   it('does not use count-based fallback when most files match by name', () => {
     // If more than 50% match by name, don't use fallback for the rest
     const pages = [
@@ -324,8 +400,10 @@ describe('processVolume', () => {
   it('calculates cumulative character counts', async () => {
     const mokuroFile = createMokuroFile({
       pages: [
-        { img_path: 'page001.jpg', blocks: [{ lines: ['あいう'] }] }, // 3 chars
-        { img_path: 'page002.jpg', blocks: [{ lines: ['かきくけ'] }] } // 4 chars
+        // 3 chars
+        { img_path: 'page001.jpg', blocks: [{ box: [0, 0, 10, 30], lines: ['あいう'] }] },
+        // 4 chars
+        { img_path: 'page002.jpg', blocks: [{ box: [0, 0, 10, 40], lines: ['かきくけ'] }] }
       ],
       chars: 7
     });
@@ -361,6 +439,67 @@ describe('processVolume', () => {
     expect(result.metadata.volume).toBe('Volume 01');
   });
 
+  it('orders image-only pages numerically, not lexicographically', async () => {
+    const input = createDecompressedVolume({
+      mokuroFile: null,
+      imageFiles: createImageFiles(['1.jpg', '10.jpg', '11.jpg', '2.jpg'])
+    });
+
+    const result = await processVolume(input);
+
+    expect(result.ocrData.pages.map((page) => page.img_path)).toEqual([
+      '1.jpg',
+      '2.jpg',
+      '10.jpg',
+      '11.jpg'
+    ]);
+  });
+
+  it('can order image-only pages by exact filename instead', async () => {
+    miscSettings.update((current) => ({ ...current, pageSortMode: 'filename' }));
+    try {
+      const input = createDecompressedVolume({
+        mokuroFile: null,
+        imageFiles: createImageFiles(['2.jpg', '10.jpg', '1.jpg'])
+      });
+
+      const result = await processVolume(input);
+
+      expect(result.ocrData.pages.map((page) => page.img_path)).toEqual([
+        '1.jpg',
+        '10.jpg',
+        '2.jpg'
+      ]);
+    } finally {
+      miscSettings.update((current) => ({ ...current, pageSortMode: 'natural' }));
+    }
+  });
+
+  it('maps .mokuro pages to the right unpadded images', async () => {
+    const mokuroFile = createMokuroFile({
+      pages: ['1', '2', '10', '11'].map((name) => ({
+        img_path: `${name}.jpg`,
+        blocks: [{ box: [0, 0, 10, 10], lines: [`ページ${name}`] }]
+      })),
+      chars: 0
+    });
+    const input = createDecompressedVolume({
+      mokuroFile,
+      imageFiles: createImageFiles(['1.jpg', '10.jpg', '11.jpg', '2.jpg'])
+    });
+
+    const result = await processVolume(input);
+
+    expect(
+      result.ocrData.pages.map((page) => [page.img_path, page.blocks[0].lines[0]])
+    ).toEqual([
+      ['1.jpg', 'ページ1'],
+      ['2.jpg', 'ページ2'],
+      ['10.jpg', 'ページ10'],
+      ['11.jpg', 'ページ11']
+    ]);
+  });
+
   it('generates thumbnail from first image', async () => {
     const input = createDecompressedVolume();
 
@@ -0,0 +1,85 @@
+/**
+ * Tests for the archive quick scan
+ *
+ * The scan reads only an archive's central directory (plus its .mokuro) to
+ * report page count and extracted size before a full import.
+ */
+
+import { describe, it, expect, vi } from 'vitest';
+import { BlobWriter, TextReader, Uint8ArrayReader, ZipWriter } from '@zip.js/zip.js';
+import {
+  countMokuroPages,
+  describeQuickScan,
+  scanArchive,
+  LARGE_IMPORT_BYTES
+} from '../archive-extraction';
+
+const PAGE_BYTES = 2048;
+
+async function createArchive(pageCount: number, withMokuro: boolean): Promise<Blob> {
+  const writer = new ZipWriter(new BlobWriter('application/zip'), { level: 0 });
+  const image = new Uint8Array(PAGE_BYTES);
+  for (let i = 1; i <= pageCount; i++) {
+    await writer.add(`Volume/${String(i).padStart(3, '0')}.jpg`, new Uint8ArrayReader(image));
+  }
+  if (withMokuro) {
+    const pages = Array.from({ length: pageCount }, (_, i) => ({ img_path: `${i + 1}.jpg` }));
+    await writer.add('Volume.mokuro', new TextReader(JSON.stringify({ pages })));
+  }
+  return writer.close();
+}
+
+describe('scanArchive', () => {
+  it('reports the page count of a 600-page archive without extracting images', async () => {
+    const archive = await createArchive(600, true);
+    const slice = vi.spyOn(archive, 'slice');
+
+    const scan = await scanArchive(archive);
+
+    expect(scan?.pageCount).toBe(600);
+    expect(scan?.decompressedSize).toBeGreaterThanOrEqual(600 * PAGE_BYTES);
+    // Only the central directory and the .mokuro are read, not the image data
+    const bytesRead = slice.mock.calls.reduce(
+      (sum, [start = 0, end = archive.size]) => sum + (end - start),
+      0
+    );
+    expect(bytesRead).toBeLessThan(archive.size / 10);
+  });
+
+  it('counts images when the archive has no .mokuro', async () => {
+    const scan = await scanArchive(await createArchive(12, false));
+
+    expect(scan?.pageCount).toBe(12);
+  });
+
+  it('prefers a paired external .mokuro', async () => {
+    const mokuro = new File([JSON.stringify({ pages: [{}, {}, {}] })], 'Volume.mokuro');
+
+    const scan = await scanArchive(await createArchive(12, false), mokuro);
+
+    expect(scan?.pageCount).toBe(3);
+  });
+
+  it('returns null for archives it cannot read', async () => {
+    expect(await scanArchive(new Blob(['Rar!\x1a\x07\x00 not a zip']))).toBeNull();
+  });
+});
+
+describe('countMokuroPages', () => {
+  it('reads the pages array and rejects other JSON', () => {
+    expect(countMokuroPages('{"pages":[{},{}]}')).toBe(2);
+    expect(countMokuroPages('{"title":"x"}')).toBeNull();
+    expect(countMokuroPages('not json')).toBeNull();
+  });
+});
+
+describe('describeQuickScan', () => {
+  it('summarizes pages and extracted size', () => {
+    expect(describeQuickScan({ pageCount: 600, decompressedSize: LARGE_IMPORT_BYTES })).toBe(
+      '600 pages, about 1 GB once extracted'
+    );
+    expect(describeQuickScan({ pageCount: 0, decompressedSize: 1024 * 1024 })).toBe(
+      'About 1 MB once extracted'
+    );
+  });
+});
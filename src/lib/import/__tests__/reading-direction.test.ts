@@ -0,0 +1,27 @@
+import { describe, it, expect } from 'vitest';
+import { detectReadingDirection } from '../reading-direction';
+
+function page(vertical: number, horizontal: number) {
+  return {
+    blocks: [
+      ...Array.from({ length: vertical }, () => ({ vertical: true })),
+      ...Array.from({ length: horizontal }, () => ({ vertical: false }))
+    ]
+  };
+}
+
+describe('detectReadingDirection', () => {
+  it('reads mostly vertical text as right to left', () => {
+    expect(detectReadingDirection([page(8, 1), page(6, 2)])).toBe(true);
+  });
+
+  it('reads horizontal text as left to right', () => {
+    expect(detectReadingDirection([page(0, 9), page(1, 8)])).toBe(false);
+  });
+
+  it('gives no answer for mixed or sparse text', () => {
+    expect(detectReadingDirection([page(6, 6)])).toBeUndefined();
+    expect(detectReadingDirection([page(3, 0)])).toBeUndefined();
+    expect(detectReadingDirection([])).toBeUndefined();
+  });
+});
@@ -0,0 +1,175 @@
+/**
+ * Tests for importing from a direct link
+ */
+
+import { describe, it, expect, vi, afterEach } from 'vitest';
+
+vi.mock('../import-service', () => ({
+  importFiles: vi.fn()
+}));
+
+import { importFiles } from '../import-service';
+import {
+  downloadArchiveFromUrl,
+  downloadFilename,
+  importFromUrl,
+  parseHeaderLine,
+  parseImportUrl,
+  sniffArchiveKind
+} from '../url-import';
+
+const ZIP_BYTES = new Uint8Array([0x50, 0x4b, 0x03, 0x04, 0, 0, 0, 0]);
+
+function mockFetch(body: BodyInit, init: ResponseInit = {}, finalUrl?: string) {
+  const fetchMock = vi.fn(async () => {
+    const response = new Response(body, { status: 200, ...init });
+    if (finalUrl) Object.defineProperty(response, 'url', { value: finalUrl });
+    return response;
+  });
+  vi.stubGlobal('fetch', fetchMock);
+  return fetchMock;
+}
+
+afterEach(() => {
+  vi.unstubAllGlobals();
+});
+
+describe('sniffArchiveKind', () => {
+  it('recognises zip, rar and 7z signatures', () => {
+    expect(sniffArchiveKind(ZIP_BYTES)).toBe('zip');
+    expect(sniffArchiveKind(new Uint8Array([0x52, 0x61, 0x72, 0x21, 0x1a, 0x07, 0x00]))).toBe(
+      'rar'
+    );
+    expect(sniffArchiveKind(new Uint8Array([0x37, 0x7a, 0xbc, 0xaf, 0x27, 0x1c]))).toBe('7z');
+  });
+
+  it('rejects anything else', () => {
+    expect(sniffArchiveKind(new TextEncoder().encode('<!doctype html>'))).toBeNull();
+    expect(sniffArchiveKind(new Uint8Array())).toBeNull();
+  });
+});
+
+describe('parseImportUrl', () => {
+  it('accepts http and https links', () => {
+    expect(parseImportUrl(' https://example.com/a.cbz ').href).toBe('https://example.com/a.cbz');
+  });
+
+  it('rejects other schemes and non-links', () => {
+    expect(() => parseImportUrl('file:///tmp/a.cbz')).toThrow(/http and https/);
+    expect(() => parseImportUrl('volume.cbz')).toThrow(/full link/);
+  });
+});
+
+describe('parseHeaderLine', () => {
+  it('splits name and value', () => {
+    expect(parseHeaderLine('Authorization: Bearer abc:def')).toEqual([
+      'Authorization',
+      'Bearer abc:def'
+    ]);
+  });
+
+  it('treats an empty line as no header', () => {
+    expect(parseHeaderLine('  ')).toBeNull();
+  });
+
+  it('rejects malformed lines', () => {
+    expect(() => parseHeaderLine('Bearer abc')).toThrow();
+    expect(() => parseHeaderLine('Bad Name: x')).toThrow();
+  });
+});
+
+describe('downloadFilename', () => {
+  it('prefers Content-Disposition', () => {
+    expect(
+      downloadFilename('attachment; filename="Vol 01.cbz"', 'https://x.test/dl?id=1', 'zip')
+    ).toBe('Vol 01.cbz');
+    expect(
+      downloadFilename(
+        "attachment; filename*=UTF-8''%E5%B7%BB1.zip",
+        'https://x.test/dl?id=1',
+        'zip'
+      )
+    ).toBe('巻1.zip');
+  });
+
+  it('falls back to the URL and adds a missing extension', () => {
+    expect(downloadFilename(null, 'https://x.test/files/Vol%2002.cbz', 'zip')).toBe('Vol 02.cbz');
+    expect(downloadFilename(null, 'https://x.test/files/volume-3', 'zip')).toBe('volume-3.cbz');
+    expect(downloadFilename(null, 'https://x.test/files/volume-3', 'rar')).toBe('volume-3.cbr');
+  });
+});
+
+describe('downloadArchiveFromUrl', () => {
+  it('downloads an archive under the final URL name', async () => {
+    mockFetch(ZIP_BYTES, {}, 'https://cdn.test/Vol%2001.cbz');
+    const progress = vi.fn();
+
+    const file = await downloadArchiveFromUrl(new URL('https://x.test/share/abc'), {
+      onProgress: progress
+    });
+
+    expect(file.name).toBe('Vol 01.cbz');
+    expect(file.size).toBe(ZIP_BYTES.byteLength);
+    expect(progress).toHaveBeenCalled();
+  });
+
+  it('sends the optional header and follows redirects', async () => {
+    const fetchMock = mockFetch(ZIP_BYTES);
+
+    await downloadArchiveFromUrl(new URL('https://x.test/a.cbz'), {
+      header: ['Authorization', 'Bearer token']
+    });
+
+    expect(fetchMock).toHaveBeenCalledWith(
+      expect.any(URL),
+      expect.objectContaining({
+        redirect: 'follow',
+        headers: { Authorization: 'Bearer token' }
+      })
+    );
+  });
+
+  it('rejects web pages', async () => {
+    mockFetch('<html></html>', { headers: { 'content-type': 'text/html; charset=utf-8' } });
+    await expect(downloadArchiveFromUrl(new URL('https://x.test/a.cbz'))).rejects.toThrow(
+      /web page/
+    );
+  });
+
+  it('rejects files that are not archives', async () => {
+    mockFetch('just text', { headers: { 'content-type': 'application/octet-stream' } });
+    await expect(downloadArchiveFromUrl(new URL('https://x.test/a.cbz'))).rejects.toThrow(
+      /isn't a ZIP/
+    );
+  });
+
+  it('enforces the size cap', async () => {
+    mockFetch(new Uint8Array(64).fill(0x50), { headers: { 'content-length': '64' } });
+    await expect(
+      downloadArchiveFromUrl(new URL('https://x.test/a.cbz'), { maxBytes: 32 })
+    ).rejects.toThrow(/limit/);
+
+    mockFetch(new Uint8Array(64).fill(0x50));
+    await expect(
+      downloadArchiveFromUrl(new URL('https://x.test/a.cbz'), { maxBytes: 32 })
+    ).rejects.toThrow(/limit/);
+  });
+
+  it('asks for a header when the link needs a login', async () => {
+    mockFetch('', { status: 401 });
+    await expect(downloadArchiveFromUrl(new URL('https://x.test/a.cbz'))).rejects.toThrow(
+      /auth header/
+    );
+  });
+});
+
+describe('importFromUrl', () => {
+  it('imports a link whose path has a stray percent sign', async () => {
+    mockFetch(ZIP_BYTES);
+    const result = { success: true, imported: 1, failed: 0, skipped: 0, errors: [] };
+    vi.mocked(importFiles).mockResolvedValue(result);
+
+    await expect(importFromUrl(new URL('https://x.test/files/100%.cbz'))).resolves.toBe(result);
+    expect(vi.mocked(importFiles).mock.calls[0][0][0].name).toBe('100%.cbz');
+  });
+});
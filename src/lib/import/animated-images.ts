@@ -0,0 +1,130 @@
+/**
+ * Animated page images
+ *
+ * Some archives carry animated WebP, GIF or APNG pages. Browsers play them in
+ * the page view as-is, but decoders disagree on their size (a frame can be
+ * smaller than the canvas it is drawn on), so the canvas size from the file
+ * header is used as the page size — the same frame OCR coordinates refer to.
+ * Alternatively pages can be flattened to their first frame on import.
+ */
+
+/** Canvas size of an animated image */
+export interface AnimatedImageInfo {
+  width: number;
+  height: number;
+}
+
+/** APNG puts its animation chunk before the image data, well within this */
+const PNG_SCAN_BYTES = 64 * 1024;
+
+function ascii(bytes: Uint8Array, offset: number, length: number): string {
+  return String.fromCharCode(...bytes.subarray(offset, offset + length));
+}
+
+/** Animated WebP: extended (VP8X) header with the animation flag set */
+function detectWebp(bytes: Uint8Array): AnimatedImageInfo | null {
+  if (ascii(bytes, 0, 4) !== 'RIFF' || ascii(bytes, 8, 4) !== 'WEBP') return null;
+  if (ascii(bytes, 12, 4) !== 'VP8X' || bytes.length < 30 || !(bytes[20] & 0x02)) return null;
+  return {
+    width: 1 + (bytes[24] | (bytes[25] << 8) | (bytes[26] << 16)),
+    height: 1 + (bytes[27] | (bytes[28] << 8) | (bytes[29] << 16))
+  };
+}
+
+/** APNG: an acTL chunk before the first IDAT */
+function detectPng(bytes: Uint8Array): AnimatedImageInfo | null {
+  if (bytes[0] !== 0x89 || ascii(bytes, 1, 3) !== 'PNG') return null;
+  const view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+  let offset = 8;
+  while (offset + 8 <= bytes.length) {
+    const type = ascii(bytes, offset + 4, 4);
+    if (type === 'acTL') {
+      return { width: view.getUint32(16), height: view.getUint32(20) };
+    }
+    if (type === 'IDAT') return null;
+    offset += 12 + view.getUint32(offset);
+  }
+  return null;
+}
+
+/** Skip a run of GIF data sub-blocks, returning the offset after the terminator */
+function skipGifSubBlocks(bytes: Uint8Array, offset: number): number {
+  while (offset < bytes.length && bytes[offset] !== 0) {
+    offset += bytes[offset] + 1;
+  }
+  return offset + 1;
+}
+
+/** GIF: more than one image descriptor */
+function detectGif(bytes: Uint8Array): AnimatedImageInfo | null {
+  if (ascii(bytes, 0, 4) !== 'GIF8' || bytes.length < 13) return null;
+  const width = bytes[6] | (bytes[7] << 8);
+  const height = bytes[8] | (bytes[9] << 8);
+
+  let offset = 13;
+  if (bytes[10] & 0x80) offset += 3 * (1 << ((bytes[10] & 0x07) + 1));
+
+  let frames = 0;
+  while (offset < bytes.length) {
+    const marker = bytes[offset];
+    if (marker === 0x21) {
+      // Extension: label, then data sub-blocks
+      offset = skipGifSubBlocks(bytes, offset + 2);
+    } else if (marker === 0x2c) {
+      if (++frames > 1) return { width, height };
+      const packed = bytes[offset + 9];
+      offset += 10;
+      if (packed & 0x80) offset += 3 * (1 << ((packed & 0x07) + 1));
+      // LZW minimum code size, then image data sub-blocks
+      offset = skipGifSubBlocks(bytes, offset + 1);
+    } else {
+      // Trailer or garbage
+      break;
+    }
+  }
+  return null;
+}
+
+/**
+ * Canvas size of an animated image from its header, or null for still images
+ * and anything unrecognized
+ */
+export function detectAnimatedImage(bytes: Uint8Array): AnimatedImageInfo | null {
+  return detectWebp(bytes) ?? detectPng(bytes) ?? detectGif(bytes);
+}
+
+/**
+ * Read just enough of a file to tell whether it's animated. GIFs have to be
+ * walked frame by frame, so they're read whole.
+ */
+export async function readAnimatedImageInfo(file: Blob): Promise<AnimatedImageInfo | null> {
+  const header = new Uint8Array(await file.slice(0, 4).arrayBuffer());
+  const isGif = ascii(header, 0, 4) === 'GIF8';
+  const length = isGif ? file.size : header[0] === 0x89 ? PNG_SCAN_BYTES : 30;
+  return detectAnimatedImage(new Uint8Array(await file.slice(0, length).arrayBuffer()));
+}
+
+/**
+ * Replace an animated image with a PNG of its first frame, keeping the file name
+ * (pages are matched by path). Returns null where canvas encoding isn't available.
+ */
+export async function flattenToFirstFrame(file: File): Promise<File | null> {
+  if (typeof OffscreenCanvas === 'undefined' || typeof createImageBitmap === 'undefined') {
+    return null;
+  }
+  let bitmap: ImageBitmap | null = null;
+  try {
+    bitmap = await createImageBitmap(file);
+    const canvas = new OffscreenCanvas(bitmap.width, bitmap.height);
+    const ctx = canvas.getContext('2d');
+    if (!ctx) return null;
+    ctx.drawImage(bitmap, 0, 0);
+    const png = await canvas.convertToBlob({ type: 'image/png' });
+    return new File([png], file.name, { type: 'image/png', lastModified: file.lastModified });
+  } catch (error) {
+    console.warn(`Failed to flatten animated page ${file.name}:`, error);
+    return null;
+  } finally {
+    bitmap?.close?.();
+  }
+}
@@ -5,8 +5,9 @@
  * This module contains pure logic without Worker-specific code.
  */
 
-import { BlobReader, ZipReader, Uint8ArrayWriter, configure } from '@zip.js/zip.js';
+import { BlobReader, ZipReader, Uint8ArrayWriter, TextWriter, configure } from '@zip.js/zip.js';
 import { isSystemFile, isImageExtension, isMokuroExtension } from './types';
+import { formatBytes } from '$lib/util/upload';
 
 // Configure zip.js to not use web workers in Node.js environments
 // Web workers don't work properly in jsdom/vitest
@@ -54,6 +55,7 @@ export interface ExtractionResult {
  * 1. Specific prefixes are checked first (e.g., "vol1/" matches "vol1/page.jpg")
  * 2. Root-level prefix ("." or "") matches files without directory separators
  * 3. More specific prefixes take priority over root-level
+ * 4. The longest matching prefix wins, so "series/vol1/" beats "series/" for nested volumes
  *
  * @param filename - The file path to match
  * @param volumePrefixes - Map of pathPrefix → volumeId
@@ -63,14 +65,18 @@ export function matchFileToVolume(
   filename: string,
   volumePrefixes: Map<string, string>
 ): string | null {
-  // First pass: check specific prefixes (not root)
+  // First pass: check specific prefixes (not root), preferring the deepest match
+  let bestMatch: string | null = null;
+  let bestLength = -1;
   for (const [prefix, volId] of volumePrefixes) {
     // Skip root-level prefix for now, check it last
     if (prefix === '.' || prefix === '') continue;
-    if (filename.startsWith(prefix + '/') || filename === prefix) {
-      return volId;
+    if ((filename.startsWith(prefix + '/') || filename === prefix) && prefix.length > bestLength) {
+      bestMatch = volId;
+      bestLength = prefix.length;
     }
   }
+  if (bestMatch) return bestMatch;
 
   // Second pass: if no specific prefix matched, check for root-level volume
   // Root-level means files without directory separators
@@ -355,3 +361,74 @@ export async function decompressArchive(
 
   return results;
 }
+
+/**
+ * What an archive holds, read from its central directory without extracting images
+ */
+export interface QuickScan {
+  pageCount: number;
+  /** Total size of the archive's files once extracted, in bytes */
+  decompressedSize: number;
+}
+
+/** Archives this large once extracted ask for confirmation before importing or downloading */
+export const LARGE_IMPORT_BYTES = 1024 ** 3;
+
+/** "600 pages, about 1.2 GB once extracted" (no page count when it's unknown) */
+export function describeQuickScan(scan: QuickScan): string {
+  const size = `about ${formatBytes(scan.decompressedSize, 1)} once extracted`;
+  if (scan.pageCount === 0) return size.charAt(0).toUpperCase() + size.slice(1);
+  return `${scan.pageCount} page${scan.pageCount === 1 ? '' : 's'}, ${size}`;
+}
+
+/** Page count of a .mokuro file's contents, or null if it isn't one */
+export function countMokuroPages(json: string): number | null {
+  try {
+    const pages = JSON.parse(json)?.pages;
+    return Array.isArray(pages) ? pages.length : null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Page count and decompressed size of an archive, reading only its central
+ * directory and (for the page count) its .mokuro file. Without a .mokuro the
+ * image count is used. Returns null for archives zip.js can't read (rar, 7z).
+ *
+ * @param archiveBlob - The archive file as a Blob
+ * @param mokuroFile - External .mokuro paired with the archive, if any
+ */
+export async function scanArchive(
+  archiveBlob: Blob,
+  mokuroFile?: File | null
+): Promise<QuickScan | null> {
+  let zipReader: ZipReader<unknown> | undefined;
+  try {
+    zipReader = new ZipReader(new BlobReader(archiveBlob));
+    const entries = (await zipReader.getEntries()).filter(
+      (entry) => !entry.directory && !isSystemFile(entry.filename)
+    );
+
+    const extension = (filename: string) => filename.split('.').pop() || '';
+    const decompressedSize = entries.reduce((sum, entry) => sum + entry.uncompressedSize, 0);
+    const images = entries.filter((entry) => isImageExtension(extension(entry.filename)));
+
+    let pageCount: number | null = null;
+    if (mokuroFile) {
+      pageCount = countMokuroPages(await mokuroFile.text());
+    } else {
+      const mokuroEntry = entries.find((entry) => isMokuroExtension(extension(entry.filename)));
+      if (mokuroEntry) {
+        // eslint-disable-next-line @typescript-eslint/no-explicit-any
+        pageCount = countMokuroPages(await (mokuroEntry as any).getData(new TextWriter()));
+      }
+    }
+
+    return { pageCount: pageCount ?? images.length, decompressedSize };
+  } catch {
+    return null;
+  } finally {
+    await zipReader?.close().catch(() => {});
+  }
+}
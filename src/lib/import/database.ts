@@ -11,9 +11,17 @@
 import { db } from '$lib/catalog/db';
 import { requestPersistentStorage } from '$lib/util/upload';
 import { sanitizeTitleSegment } from '$lib/util/sanitize-title';
-import type { ProcessedVolume } from './types';
+import type { ProcessedVolume, VolumeOrigin } from './types';
 import type { VolumeMetadata } from '$lib/types';
 import { naturalSort } from '$lib/util/natural-sort';
+import {
+  applyDetectedDirection,
+  updateProgress,
+  volumes as volumeDataStore
+} from '$lib/settings/volume-data';
+import { detectReadingDirection } from './reading-direction';
+import { visiblePageCharCounts } from './duplicate-pages';
+import { get } from 'svelte/store';
 
 /**
  * Check if a volume already exists in the database
@@ -26,6 +34,45 @@ export async function volumeExists(volumeUuid: string): Promise<boolean> {
   return existing !== undefined;
 }
 
+/**
+ * Whether an existing volume was imported from the same file: same file (or
+ * folder) name, same path and page count. Catches the same archive imported
+ * again under a different UUID, e.g. after re-running it through mokuro. The
+ * path keeps generically named folders (e.g. "images") in different series
+ * apart.
+ */
+export function isFromSameSource(
+  volume: VolumeMetadata,
+  origin: VolumeOrigin,
+  pageCount: number
+): boolean {
+  return (
+    !!volume.original_filename &&
+    !!volume.source_path &&
+    volume.original_filename.toLowerCase() === origin.originalFilename.toLowerCase() &&
+    volume.source_path.toLowerCase() === origin.sourcePath.toLowerCase() &&
+    volume.page_count === pageCount
+  );
+}
+
+/**
+ * Find a volume already imported from the same file as a new import
+ *
+ * @param origin - Where the new import came from
+ * @param pageCount - The new import's page count
+ * @returns The earlier import, if any
+ */
+export async function findVolumeFromSameSource(
+  origin: VolumeOrigin,
+  pageCount: number
+): Promise<VolumeMetadata | undefined> {
+  return db.volumes
+    .where('original_filename')
+    .equalsIgnoreCase(origin.originalFilename)
+    .filter((volume) => isFromSameSource(volume, origin, pageCount))
+    .first();
+}
+
 /**
  * Save a processed volume to the database
  *
@@ -40,12 +87,13 @@ export async function volumeExists(volumeUuid: string): Promise<boolean> {
  *   volume would read as un-backed-up and renames would miss its files).
  *   Legacy titles get sanitized later, at rename time, where the rename
  *   machinery moves the cloud files along with the title.
+ * @returns The metadata as stored
  * @throws If the volume already exists or if the transaction fails
  */
 export async function saveVolume(
   volume: ProcessedVolume,
   options?: { preserveTitles?: boolean }
-): Promise<void> {
+): Promise<VolumeMetadata> {
   const { metadata, ocrData, fileData } = volume;
   const canonicalVolumeUuid = metadata.volumeUuid;
 
@@ -57,8 +105,8 @@ export async function saveVolume(
     Object.entries(fileData.files).sort(([aKey], [bKey]) => naturalSort(aKey, bKey))
   );
 
-  // Calculate page_char_counts from pages
-  const pageCharCounts = ocrData.pages.map((page) => page.cumulativeChars);
+  // Calculate page_char_counts from the pages shown (skipped pages are kept but hidden)
+  const pageCharCounts = visiblePageCharCounts(ocrData.pages, metadata.skippedPagePaths);
 
   // Convert ProcessedMetadata to VolumeMetadata format
   const volumeMetadata: VolumeMetadata = {
@@ -84,12 +132,29 @@ export async function saveVolume(
         : undefined,
     thumbnail_width: metadata.thumbnailWidth,
     thumbnail_height: metadata.thumbnailHeight,
+    thumbnail_version: metadata.thumbnailVersion,
+    thumbnail_custom: metadata.thumbnailCustom,
     missing_pages: metadata.missingPages,
     missing_page_paths: metadata.missingPagePaths,
-    spine_width: metadata.spineWidth
+    skipped_page_paths: metadata.skippedPagePaths,
+    spine_width: metadata.spineWidth,
+    original_filename: metadata.origin?.originalFilename,
+    source_path: metadata.origin?.sourcePath,
+    source_provider: metadata.origin?.provider,
+    source_file_id: metadata.origin?.fileId,
+    // Keep a manual grouping, order, series cover, chapters, page fixes and notes set
+    // before this volume was deleted or evicted
+    series_override: get(volumeDataStore)[metadata.volumeUuid]?.series_override,
+    sort_index: get(volumeDataStore)[metadata.volumeUuid]?.sort_index,
+    series_cover: get(volumeDataStore)[metadata.volumeUuid]?.series_cover,
+    chapter_markers: get(volumeDataStore)[metadata.volumeUuid]?.chapter_markers,
+    page_adjustments: get(volumeDataStore)[metadata.volumeUuid]?.page_adjustments,
+    notes: get(volumeDataStore)[metadata.volumeUuid]?.notes
   };
 
-  // Write to all 3 tables atomically
+  // Write to all 3 tables atomically: if any write fails the transaction aborts
+  // and rolls back the others, so a failed import never leaves a partial volume
+  // (or its staged page blobs) in the catalog
   await db.transaction('rw', [db.volumes, db.volume_ocr, db.volume_files], async () => {
     const [existingVolume, existingOcr, existingFiles] = await Promise.all([
       db.volumes.get(canonicalVolumeUuid),
@@ -127,6 +192,12 @@ export async function saveVolume(
     });
   });
 
+  applyDetectedDirection(
+    canonicalVolumeUuid,
+    metadata.seriesUuid,
+    detectReadingDirection(ocrData.pages)
+  );
+
   // Import-time thumbnail generation can fail for some files.
   // Trigger best-effort background recovery so UI placeholders resolve
   // without requiring navigation or refresh.
@@ -139,6 +210,101 @@ export async function saveVolume(
       console.error('Failed to recover missing thumbnail after import:', error);
     });
   }
+
+  return volumeMetadata;
+}
+
+/**
+ * Warning shown before replacing a volume's pages with a different number of
+ * pages, since reading progress is a page number
+ *
+ * @returns The warning, or null if the page counts match
+ */
+export function pageCountWarning(currentCount: number, newCount: number): string | null {
+  if (currentCount === newCount) return null;
+  const difference = Math.abs(newCount - currentCount);
+  const change = newCount > currentCount ? 'more' : 'fewer';
+  return (
+    `The replacement has ${difference} ${change} page${difference === 1 ? '' : 's'} ` +
+    `(${newCount} instead of ${currentCount}). Reading progress is kept as a page number ` +
+    'and may no longer point at the same place.'
+  );
+}
+
+/**
+ * Replace an existing volume's pages and OCR with a newly processed import
+ *
+ * Keeps the volume UUID, titles, series and manual overrides, so reading
+ * progress, stats and cloud identity carry over. Only the content-derived
+ * fields (pages, character counts, cover, missing pages) are replaced.
+ * Progress past the new last page is clamped to it.
+ *
+ * @param volumeUuid - The existing volume to update
+ * @param volume - The processed replacement
+ * @throws If the volume doesn't exist or if the transaction fails
+ */
+export async function replaceVolumeContent(
+  volumeUuid: string,
+  volume: ProcessedVolume
+): Promise<void> {
+  const { metadata, ocrData, fileData } = volume;
+
+  await requestPersistentStorage();
+
+  const sortedFiles = Object.fromEntries(
+    Object.entries(fileData.files).sort(([aKey], [bKey]) => naturalSort(aKey, bKey))
+  );
+  const pagesForDb = ocrData.pages.map(({ cumulativeChars, ...page }) => page);
+
+  await db.transaction('rw', [db.volumes, db.volume_ocr, db.volume_files], async () => {
+    const existing = await db.volumes.get(volumeUuid);
+    if (!existing) {
+      throw new Error(`Volume ${volumeUuid} does not exist in database`);
+    }
+
+    await db.volumes.put({
+      ...existing,
+      mokuro_version: metadata.mokuroVersion || '',
+      page_count: metadata.pageCount,
+      character_count: metadata.chars,
+      page_char_counts: visiblePageCharCounts(ocrData.pages, metadata.skippedPagePaths),
+      thumbnail:
+        metadata.thumbnail instanceof Blob
+          ? metadata.thumbnail instanceof File
+            ? metadata.thumbnail
+            : new File([metadata.thumbnail], 'thumbnail', {
+                type: metadata.thumbnail.type || 'image/jpeg'
+              })
+          : undefined,
+      thumbnail_width: metadata.thumbnailWidth,
+      thumbnail_height: metadata.thumbnailHeight,
+      thumbnail_version: metadata.thumbnailVersion,
+      thumbnail_custom: metadata.thumbnailCustom,
+      missing_pages: metadata.missingPages,
+      missing_page_paths: metadata.missingPagePaths,
+      skipped_page_paths: metadata.skippedPagePaths,
+      spine_width: metadata.spineWidth,
+      // The volume now comes from the replacement files
+      original_filename: metadata.origin?.originalFilename ?? existing.original_filename,
+      source_path: metadata.origin?.sourcePath ?? existing.source_path,
+      source_provider: metadata.origin ? metadata.origin.provider : existing.source_provider,
+      source_file_id: metadata.origin ? metadata.origin.fileId : existing.source_file_id,
+      content_modified_at: new Date().toISOString()
+    });
+    await db.volume_ocr.put({ volume_uuid: volumeUuid, pages: pagesForDb as any });
+    await db.volume_files.put({ volume_uuid: volumeUuid, files: sortedFiles });
+  });
+
+  const volumeData = get(volumeDataStore)[volumeUuid];
+  if (volumeData && volumeData.progress > metadata.pageCount) {
+    updateProgress(volumeUuid, metadata.pageCount, metadata.chars, volumeData.completed);
+  }
+
+  if (!metadata.thumbnail || !metadata.thumbnailWidth || !metadata.thumbnailHeight) {
+    db.processThumbnails(1).catch((error) => {
+      console.error('Failed to recover missing thumbnail after replacement:', error);
+    });
+  }
 }
 
 /**
@@ -0,0 +1,172 @@
+/**
+ * Duplicate Page Detection
+ *
+ * Some rips repeat a cover scan or credit page, which throws off the page
+ * count and double-page pairing. Adjacent pages are compared by perceptual
+ * (difference) hash; near-identical neighbors are flagged so the user can
+ * skip them on import.
+ *
+ * Skipping is non-destructive: pages, their OCR and their images are all kept,
+ * and their paths are recorded in `skipped_page_paths`. Skipped pages are
+ * hidden when the volume is opened, and the volume's page and character
+ * counts cover only the pages shown. Restoring them clears the list.
+ */
+
+import { matchFilesToPages } from '$lib/reader/image-cache';
+import type { ProcessedPage, ProcessedVolume } from './types';
+
+/** dHash grid: 9 columns give 8 horizontal gradients per row */
+const HASH_WIDTH = 9;
+const HASH_HEIGHT = 8;
+
+/** Hashes this many bits apart (out of 64) or fewer count as duplicates */
+export const DUPLICATE_MAX_DISTANCE = 5;
+
+/**
+ * Difference hash of a 9x8 grayscale image, as 16 hex digits. Each bit is
+ * whether a pixel is brighter than its right-hand neighbor, so the hash
+ * survives re-encoding, resizing and small brightness shifts.
+ */
+export function differenceHash(gray: ArrayLike<number>): string {
+  let hex = '';
+  let nibble = 0;
+  let bits = 0;
+  for (let y = 0; y < HASH_HEIGHT; y++) {
+    for (let x = 0; x < HASH_WIDTH - 1; x++) {
+      const i = y * HASH_WIDTH + x;
+      nibble = (nibble << 1) | (gray[i] > gray[i + 1] ? 1 : 0);
+      if (++bits === 4) {
+        hex += nibble.toString(16);
+        nibble = 0;
+        bits = 0;
+      }
+    }
+  }
+  return hex;
+}
+
+/** Number of differing bits between two hashes of equal length */
+export function hashDistance(a: string, b: string): number {
+  let distance = 0;
+  for (let i = 0; i < a.length; i++) {
+    let diff = parseInt(a[i], 16) ^ parseInt(b[i], 16);
+    while (diff) {
+      distance += diff & 1;
+      diff >>= 1;
+    }
+  }
+  return distance;
+}
+
+/**
+ * Indices of pages that look like a duplicate of the page before them.
+ * Pages that couldn't be hashed (null) are never flagged.
+ */
+export function findDuplicateAdjacentPages(
+  hashes: (string | null)[],
+  maxDistance = DUPLICATE_MAX_DISTANCE
+): number[] {
+  const duplicates: number[] = [];
+  for (let i = 1; i < hashes.length; i++) {
+    const previous = hashes[i - 1];
+    const current = hashes[i];
+    if (previous && current && hashDistance(previous, current) <= maxDistance) {
+      duplicates.push(i);
+    }
+  }
+  return duplicates;
+}
+
+/** Perceptual hash of an image file, or null if it can't be decoded */
+export async function hashImageFile(file: File): Promise<string | null> {
+  try {
+    const bitmap = await createImageBitmap(file, {
+      resizeWidth: HASH_WIDTH,
+      resizeHeight: HASH_HEIGHT,
+      resizeQuality: 'medium'
+    });
+    const canvas = document.createElement('canvas');
+    canvas.width = HASH_WIDTH;
+    canvas.height = HASH_HEIGHT;
+    const ctx = canvas.getContext('2d');
+    if (!ctx) return null;
+    ctx.drawImage(bitmap, 0, 0);
+    bitmap.close();
+
+    const { data } = ctx.getImageData(0, 0, HASH_WIDTH, HASH_HEIGHT);
+    const gray = new Array<number>(HASH_WIDTH * HASH_HEIGHT);
+    for (let i = 0; i < gray.length; i++) {
+      gray[i] = 0.299 * data[i * 4] + 0.587 * data[i * 4 + 1] + 0.114 * data[i * 4 + 2];
+    }
+    return differenceHash(gray);
+  } catch {
+    return null;
+  }
+}
+
+/** Indices of likely duplicate pages in a processed volume */
+export async function detectDuplicatePages(volume: ProcessedVolume): Promise<number[]> {
+  const pages = volume.ocrData.pages;
+  const files = matchFilesToPages(volume.fileData.files, pages);
+  const missing = new Set(volume.metadata.missingPagePaths ?? []);
+
+  const hashes: (string | null)[] = [];
+  for (const [index, page] of pages.entries()) {
+    const file = files[index];
+    hashes.push(file && !missing.has(page.img_path) ? await hashImageFile(file) : null);
+  }
+  return findDuplicateAdjacentPages(hashes);
+}
+
+/** The pages shown when a volume is opened: all but the skipped ones */
+export function withoutSkippedPages<T extends { img_path: string }>(
+  pages: T[],
+  skippedPaths: string[] | undefined
+): T[] {
+  if (!skippedPaths?.length) return pages;
+  const skipped = new Set(skippedPaths);
+  return pages.filter((page) => !skipped.has(page.img_path));
+}
+
+/** Cumulative character counts of the pages shown, skipping `skippedPaths` */
+export function visiblePageCharCounts(
+  pages: ProcessedPage[],
+  skippedPaths: string[] | undefined
+): number[] {
+  const skipped = new Set(skippedPaths);
+  const counts: number[] = [];
+  let cumulative = 0;
+  let previousCumulative = 0;
+  for (const page of pages) {
+    const pageChars = page.cumulativeChars - previousCumulative;
+    previousCumulative = page.cumulativeChars;
+    if (skipped.has(page.img_path)) continue;
+    cumulative += pageChars;
+    counts.push(cumulative);
+  }
+  return counts;
+}
+
+/**
+ * Mark pages of a processed volume as skipped and recompute the page and
+ * character counts for the pages left. The pages themselves are kept.
+ */
+export function skipPages(volume: ProcessedVolume, indices: number[]): ProcessedVolume {
+  if (indices.length === 0) return volume;
+  const pages = volume.ocrData.pages;
+  const skippedPagePaths = [
+    ...(volume.metadata.skippedPagePaths ?? []),
+    ...indices.map((index) => pages[index].img_path)
+  ];
+  const counts = visiblePageCharCounts(pages, skippedPagePaths);
+
+  return {
+    ...volume,
+    metadata: {
+      ...volume.metadata,
+      pageCount: counts.length,
+      chars: counts.at(-1) ?? 0,
+      skippedPagePaths
+    }
+  };
+}
@@ -9,16 +9,24 @@ import { writable, get } from 'svelte/store';
 import { pairMokuroWithSources } from './pairing';
 import { decideImportRouting } from './routing';
 import { processVolume, parseMokuroFile, matchImagesToPages } from './processing';
-import { saveVolume, volumeExists } from './database';
+import {
+  findVolumeFromSameSource,
+  pageCountWarning,
+  replaceVolumeContent,
+  saveVolume
+} from './database';
 import { createLocalQueueItem, requiresWorkerDecompression } from './local-provider';
 import type {
   FileEntry,
   PairedSource,
   ImportQueueItem,
+  SkippedImport,
   DecompressedVolume,
-  ProcessedVolume
+  ProcessedVolume,
+  VolumeOrigin
 } from './types';
 import {
+  categorizeFile,
   isImageExtension,
   isMokuroExtension,
   isArchiveExtension,
@@ -30,10 +38,17 @@ import {
   incrementPoolUsers,
   decrementPoolUsers
 } from '$lib/util/file-processing-pool';
-import { getImportUiBridge, type MissingFilesInfo } from './import-ui';
+import { getImportUiBridge, type ImageOnlyChoice, type MissingFilesInfo } from './import-ui';
+import { getOcrEngine, runOcrOnVolume, OcrCancelledError } from './ocr';
+import { detectDuplicatePages, skipPages } from './duplicate-pages';
+import { LARGE_IMPORT_BYTES, describeQuickScan, scanArchive } from './archive-extraction';
 import { extractSeriesName } from '$lib/upload/image-only-fallback';
 import { generateUUID } from '$lib/util/uuid';
+import { emitLibraryEvent } from '$lib/util/library-events';
 import { requestPersistentStorage } from '$lib/util/upload';
+import { db } from '$lib/catalog/db';
+import { miscSettings } from '$lib/settings/misc';
+import { thumbnailCache } from '$lib/catalog/thumbnail-cache';
 import {
   extractArchiveByVolumes,
   decompressArchive,
@@ -60,6 +75,92 @@ export const currentImport = writable<ImportQueueItem | null>(null);
  */
 export const isImporting = writable<boolean>(false);
 
+/**
+ * Dropped files that were not imported (not an archive, image or mokuro file)
+ */
+export const skippedImports = writable<SkippedImport[]>([]);
+
+function isActiveImport(item: ImportQueueItem): boolean {
+  return item.status === 'extracting' || item.status === 'writing';
+}
+
+function updateQueueItem(id: string, changes: Partial<ImportQueueItem>): void {
+  importQueue.update((q) => q.map((item) => (item.id === id ? { ...item, ...changes } : item)));
+}
+
+/**
+ * Map a progress message to the phase shown in the import panel.
+ * Everything up to having the pages in memory counts as extracting.
+ */
+function importPhase(statusText: string): 'extracting' | 'writing' {
+  return /^(Checking|Processing|Saving|Complete)/.test(statusText) ? 'writing' : 'extracting';
+}
+
+/**
+ * Queue sources found while importing (multi-volume or nested archives).
+ * Added at the FRONT of the queue so all volumes from the same archive complete together.
+ */
+function queueAdditionalSources(sources: PairedSource[]): void {
+  const newItems = sources.map(createLocalQueueItem);
+  newItems.forEach(addToProgressTracker);
+  importQueue.update((q) => {
+    const finished = q.filter((item) => item.status === 'done' || item.status === 'error');
+    const active = q.filter(isActiveImport);
+    const queued = q.filter((item) => item.status === 'queued');
+    return [...finished, ...active, ...newItems, ...queued];
+  });
+}
+
+/**
+ * Import one queue item, keeping its queue entry and progress tracker entry in step.
+ * Successful items stay in the queue as 'done' so the import panel can list them.
+ */
+async function runQueueItem(
+  item: ImportQueueItem
+): Promise<{ success: boolean; error?: string; additionalSources?: PairedSource[] }> {
+  updateQueueItem(item.id, {
+    status: 'extracting',
+    statusText: 'Processing',
+    progress: 5,
+    errorMessage: undefined
+  });
+  currentImport.set({ ...item, status: 'extracting' });
+  updateProgressTracker(item.id, 'Processing', 5);
+
+  const result = await processSingleVolume(item.source, (statusText, progress) => {
+    updateQueueItem(item.id, { status: importPhase(statusText), statusText, progress });
+    updateProgressTracker(item.id, statusText, progress);
+  });
+
+  if (result.additionalSources && result.additionalSources.length > 0) {
+    queueAdditionalSources(result.additionalSources);
+  }
+
+  if (result.success) {
+    updateQueueItem(item.id, { status: 'done', statusText: 'Done', progress: 100 });
+    removeFromProgressTracker(item.id);
+  } else {
+    const error = result.error || 'Unknown error';
+    updateQueueItem(item.id, { status: 'error', statusText: 'Failed', errorMessage: error });
+    markProgressTrackerError(item.id, error);
+  }
+
+  currentImport.set(null);
+  return result;
+}
+
+/**
+ * Once nothing is left to import, drop the finished items if all of them
+ * succeeded; failures and skipped files stay listed until dismissed.
+ */
+function clearIfAllSucceeded(): void {
+  const queue = get(importQueue);
+  const settled = queue.every((item) => item.status === 'done');
+  if (settled && get(skippedImports).length === 0) {
+    importQueue.set([]);
+  }
+}
+
 // ============================================
 // PROGRESS TRACKER SYNC
 // ============================================
@@ -94,7 +195,7 @@ function removeFromProgressTracker(id: string): void {
  * Mark an import as failed in the progress tracker (keeps visible briefly)
  */
 function markProgressTrackerError(id: string, error: string): void {
-  getImportUiBridge().updateProgress(`import-${id}`, `Failed: ${error}`, 0);
+  getImportUiBridge().failProgress(`import-${id}`, `Failed: ${error}`);
   // Remove after delay so user can see the error
   setTimeout(() => {
     removeFromProgressTracker(id);
@@ -134,6 +235,23 @@ function filesToEntries(files: File[]): FileEntry[] {
     .filter((entry) => !isThumbnailSidecarPath(entry.path, sourceStems));
 }
 
+/**
+ * Where a paired source came from. Directory imports record the folder.
+ */
+export function sourceOrigin(source: PairedSource): VolumeOrigin {
+  if (source.source.type === 'archive') {
+    return archiveOrigin(source.source.file);
+  }
+  return {
+    originalFilename: source.basePath.split('/').pop() || source.basePath,
+    sourcePath: source.basePath
+  };
+}
+
+function archiveOrigin(file: File): VolumeOrigin {
+  return { originalFilename: file.name, sourcePath: file.webkitRelativePath || file.name };
+}
+
 function isThumbnailSidecarPath(path: string, sourceStems?: Set<string>): boolean {
   const filename = path.split('/').pop()?.toLowerCase() || '';
   if (!filename.endsWith('.webp')) return false;
@@ -375,7 +493,8 @@ async function streamExtractAllVolumes(
 async function processArchiveContents(
   archiveFile: File,
   externalMokuroFile: File | null,
-  onProgress?: (status: string, progress: number) => void
+  onProgress?: (status: string, progress: number) => void,
+  replaceVolumeUuid?: string
 ): Promise<{
   success: boolean;
   error?: string;
@@ -472,9 +591,12 @@ async function processArchiveContents(
   // If there are image-only pairings, prompt user for confirmation
   let confirmedImageOnlyPairings: PairedSource[] = [];
   if (imageOnlyPairings.length > 0) {
-    const confirmed = await promptForImageOnlyImport(imageOnlyPairings);
-    if (confirmed) {
-      confirmedImageOnlyPairings = imageOnlyPairings;
+    const choice = await promptForImageOnlyImport(imageOnlyPairings);
+    if (choice !== 'skip') {
+      confirmedImageOnlyPairings = imageOnlyPairings.map((p) => ({
+        ...p,
+        runOcr: choice === 'ocr'
+      }));
     }
   }
 
@@ -512,6 +634,14 @@ async function processArchiveContents(
     return { success: false, error: 'No importable volumes found in archive' };
   }
 
+  // A replacement must be exactly one volume, or it's unclear which one to use
+  if (replaceVolumeUuid && (allPairings.length !== 1 || nestedArchivePaths.length > 0)) {
+    return {
+      success: false,
+      error: 'A replacement archive must contain exactly one volume'
+    };
+  }
+
   // PASS 2: Extract ALL volumes' images in a single archive pass
   // This is much faster than opening the archive N times
   const allNestedSources: PairedSource[] = [];
@@ -595,15 +725,20 @@ async function processArchiveContents(
           }
         }
 
+        await runOcrIfRequested(pairing, decompressed, processingProgress, onProgress);
+
         // Process the volume
         const processed = await processVolume(decompressed);
 
-        // Check for duplicates
-        if (await volumeExists(processed.metadata.volumeUuid)) {
-          lastError = `Volume "${processed.metadata.volume}" already exists`;
+        // Save to database (or replace the target volume's content)
+        const saveError = await saveProcessedVolume(
+          processed,
+          archiveOrigin(archiveFile),
+          replaceVolumeUuid
+        );
+        if (saveError) {
+          lastError = saveError;
         } else {
-          // Save to database
-          await saveVolume(processed);
           successCount++;
         }
 
@@ -614,6 +749,8 @@ async function processArchiveContents(
       } catch (err) {
         lastError = err instanceof Error ? err.message : 'Unknown error';
         console.error(`[Archive Import] Error processing volume ${i + 1}:`, err);
+        // Cancelling OCR stops the rest of the archive rather than starting the next volume
+        if (err instanceof OcrCancelledError) break;
       }
 
       // Clear this volume's files to free memory before next volume
@@ -702,6 +839,89 @@ function tocDirectoryToDecompressed(source: PairedSource): DecompressedVolume {
 // SINGLE VOLUME PROCESSING
 // ============================================
 
+/**
+ * Save a processed volume, or swap it into `replaceVolumeUuid` in place.
+ * @returns An error message, or undefined on success
+ */
+async function saveProcessedVolume(
+  processed: ProcessedVolume,
+  origin: VolumeOrigin,
+  replaceVolumeUuid?: string
+): Promise<string | undefined> {
+  processed = await offerToSkipDuplicatePages(processed);
+  processed.metadata.origin = origin;
+
+  if (replaceVolumeUuid) {
+    return replaceExistingVolume(replaceVolumeUuid, processed);
+  }
+
+  // Check for duplicates
+  const existing = await db.volumes.get(processed.metadata.volumeUuid);
+  if (existing) {
+    const from = existing.original_filename ? ` (imported from ${existing.original_filename})` : '';
+    return `Volume "${processed.metadata.volume}" already exists${from}`;
+  }
+  const sameSource = await findVolumeFromSameSource(origin, processed.metadata.pageCount);
+  if (sameSource) {
+    const message =
+      `"${processed.metadata.volume}" looks like "${sameSource.volume_title}", already ` +
+      `imported from the same file (${sameSource.original_filename}). Import it again as a ` +
+      'separate volume?';
+    if (!(await getImportUiBridge().confirm(message))) {
+      return `Volume "${processed.metadata.volume}" was already imported from this file`;
+    }
+  }
+  const saved = await saveVolume(processed);
+  emitLibraryEvent('volume-imported', {
+    volumeUuid: saved.volume_uuid,
+    seriesUuid: saved.series_uuid,
+    seriesTitle: saved.series_title,
+    volumeTitle: saved.volume_title
+  });
+}
+
+/**
+ * When duplicate detection is on, ask whether to skip near-identical
+ * adjacent pages (e.g. a repeated cover scan) before the volume is saved
+ */
+async function offerToSkipDuplicatePages(processed: ProcessedVolume): Promise<ProcessedVolume> {
+  if (!get(miscSettings).detectDuplicatePages) return processed;
+
+  const duplicates = await detectDuplicatePages(processed);
+  if (duplicates.length === 0) return processed;
+
+  const pageList = duplicates.map((index) => index + 1).join(', ');
+  const message =
+    `"${processed.metadata.volume}" has ${duplicates.length} page(s) that look like a ` +
+    `duplicate of the page before: ${pageList}. Skip them? They are only hidden, and can ` +
+    'be restored from the volume editor.';
+  const skip = await getImportUiBridge().confirm(message);
+  return skip ? skipPages(processed, duplicates) : processed;
+}
+
+async function replaceExistingVolume(
+  volumeUuid: string,
+  processed: ProcessedVolume
+): Promise<string | undefined> {
+  const existing = await db.volumes.get(volumeUuid);
+  if (!existing) {
+    return 'The volume to replace no longer exists';
+  }
+
+  const warning = pageCountWarning(existing.page_count, processed.metadata.pageCount);
+  if (warning && !(await getImportUiBridge().confirm(`${warning} Replace anyway?`))) {
+    return 'Replacement cancelled - page count differs';
+  }
+
+  await replaceVolumeContent(volumeUuid, processed);
+
+  // Drop cached covers and page previews decoded from the old images
+  thumbnailCache.invalidate(volumeUuid);
+  for (let i = 0; i < existing.page_count; i++) {
+    thumbnailCache.invalidatePagePreviews(volumeUuid, i);
+  }
+}
+
 /**
  * Process and save a single volume
  * Returns additional sources to queue (for multi-volume archives and nested archives)
@@ -772,15 +992,15 @@ async function processSingleVolume(
       onProgress?.('Processing...', 60);
       const processed = await processVolume(decompressed);
 
-      if (await volumeExists(processed.metadata.volumeUuid)) {
-        return {
-          success: false,
-          error: `Volume "${processed.metadata.volume}" already exists`
-        };
-      }
-
       onProgress?.('Saving...', 85);
-      await saveVolume(processed);
+      const saveError = await saveProcessedVolume(
+        processed,
+        sourceOrigin(source),
+        source.replaceVolumeUuid
+      );
+      if (saveError) {
+        return { success: false, error: saveError };
+      }
       onProgress?.('Complete', 100);
       return { success: true };
     }
@@ -791,7 +1011,8 @@ async function processSingleVolume(
       const result = await processArchiveContents(
         source.source.file,
         source.mokuroFile,
-        onProgress
+        onProgress,
+        source.replaceVolumeUuid
       );
 
       return {
@@ -834,35 +1055,31 @@ async function processSingleVolume(
       }
     }
 
+    await runOcrIfRequested(source, decompressed, 50, onProgress);
+
     onProgress?.('Processing...', 50);
 
     // Process the volume
     const processed = await processVolume(decompressed);
 
-    // Check for duplicates
-    if (await volumeExists(processed.metadata.volumeUuid)) {
-      return {
-        success: false,
-        error: `Volume "${processed.metadata.volume}" already exists`
-      };
-    }
-
     onProgress?.('Saving...', 80);
 
-    // Save to database
-    await saveVolume(processed);
+    // Save to database (or replace the target volume's content)
+    const saveError = await saveProcessedVolume(
+      processed,
+      sourceOrigin(source),
+      source.replaceVolumeUuid
+    );
+    if (saveError) {
+      return { success: false, error: saveError };
+    }
 
     onProgress?.('Complete', 100);
 
     // Queue nested archives for processing
     // Add at FRONT of queue so nested archives complete before moving to other items
     if (processed.nestedSources.length > 0) {
-      const queue = get(importQueue);
-      const newItems = processed.nestedSources.map(createLocalQueueItem);
-      newItems.forEach(addToProgressTracker);
-      const processing = queue.filter((item) => item.status === 'processing');
-      const queued = queue.filter((item) => item.status === 'queued');
-      importQueue.set([...processing, ...newItems, ...queued]);
+      queueAdditionalSources(processed.nestedSources);
     }
 
     return { success: true };
@@ -895,61 +1112,14 @@ async function processQueue(): Promise<void> {
 
       if (!nextItem) break;
 
-      // Update status
-      importQueue.update((q) =>
-        q.map((item) =>
-          item.id === nextItem.id ? { ...item, status: 'processing' as const } : item
-        )
-      );
-      currentImport.set({ ...nextItem, status: 'processing' });
-      updateProgressTracker(nextItem.id, 'Processing', 5);
-
-      // Process the volume
-      const result = await processSingleVolume(nextItem.source, (status, progress) => {
-        importQueue.update((q) =>
-          q.map((item) =>
-            item.id === nextItem.id ? { ...item, status: status as any, progress } : item
-          )
-        );
-        updateProgressTracker(nextItem.id, status, progress);
-      });
-
-      // Queue additional sources (from multi-volume archives or nested archives)
-      // Add at FRONT of queue so all volumes from same archive complete together
-      if (result.additionalSources && result.additionalSources.length > 0) {
-        const newItems = result.additionalSources.map(createLocalQueueItem);
-        newItems.forEach(addToProgressTracker);
-        importQueue.update((q) => {
-          // Insert after any currently processing items, before queued items
-          const processing = q.filter((item) => item.status === 'processing');
-          const queued = q.filter((item) => item.status === 'queued');
-          return [...processing, ...newItems, ...queued];
-        });
-      }
-
-      if (result.success) {
-        // Remove from queue on success
-        importQueue.update((q) => q.filter((item) => item.id !== nextItem.id));
-        removeFromProgressTracker(nextItem.id);
-      } else {
-        // Mark as error
-        importQueue.update((q) =>
-          q.map((item) =>
-            item.id === nextItem.id
-              ? { ...item, status: 'error' as const, errorMessage: result.error }
-              : item
-          )
-        );
-        markProgressTrackerError(nextItem.id, result.error || 'Unknown error');
-      }
-
-      currentImport.set(null);
+      await runQueueItem(nextItem);
     }
   } finally {
     processingQueue = false;
     isImporting.set(false);
     currentImport.set(null);
     decrementPoolUsers(); // Release pool when queue is empty
+    clearIfAllSucceeded();
   }
 }
 
@@ -961,6 +1131,8 @@ export interface ImportResult {
   success: boolean;
   imported: number;
   failed: number;
+  /** Dropped files that aren't archives, images or mokuro files */
+  skipped: number;
   errors: string[];
 }
 
@@ -997,6 +1169,27 @@ function createArchiveSource(archiveFile: File, mokuroFile: File | null): Paired
   };
 }
 
+/**
+ * Quick-scan archives and ask before importing very large ones, so a huge
+ * drop can be cancelled before any extraction starts. Returns the sources to import.
+ */
+async function confirmLargeArchives(sources: PairedSource[]): Promise<PairedSource[]> {
+  const confirmed: PairedSource[] = [];
+  for (const source of sources) {
+    if (source.source.type === 'archive') {
+      const scan = await scanArchive(source.source.file, source.mokuroFile);
+      if (scan && scan.decompressedSize >= LARGE_IMPORT_BYTES) {
+        const proceed = await getImportUiBridge().confirm(
+          `${source.basePath}: ${describeQuickScan(scan)}. Import it?`
+        );
+        if (!proceed) continue;
+      }
+    }
+    confirmed.push(source);
+  }
+  return confirmed;
+}
+
 export async function importArchiveWithOptionalMokuro(
   archiveFile: File,
   mokuroFile: File | null
@@ -1005,6 +1198,7 @@ export async function importArchiveWithOptionalMokuro(
     success: true,
     imported: 0,
     failed: 0,
+    skipped: 0,
     errors: []
   };
 
@@ -1012,42 +1206,12 @@ export async function importArchiveWithOptionalMokuro(
   // note in importFiles) before we await any decompression/processing work.
   void requestPersistentStorage();
 
-  const pairedSource = createArchiveSource(archiveFile, mokuroFile);
-  const queueItem = createLocalQueueItem(pairedSource);
-  addToProgressTracker(queueItem);
-
-  isImporting.set(true);
-  currentImport.set({ ...queueItem, status: 'processing' });
-  updateProgressTracker(queueItem.id, 'Processing', 5);
-
-  try {
-    const processResult = await processSingleVolume(pairedSource, (status, progress) => {
-      updateProgressTracker(queueItem.id, status, progress);
-    });
-
-    if (processResult.additionalSources && processResult.additionalSources.length > 0) {
-      const newItems = processResult.additionalSources.map(createLocalQueueItem);
-      newItems.forEach(addToProgressTracker);
-      importQueue.update((q) => [...q, ...newItems]);
-      processQueue();
-      result.imported += processResult.additionalSources.length;
-    }
-
-    if (processResult.success) {
-      result.imported += 1;
-      removeFromProgressTracker(queueItem.id);
-    } else {
-      result.success = false;
-      result.failed = 1;
-      result.errors.push(processResult.error || 'Unknown error');
-      markProgressTrackerError(queueItem.id, processResult.error || 'Unknown error');
-    }
-  } finally {
-    isImporting.set(false);
-    currentImport.set(null);
+  const [pairedSource] = await confirmLargeArchives([createArchiveSource(archiveFile, mokuroFile)]);
+  if (!pairedSource) {
+    result.skipped = 1;
+    return result;
   }
-
-  return result;
+  return importDirectly(createLocalQueueItem(pairedSource), result);
 }
 
 export async function importFiles(files: File[], options?: ImportOptions): Promise<ImportResult> {
@@ -1055,6 +1219,7 @@ export async function importFiles(files: File[], options?: ImportOptions): Promi
     success: true,
     imported: 0,
     failed: 0,
+    skipped: 0,
     errors: []
   };
 
@@ -1073,6 +1238,14 @@ export async function importFiles(files: File[], options?: ImportOptions): Promi
     // Convert to FileEntry format
     const entries = filesToEntries(files);
 
+    // Report loose files that can't be imported instead of silently dropping them.
+    // Stray files inside a volume folder (txt, nfo, ...) are still ignored quietly.
+    const skipped = findSkippedFiles(entries);
+    if (skipped.length > 0) {
+      skippedImports.update((list) => [...list, ...skipped]);
+      result.skipped = skipped.length;
+    }
+
     // Pair mokuro files with sources
     const pairingResult = await pairMokuroWithSources(entries);
 
@@ -1083,7 +1256,11 @@ export async function importFiles(files: File[], options?: ImportOptions): Promi
     }
 
     if (pairingResult.pairings.length === 0) {
-      getImportUiBridge().notify('No importable volumes found');
+      getImportUiBridge().notify(
+        result.skipped > 0
+          ? `No importable volumes found (${result.skipped} unsupported file(s) skipped)`
+          : 'No importable volumes found'
+      );
       return result;
     }
 
@@ -1094,14 +1271,20 @@ export async function importFiles(files: File[], options?: ImportOptions): Promi
     // If there are image-only pairings, prompt user for confirmation
     let confirmedImageOnlyPairings: PairedSource[] = [];
     if (imageOnlyPairings.length > 0) {
-      const confirmed = await promptForImageOnlyImport(imageOnlyPairings);
-      if (confirmed) {
-        confirmedImageOnlyPairings = imageOnlyPairings;
+      const choice = await promptForImageOnlyImport(imageOnlyPairings);
+      if (choice !== 'skip') {
+        confirmedImageOnlyPairings = imageOnlyPairings.map((p) => ({
+          ...p,
+          runOcr: choice === 'ocr'
+        }));
       }
     }
 
-    // Combine confirmed pairings
-    const allPairings = [...mokuroPairings, ...confirmedImageOnlyPairings];
+    // Combine confirmed pairings, dropping large archives the user declined
+    const allPairings = await confirmLargeArchives([
+      ...mokuroPairings,
+      ...confirmedImageOnlyPairings
+    ]);
 
     if (allPairings.length === 0) {
       getImportUiBridge().notify('No volumes to import');
@@ -1116,50 +1299,7 @@ export async function importFiles(files: File[], options?: ImportOptions): Promi
 
     if (routing.directProcess) {
       // Single item - process directly
-      const queueItem = createLocalQueueItem(routing.directProcess);
-      isImporting.set(true);
-      currentImport.set(queueItem);
-      addToProgressTracker(queueItem);
-      updateProgressTracker(queueItem.id, 'Processing', 5);
-
-      try {
-        const processResult = await processSingleVolume(
-          routing.directProcess,
-          (status, progress) => {
-            updateProgressTracker(queueItem.id, status, progress);
-          }
-        );
-
-        // Queue additional sources (from multi-volume archives or nested archives)
-        // Add at FRONT of queue so all volumes from same archive complete together
-        if (processResult.additionalSources && processResult.additionalSources.length > 0) {
-          const newItems = processResult.additionalSources.map(createLocalQueueItem);
-          newItems.forEach(addToProgressTracker);
-          importQueue.update((q) => {
-            const processing = q.filter((item) => item.status === 'processing');
-            const queued = q.filter((item) => item.status === 'queued');
-            return [...processing, ...newItems, ...queued];
-          });
-
-          // Start processing queue for additional items
-          processQueue();
-
-          result.imported += processResult.additionalSources.length;
-        }
-
-        if (processResult.success) {
-          result.imported += 1;
-          removeFromProgressTracker(queueItem.id);
-        } else {
-          result.failed = 1;
-          result.errors.push(processResult.error || 'Unknown error');
-          result.success = false;
-          markProgressTrackerError(queueItem.id, processResult.error || 'Unknown error');
-        }
-      } finally {
-        isImporting.set(false);
-        currentImport.set(null);
-      }
+      await importDirectly(createLocalQueueItem(routing.directProcess), result);
     } else {
       // Multiple items - queue all
       const queueItems = routing.queuedItems.map(createLocalQueueItem);
@@ -1183,11 +1323,117 @@ export async function importFiles(files: File[], options?: ImportOptions): Promi
   }
 }
 
+/**
+ * Replace an existing volume's pages and OCR with a newly picked archive or
+ * folder, e.g. a re-OCR'd or higher-quality scan of the same volume.
+ *
+ * The volume keeps its UUID, titles and series, so reading progress and
+ * stats carry over. The files must contain exactly one volume; if its page
+ * count differs the user is asked to confirm first. Errors are returned,
+ * not shown, so the caller can report them.
+ */
+export async function replaceVolumeFromFiles(
+  volumeUuid: string,
+  files: File[]
+): Promise<ImportResult> {
+  const result: ImportResult = {
+    success: true,
+    imported: 0,
+    failed: 0,
+    skipped: 0,
+    errors: []
+  };
+
+  const fail = (message: string) => {
+    result.success = false;
+    result.failed = 1;
+    result.errors.push(message);
+    return result;
+  };
+
+  if (files.length === 0) {
+    return result;
+  }
+
+  void requestPersistentStorage();
+
+  try {
+    const { pairings } = await pairMokuroWithSources(filesToEntries(files));
+    if (pairings.length !== 1) {
+      return fail(
+        pairings.length === 0
+          ? 'No importable volume found'
+          : `Found ${pairings.length} volumes - pick a single volume to replace with`
+      );
+    }
+
+    let pairing: PairedSource = { ...pairings[0], replaceVolumeUuid: volumeUuid };
+    if (pairing.imageOnly) {
+      const choice = await promptForImageOnlyImport([pairing]);
+      if (choice === 'skip') {
+        return result;
+      }
+      pairing = { ...pairing, runOcr: choice === 'ocr' };
+    }
+
+    return importDirectly(createLocalQueueItem(pairing), result);
+  } catch (error) {
+    return fail(`Replace failed: ${error instanceof Error ? error.message : 'Unknown error'}`);
+  }
+}
+
+/**
+ * Import a single item right away (not through the background queue) so the
+ * caller gets its result. It is still listed in the queue for the import panel.
+ */
+async function importDirectly(item: ImportQueueItem, result: ImportResult): Promise<ImportResult> {
+  addToProgressTracker(item);
+  importQueue.update((q) => [...q, { ...item, status: 'extracting' as const }]);
+  isImporting.set(true);
+
+  try {
+    const processResult = await runQueueItem(item);
+
+    if (processResult.additionalSources && processResult.additionalSources.length > 0) {
+      // Start processing queue for additional items
+      processQueue();
+      result.imported += processResult.additionalSources.length;
+    }
+
+    if (processResult.success) {
+      result.imported += 1;
+    } else {
+      result.success = false;
+      result.failed = 1;
+      result.errors.push(processResult.error || 'Unknown error');
+    }
+  } finally {
+    isImporting.set(false);
+    currentImport.set(null);
+    if (!processingQueue) clearIfAllSucceeded();
+  }
+
+  return result;
+}
+
+/**
+ * Loose top-level files that are neither archives, images nor mokuro files
+ */
+function findSkippedFiles(entries: FileEntry[]): SkippedImport[] {
+  return entries
+    .filter((entry) => !entry.path.includes('/') && !isSystemFile(entry.path))
+    .filter((entry) => categorizeFile(entry).category === 'other')
+    .map((entry) => ({
+      name: entry.path,
+      reason: 'Not an archive, image or .mokuro file'
+    }));
+}
+
 /**
  * Prompt user to confirm image-only import
  * Groups volumes by series and shows a confirmation modal
  */
-async function promptForImageOnlyImport(pairings: PairedSource[]): Promise<boolean> {
+async function promptForImageOnlyImport(pairings: PairedSource[]): Promise<ImageOnlyChoice> {
   // Group by series name
   const seriesGroups = new Map<string, number>();
 
@@ -1203,10 +1449,49 @@ async function promptForImageOnlyImport(pairings: PairedSource[]): Promise<boole
 
   return getImportUiBridge().promptImageOnly({
     seriesList,
-    totalVolumeCount: pairings.length
+    totalVolumeCount: pairings.length,
+    ocrAvailable: getOcrEngine() !== null
   });
 }
 
+/**
+ * Generate .mokuro data for an image-only volume the user chose to OCR.
+ * Per-page progress gets its own tracker entry, whose Cancel aborts the job
+ * (the import then fails and can be retried from the import panel).
+ */
+async function runOcrIfRequested(
+  source: PairedSource,
+  decompressed: DecompressedVolume,
+  progress: number,
+  onProgress?: (status: string, progress: number) => void
+): Promise<void> {
+  if (!source.runOcr || decompressed.mokuroFile) return;
+
+  const engine = getOcrEngine();
+  if (!engine) {
+    throw new Error('OCR was requested but no Cloud Vision API key is configured');
+  }
+
+  const ui = getImportUiBridge();
+  const processId = `ocr-${source.id}`;
+  const controller = new AbortController();
+  ui.addProgress(processId, `OCR: ${source.basePath}`, 'Starting...', 0, () =>
+    controller.abort()
+  );
+
+  try {
+    decompressed.mokuroFile = await runOcrOnVolume(decompressed, engine, {
+      signal: controller.signal,
+      onPage: (done, total) => {
+        ui.updateProgress(processId, `Page ${done}/${total}`, (done / total) * 100);
+        onProgress?.(`Running OCR (${done}/${total})...`, progress);
+      }
+    });
+  } finally {
+    ui.removeProgress(processId);
+  }
+}
+
 /**
  * Prompt user when importing a volume with missing files
  * Shows the list of missing files and lets user choose to import anyway
@@ -1216,17 +1501,42 @@ async function promptForMissingFiles(info: MissingFilesInfo): Promise<boolean> {
 }
 
 /**
- * Clear completed/errored items from the queue
+ * Clear completed/errored items and skipped files from the import panel
  */
 export function clearCompletedImports(): void {
-  importQueue.update((q) =>
-    q.filter((item) => item.status === 'queued' || item.status === 'processing')
-  );
+  importQueue.update((q) => q.filter((item) => item.status === 'queued' || isActiveImport(item)));
+  skippedImports.set([]);
 }
 
 /**
  * Cancel all queued imports
  */
 export function cancelQueuedImports(): void {
-  importQueue.update((q) => q.filter((item) => item.status === 'processing'));
+  importQueue.update((q) => q.filter((item) => item.status !== 'queued'));
+}
+
+/**
+ * Re-queue a failed import. Volumes that already imported are left alone.
+ */
+export function retryImport(id: string): void {
+  const item = get(importQueue).find((i) => i.id === id);
+  if (!item || item.status !== 'error') return;
+
+  updateQueueItem(id, {
+    status: 'queued',
+    statusText: undefined,
+    errorMessage: undefined,
+    progress: 0
+  });
+  addToProgressTracker(item);
+  processQueue();
+}
+
+/**
+ * Re-queue every failed import
+ */
+export function retryFailedImports(): void {
+  get(importQueue)
+    .filter((item) => item.status === 'error')
+    .forEach((item) => retryImport(item.id));
 }
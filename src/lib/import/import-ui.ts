@@ -1,30 +1,46 @@
 import { progressTrackerStore } from '$lib/util/progress-tracker';
 import { showSnackbar } from '$lib/util/snackbar';
-import { promptMissingFiles, promptImageOnlyImport } from '$lib/util/modals';
+import { promptConfirmation, promptMissingFiles, promptImageOnlyImport } from '$lib/util/modals';
 import type { MissingFilesInfo, SeriesImportInfo as ModalSeriesImportInfo } from '$lib/util/modals';
 export type { MissingFilesInfo } from '$lib/util/modals';
 
 export interface SeriesImportInfo {
   seriesList: ModalSeriesImportInfo[];
   totalVolumeCount: number;
+  /** Whether an OCR engine is configured, so OCR can be offered */
+  ocrAvailable: boolean;
 }
 
+/** What to do with volumes that have no .mokuro file */
+export type ImageOnlyChoice = 'image-only' | 'ocr' | 'skip';
+
 export interface ImportUiBridge {
-  addProgress(processId: string, description: string, status: string, progress: number): void;
+  addProgress(
+    processId: string,
+    description: string,
+    status: string,
+    progress: number,
+    onCancel?: () => void
+  ): void;
   updateProgress(processId: string, status: string, progress: number): void;
+  failProgress(processId: string, status: string): void;
   removeProgress(processId: string): void;
   notify(message: string): void;
-  promptImageOnly(info: SeriesImportInfo): Promise<boolean>;
+  promptImageOnly(info: SeriesImportInfo): Promise<ImageOnlyChoice>;
   promptMissing(info: MissingFilesInfo): Promise<boolean>;
+  confirm(message: string): Promise<boolean>;
 }
 
 let uiBridge: ImportUiBridge = {
-  addProgress: (processId, description, status, progress) => {
-    progressTrackerStore.addProcess({ id: processId, description, status, progress });
+  addProgress: (processId, description, status, progress, onCancel) => {
+    progressTrackerStore.addProcess({ id: processId, description, status, progress, onCancel });
   },
   updateProgress: (processId, status, progress) => {
     progressTrackerStore.updateProcess(processId, { status, progress });
   },
+  failProgress: (processId, status) => {
+    progressTrackerStore.failProcess(processId, status);
+  },
   removeProgress: (processId) => {
     progressTrackerStore.removeProcess(processId);
   },
@@ -32,12 +48,13 @@ let uiBridge: ImportUiBridge = {
     showSnackbar(message);
   },
   promptImageOnly: (info) =>
-    new Promise<boolean>((resolve) => {
+    new Promise<ImageOnlyChoice>((resolve) => {
       promptImageOnlyImport(
         info.seriesList,
         info.totalVolumeCount,
-        () => resolve(true),
-        () => resolve(false)
+        () => resolve('image-only'),
+        () => resolve('skip'),
+        info.ocrAvailable ? () => resolve('ocr') : undefined
       );
     }),
   promptMissing: (info) =>
@@ -47,6 +64,14 @@ let uiBridge: ImportUiBridge = {
         () => resolve(true),
         () => resolve(false)
       );
+    }),
+  confirm: (message) =>
+    new Promise<boolean>((resolve) => {
+      promptConfirmation(
+        message,
+        () => resolve(true),
+        () => resolve(false)
+      );
     })
 };
 
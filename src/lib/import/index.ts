@@ -21,9 +21,11 @@ export type {
   DecompressedVolume,
   ProcessedVolume,
   ProcessedMetadata,
+  VolumeOrigin,
   ProcessedPage,
   ImportProvider,
-  ImportQueueItem
+  ImportQueueItem,
+  SkippedImport
 } from './types';
 
 // Type utilities
@@ -70,22 +72,43 @@ export {
   parseMokuroFile,
   matchImagesToPages,
   extractVolumeInfo,
+  ImportEntryError,
   type ParsedMokuro,
   type ImageMatchResult,
   type VolumeInfo
 } from './processing';
 
 // Database
-export { saveVolume, volumeExists, deleteVolume } from './database';
+export {
+  saveVolume,
+  volumeExists,
+  findVolumeFromSameSource,
+  deleteVolume,
+  replaceVolumeContent,
+  pageCountWarning
+} from './database';
 
 // Import Service (main entry point)
 export {
   importFiles,
   importArchiveWithOptionalMokuro,
+  replaceVolumeFromFiles,
   importQueue,
   currentImport,
   isImporting,
+  skippedImports,
   clearCompletedImports,
   cancelQueuedImports,
+  retryImport,
+  retryFailedImports,
   type ImportResult
 } from './import-service';
+
+// Import from a direct link
+export {
+  importFromUrl,
+  downloadArchiveFromUrl,
+  parseImportUrl,
+  parseHeaderLine,
+  MAX_URL_IMPORT_BYTES
+} from './url-import';
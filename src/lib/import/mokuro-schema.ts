@@ -0,0 +1,204 @@
+/**
+ * .mokuro schema detection, validation and migration
+ *
+ * Different mokuro releases emit slightly different JSON. Importing a layout the
+ * reader doesn't understand used to "succeed" and then show pages with no text
+ * overlay. Files are checked here at import time: known older layouts are
+ * migrated to the current shape, and anything else is rejected with an error
+ * that names the offending version, page or block.
+ */
+
+/** Highest mokuro major version whose output this reader understands */
+export const MAX_SUPPORTED_MOKURO_MAJOR = 0;
+
+export class UnsupportedMokuroVersionError extends Error {
+  constructor(public readonly version: string) {
+    super(
+      `Unsupported mokuro version ${version}: this reader supports files from mokuro ` +
+        `0.x. Re-run an older mokuro or update the reader.`
+    );
+    this.name = 'UnsupportedMokuroVersionError';
+  }
+}
+
+export interface MokuroBlockData {
+  box: number[];
+  vertical: boolean;
+  font_size: number;
+  lines: string[];
+  lines_coords?: number[][][];
+  [key: string]: unknown;
+}
+
+export interface MokuroPageData {
+  version?: string;
+  img_width?: number;
+  img_height?: number;
+  img_path: string;
+  blocks: MokuroBlockData[];
+}
+
+export interface NormalizedMokuroPages {
+  pages: MokuroPageData[];
+  /** True when an older layout was rewritten to the current one */
+  migrated: boolean;
+}
+
+const isRecord = (value: unknown): value is Record<string, unknown> =>
+  typeof value === 'object' && value !== null && !Array.isArray(value);
+
+const isFiniteNumber = (value: unknown): value is number =>
+  typeof value === 'number' && Number.isFinite(value);
+
+/**
+ * Schema version of a parsed .mokuro file. Early files only carried the
+ * version on each page, so fall back to the first page's.
+ */
+export function detectMokuroVersion(data: Record<string, unknown>): string | null {
+  if (typeof data.version === 'string' && data.version.trim()) {
+    return data.version.trim();
+  }
+  const firstPage = Array.isArray(data.pages) ? data.pages[0] : undefined;
+  if (isRecord(firstPage) && typeof firstPage.version === 'string' && firstPage.version.trim()) {
+    return firstPage.version.trim();
+  }
+  return null;
+}
+
+/**
+ * Throw UnsupportedMokuroVersionError for versions this reader can't read
+ */
+export function assertSupportedMokuroVersion(version: string): void {
+  const match = version.match(/^v?(\d+)\.(\d+)/);
+  if (!match || Number(match[1]) > MAX_SUPPORTED_MOKURO_MAJOR) {
+    throw new UnsupportedMokuroVersionError(version);
+  }
+}
+
+/** Bounding box [x1, y1, x2, y2] enclosing all line quads */
+function boxFromLineCoords(linesCoords: number[][][]): number[] | null {
+  const points = linesCoords.flat().filter((p) => isFiniteNumber(p?.[0]) && isFiniteNumber(p?.[1]));
+  if (points.length === 0) return null;
+  const xs = points.map((p) => p[0]);
+  const ys = points.map((p) => p[1]);
+  return [Math.min(...xs), Math.min(...ys), Math.max(...xs), Math.max(...ys)];
+}
+
+function normalizeBlock(
+  block: unknown,
+  where: string
+): { block: MokuroBlockData; migrated: boolean } {
+  if (!isRecord(block)) {
+    throw new Error(`Invalid mokuro file: ${where} is not an object`);
+  }
+
+  let migrated = false;
+
+  // Some older output stored single-line blocks as a plain string
+  let lines = block.lines;
+  if (typeof lines === 'string') {
+    lines = [lines];
+    migrated = true;
+  }
+  if (!Array.isArray(lines) || !lines.every((line) => typeof line === 'string')) {
+    throw new Error(`Invalid mokuro file: ${where} has no text lines`);
+  }
+
+  const linesCoords = Array.isArray(block.lines_coords)
+    ? (block.lines_coords as number[][][])
+    : undefined;
+
+  let box = block.box;
+  if (!Array.isArray(box) && linesCoords) {
+    box = boxFromLineCoords(linesCoords);
+    migrated = true;
+  }
+  if (!Array.isArray(box) || box.length !== 4 || !box.every(isFiniteNumber)) {
+    throw new Error(`Invalid mokuro file: ${where} has no valid bounding box`);
+  }
+
+  let vertical = block.vertical;
+  if (typeof vertical !== 'boolean') {
+    vertical = false;
+    migrated = true;
+  }
+
+  // Estimate the font size from the box when it's missing: one line per column
+  // (vertical) or row (horizontal)
+  let fontSize = block.font_size;
+  if (!isFiniteNumber(fontSize)) {
+    const [x1, y1, x2, y2] = box;
+    const extent = vertical ? x2 - x1 : y2 - y1;
+    fontSize = Math.max(1, Math.round(extent / Math.max(1, lines.length)));
+    migrated = true;
+  }
+
+  return {
+    block: {
+      ...block,
+      box,
+      vertical,
+      font_size: fontSize,
+      lines: lines as string[],
+      ...(linesCoords && { lines_coords: linesCoords })
+    },
+    migrated
+  };
+}
+
+/**
+ * Validate every page and block, migrating known older layouts.
+ * Page dimensions may be missing (they're filled in from the images during
+ * processing) but must be positive numbers when present.
+ */
+export function normalizeMokuroPages(pages: unknown): NormalizedMokuroPages {
+  if (!Array.isArray(pages)) {
+    throw new Error('Invalid mokuro file: pages is not a list');
+  }
+
+  let migrated = false;
+
+  const normalized = pages.map((page, pageIndex): MokuroPageData => {
+    const where = `page ${pageIndex + 1}`;
+    if (!isRecord(page)) {
+      throw new Error(`Invalid mokuro file: ${where} is not an object`);
+    }
+    if (typeof page.img_path !== 'string' || !page.img_path) {
+      throw new Error(`Invalid mokuro file: ${where} has no image path`);
+    }
+
+    for (const dimension of ['img_width', 'img_height'] as const) {
+      const value = page[dimension];
+      if (value !== undefined && !(isFiniteNumber(value) && value > 0)) {
+        throw new Error(
+          `Invalid mokuro file: ${where} (${page.img_path}) has invalid ${dimension}`
+        );
+      }
+    }
+
+    // Pages without any detected text were written without a blocks list
+    let blocks = page.blocks;
+    if (blocks === undefined) {
+      blocks = [];
+      migrated = true;
+    }
+    if (!Array.isArray(blocks)) {
+      throw new Error(`Invalid mokuro file: ${where} (${page.img_path}) has invalid blocks`);
+    }
+
+    return {
+      ...page,
+      img_path: page.img_path,
+      img_width: page.img_width as number | undefined,
+      img_height: page.img_height as number | undefined,
+      version: typeof page.version === 'string' ? page.version : undefined,
+      blocks: blocks.map((block, blockIndex) => {
+        const result = normalizeBlock(block, `${where} (${page.img_path}) block ${blockIndex + 1}`);
+        migrated ||= result.migrated;
+        return result.block;
+      })
+    };
+  });
+
+  return { pages: normalized, migrated };
+}
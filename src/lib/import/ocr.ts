@@ -0,0 +1,296 @@
+/**
+ * OCR for image-only volumes
+ *
+ * Archives without a .mokuro file import as image-only volumes: readable, but
+ * with no text overlay. When the user opts in, every page is sent to Google
+ * Cloud Vision's document text detection and the results are assembled into a
+ * .mokuro file before the volume is processed, so it imports exactly as if it
+ * had been run through mokuro.
+ *
+ * https://cloud.google.com/vision/docs/fulltext-annotations
+ */
+
+import { get } from 'svelte/store';
+import { miscSettings } from '$lib/settings/misc';
+import { extractSeriesName, generateDeterministicUUID } from '$lib/util/series-extraction';
+import { normalizeMokuroPages, type MokuroPageData } from './mokuro-schema';
+import { extractVolumeInfo, sortImagePaths } from './processing';
+import type { DecompressedVolume } from './types';
+
+/** Version written to .mokuro files generated here */
+export const GENERATED_MOKURO_VERSION = '0.2.0';
+
+export class OcrCancelledError extends Error {
+  constructor() {
+    super('OCR cancelled');
+    this.name = 'OcrCancelledError';
+  }
+}
+
+export interface OcrEngine {
+  /** Recognize one page, returning it in mokuro's per-page format */
+  recognizePage(image: File, signal?: AbortSignal): Promise<unknown>;
+}
+
+const VISION_ANNOTATE_URL = 'https://vision.googleapis.com/v1/images:annotate';
+
+/** Vision rejects requests whose base64 image content is over about 10 MB */
+const MAX_IMAGE_CONTENT_LENGTH = 10_000_000;
+
+/** Attempts at re-encoding an oversized page, each smaller than the last */
+const MAX_SHRINK_ATTEMPTS = 4;
+
+interface VisionVertex {
+  x?: number;
+  y?: number;
+}
+
+interface VisionSymbol {
+  text: string;
+  property?: { detectedBreak?: { type: string } };
+}
+
+interface VisionBlock {
+  boundingBox?: { vertices?: VisionVertex[] };
+  paragraphs?: { words?: { symbols?: VisionSymbol[] }[] }[];
+}
+
+interface VisionPage {
+  width?: number;
+  height?: number;
+  blocks?: VisionBlock[];
+}
+
+/** Breaks that end a line of text rather than separating words within it */
+const LINE_BREAKS = new Set(['LINE_BREAK', 'EOL_SURE_SPACE']);
+
+function base64Length(size: number): number {
+  return Math.ceil(size / 3) * 4;
+}
+
+async function toBase64(image: Blob): Promise<string> {
+  const bytes = new Uint8Array(await image.arrayBuffer());
+  let binary = '';
+  for (let i = 0; i < bytes.length; i += 0x8000) {
+    binary += String.fromCharCode(...bytes.subarray(i, i + 0x8000));
+  }
+  return btoa(binary);
+}
+
+interface ShrunkImage {
+  image: Blob;
+  /** Factors mapping coordinates on `image` back to the original page */
+  scaleX: number;
+  scaleY: number;
+}
+
+/**
+ * Re-encode a page too big to send as JPEG, downscaling it until it fits.
+ * Returns null where canvas encoding isn't available or nothing small enough
+ * came out.
+ */
+async function shrinkImage(image: File): Promise<ShrunkImage | null> {
+  if (typeof OffscreenCanvas === 'undefined' || typeof createImageBitmap === 'undefined') {
+    return null;
+  }
+  let bitmap: ImageBitmap | null = null;
+  try {
+    bitmap = await createImageBitmap(image);
+    let scale = 1;
+    for (let attempt = 0; attempt < MAX_SHRINK_ATTEMPTS; attempt++) {
+      const width = Math.max(1, Math.round(bitmap.width * scale));
+      const height = Math.max(1, Math.round(bitmap.height * scale));
+      const canvas = new OffscreenCanvas(width, height);
+      const ctx = canvas.getContext('2d');
+      if (!ctx) return null;
+      ctx.drawImage(bitmap, 0, 0, width, height);
+      const jpeg = await canvas.convertToBlob({ type: 'image/jpeg', quality: 0.9 });
+      const length = base64Length(jpeg.size);
+      if (length <= MAX_IMAGE_CONTENT_LENGTH) {
+        return { image: jpeg, scaleX: bitmap.width / width, scaleY: bitmap.height / height };
+      }
+      // Encoded size tracks pixel count, with some margin for JPEG overhead
+      scale *= Math.sqrt(MAX_IMAGE_CONTENT_LENGTH / length) * 0.9;
+    }
+    return null;
+  } catch (error) {
+    console.warn(`Failed to re-encode page ${image.name}:`, error);
+    return null;
+  } finally {
+    bitmap?.close?.();
+  }
+}
+
+/** `page` with its coordinates mapped back from a downscaled copy */
+function scaleVisionPage(page: VisionPage, { scaleX, scaleY }: ShrunkImage): VisionPage {
+  const scaleVertex = (v: VisionVertex) => ({
+    x: Math.round((v.x ?? 0) * scaleX),
+    y: Math.round((v.y ?? 0) * scaleY)
+  });
+  return {
+    width: page.width === undefined ? undefined : Math.round(page.width * scaleX),
+    height: page.height === undefined ? undefined : Math.round(page.height * scaleY),
+    blocks: page.blocks?.map((block) => ({
+      ...block,
+      boundingBox: { vertices: block.boundingBox?.vertices?.map(scaleVertex) }
+    }))
+  };
+}
+
+/** Text lines of a Vision block, split where Vision detected a line break */
+function blockLines(block: VisionBlock): string[] {
+  const lines: string[] = [];
+  let line = '';
+  for (const paragraph of block.paragraphs ?? []) {
+    for (const word of paragraph.words ?? []) {
+      for (const symbol of word.symbols ?? []) {
+        line += symbol.text;
+        const breakType = symbol.property?.detectedBreak?.type;
+        if (breakType && LINE_BREAKS.has(breakType)) {
+          lines.push(line);
+          line = '';
+        }
+      }
+    }
+    if (line) lines.push(line);
+    line = '';
+  }
+  return lines;
+}
+
+/**
+ * A Vision page in mokuro's per-page format. Vision doesn't report writing
+ * direction, so blocks taller than they are wide are taken as vertical text;
+ * font sizes are left for the .mokuro normalizer to estimate from the box.
+ */
+export function visionPageToMokuro(page: VisionPage): unknown {
+  const blocks = (page.blocks ?? []).flatMap((block) => {
+    const vertices = block.boundingBox?.vertices ?? [];
+    const lines = blockLines(block);
+    if (vertices.length === 0 || lines.length === 0) return [];
+
+    // Vision leaves out coordinates that are zero
+    const xs = vertices.map((v) => v.x ?? 0);
+    const ys = vertices.map((v) => v.y ?? 0);
+    const box = [Math.min(...xs), Math.min(...ys), Math.max(...xs), Math.max(...ys)];
+    return [{ box, vertical: box[3] - box[1] > box[2] - box[0], lines }];
+  });
+
+  return { img_width: page.width, img_height: page.height, blocks };
+}
+
+/**
+ * Engine backed by Google Cloud Vision's DOCUMENT_TEXT_DETECTION, called with
+ * the user's API key. Pages over Vision's size limit are sent as a smaller
+ * JPEG, with the results mapped back to the original page.
+ */
+export function createCloudVisionOcrEngine(
+  apiKey: string,
+  fetchImpl: typeof fetch = fetch
+): OcrEngine {
+  return {
+    async recognizePage(image, signal) {
+      let shrunk: ShrunkImage | null = null;
+      if (base64Length(image.size) > MAX_IMAGE_CONTENT_LENGTH) {
+        shrunk = await shrinkImage(image);
+        if (!shrunk) {
+          throw new Error(
+            `Cloud Vision failed for ${image.name}: the page is over Vision's 10 MB limit and ` +
+              'could not be re-encoded smaller'
+          );
+        }
+      }
+
+      const response = await fetchImpl(`${VISION_ANNOTATE_URL}?key=${encodeURIComponent(apiKey)}`, {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/json' },
+        body: JSON.stringify({
+          requests: [
+            {
+              image: { content: await toBase64(shrunk?.image ?? image) },
+              features: [{ type: 'DOCUMENT_TEXT_DETECTION' }],
+              imageContext: { languageHints: ['ja'] }
+            }
+          ]
+        }),
+        signal
+      });
+      const data = await response.json().catch(() => null);
+      const result = data?.responses?.[0];
+      const error = data?.error?.message ?? result?.error?.message;
+      if (!response.ok || error) {
+        throw new Error(`Cloud Vision failed for ${image.name}: ${error ?? response.status}`);
+      }
+
+      // Pages with no text come back without an annotation
+      const page: VisionPage = result?.fullTextAnnotation?.pages?.[0] ?? {};
+      return visionPageToMokuro(shrunk ? scaleVisionPage(page, shrunk) : page);
+    }
+  };
+}
+
+/**
+ * The configured OCR engine, or null when OCR isn't set up
+ */
+export function getOcrEngine(): OcrEngine | null {
+  const apiKey = get(miscSettings).cloudVisionApiKey.trim();
+  return apiKey ? createCloudVisionOcrEngine(apiKey) : null;
+}
+
+export interface OcrOptions {
+  signal?: AbortSignal;
+  onPage?: (done: number, total: number) => void;
+}
+
+/**
+ * OCR every image of an image-only volume and return the resulting .mokuro file.
+ * Series and volume UUIDs match what an image-only import of the same volume
+ * would get, so OCR'd and plain copies are recognized as the same volume.
+ *
+ * @throws OcrCancelledError when `signal` aborts
+ */
+export async function runOcrOnVolume(
+  volume: DecompressedVolume,
+  engine: OcrEngine,
+  options: OcrOptions = {}
+): Promise<File> {
+  const { signal, onPage } = options;
+  const imagePaths = sortImagePaths(volume.imageFiles.keys());
+  const pages: MokuroPageData[] = [];
+
+  for (const [index, imgPath] of imagePaths.entries()) {
+    if (signal?.aborted) throw new OcrCancelledError();
+
+    const image = volume.imageFiles.get(imgPath)!;
+    let result: unknown;
+    try {
+      result = await engine.recognizePage(image, signal);
+    } catch (error) {
+      if (signal?.aborted) throw new OcrCancelledError();
+      throw error;
+    }
+
+    const page = typeof result === 'object' && result !== null ? result : {};
+    pages.push({ ...page, img_path: imgPath } as MokuroPageData);
+    onPage?.(index + 1, imagePaths.length);
+  }
+
+  // Catch a Vision response that doesn't convert to valid pages here, naming
+  // the page, rather than as a confusing import error
+  const normalized = normalizeMokuroPages(pages).pages;
+
+  const { volume: volumeTitle } = extractVolumeInfo(volume.basePath);
+  const seriesTitle = extractSeriesName(volume.basePath);
+  const data = {
+    version: GENERATED_MOKURO_VERSION,
+    title: seriesTitle,
+    title_uuid: generateDeterministicUUID(seriesTitle),
+    volume: volumeTitle,
+    volume_uuid: generateDeterministicUUID(`${seriesTitle}/${volumeTitle}`),
+    pages: normalized
+  };
+
+  return new File([JSON.stringify(data)], `${volumeTitle}.mokuro`, {
+    type: 'application/json'
+  });
+}
@@ -6,12 +6,16 @@
  *
  * Pairing Rules:
  * 1. Mokuro inside a directory → pairs with that directory
- * 2. Mokuro at root with same-name directory → pairs with that directory
- * 3. Mokuro at root with same-name archive → pairs with that archive
+ * 2. Mokuro with same-name sibling directory → pairs with that directory
+ * 3. Mokuro with same-name sibling archive → pairs with that archive
  * 4. TOC format: Mokuro alone in dir with only subdirs (no sibling images) → pairs as TOC
+ * 4b. Only mokuro in its dir, with a single image subfolder (any name) → pairs with that folder
  * 5. Archive without external mokuro → self-contained pairing (internal mokuro expected)
  * 6. Directory with images but no mokuro anywhere → image-only pairing
  *
+ * Rules 1-4b apply at any depth, so archives that wrap everything in a
+ * top-level folder (e.g. VolumeName/VolumeName.mokuro) pair the same way.
+ *
  * Priority:
  * - External mokuro takes precedence over internal mokuro
  * - Directory takes precedence over same-name archive (already decompressed)
@@ -131,8 +135,11 @@ export async function pairMokuroWithSources(entries: FileEntry[]): Promise<Pairi
       }
 
       if (mergedFiles.size > 0) {
+        // Full path to the matched directory (in its original casing), so archive
+        // extraction can find the images when the mokuro isn't at the root
+        const basePath = matchingDirs[0].slice(0, exactMatch.length);
         pairedMokuroPaths.add(mokuro.path);
-        pairings.push(createDirectoryPairing(mokuroStem, mergedFiles, mokuro.file, false));
+        pairings.push(createDirectoryPairing(basePath, mergedFiles, mokuro.file, false));
       }
     }
   }
@@ -172,6 +179,31 @@ export async function pairMokuroWithSources(entries: FileEntry[]): Promise<Pairi
     }
   }
 
+  // PASS 4b: Single image subfolder (the only mokuro in its dir, one folder of images)
+  // e.g., vol/vol.mokuro + vol/images/page001.jpg
+  // Skipped when the dir holds several mokuro files, since the folder could belong to any
+  for (const mokuro of getUnpairedMokuro()) {
+    const mokuroDir = mokuro.parentDir || '.';
+    const dirMokuroCount = mokuroFiles.filter((m) => (m.parentDir || '.') === mokuroDir).length;
+    if (dirMokuroCount > 1) continue;
+
+    const subfolder = findSingleImageSubfolder(mokuro, dirStructure, pairedImageDirs);
+    if (!subfolder) continue;
+
+    const prefix = mokuroDir === '.' ? '' : mokuroDir + '/';
+    const mergedFiles = new Map<string, File>();
+    for (const dir of subfolder.dirs) {
+      // Keys are relative to the mokuro, matching how img_path is written
+      const relativeDir = dir.slice(prefix.length);
+      for (const [filename, file] of dirStructure.get(dir)!) {
+        mergedFiles.set(relativeDir + '/' + filename, file);
+      }
+      pairedImageDirs.add(dir);
+    }
+    pairedMokuroPaths.add(mokuro.path);
+    pairings.push(createDirectoryPairing(subfolder.path, mergedFiles, mokuro.file, false));
+  }
+
   // PASS 5: Report orphaned mokuro files
   for (const mokuro of getUnpairedMokuro()) {
     warnings.push(`Orphaned mokuro file: ${mokuro.path} (no matching images or archive)`);
@@ -266,6 +298,35 @@ function checkTocFormat(
   return null;
 }
 
+/**
+ * Find the one unpaired subfolder of a mokuro's directory that holds images
+ * (directly or nested). Returns null if the mokuro has sibling images or if
+ * images are spread over more than one subfolder.
+ */
+function findSingleImageSubfolder(
+  mokuro: CategorizedFile,
+  dirStructure: Map<string, Map<string, File>>,
+  pairedImageDirs: Set<string>
+): { path: string; dirs: string[] } | null {
+  const mokuroDir = mokuro.parentDir || '.';
+  if (dirStructure.has(mokuroDir)) return null;
+
+  const prefix = mokuroDir === '.' ? '' : mokuroDir + '/';
+  let child: string | null = null;
+  const dirs: string[] = [];
+
+  for (const dir of dirStructure.keys()) {
+    if (pairedImageDirs.has(dir) || dir === '.' || !dir.startsWith(prefix)) continue;
+    const name = dir.slice(prefix.length).split('/')[0];
+    if (child !== null && name !== child) return null;
+    child = name;
+    dirs.push(dir);
+  }
+
+  if (child === null) return null;
+  return { path: prefix + child, dirs };
+}
+
 /**
  * Find directories with images that haven't been paired
  */
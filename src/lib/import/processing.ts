@@ -20,13 +20,22 @@ import type {
   ArchiveSource
 } from './types';
 import { generateThumbnail } from '$lib/catalog/thumbnails';
+import { THUMBNAIL_VERSION, pickCoverPath } from '$lib/catalog/cover-page';
 import {
   extractSeriesName,
   extractTitlesFromPath,
   generateDeterministicUUID
 } from '$lib/util/series-extraction';
 import { generateUUID } from '$lib/util/uuid';
-import { naturalSort } from '$lib/util/natural-sort';
+import { pageSortComparator } from '$lib/util/natural-sort';
+import { miscSettings } from '$lib/settings/misc';
+import { get } from 'svelte/store';
+import {
+  assertSupportedMokuroVersion,
+  detectMokuroVersion,
+  normalizeMokuroPages
+} from './mokuro-schema';
+import { flattenToFirstFrame, readAnimatedImageInfo } from './animated-images';
 
 // ============================================
 // TYPES
@@ -87,6 +96,20 @@ export interface VolumeInfo {
   volume: string;
 }
 
+/**
+ * An entry that stopped a volume's import. The volume is processed in full
+ * before anything is written, so nothing of it is saved when this is thrown.
+ */
+export class ImportEntryError extends Error {
+  constructor(
+    readonly entry: string,
+    reason: string
+  ) {
+    super(`Could not read ${entry}: ${reason}`);
+    this.name = 'ImportEntryError';
+  }
+}
+
 // ============================================
 // MOKURO PARSING
 // ============================================
@@ -109,6 +132,80 @@ async function readFileAsText(file: File | Blob): Promise<string> {
   });
 }
 
+function startsWith(bytes: Uint8Array, signature: number[] | string, offset = 0): boolean {
+  const expected =
+    typeof signature === 'string' ? [...signature].map((c) => c.charCodeAt(0)) : signature;
+  return expected.every((byte, i) => bytes[offset + i] === byte);
+}
+
+/**
+ * Whether the bytes start like one of the supported image formats.
+ * Catches pages that are truncated to garbage or aren't images at all
+ * without decoding every page.
+ */
+export function hasImageSignature(bytes: Uint8Array): boolean {
+  return (
+    startsWith(bytes, [0xff, 0xd8, 0xff]) || // JPEG
+    startsWith(bytes, [0x89, 0x50, 0x4e, 0x47]) || // PNG
+    startsWith(bytes, 'GIF8') ||
+    (startsWith(bytes, 'RIFF') && startsWith(bytes, 'WEBP', 8)) ||
+    startsWith(bytes, 'BM') ||
+    startsWith(bytes, 'ftyp', 4) || // AVIF/HEIF
+    startsWith(bytes, [0x49, 0x49, 0x2a, 0x00]) || // TIFF, little-endian
+    startsWith(bytes, [0x4d, 0x4d, 0x00, 0x2a]) || // TIFF, big-endian
+    startsWith(bytes, [0xff, 0x0a]) || // JPEG XL codestream
+    startsWith(bytes, [0x00, 0x00, 0x00, 0x0c, 0x4a, 0x58, 0x4c, 0x20]) // JPEG XL container
+  );
+}
+
+/** Pages decoded at once by verifyPageImages, to bound memory on large volumes */
+const DECODE_CONCURRENCY = 4;
+
+/**
+ * Whether a page image is readable: it starts like a supported format and,
+ * where createImageBitmap exists, actually decodes. A header check alone
+ * passes files that are truncated or corrupt past the first bytes.
+ */
+async function isReadableImage(file: File): Promise<boolean> {
+  try {
+    if (!hasImageSignature(new Uint8Array(await file.slice(0, 12).arrayBuffer()))) return false;
+    if (typeof createImageBitmap !== 'function') return true;
+    const bitmap = await createImageBitmap(file);
+    bitmap.close();
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Decode every page image before the volume is saved, so a corrupt entry
+ * fails the import instead of being stored as a broken page.
+ * Empty files are left alone: missing pages are handled by the mismatch flow.
+ *
+ * @throws ImportEntryError naming the first unreadable entry, in page order
+ */
+async function verifyPageImages(
+  pages: ProcessedPage[],
+  imageFiles: Map<string, File>
+): Promise<void> {
+  const readable = new Array<boolean>(pages.length).fill(true);
+  let next = 0;
+  const decodeNext = async () => {
+    while (next < pages.length) {
+      const index = next++;
+      const file = imageFiles.get(pages[index].img_path);
+      if (file && file.size > 0) readable[index] = await isReadableImage(file);
+    }
+  };
+  await Promise.all(Array.from({ length: DECODE_CONCURRENCY }, decodeNext));
+
+  const index = readable.indexOf(false);
+  if (index !== -1) {
+    throw new ImportEntryError(pages[index].img_path, `page ${index + 1} is not a readable image`);
+  }
+}
+
 /**
  * Get image dimensions from a File
  * Used for image-only volumes where mokuro data isn't available
@@ -146,12 +243,40 @@ async function getImageDimensions(file: File): Promise<{ width: number; height:
   return { width: 1000, height: 1400 };
 }
 
+/**
+ * Page size of an image. Animated images use their canvas size from the
+ * header, which decoders don't agree on.
+ */
+async function getPageDimensions(file: File): Promise<{ width: number; height: number }> {
+  const animated = await readAnimatedImageInfo(file).catch(() => null);
+  return animated ?? getImageDimensions(file);
+}
+
+/**
+ * Replace animated page images with their first frame, for the 'flatten'
+ * animated pages setting. Pages that can't be flattened are kept as they are.
+ */
+async function flattenAnimatedPages(
+  pages: ProcessedPage[],
+  imageFiles: Map<string, File>
+): Promise<void> {
+  for (const page of pages) {
+    const file = imageFiles.get(page.img_path);
+    if (!file || file.size === 0) continue;
+    if (!(await readAnimatedImageInfo(file).catch(() => null))) continue;
+
+    const flattened = await flattenToFirstFrame(file);
+    if (flattened) imageFiles.set(page.img_path, flattened);
+  }
+}
+
 /**
  * Parse a mokuro JSON file and extract metadata
  *
  * @param file - The mokuro File object
  * @returns Parsed mokuro data
- * @throws If the file is invalid or missing required fields
+ * @throws If the file is invalid, missing required fields, or from an unsupported
+ *   mokuro version (see mokuro-schema.ts)
  */
 export async function parseMokuroFile(file: File): Promise<ParsedMokuro> {
   const text = await readFileAsText(file);
@@ -165,20 +290,30 @@ export async function parseMokuroFile(file: File): Promise<ParsedMokuro> {
 
   // Validate required fields
   const obj = data as Record<string, unknown>;
-  const requiredFields = ['version', 'title', 'title_uuid', 'volume', 'volume_uuid', 'pages'];
-  const missingFields = requiredFields.filter((field) => !(field in obj));
-
-  if (missingFields.length > 0) {
+  const requiredFields = ['title', 'title_uuid', 'volume', 'volume_uuid', 'pages'];
+  const version = detectMokuroVersion(obj);
+  const missingFields = [
+    ...(version ? [] : ['version']),
+    ...requiredFields.filter((field) => !(field in obj))
+  ];
+
+  if (missingFields.length > 0 || !version) {
     throw new Error(`Invalid mokuro file: missing required fields: ${missingFields.join(', ')}`);
   }
 
+  assertSupportedMokuroVersion(version);
+  const { pages, migrated } = normalizeMokuroPages(obj.pages);
+  if (migrated) {
+    console.info(`[Import] Migrated older mokuro ${version} layout in ${file.name}`);
+  }
+
   return {
-    version: obj.version as string,
+    version,
     series: obj.title as string,
     seriesUuid: obj.title_uuid as string,
     volume: obj.volume as string,
     volumeUuid: obj.volume_uuid as string,
-    pages: obj.pages as MokuroPage[],
+    pages,
     chars: (obj.chars as number) ?? 0,
     ...(obj.spine_width != null && { spineWidth: obj.spine_width as number })
   };
@@ -204,6 +339,15 @@ function normalizePath(path: string): string {
   return path.toLowerCase().replace(/\\/g, '/');
 }
 
+/**
+ * Order image paths as pages when there's no .mokuro reading order.
+ * Numeric-aware by default so 2.jpg comes before 10.jpg; the page sort
+ * setting switches to strict filename order for unusual naming.
+ */
+export function sortImagePaths(paths: Iterable<string>): string[] {
+  return Array.from(paths).sort(pageSortComparator(get(miscSettings).pageSortMode));
+}
+
 /**
  * Match image files to mokuro page paths
  *
@@ -211,7 +355,8 @@ function normalizePath(path: string): string {
  * 1. Exact path matches (normalized for case and slashes)
  * 2. Stem-based matching (handles extension changes like .png → .webp)
  * 3. Count-based fallback: if name matching fails but counts match,
- *    sort both lists and pair them positionally (handles renamed files)
+ *    pair the unmatched pages (in .mokuro reading order) with the unmatched
+ *    images sorted by filename (handles renamed files)
  *
  * @param pages - Pages from the mokuro file
  * @param files - Map of available image files
@@ -281,13 +426,12 @@ export function matchImagesToPages(
     // Only use fallback if most pages failed to match (>50% missing)
     const matchRatio = matched.length / pages.length;
     if (matchRatio < 0.5) {
-      // Sort both lists naturally (numeric-aware)
-      const sortedMissing = [...missing].sort(naturalSort);
-      const sortedExtra = [...extra].sort(naturalSort);
+      // The .mokuro page list is already in reading order; only the images need sorting
+      const sortedExtra = sortImagePaths(extra);
 
       // Pair them positionally
-      for (let i = 0; i < sortedMissing.length; i++) {
-        const pagePath = sortedMissing[i];
+      for (let i = 0; i < missing.length; i++) {
+        const pagePath = missing[i];
         const filePath = sortedExtra[i];
         matched.push(pagePath);
         remapped.set(pagePath, filePath);
@@ -502,20 +646,44 @@ export async function processVolume(input: DecompressedVolume): Promise<Processe
     const cumulativeCounts = calculateCumulativeChars(mokuroData.pages);
 
     // Create processed pages (preserve all mokuro page fields)
-    pages = mokuroData.pages.map((page, index) => ({
-      version: page.version,
-      img_width: page.img_width,
-      img_height: page.img_height,
-      img_path: matchResult.remapped.get(page.img_path) || page.img_path,
-      blocks: page.blocks,
-      cumulativeChars: cumulativeCounts[index]
-    }));
+    pages = await Promise.all(
+      mokuroData.pages.map(async (page, index) => {
+        const imgPath = matchResult.remapped.get(page.img_path) || page.img_path;
+        let { img_width, img_height } = page;
+
+        // Older mokuro layouts can omit page dimensions, which the text overlay
+        // needs to scale block positions; read them from the image instead
+        if (img_width === undefined || img_height === undefined) {
+          const file = imageFiles.get(imgPath);
+          img_width = 1000;
+          img_height = 1400;
+          if (file && file.size > 0) {
+            try {
+              const dims = await getPageDimensions(file);
+              img_width = dims.width;
+              img_height = dims.height;
+            } catch {
+              // Keep defaults
+            }
+          }
+        }
+
+        return {
+          version: page.version,
+          img_width,
+          img_height,
+          img_path: imgPath,
+          blocks: page.blocks,
+          cumulativeChars: cumulativeCounts[index]
+        };
+      })
+    );
 
     pageCount = mokuroData.pages.length;
     totalChars = mokuroData.chars || cumulativeCounts[cumulativeCounts.length - 1] || 0;
   } else {
     // Image-only: sort images and create minimal pages with dimensions
-    const sortedImages = Array.from(imageFiles.keys()).sort(naturalSort);
+    const sortedImages = sortImagePaths(imageFiles.keys());
 
     // Get dimensions for each image
     pages = await Promise.all(
@@ -526,7 +694,7 @@ export async function processVolume(input: DecompressedVolume): Promise<Processe
 
         if (file && file.size > 0) {
           try {
-            const dims = await getImageDimensions(file);
+            const dims = await getPageDimensions(file);
             width = dims.width;
             height = dims.height;
           } catch {
@@ -555,28 +723,28 @@ export async function processVolume(input: DecompressedVolume): Promise<Processe
     totalChars = 0;
   }
 
-  // Generate thumbnail from first image
+  await verifyPageImages(pages, imageFiles);
+
+  if (get(miscSettings).animatedPages === 'flatten') {
+    await flattenAnimatedPages(pages, imageFiles);
+  }
+
+  // Generate thumbnail from a file named like a cover, or else the first page
   let thumbnail: Blob | null = null;
   let thumbnailWidth = 0;
   let thumbnailHeight = 0;
+  let thumbnailVersion: number | undefined;
+  let thumbnailCustom: boolean | undefined;
 
-  // First try to find a file with "cover" in the name (case-insensitive)
-  let coverImagePath: string | undefined;
-  const imagePaths = Array.from(imageFiles.keys());
-
-  coverImagePath = imagePaths.find((path) => path.toLowerCase().includes('cover'));
-
-  // If no cover file found, use the first page (already sorted)
-  if (!coverImagePath && pages.length > 0) {
-    coverImagePath = pages[0]?.img_path;
-  }
-
+  const coverImagePath = pickCoverPath(Object.fromEntries(imageFiles), pages);
   const firstImage = coverImagePath ? imageFiles.get(coverImagePath) : null;
 
   // If a thumbnail sidecar is provided, use it directly as the local cover thumbnail.
   // This preserves externally curated covers and skips generation from page images.
   if (thumbnailSidecar) {
     thumbnail = thumbnailSidecar;
+    // Flagged like a user-set cover so regeneration and invalidation leave it alone
+    thumbnailCustom = true;
     try {
       const dims = await getImageDimensions(thumbnailSidecar);
       thumbnailWidth = dims.width;
@@ -595,6 +763,7 @@ export async function processVolume(input: DecompressedVolume): Promise<Processe
       thumbnail = result.file;
       thumbnailWidth = result.width;
       thumbnailHeight = result.height;
+      thumbnailVersion = THUMBNAIL_VERSION;
     } catch (error) {
       console.warn('Failed to generate thumbnail:', error);
     }
@@ -681,6 +850,8 @@ export async function processVolume(input: DecompressedVolume): Promise<Processe
     thumbnail,
     thumbnailWidth,
     thumbnailHeight,
+    thumbnailVersion,
+    thumbnailCustom,
     mismatchWarning,
     missingPages: matchResult.missing.length > 0 ? matchResult.missing.length : undefined,
     missingPagePaths: matchResult.missing.length > 0 ? matchResult.missing : undefined,
@@ -0,0 +1,42 @@
+/**
+ * Reading direction detection at import
+ *
+ * Japanese manga sets its dialogue vertically and reads right to left;
+ * webtoons, translated comics and most horizontal-text works read left to
+ * right. mokuro records the orientation of every text block, so the share
+ * of vertical blocks is a good hint for the volume's direction.
+ */
+
+/** Fewer text blocks than this is too little to go on (covers, art books) */
+const MIN_BLOCKS = 10;
+
+/** Share of vertical blocks at or above which a volume reads right to left */
+const RTL_THRESHOLD = 0.6;
+
+/** Share of vertical blocks at or below which a volume reads left to right */
+const LTR_THRESHOLD = 0.2;
+
+/**
+ * Guess whether a volume reads right to left from its OCR blocks
+ *
+ * @returns true for right to left, false for left to right, or undefined
+ *   when there isn't enough text or the blocks are mixed
+ */
+export function detectReadingDirection(pages: { blocks: unknown[] }[]): boolean | undefined {
+  let vertical = 0;
+  let total = 0;
+
+  for (const page of pages) {
+    for (const block of page.blocks) {
+      if (typeof (block as { vertical?: unknown })?.vertical !== 'boolean') continue;
+      total++;
+      if ((block as { vertical: boolean }).vertical) vertical++;
+    }
+  }
+
+  if (total < MIN_BLOCKS) return undefined;
+  const share = vertical / total;
+  if (share >= RTL_THRESHOLD) return true;
+  if (share <= LTR_THRESHOLD) return false;
+  return undefined;
+}
@@ -5,6 +5,8 @@
  * through a unified pairing and processing pipeline.
  */
 
+import type { ProviderType } from '$lib/util/sync/provider-interface';
+
 // ============================================
 // INPUT TYPES - What we receive from various sources
 // ============================================
@@ -56,6 +58,10 @@ export interface PairedSource {
   estimatedSize: number;
   /** Whether this is an image-only source (no mokuro found anywhere) */
   imageOnly: boolean;
+  /** Generate OCR data for this image-only source before processing */
+  runOcr?: boolean;
+  /** Replace this existing volume's pages and OCR instead of importing a new volume */
+  replaceVolumeUuid?: string;
 }
 
 /**
@@ -175,17 +181,38 @@ export interface ProcessedMetadata {
   thumbnail: Blob | null;
   thumbnailWidth: number;
   thumbnailHeight: number;
+  /** THUMBNAIL_VERSION of a generated thumbnail */
+  thumbnailVersion?: number;
+  /** Thumbnail came from a sidecar rather than a page */
+  thumbnailCustom?: boolean;
   mismatchWarning?: string;
   /** Number of pages that were missing and replaced with placeholders */
   missingPages?: number;
   /** Paths of pages that were replaced with placeholders */
   missingPagePaths?: string[];
+  /** Paths of duplicate pages left out of the page list (images are kept) */
+  skippedPagePaths?: string[];
   /** Whether this is an image-only volume (no mokuro) */
   imageOnly?: boolean;
   /** Where this volume came from */
   sourceType?: 'local' | 'cloud';
   /** Spine width in pixels (from mokuro metadata) */
   spineWidth?: number;
+  /** Where the volume was imported from */
+  origin?: VolumeOrigin;
+}
+
+/**
+ * The file or folder a volume was imported from
+ */
+export interface VolumeOrigin {
+  /** Archive file name, or the folder name for directory imports */
+  originalFilename: string;
+  /** Path as picked or dropped (relative to the picked folder), or the cloud path */
+  sourcePath: string;
+  /** Cloud provider and file ID, for downloaded volumes */
+  provider?: ProviderType;
+  fileId?: string;
 }
 
 /**
@@ -215,8 +242,10 @@ export interface ImportQueueItem {
   source: PairedSource;
   /** Provider type for concurrency management */
   provider: ImportProvider;
-  /** Current status */
-  status: 'queued' | 'decompressing' | 'processing' | 'complete' | 'error';
+  /** Current status: queued → extracting → writing → done, or error */
+  status: 'queued' | 'extracting' | 'writing' | 'done' | 'error';
+  /** Latest step reported while importing, e.g. "Extracting... 40%" */
+  statusText?: string;
   /** Progress 0-100 */
   progress: number;
   /** Display title for UI */
@@ -225,6 +254,15 @@ export interface ImportQueueItem {
   errorMessage?: string;
 }
 
+/**
+ * A dropped file that was not imported because it isn't an archive, image or
+ * mokuro file (e.g. a PDF dropped by mistake)
+ */
+export interface SkippedImport {
+  name: string;
+  reason: string;
+}
+
 // ============================================
 // UTILITY TYPES
 // ============================================
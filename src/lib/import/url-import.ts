@@ -0,0 +1,239 @@
+/**
+ * Import from a URL
+ *
+ * Downloads an archive from a direct link (a CBZ/ZIP on the user's own
+ * server, a share link) and hands it to the normal import pipeline. Runs in
+ * the browser, so the server has to allow cross-origin requests (CORS);
+ * links behind a login can be given one extra request header, e.g.
+ * `Authorization: Bearer ...`.
+ */
+
+import { importFiles, type ImportResult } from './import-service';
+import { progressTrackerStore } from '$lib/util/progress-tracker';
+import { formatBytes } from '$lib/util/upload';
+
+/** Largest download accepted; the archive is held in memory while importing */
+export const MAX_URL_IMPORT_BYTES = 1024 ** 3;
+
+export type ArchiveKind = 'zip' | 'rar' | '7z';
+
+const ARCHIVE_SIGNATURES: Array<{ kind: ArchiveKind; bytes: number[] }> = [
+  { kind: 'zip', bytes: [0x50, 0x4b, 0x03, 0x04] },
+  // Empty or spanned zips
+  { kind: 'zip', bytes: [0x50, 0x4b, 0x05, 0x06] },
+  { kind: 'zip', bytes: [0x50, 0x4b, 0x07, 0x08] },
+  { kind: 'rar', bytes: [0x52, 0x61, 0x72, 0x21, 0x1a, 0x07] },
+  { kind: '7z', bytes: [0x37, 0x7a, 0xbc, 0xaf, 0x27, 0x1c] }
+];
+
+const ARCHIVE_EXTENSION = /\.(zip|cbz|cbr|rar|7z)$/i;
+
+/** The archive format from a file's first bytes, or null if it isn't one */
+export function sniffArchiveKind(head: Uint8Array): ArchiveKind | null {
+  const match = ARCHIVE_SIGNATURES.find(({ bytes }) =>
+    bytes.every((byte, index) => head[index] === byte)
+  );
+  return match?.kind ?? null;
+}
+
+/** An http(s) URL from user input, or an error saying what's wrong with it */
+export function parseImportUrl(input: string): URL {
+  let url: URL;
+  try {
+    url = new URL(input.trim());
+  } catch {
+    throw new Error('Enter a full link, starting with https://');
+  }
+  if (url.protocol !== 'https:' && url.protocol !== 'http:') {
+    throw new Error('Only http and https links can be imported');
+  }
+  return url;
+}
+
+/** A `Name: value` header line, or null when left empty */
+export function parseHeaderLine(input: string): [string, string] | null {
+  const line = input.trim();
+  if (!line) return null;
+  const separator = line.indexOf(':');
+  const name = separator > 0 ? line.slice(0, separator).trim() : '';
+  const value = separator > 0 ? line.slice(separator + 1).trim() : '';
+  if (!/^[A-Za-z0-9-]+$/.test(name) || !value) {
+    throw new Error('Headers look like "Authorization: Bearer <token>"');
+  }
+  return [name, value];
+}
+
+/**
+ * The file name to import under: Content-Disposition's, else the last path
+ * segment of the final (post-redirect) URL, with an archive extension added
+ * if it has none.
+ */
+export function downloadFilename(
+  contentDisposition: string | null,
+  url: string,
+  kind: ArchiveKind
+): string {
+  let name = '';
+  const encoded = contentDisposition?.match(/filename\*\s*=\s*(?:UTF-8'')?([^;]+)/i);
+  const plain = contentDisposition?.match(/filename\s*=\s*"?([^";]+)"?/i);
+  try {
+    if (encoded) name = decodeURIComponent(encoded[1].trim().replace(/^"|"$/g, ''));
+  } catch {
+    name = '';
+  }
+  if (!name && plain) name = plain[1].trim();
+  if (!name) {
+    const segments = new URL(url).pathname.split('/').filter(Boolean);
+    try {
+      name = decodeURIComponent(segments.at(-1) ?? '');
+    } catch {
+      name = segments.at(-1) ?? '';
+    }
+  }
+  // No path tricks in the stored name
+  name = name.split(/[\\/]/).pop()?.trim() || 'volume';
+  if (!ARCHIVE_EXTENSION.test(name)) {
+    name += kind === 'zip' ? '.cbz' : kind === 'rar' ? '.cbr' : '.7z';
+  }
+  return name;
+}
+
+export interface UrlDownloadOptions {
+  /** One extra request header, e.g. for links that need a login */
+  header?: [string, string] | null;
+  onProgress?: (loaded: number, total: number | null) => void;
+  maxBytes?: number;
+}
+
+/**
+ * Download an archive from a direct link, following redirects
+ *
+ * @throws If the request fails, the file is too large, or it isn't an archive
+ */
+export async function downloadArchiveFromUrl(
+  url: URL,
+  options: UrlDownloadOptions = {}
+): Promise<File> {
+  const maxBytes = options.maxBytes ?? MAX_URL_IMPORT_BYTES;
+  const headers = options.header ? { [options.header[0]]: options.header[1] } : undefined;
+
+  let response: Response;
+  try {
+    response = await fetch(url, { headers, redirect: 'follow', cache: 'no-store' });
+  } catch {
+    // Network failures and CORS rejections look the same from here
+    throw new Error(
+      "Couldn't reach the link. The server may not allow downloads from other sites (CORS)."
+    );
+  }
+  if (response.status === 401 || response.status === 403) {
+    throw new Error(`The link needs a login (HTTP ${response.status}). Add an auth header.`);
+  }
+  if (!response.ok) {
+    throw new Error(`Download failed (HTTP ${response.status})`);
+  }
+
+  const contentType = response.headers.get('content-type') ?? '';
+  if (/^text\/html/i.test(contentType)) {
+    throw new Error('The link opens a web page, not an archive. Use a direct download link.');
+  }
+
+  const lengthHeader = response.headers.get('content-length');
+  const total = lengthHeader ? Number(lengthHeader) : null;
+  if (total !== null && total > maxBytes) {
+    throw new Error(`The file is ${formatBytes(total)}; the limit is ${formatBytes(maxBytes)}`);
+  }
+
+  const chunks: Uint8Array[] = [];
+  let loaded = 0;
+  const reader = response.body?.getReader();
+  if (reader) {
+    while (true) {
+      const { done, value } = await reader.read();
+      if (done) break;
+      loaded += value.byteLength;
+      if (loaded > maxBytes) {
+        await reader.cancel();
+        throw new Error(`The file is over the ${formatBytes(maxBytes)} limit`);
+      }
+      chunks.push(value);
+      options.onProgress?.(loaded, total);
+    }
+  } else {
+    const buffer = new Uint8Array(await response.arrayBuffer());
+    if (buffer.byteLength > maxBytes) {
+      throw new Error(`The file is over the ${formatBytes(maxBytes)} limit`);
+    }
+    chunks.push(buffer);
+    loaded = buffer.byteLength;
+    options.onProgress?.(loaded, total);
+  }
+
+  const head = new Uint8Array(await new Blob(chunks.slice(0, 1) as BlobPart[]).arrayBuffer());
+  const kind = sniffArchiveKind(head);
+  if (!kind) {
+    throw new Error("The downloaded file isn't a ZIP, CBZ, RAR or 7z archive");
+  }
+
+  const name = downloadFilename(
+    response.headers.get('content-disposition'),
+    response.url || url.toString(),
+    kind
+  );
+  return new File(chunks as BlobPart[], name, {
+    type: kind === 'zip' ? 'application/zip' : 'application/octet-stream'
+  });
+}
+
+/**
+ * Download an archive from a link and import it, reporting progress in the
+ * progress tracker
+ */
+export async function importFromUrl(
+  url: URL,
+  header: [string, string] | null = null
+): Promise<ImportResult> {
+  const processId = `url-import-${Date.now()}`;
+  const segment = url.pathname.split('/').filter(Boolean).at(-1);
+  let label = segment ?? url.host;
+  try {
+    if (segment) label = decodeURIComponent(segment);
+  } catch {
+    // A stray "%" in the link; show the segment as written
+  }
+
+  progressTrackerStore.addProcess({
+    id: processId,
+    description: `Importing ${label}`,
+    status: 'Connecting...',
+    progress: 0
+  });
+
+  try {
+    const file = await downloadArchiveFromUrl(url, {
+      header,
+      onProgress: (loaded, total) => {
+        progressTrackerStore.updateProcess(processId, {
+          status: total
+            ? `Downloading... ${formatBytes(loaded)} / ${formatBytes(total)}`
+            : `Downloading... ${formatBytes(loaded)}`,
+          progress: total ? Math.round((loaded / total) * 90) : 45
+        });
+      }
+    });
+
+    progressTrackerStore.updateProcess(processId, {
+      status: 'Adding to catalog...',
+      progress: 95
+    });
+    const result = await importFiles([file]);
+    progressTrackerStore.completeProcess(processId);
+    setTimeout(() => progressTrackerStore.removeProcess(processId), 2000);
+    return result;
+  } catch (error) {
+    const message = error instanceof Error ? error.message : 'Unknown error';
+    progressTrackerStore.failProcess(processId, `Failed: ${message}`);
+    setTimeout(() => progressTrackerStore.removeProcess(processId), 5000);
+    throw error;
+  }
+}
@@ -0,0 +1,163 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+import type { Page } from '$lib/types';
+import { decodedBytes, ImageCache, imageCacheLimits, pagesToEvict } from './image-cache';
+
+const MB = 1024 * 1024;
+
+describe('pagesToEvict', () => {
+  const entry = (index: number, lastViewed: number, bytes = MB) => ({ index, lastViewed, bytes });
+
+  it('drops the least recently viewed pages first', () => {
+    const entries = [entry(0, 3), entry(1, 1), entry(2, 2), entry(3, 4)];
+
+    expect(pagesToEvict(entries, new Set(), { maxPages: 2, maxBytes: Infinity })).toEqual([1, 2]);
+  });
+
+  it('drops preloaded pages that were never viewed before viewed ones', () => {
+    const entries = [entry(0, 5), entry(9, 0)];
+
+    expect(pagesToEvict(entries, new Set(), { maxPages: 1, maxBytes: Infinity })).toEqual([9]);
+  });
+
+  it('enforces the byte cap', () => {
+    const entries = [entry(0, 1, 40 * MB), entry(1, 2, 40 * MB), entry(2, 3, 40 * MB)];
+
+    expect(pagesToEvict(entries, new Set(), { maxPages: 10, maxBytes: 100 * MB })).toEqual([0]);
+  });
+
+  it('never drops pages in the preload window', () => {
+    const entries = [entry(0, 1), entry(1, 2), entry(2, 3)];
+
+    expect(pagesToEvict(entries, new Set([0, 1, 2]), { maxPages: 1, maxBytes: 0 })).toEqual([]);
+  });
+});
+
+describe('imageCacheLimits', () => {
+  it('uses the configured size, or scales with device RAM', () => {
+    expect(imageCacheLimits(512, 4).maxBytes).toBe(512 * MB);
+    expect(imageCacheLimits(0, 4).maxBytes).toBe(128 * MB);
+    expect(imageCacheLimits(0, 16).maxBytes).toBe(512 * MB);
+  });
+});
+
+describe('ImageCache', () => {
+  const PAGE_WIDTH = 1000;
+  const PAGE_HEIGHT = 1500;
+  const PAGE_BYTES = decodedBytes(PAGE_WIDTH, PAGE_HEIGHT);
+  const live = new Set<string>();
+  const corrupt = new Set<string>();
+  let nextUrl = 0;
+
+  // jsdom neither loads images nor creates blob URLs
+  class LoadedImage {
+    naturalWidth = PAGE_WIDTH;
+    naturalHeight = PAGE_HEIGHT;
+    onload: (() => void) | null = null;
+    onerror: (() => void) | null = null;
+    private url = '';
+    get src() {
+      return this.url;
+    }
+    set src(url: string) {
+      this.url = url;
+      const broken = [...corrupt].some((name) => url.endsWith(name));
+      queueMicrotask(() => (broken ? this.onerror : this.onload)?.());
+    }
+    decode() {
+      return Promise.resolve();
+    }
+  }
+
+  beforeEach(() => {
+    vi.stubGlobal('Image', LoadedImage);
+    URL.createObjectURL = vi.fn((file: Blob) => {
+      const url = `blob:${nextUrl++}/${(file as File).name}`;
+      live.add(url);
+      return url;
+    });
+    URL.revokeObjectURL = vi.fn((url: string) => {
+      live.delete(url);
+    });
+  });
+
+  afterEach(() => {
+    vi.unstubAllGlobals();
+    vi.restoreAllMocks();
+    live.clear();
+    corrupt.clear();
+  });
+
+  function volume(pageCount: number) {
+    const files: Record<string, File> = {};
+    const pages = [];
+    for (let i = 0; i < pageCount; i++) {
+      const name = `page${String(i).padStart(3, '0')}.jpg`;
+      files[name] = new File(['x'], name);
+      pages.push({ img_path: name } as Page);
+    }
+    return { files, pages };
+  }
+
+  it('keeps memory bounded while reading a 400-page volume', async () => {
+    const { files, pages } = volume(400);
+    const cache = new ImageCache();
+    cache.setLimits({ maxPages: 24, maxBytes: 12 * PAGE_BYTES });
+    let peak = 0;
+
+    for (let i = 0; i < pages.length; i++) {
+      cache.updateCache(files, pages, i);
+      await cache.getImage(i);
+      await vi.waitFor(() => expect(cache.getStats().decoded).toContain(i));
+      peak = Math.max(peak, cache.getStats().bytes);
+    }
+
+    expect(peak).toBeLessThanOrEqual(12 * PAGE_BYTES);
+    expect(live.size).toBe(cache.getStats().size);
+    cache.cleanup();
+    expect(live.size).toBe(0);
+  });
+
+  it('keeps recently viewed pages outside the preload window', async () => {
+    const { files, pages } = volume(40);
+    const cache = new ImageCache();
+
+    for (const i of [0, 10, 20]) {
+      cache.updateCache(files, pages, i);
+      await cache.getImage(i);
+    }
+
+    expect(cache.getImageSync(0)).not.toBeNull();
+    expect(cache.getImageSync(10)).not.toBeNull();
+  });
+
+  it('fails only the corrupt page, and loads it again on retry', async () => {
+    const { files, pages } = volume(10);
+    corrupt.add('page004.jpg');
+    const cache = new ImageCache();
+    vi.spyOn(console, 'error').mockImplementation(() => {});
+
+    cache.updateCache(files, pages, 3);
+
+    await expect(cache.getImage(4)).rejects.toThrow();
+    expect(cache.getError(4)).toMatch(/corrupt/);
+    expect(await cache.getImage(3)).not.toBeNull();
+    expect(await cache.getImage(5)).not.toBeNull();
+    // Moving on doesn't keep retrying the broken page
+    cache.updateCache(files, pages, 5);
+    expect(cache.getStats().cached).not.toContain(4);
+
+    corrupt.clear();
+    expect(await cache.retry(4)).not.toBeNull();
+    expect(cache.getError(4)).toBeNull();
+  });
+
+  it('preloads fewer pages ahead when asked to', async () => {
+    const { files, pages } = volume(10);
+    const cache = new ImageCache();
+    cache.setPreloadAhead(1);
+
+    cache.updateCache(files, pages, 0);
+
+    expect(cache.getStats().cached).toEqual([0, 1]);
+  });
+});
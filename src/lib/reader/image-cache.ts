@@ -1,6 +1,10 @@
 /**
  * Image cache for preloading and decoding manga pages
- * Maintains a windowed cache: previous 2 + current + next 3 pages
+ * Preloads a window around the current page (previous 2 + current + next 3,
+ * fewer ahead on metered connections)
+ * and keeps recently viewed pages beyond it, least recently viewed evicted
+ * first, within a page count and decoded-size cap. Evicted pages reload from
+ * their File on demand.
  *
  * Public API is index-based for clean caller usage.
  * Uses fuzzy matching to align files with pages when paths don't match exactly.
@@ -13,10 +17,66 @@ export interface CachedImage {
   image: HTMLImageElement; // Image element holds decoded bitmap and blob URL (in img.src)
   decoded: boolean;
   loading: Promise<void> | null;
+  /** Estimated memory held: decoded size once known, file size until then */
+  bytes: number;
 }
 
+export interface ImageCacheLimits {
+  maxPages: number;
+  maxBytes: number;
+}
+
+/** Pages preloaded after the current one */
+export const PRELOAD_AHEAD = 3;
+
+/** Pages preloaded ahead on a metered connection */
+export const METERED_PRELOAD_AHEAD = 1;
+
+export const DEFAULT_IMAGE_CACHE_LIMITS: ImageCacheLimits = {
+  maxPages: 24,
+  maxBytes: 256 * 1024 * 1024
+};
+
 import { naturalSort } from '$lib/util/natural-sort';
 
+/** Limits for the reader's memory setting in MB; 0 allows 32 MB per GB of device RAM */
+export function imageCacheLimits(cacheMB: number, deviceRamGB: number): ImageCacheLimits {
+  const megabytes = cacheMB > 0 ? cacheMB : deviceRamGB * 32;
+  return { maxPages: DEFAULT_IMAGE_CACHE_LIMITS.maxPages, maxBytes: megabytes * 1024 * 1024 };
+}
+
+/** Memory a decoded page takes (RGBA) */
+export function decodedBytes(width: number, height: number): number {
+  return width * height * 4;
+}
+
+/**
+ * Pages to drop so the cache fits `limits`, least recently viewed first.
+ * Pinned pages (the preload window) are never dropped, so the preloader and
+ * the eviction don't undo each other's work; if the window alone exceeds the
+ * cap, only the window is kept.
+ */
+export function pagesToEvict(
+  entries: { index: number; lastViewed: number; bytes: number }[],
+  pinned: Set<number>,
+  limits: ImageCacheLimits
+): number[] {
+  let count = entries.length;
+  let bytes = entries.reduce((sum, entry) => sum + entry.bytes, 0);
+  const candidates = entries
+    .filter((entry) => !pinned.has(entry.index))
+    .sort((a, b) => a.lastViewed - b.lastViewed);
+
+  const evicted: number[] = [];
+  for (const entry of candidates) {
+    if (count <= limits.maxPages && bytes <= limits.maxBytes) break;
+    evicted.push(entry.index);
+    count--;
+    bytes -= entry.bytes;
+  }
+  return evicted;
+}
+
 /**
  * Match files to pages using fuzzy matching strategies
  * Returns an indexed array of Files aligned with page order
@@ -28,7 +88,10 @@ import { naturalSort } from '$lib/util/natural-sort';
  * 4. Basename without extension - handles format conversions (e.g., png->webp, jpg->avif)
  * 5. Page order fallback - sort files naturally and align by index
  */
-export function matchFilesToPages(files: Record<string, File>, pages: Page[]): File[] {
+export function matchFilesToPages(
+  files: Record<string, File>,
+  pages: Pick<Page, 'img_path'>[]
+): File[] {
   const fileKeys = Object.keys(files);
   const result: File[] = new Array(pages.length);
 
@@ -182,7 +245,25 @@ export class ImageCache {
   private files: File[] = []; // Indexed array aligned with pages
   private pages: Page[] = [];
   private currentIndex = 0;
-  private windowSize = { prev: 2, next: 3 };
+  private windowSize = { prev: 2, next: PRELOAD_AHEAD };
+  private window = new Set<number>();
+  private limits: ImageCacheLimits = { ...DEFAULT_IMAGE_CACHE_LIMITS };
+  // View clock tick when each page was last shown; preloaded-only pages have none
+  private lastViewed = new Map<number, number>();
+  private viewClock = 0;
+  // Pages that failed to load, with the reason; not preloaded again until retried
+  private failures = new Map<number, string>();
+
+  /** How many pages after the current one to preload */
+  setPreloadAhead(next: number): void {
+    this.windowSize = { ...this.windowSize, next };
+  }
+
+  /** Change the memory cap; takes effect immediately */
+  setLimits(limits: ImageCacheLimits): void {
+    this.limits = { ...limits };
+    this.enforceLimits();
+  }
 
   /**
    * Initialize or update the cache with new files and current page
@@ -207,26 +288,37 @@ export class ImageCache {
     const endIndex = Math.min(pages.length - 1, currentIndex + this.windowSize.next);
 
     // Get indices in the window
-    const windowIndices = new Set<number>();
+    this.window = new Set<number>();
     for (let i = startIndex; i <= endIndex; i++) {
-      windowIndices.add(i);
+      this.window.add(i);
     }
 
-    // Remove items outside the window
-    for (const [index] of this.cache.entries()) {
-      if (!windowIndices.has(index)) {
-        this.removeFromCache(index);
-      }
-    }
+    this.markViewed(currentIndex);
+    this.enforceLimits();
 
     // Preload all items in the window (non-blocking)
     for (let i = startIndex; i <= endIndex; i++) {
-      this.preloadImage(i).catch((err) => {
-        console.error(`Failed to preload image at index ${i}:`, err);
+      if (this.failures.has(i)) continue;
+      this.preloadImage(i).catch(() => {
+        // Recorded in failures and logged by preloadImage
       });
     }
   }
 
+  /** Why a page failed to load, or null if it hasn't */
+  getError(index: number): string | null {
+    return this.failures.get(index) ?? null;
+  }
+
+  /**
+   * Load a failed page again from its file. Resolves to its URL, or rejects
+   * if it fails again.
+   */
+  async retry(index: number): Promise<string | null> {
+    this.failures.delete(index);
+    return this.getImage(index);
+  }
+
   /**
    * Get the File for a page index (for MangaPage fallback rendering)
    */
@@ -246,9 +338,13 @@ export class ImageCache {
   }
 
   /**
-   * Get a cached image URL, waiting for it to be ready if necessary
+   * Get a cached image URL, waiting for it to be ready if necessary.
+   * Rejects if the page can't be loaded; see getError() and retry().
    */
   async getImage(index: number): Promise<string | null> {
+    this.markViewed(index);
+    const failure = this.failures.get(index);
+    if (failure) throw new Error(failure);
     const cached = this.cache.get(index);
     if (cached) {
       // Wait for image to be decoded if it's still loading
@@ -295,17 +391,48 @@ export class ImageCache {
     this.cache.set(index, {
       image: img,
       decoded: false,
-      loading
+      loading,
+      bytes: file.size
     });
 
-    // Wait for decode
-    await loading;
+    // Wait for decode. A corrupt page is dropped and remembered, so the rest
+    // of the volume keeps loading and the reader can offer a retry
+    try {
+      await loading;
+    } catch (error) {
+      const reason = error instanceof Error ? error.message : String(error);
+      console.error(`Page ${index + 1} (${file.name}) failed to load:`, error);
+      this.failures.set(index, reason);
+      if (this.cache.get(index)?.image === img) this.removeFromCache(index);
+      throw error;
+    }
 
     // Mark as decoded
     const cached = this.cache.get(index);
     if (cached) {
       cached.decoded = true;
       cached.loading = null;
+      cached.bytes = decodedBytes(img.naturalWidth, img.naturalHeight) || file.size;
+      this.enforceLimits();
+    }
+  }
+
+  private markViewed(index: number): void {
+    this.lastViewed.set(index, ++this.viewClock);
+  }
+
+  /** Drop least recently viewed pages outside the window until under the cap */
+  private enforceLimits(): void {
+    // Pages still loading are left alone: revoking their URL would fail the load
+    const settled = Array.from(this.cache.entries())
+      .filter(([, cached]) => !cached.loading)
+      .map(([index, cached]) => ({
+        index,
+        lastViewed: this.lastViewed.get(index) ?? 0,
+        bytes: cached.bytes
+      }));
+    for (const index of pagesToEvict(settled, this.window, this.limits)) {
+      this.removeFromCache(index);
     }
   }
 
@@ -328,7 +455,7 @@ export class ImageCache {
       };
 
       img.onerror = () => {
-        reject(new Error(`Failed to load image: ${url}`));
+        reject(new Error('The image is corrupt or in an unsupported format'));
       };
 
       img.src = url;
@@ -354,6 +481,8 @@ export class ImageCache {
       this.removeFromCache(index);
     }
     this.cache.clear();
+    this.lastViewed.clear();
+    this.failures.clear();
   }
 
   /**
@@ -362,9 +491,11 @@ export class ImageCache {
   getStats() {
     return {
       size: this.cache.size,
+      bytes: Array.from(this.cache.values()).reduce((sum, cached) => sum + cached.bytes, 0),
       currentIndex: this.currentIndex,
       fileCount: this.files.length,
       cached: Array.from(this.cache.keys()),
+      failed: Array.from(this.failures.keys()),
       decoded: Array.from(this.cache.entries())
         .filter(([_, v]) => v.decoded)
         .map(([k]) => k)
@@ -0,0 +1,114 @@
+import { describe, it, expect, vi, beforeEach, afterEach } from 'vitest';
+import type { Page } from '$lib/types';
+import { getExportFilename, renderPageExport, resolveExportRegion } from './page-export';
+
+const page: Page = {
+  version: '0.2.0',
+  img_width: 1000,
+  img_height: 1500,
+  blocks: [
+    {
+      box: [100, 200, 300, 400],
+      vertical: false,
+      font_size: 20,
+      lines: ['こんにちは'],
+      lines_coords: []
+    }
+  ],
+  img_path: '001.jpg'
+};
+
+describe('resolveExportRegion', () => {
+  it('uses the whole image without a crop', () => {
+    expect(resolveExportRegion(undefined, 2000, 3000)).toEqual([0, 0, 2000, 3000]);
+  });
+
+  it('rounds and clamps a crop to the image', () => {
+    expect(resolveExportRegion([-5.4, 10.6, 500.2, 400], 2000, 3000)).toEqual([0, 11, 500, 400]);
+    expect(resolveExportRegion([1800, 2900, 500, 500], 2000, 3000)).toEqual([1800, 2900, 200, 100]);
+  });
+});
+
+describe('getExportFilename', () => {
+  it('pads the page number and marks crops', () => {
+    expect(getExportFilename('Vol 1', 7, 'png', false)).toBe('Vol 1 - p007.png');
+    expect(getExportFilename('Vol 1', 12, 'jpeg', true)).toBe('Vol 1 - p012 (crop).jpg');
+  });
+});
+
+describe('renderPageExport', () => {
+  let ctx: Record<string, ReturnType<typeof vi.fn>>;
+  let canvas: HTMLCanvasElement | undefined;
+  let toBlobType: string | undefined;
+
+  beforeEach(() => {
+    vi.stubGlobal(
+      'createImageBitmap',
+      vi.fn().mockResolvedValue({ width: 2000, height: 3000, close: vi.fn() })
+    );
+    ctx = {
+      drawImage: vi.fn(),
+      fillRect: vi.fn(),
+      fillText: vi.fn(),
+      save: vi.fn(),
+      restore: vi.fn(),
+      translate: vi.fn()
+    };
+    vi.spyOn(HTMLCanvasElement.prototype, 'getContext').mockImplementation(function (
+      this: HTMLCanvasElement
+    ) {
+      canvas = this;
+      return ctx as unknown as CanvasRenderingContext2D;
+    } as never);
+    vi.spyOn(HTMLCanvasElement.prototype, 'toBlob').mockImplementation(function (
+      callback: BlobCallback,
+      type?: string
+    ) {
+      toBlobType = type;
+      callback(new Blob(['x'], { type }));
+    });
+  });
+
+  afterEach(() => {
+    vi.unstubAllGlobals();
+    vi.restoreAllMocks();
+  });
+
+  it('exports a crop at source resolution', async () => {
+    const blob = await renderPageExport(new Blob(), page, {
+      crop: [400, 600, 800, 500],
+      format: 'png',
+      burnInOcr: false
+    });
+
+    expect(blob.type).toBe('image/png');
+    expect(canvas?.width).toBe(800);
+    expect(canvas?.height).toBe(500);
+    expect(ctx.drawImage).toHaveBeenCalledWith(
+      expect.anything(),
+      400,
+      600,
+      800,
+      500,
+      0,
+      0,
+      800,
+      500
+    );
+    expect(ctx.fillText).not.toHaveBeenCalled();
+  });
+
+  it('burns in OCR text scaled from page to image coordinates', async () => {
+    await renderPageExport(new Blob(), page, {
+      crop: [100, 200, 1000, 1000],
+      format: 'jpeg',
+      burnInOcr: true
+    });
+
+    expect(toBlobType).toBe('image/jpeg');
+    expect(ctx.translate).toHaveBeenCalledWith(-100, -200);
+    // Image is twice the .mokuro page size, so the box doubles too
+    expect(ctx.fillRect).toHaveBeenCalledWith(200, 400, 400, 400);
+    expect(ctx.fillText).toHaveBeenCalledWith('こんにちは', 200, 400);
+  });
+});
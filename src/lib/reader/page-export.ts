@@ -0,0 +1,155 @@
+/**
+ * Export the current page, or a cropped region of it, as an image file.
+ *
+ * Rendering always happens at the page image's own resolution so exports are
+ * full quality regardless of how far the reader is zoomed out. Crop regions
+ * are in source image pixels (what cropperjs reports with getData(true)).
+ */
+
+import { writable } from 'svelte/store';
+import type { Page } from '$lib/types';
+
+export type PageExportFormat = 'png' | 'jpeg';
+
+/** [x, y, width, height] in source image pixels */
+export type ExportRegion = [number, number, number, number];
+
+export interface PageExportOptions {
+  crop?: ExportRegion;
+  format: PageExportFormat;
+  /** Draw the OCR text over its boxes, as the reader does when text is shown */
+  burnInOcr: boolean;
+}
+
+type PageExportState = {
+  open: boolean;
+  image?: File;
+  page?: Page;
+  pageNumber?: number;
+  volumeTitle?: string;
+};
+
+export const pageExportStore = writable<PageExportState>({ open: false });
+
+export function showPageExport(image: File, page: Page, pageNumber: number, volumeTitle: string) {
+  pageExportStore.set({ open: true, image, page, pageNumber, volumeTitle });
+}
+
+export function closePageExport() {
+  pageExportStore.set({ open: false });
+}
+
+/**
+ * Clamp a crop to the image and round it to whole pixels; no crop means the full page
+ */
+export function resolveExportRegion(
+  crop: ExportRegion | undefined,
+  width: number,
+  height: number
+): ExportRegion {
+  if (!crop) return [0, 0, width, height];
+
+  const x = Math.min(Math.max(0, Math.round(crop[0])), width - 1);
+  const y = Math.min(Math.max(0, Math.round(crop[1])), height - 1);
+  const w = Math.max(1, Math.min(Math.round(crop[2]), width - x));
+  const h = Math.max(1, Math.min(Math.round(crop[3]), height - y));
+  return [x, y, w, h];
+}
+
+function drawOcrBlocks(ctx: CanvasRenderingContext2D, page: Page, scaleX: number, scaleY: number) {
+  for (const block of page.blocks) {
+    const [xmin, ymin, xmax, ymax] = block.box;
+    const x = xmin * scaleX;
+    const y = ymin * scaleY;
+    const w = (xmax - xmin) * scaleX;
+    const h = (ymax - ymin) * scaleY;
+    const fontSize = Math.max(8, block.font_size * scaleY);
+
+    ctx.fillStyle = 'white';
+    ctx.fillRect(x, y, w, h);
+    ctx.fillStyle = 'black';
+    ctx.font = `${fontSize}px sans-serif`;
+    ctx.textBaseline = 'top';
+
+    if (block.vertical) {
+      // Columns run right to left, characters top to bottom
+      block.lines.forEach((line, column) => {
+        const cx = x + w - (column + 1) * fontSize;
+        Array.from(line).forEach((char, row) => {
+          ctx.fillText(char, cx, y + row * fontSize);
+        });
+      });
+    } else {
+      block.lines.forEach((line, row) => {
+        ctx.fillText(line, x, y + row * fontSize);
+      });
+    }
+  }
+}
+
+/**
+ * Render the page (or region) at source resolution and encode it
+ */
+export async function renderPageExport(
+  image: Blob,
+  page: Page,
+  options: PageExportOptions
+): Promise<Blob> {
+  const bitmap = await createImageBitmap(image);
+  try {
+    const [sx, sy, sw, sh] = resolveExportRegion(options.crop, bitmap.width, bitmap.height);
+
+    const canvas = document.createElement('canvas');
+    canvas.width = sw;
+    canvas.height = sh;
+    const ctx = canvas.getContext('2d');
+    if (!ctx) throw new Error('Canvas is not available');
+
+    if (options.format === 'jpeg') {
+      // JPEG has no alpha; avoid transparent areas turning black
+      ctx.fillStyle = 'white';
+      ctx.fillRect(0, 0, sw, sh);
+    }
+    ctx.drawImage(bitmap, sx, sy, sw, sh, 0, 0, sw, sh);
+
+    if (options.burnInOcr) {
+      // OCR boxes are in .mokuro page coordinates, which can differ from the
+      // decoded image size; shift them into the exported region
+      const scaleX = bitmap.width / (page.img_width || bitmap.width);
+      const scaleY = bitmap.height / (page.img_height || bitmap.height);
+      ctx.save();
+      ctx.translate(-sx, -sy);
+      drawOcrBlocks(ctx, page, scaleX, scaleY);
+      ctx.restore();
+    }
+
+    const type = options.format === 'png' ? 'image/png' : 'image/jpeg';
+    return await new Promise<Blob>((resolve, reject) => {
+      canvas.toBlob(
+        (blob) => (blob ? resolve(blob) : reject(new Error('Failed to encode image'))),
+        type,
+        0.92
+      );
+    });
+  } finally {
+    bitmap.close();
+  }
+}
+
+export function getExportFilename(
+  volumeTitle: string,
+  pageNumber: number,
+  format: PageExportFormat,
+  cropped: boolean
+): string {
+  const base = `${volumeTitle} - p${String(pageNumber).padStart(3, '0')}`;
+  return `${base}${cropped ? ' (crop)' : ''}.${format === 'png' ? 'png' : 'jpg'}`;
+}
+
+export function downloadExport(blob: Blob, filename: string) {
+  const link = document.createElement('a');
+  link.href = URL.createObjectURL(blob);
+  link.download = filename;
+  link.click();
+  URL.revokeObjectURL(link.href);
+}
@@ -0,0 +1,63 @@
+import { describe, it, expect } from 'vitest';
+import {
+  PageTurnPacer,
+  pageTransitionCss,
+  pageTransitionDuration,
+  pageTurnSide
+} from './page-transition';
+
+describe('pageTransitionDuration', () => {
+  it('uses the override, the style default, or nothing for no transition', () => {
+    expect(pageTransitionDuration('slide', 150)).toBe(150);
+    expect(pageTransitionDuration('swipe', 0)).toBe(350);
+    expect(pageTransitionDuration('none', 150)).toBe(0);
+  });
+});
+
+describe('slide transition', () => {
+  it('brings the next page in from the left in right-to-left volumes', () => {
+    const side = pageTurnSide('forward', true);
+
+    expect(side).toBe('left');
+    expect(pageTransitionCss('slide', 'in', side, 0)).toBe('transform: translateX(-100%);');
+    expect(pageTransitionCss('slide', 'out', side, 0)).toBe('transform: translateX(100%);');
+    expect(pageTransitionCss('slide', 'in', side, 1)).toBe('transform: translateX(0%);');
+  });
+
+  it('brings the next page in from the right in left-to-right volumes', () => {
+    const side = pageTurnSide('forward', false);
+
+    expect(side).toBe('right');
+    expect(pageTransitionCss('slide', 'in', side, 0)).toBe('transform: translateX(100%);');
+    expect(pageTransitionCss('slide', 'out', side, 0)).toBe('transform: translateX(-100%);');
+  });
+
+  it('reverses when paging back', () => {
+    expect(pageTurnSide('backward', true)).toBe('right');
+    expect(pageTurnSide('backward', false)).toBe('left');
+  });
+});
+
+describe('PageTurnPacer', () => {
+  it('animates turns spaced further apart than the animation', () => {
+    const pacer = new PageTurnPacer();
+
+    pacer.turn(1000, 150);
+    expect(pacer.snap).toBe(false);
+    pacer.turn(1200, 150);
+    expect(pacer.snap).toBe(false);
+  });
+
+  it('snaps key-repeat turns instead of stacking animations', () => {
+    const pacer = new PageTurnPacer();
+    pacer.turn(1000, 150);
+
+    // Held key: auto-repeat every ~33ms
+    const snapped = [1033, 1066, 1100, 1133].map((now) => {
+      pacer.turn(now, 150);
+      return pacer.snap;
+    });
+
+    expect(snapped).toEqual([true, true, true, true]);
+  });
+});
@@ -0,0 +1,104 @@
+/**
+ * Page-turn transitions for the paged reader.
+ *
+ * The page shown is keyed on the page number, so each turn runs an intro on
+ * the new page and an outro on the old one. Styles are picked in settings
+ * with an optional duration override. A turn that starts while the previous
+ * one is still animating snaps instantly, so holding the next-page key never
+ * builds a backlog of animations.
+ */
+
+import type { PageTransition } from '$lib/settings';
+
+export type PageTurnDirection = 'forward' | 'backward';
+
+/** Screen side the new page comes in from */
+export type PageTurnSide = 'left' | 'right';
+
+/** Built-in length of each style, in ms */
+const STYLE_DURATIONS: Record<PageTransition, number> = {
+  none: 0,
+  crossfade: 200,
+  pageTurn: 200,
+  swipe: 350,
+  slide: 250
+};
+
+/**
+ * Length of a page turn in ms. `override` replaces the style's own length when
+ * set; 0 means the style default. 'none' (and legacy persisted values) are instant.
+ */
+export function pageTransitionDuration(transition: PageTransition, override: number): number {
+  const base = STYLE_DURATIONS[transition] || 0;
+  if (base === 0) return 0;
+  return override > 0 ? override : base;
+}
+
+/**
+ * Turning forward brings the next page in from the right in left-to-right
+ * volumes, and from the left in right-to-left (manga) volumes
+ */
+export function pageTurnSide(direction: PageTurnDirection, rightToLeft: boolean): PageTurnSide {
+  return (direction === 'forward') !== rightToLeft ? 'right' : 'left';
+}
+
+/**
+ * CSS for one frame of a page transition. `t` runs 0 → 1 for the incoming
+ * page and 1 → 0 for the outgoing one, as Svelte transitions do.
+ */
+export function pageTransitionCss(
+  transition: PageTransition,
+  phase: 'in' | 'out',
+  side: PageTurnSide,
+  t: number
+): string {
+  switch (transition) {
+    case 'crossfade':
+      return `opacity: ${t}`;
+
+    case 'pageTurn': {
+      // Old page stays visible underneath while the new one wipes in over it
+      if (phase === 'out') return 'opacity: 1';
+      const clipPercent = side === 'right' ? 100 * (1 - t) : 100 * t;
+      return side === 'right'
+        ? `clip-path: polygon(${clipPercent}% 0, 100% 0, 100% 100%, ${clipPercent}% 100%);`
+        : `clip-path: polygon(0 0, ${clipPercent}% 0, ${clipPercent}% 100%, 0 100%);`;
+    }
+
+    case 'swipe': {
+      // New page swipes in from its side; the old one drifts a little the opposite way
+      const position = phase === 'in' ? (side === 'left' ? -100 : 100) : side === 'left' ? 30 : -30;
+      const scale = phase === 'in' ? 0.8 + t * 0.2 : 1 - (1 - t) * 0.1;
+      return `transform: translateX(${position * (1 - t)}%) scale(${scale}); opacity: ${t};`;
+    }
+
+    case 'slide': {
+      // Both pages move together, the old one leaving by the opposite edge
+      const offset = phase === 'in' ? (side === 'left' ? -100 : 100) : side === 'left' ? 100 : -100;
+      return `transform: translateX(${offset * (1 - t)}%);`;
+    }
+
+    default:
+      return '';
+  }
+}
+
+/**
+ * Tracks page turns to decide whether the next one animates: a turn within
+ * `duration` of the previous one snaps instead
+ */
+export class PageTurnPacer {
+  private lastTurnAt = -Infinity;
+  private snapping = false;
+
+  /** Record a turn starting at `now` (ms) */
+  turn(now: number, duration: number): void {
+    this.snapping = now - this.lastTurnAt < duration;
+    this.lastTurnAt = now;
+  }
+
+  /** Whether the current turn should skip its animation */
+  get snap(): boolean {
+    return this.snapping;
+  }
+}
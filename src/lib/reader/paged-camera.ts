@@ -19,6 +19,7 @@ import { createKinetic, type KineticControls } from './kinetic';
 import {
   basePosition,
   clampTranslate,
+  contentFitsViewport,
   panEdgeState,
   type BaseLayout,
   type Size,
@@ -204,6 +205,11 @@ export class PagedCamera {
     return panEdgeState(this.translate, this.scaledSize(), this.config.getViewport());
   }
 
+  /** Nothing to pan to: the whole page or spread is on screen. */
+  fitsViewport(): boolean {
+    return contentFitsViewport(this.scaledSize(), this.config.getViewport());
+  }
+
   /**
    * Where the content under `point` will actually sit after zooming to
    * `userZoomTarget` while trying to center it — i.e. the centered position
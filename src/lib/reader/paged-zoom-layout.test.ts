@@ -3,6 +3,7 @@ import {
   alignPosition,
   baseTransform,
   clampTranslate,
+  contentFitsViewport,
   panEdgeState,
   spreadContentSize
 } from './paged-zoom-layout';
@@ -40,6 +41,33 @@ describe('baseTransform', () => {
     expect(t.alignY).toBe('start');
   });
 
+  it('fit-to-width makes a wide spread fill horizontally', () => {
+    const wide = spreadContentSize({ width: 1200, height: 800 }, { width: 1200, height: 800 }, 0);
+    const t = baseTransform('zoomFitToWidth', wide, viewport, true);
+    expect(wide.width * t.scale).toBeCloseTo(1600, 6);
+    expect(t.x).toBeCloseTo(0, 6);
+    expect(t.y).toBeCloseTo((900 - 800 * t.scale) / 2, 6); // fitting height centers
+  });
+
+  it('fit-to-height fills the viewport height; a wide spread overflows at the reading corner', () => {
+    const wide = { width: 3200, height: 1200 };
+    const rtl = baseTransform('zoomFitToHeight', wide, viewport, true);
+    expect(rtl.scale).toBeCloseTo(900 / 1200, 6);
+    expect(rtl.y).toBeCloseTo(0, 6);
+    expect(rtl.x).toBeCloseTo(1600 - 3200 * rtl.scale, 6); // RTL — right edge visible
+    expect(baseTransform('zoomFitToHeight', wide, viewport, false).x).toBe(0);
+  });
+
+  it('fit-to-height on a very tall page still pans vertically once zoomed in', () => {
+    const strip = { width: 700, height: 6000 };
+    const t = baseTransform('zoomFitToHeight', strip, viewport, true);
+    expect(strip.height * t.scale).toBeCloseTo(900, 6);
+    expect(t.x).toBeCloseTo((1600 - 700 * t.scale) / 2, 6); // narrow width centers
+
+    const zoomed = { width: 700 * t.scale * 4, height: 6000 * t.scale * 4 };
+    expect(clampTranslate({ x: 0, y: -1200 }, zoomed, viewport).y).toBe(-1200);
+  });
+
   it('original is 1:1; fitting axes center, overflowing axes start at the top', () => {
     // tall: width (700) fits the 1600 viewport → centered, never pinned to
     // the corner; height (1000) overflows the 900 viewport → reading start.
@@ -197,6 +225,17 @@ describe('panEdgeState', () => {
   });
 });
 
+describe('contentFitsViewport', () => {
+  it('fits at fit-to-screen, including sub-pixel rounding', () => {
+    expect(contentFitsViewport({ width: 630, height: 900.4 }, viewport)).toBe(true);
+  });
+
+  it('does not fit once either axis overflows', () => {
+    expect(contentFitsViewport({ width: 1600, height: 2286 }, viewport)).toBe(false);
+    expect(contentFitsViewport({ width: 3200, height: 900 }, viewport)).toBe(false);
+  });
+});
+
 describe('spreadContentSize', () => {
   const a = { width: 700, height: 1000 };
   const b = { width: 720, height: 980 };
@@ -39,6 +39,7 @@ export interface BaseLayout extends Translate {
 export type PagedZoomMode =
   | 'zoomFitToScreen'
   | 'zoomFitToWidth'
+  | 'zoomFitToHeight'
   | 'zoomFillScreen'
   | 'zoomOriginal'
   | 'keepZoom'
@@ -72,6 +73,8 @@ export function basePosition(align: Align, scaled: number, viewport: number): nu
  *
  * - fit-to-screen: limiting-axis fit, centered both axes
  * - fit-to-width: fill viewport width; an overflowing height starts at the top
+ * - fit-to-height: fill viewport height; an overflowing width (wide spreads)
+ *   starts at the reading corner
  * - fill-screen: fill the non-limiting axis — tall pages fit the width, wide
  *   spreads fit the height; the overflowing axis starts at the reading corner
  * - original: 1:1; overflowing axes start at the reading corner (right in RTL)
@@ -107,6 +110,11 @@ export function baseTransform(
       alignX = 'center';
       alignY = 'start';
       break;
+    case 'zoomFitToHeight':
+      scale = viewport.height / content.height;
+      alignX = corner;
+      alignY = 'center';
+      break;
     case 'zoomFillScreen':
       scale = Math.max(viewport.width / content.width, viewport.height / content.height);
       alignX = corner;
@@ -187,6 +195,17 @@ export function panEdgeState(
   };
 }
 
+/**
+ * Whether the scaled content fits inside the viewport on both axes, i.e.
+ * there is nothing to pan to. Bare wheel turns pages instead of panning then.
+ */
+export function contentFitsViewport(scaledContent: Size, viewport: Size): boolean {
+  return (
+    scaledContent.width <= viewport.width + EDGE_EPSILON &&
+    scaledContent.height <= viewport.height + EDGE_EPSILON
+  );
+}
+
 /**
  * Native-pixel content size of the displayed page or pair. The gap between a
  * pair lives in image-pixel space (it scales with zoom like a physical
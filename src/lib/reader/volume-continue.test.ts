@@ -0,0 +1,42 @@
+import { describe, expect, it } from 'vitest';
+import type { VolumeMetadata } from '$lib/types';
+import { continuationAfter, continuePage } from './volume-continue';
+
+function volume(uuid: string, extra: Partial<VolumeMetadata> = {}): VolumeMetadata {
+  return {
+    volume_uuid: uuid,
+    volume_title: uuid,
+    series_uuid: 'series',
+    series_title: 'Series',
+    page_count: 100,
+    ...extra
+  } as VolumeMetadata;
+}
+
+describe('continuationAfter', () => {
+  const series = [volume('v1'), volume('v2'), volume('v3', { isPlaceholder: true })];
+
+  it('flows from volume 1 into volume 2', () => {
+    expect(continuationAfter(series, 'v1')).toEqual({ kind: 'next', volume: series[1] });
+  });
+
+  it('offers to download a next volume that is still in the cloud', () => {
+    expect(continuationAfter(series, 'v2')).toEqual({ kind: 'download', volume: series[2] });
+  });
+
+  it('ends at the last volume, or a volume not in the series', () => {
+    expect(continuationAfter(series, 'v3')).toEqual({ kind: 'end' });
+    expect(continuationAfter(series, 'elsewhere')).toEqual({ kind: 'end' });
+  });
+});
+
+describe('continuePage', () => {
+  it('starts unread and finished volumes at page one', () => {
+    expect(continuePage(undefined, 100)).toBe(1);
+    expect(continuePage(100, 100)).toBe(1);
+  });
+
+  it('resumes a volume that was left partway', () => {
+    expect(continuePage(37, 100)).toBe(37);
+  });
+});
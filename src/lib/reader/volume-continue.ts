@@ -0,0 +1,44 @@
+/**
+ * Auto-continue across volume boundaries.
+ *
+ * Turning past the last page opens the next volume of the series (in the
+ * catalog's manual or numeric order), with a brief "this → next" banner.
+ * A next volume still in the cloud is offered for download first.
+ */
+
+import { writable } from 'svelte/store';
+import type { VolumeMetadata } from '$lib/types';
+
+export type VolumeContinuation =
+  /** Open this volume */
+  | { kind: 'next'; volume: VolumeMetadata }
+  /** The next volume is a cloud placeholder; it has to be downloaded first */
+  | { kind: 'download'; volume: VolumeMetadata }
+  /** Last volume of the series */
+  | { kind: 'end' };
+
+/** Where reading continues after the last page of `currentUuid` */
+export function continuationAfter(
+  seriesVolumes: VolumeMetadata[],
+  currentUuid: string
+): VolumeContinuation {
+  const index = seriesVolumes.findIndex((volume) => volume.volume_uuid === currentUuid);
+  const next = index === -1 ? undefined : seriesVolumes[index + 1];
+  if (!next) return { kind: 'end' };
+  return next.isPlaceholder ? { kind: 'download', volume: next } : { kind: 'next', volume: next };
+}
+
+/**
+ * Page the next volume opens at: where it was left off, or page one when it
+ * is unread or was already finished (a reread).
+ */
+export function continuePage(savedPage: number | undefined, pageCount: number): number {
+  if (!savedPage || savedPage >= pageCount) return 1;
+  return savedPage;
+}
+
+/** Shown over the reader for a moment after continuing into the next volume */
+export const volumeContinueBanner = writable<{ from: string; to: string } | null>(null);
+
+/** How long the banner stays up, in ms */
+export const CONTINUE_BANNER_MS = 1500;
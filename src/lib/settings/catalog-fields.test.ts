@@ -0,0 +1,87 @@
+import { describe, expect, it } from 'vitest';
+import { mergeCatalogFields, mergeVolumeNotes, stampCatalogFields } from './catalog-fields';
+
+describe('catalog field timestamps', () => {
+  it('stamps only the changed fields', () => {
+    const stamps = stampCatalogFields(
+      { sort_index: '2026-01-01T00:00:00.000Z' },
+      ['chapter_markers'],
+      '2026-02-01T00:00:00.000Z'
+    );
+    expect(stamps).toEqual({
+      sort_index: '2026-01-01T00:00:00.000Z',
+      chapter_markers: '2026-02-01T00:00:00.000Z'
+    });
+  });
+
+  it('takes each field from the copy that changed it last', () => {
+    const local = {
+      sort_index: 3,
+      chapter_markers: [{ page: 0, title: 'Old' }],
+      catalog_updated: {
+        sort_index: '2026-03-01T00:00:00.000Z',
+        chapter_markers: '2026-01-01T00:00:00.000Z'
+      }
+    };
+    const cloud = {
+      sort_index: 1,
+      chapter_markers: [{ page: 4, title: 'New' }],
+      catalog_updated: {
+        sort_index: '2026-02-01T00:00:00.000Z',
+        chapter_markers: '2026-02-01T00:00:00.000Z'
+      }
+    };
+
+    expect(mergeCatalogFields(local, cloud)).toEqual({
+      sort_index: 3,
+      chapter_markers: [{ page: 4, title: 'New' }],
+      catalog_updated: {
+        sort_index: '2026-03-01T00:00:00.000Z',
+        chapter_markers: '2026-02-01T00:00:00.000Z'
+      }
+    });
+  });
+
+  it('keeps a cleared field when the clear is newer', () => {
+    const local = { catalog_updated: { series_override: '2026-02-01T00:00:00.000Z' } };
+    const cloud = {
+      series_override: 'Grouped',
+      catalog_updated: { series_override: '2026-01-01T00:00:00.000Z' }
+    };
+
+    const merged = mergeCatalogFields(local, cloud);
+    expect('series_override' in merged).toBe(true);
+    expect(merged.series_override).toBeUndefined();
+  });
+
+  it('leaves fields without timestamps to the record-level merge', () => {
+    expect(mergeCatalogFields({ sort_index: 1 }, { sort_index: 2 })).toEqual({});
+  });
+});
+
+describe('mergeVolumeNotes', () => {
+  const note = (id: string, text: string, updated?: string) => ({
+    id,
+    text,
+    created: '2026-01-01T00:00:00.000Z',
+    updated
+  });
+
+  it('keeps notes added on either device', () => {
+    const merged = mergeVolumeNotes([note('a', 'Phone')], [note('b', 'Laptop')]);
+    expect(merged?.map((n) => n.id).sort()).toEqual(['a', 'b']);
+  });
+
+  it('keeps the later edit or removal of the same note', () => {
+    const edited = note('a', 'Edited', '2026-02-01T00:00:00.000Z');
+    const removed = { ...note('a', '', '2026-03-01T00:00:00.000Z'), deleted: true };
+
+    expect(mergeVolumeNotes([note('a', 'Original')], [edited])).toEqual([edited]);
+    expect(mergeVolumeNotes([edited], [removed])).toEqual([removed]);
+  });
+
+  it('merges notes inside mergeCatalogFields even without timestamps', () => {
+    const merged = mergeCatalogFields({ notes: [note('a', 'A')] }, { notes: [note('b', 'B')] });
+    expect((merged.notes as { id: string }[]).map((n) => n.id).sort()).toEqual(['a', 'b']);
+  });
+});
@@ -0,0 +1,113 @@
+import type { SeriesMetadata, VolumeNote } from '$lib/types';
+import { mergeSeriesMetadata } from '$lib/util/series-metadata';
+
+/**
+ * Catalog fields carried in synced volume data (groupings, order, covers,
+ * chapters, page fixes, notes, series details).
+ *
+ * Each field has its own timestamp in `catalog_updated`. Editing one never
+ * touches lastProgressUpdate, so it can't override reading progress synced
+ * from another device, and sync merges the fields one by one: each comes from
+ * whichever copy changed it last.
+ */
+
+export const CATALOG_FIELDS = [
+  'series_override',
+  'sort_index',
+  'series_cover',
+  'chapter_markers',
+  'page_adjustments',
+  'notes',
+  'series_metadata'
+] as const;
+
+export type CatalogField = (typeof CATALOG_FIELDS)[number];
+
+/** ISO timestamp of the last change to each catalog field */
+export type CatalogTimestamps = Partial<Record<CatalogField, string>>;
+
+type CatalogRecord = Partial<Record<CatalogField, unknown>> & {
+  catalog_updated?: CatalogTimestamps;
+};
+
+function time(iso: string | undefined): number {
+  if (!iso) return 0;
+  const value = Date.parse(iso);
+  return Number.isNaN(value) ? 0 : value;
+}
+
+/** Timestamps for `fields`, changed at `now`, on top of the existing ones */
+export function stampCatalogFields(
+  existing: CatalogTimestamps | undefined,
+  fields: CatalogField[],
+  now: string
+): CatalogTimestamps {
+  const stamps = { ...existing };
+  for (const field of fields) stamps[field] = now;
+  return stamps;
+}
+
+function noteTime(note: VolumeNote): number {
+  return time(note.updated ?? note.created);
+}
+
+/** Notes from both copies, matched by id; a note changed on both keeps the later change */
+export function mergeVolumeNotes(
+  local: VolumeNote[] | undefined,
+  cloud: VolumeNote[] | undefined
+): VolumeNote[] | undefined {
+  const byId = new Map<string, VolumeNote>();
+  for (const note of [...(cloud ?? []), ...(local ?? [])]) {
+    const other = byId.get(note.id);
+    if (!other || noteTime(note) >= noteTime(other)) byId.set(note.id, note);
+  }
+  return byId.size > 0 ? [...byId.values()] : undefined;
+}
+
+/**
+ * The catalog fields of two copies of a volume's data, each taken from the
+ * copy that changed it last. Fields neither copy has a timestamp for (data
+ * written before timestamps existed) are left out, so the caller keeps the
+ * value from the record it picked. Series details and notes are always merged.
+ */
+export function mergeCatalogFields(
+  local: CatalogRecord,
+  cloud: CatalogRecord
+): Partial<Record<CatalogField, unknown>> & { catalog_updated?: CatalogTimestamps } {
+  const merged: Partial<Record<CatalogField, unknown>> = {};
+  const stamps: CatalogTimestamps = {};
+
+  for (const field of CATALOG_FIELDS) {
+    if (field === 'series_metadata' || field === 'notes') continue;
+    const localTime = time(local.catalog_updated?.[field]);
+    const cloudTime = time(cloud.catalog_updated?.[field]);
+    if (localTime === 0 && cloudTime === 0) continue;
+
+    const newer = cloudTime > localTime ? cloud : local;
+    merged[field] = newer[field];
+    stamps[field] = newer.catalog_updated![field];
+  }
+
+  // Series details and notes merge inside the value rather than as a whole
+  if (local.series_metadata || cloud.series_metadata) {
+    merged.series_metadata = mergeSeriesMetadata(
+      local.series_metadata as SeriesMetadata | undefined,
+      cloud.series_metadata as SeriesMetadata | undefined
+    );
+  }
+  if (local.notes || cloud.notes) {
+    merged.notes = mergeVolumeNotes(
+      local.notes as VolumeNote[] | undefined,
+      cloud.notes as VolumeNote[] | undefined
+    );
+  }
+  for (const field of ['series_metadata', 'notes'] as const) {
+    const localStamp = local.catalog_updated?.[field];
+    const cloudStamp = cloud.catalog_updated?.[field];
+    const stamp = time(cloudStamp) > time(localStamp) ? cloudStamp : localStamp;
+    if (stamp) stamps[field] = stamp;
+  }
+
+  if (Object.keys(stamps).length > 0) return { ...merged, catalog_updated: stamps };
+  return merged;
+}
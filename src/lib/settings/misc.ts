@@ -1,12 +1,60 @@
 import { browser } from '$app/environment';
 import { writable } from 'svelte/store';
+import type { PageSortMode } from '$lib/util/natural-sort';
+import type { MeteredOverride } from '$lib/util/metered';
+
+export type GalleryTileSize = 'small' | 'medium' | 'large';
+
+/**
+ * What gets downloaded from the cloud without being asked: nothing (volumes
+ * stay placeholders until downloaded), or the next volume of what's being read.
+ */
+export type CloudDownloadPolicy = 'manual' | 'reading';
+
+/** Animated page images: keep them playing, or keep only their first frame */
+export type AnimatedPagesMode = 'play' | 'flatten';
 
 export type MiscSettings = {
   galleryLayout: 'grid' | 'list';
   gallerySorting: 'ASC' | 'DESC' | 'SMART';
+  galleryTileSize: GalleryTileSize;
+  /** Show a ring on series tiles with how much of the series has been read */
+  galleryShowProgress: boolean;
+  galleryShowVolumeCount: boolean;
   deviceRamGB: 4 | 8 | 16 | 32;
   turboMode: boolean;
+  /** Maximum simultaneous cloud downloads in turbo mode */
+  downloadConcurrency: number;
+  /** Google Drive in turbo mode: parallel range requests per volume (1 downloads in one request) */
+  driveRangeConcurrency: number;
+  /** Google Drive in turbo mode: size of each range request, in MB */
+  driveRangeChunkMB: number;
   gdriveAutoReAuth: boolean;
+  /** Minutes between automatic progress syncs; 0 syncs only when asked */
+  syncIntervalMinutes: number;
+  cloudDownloadPolicy: CloudDownloadPolicy;
+  /** Treat the connection as metered: detected from the browser, or forced */
+  meteredConnection: MeteredOverride;
+  /** Still download the next volume while reading on a metered connection */
+  meteredDownloadAhead: boolean;
+  /** Evict least-recently-read cloud-backed volumes when storage exceeds the cap */
+  storageEvictionEnabled: boolean;
+  storageCapGB: number;
+  /** Google Cloud Vision API key used to OCR image-only imports; empty disables OCR */
+  cloudVisionApiKey: string;
+  /** Page order for volumes imported without a .mokuro file */
+  pageSortMode: PageSortMode;
+  /** Offer to skip near-identical adjacent pages (repeated cover scans) on import */
+  detectDuplicatePages: boolean;
+  animatedPages: AnimatedPagesMode;
+  /** Prefill series details (author, synopsis, genres) from AniList */
+  seriesMetadataFetch: boolean;
+  /** Minutes between local reader-state backups; 0 turns them off */
+  readerStateBackupMinutes: number;
+  /** How many local reader-state backups to keep */
+  readerStateBackupKeep: number;
+  /** Memory for decoded pages kept by the reader, in MB; 0 sizes it from device RAM */
+  readerImageCacheMB: number;
 };
 
 export type MiscSettingsKey = keyof MiscSettings;
@@ -28,14 +76,37 @@ function getDefaultRamSetting(): 4 | 8 | 16 | 32 {
 const defaultSettings: MiscSettings = {
   galleryLayout: 'grid',
   gallerySorting: 'SMART',
+  galleryTileSize: 'medium',
+  galleryShowProgress: true,
+  galleryShowVolumeCount: true,
   deviceRamGB: getDefaultRamSetting(),
   turboMode: false, // Default to single-operation mode (patient users)
-  gdriveAutoReAuth: true // Keep users synced during long reading sessions
+  downloadConcurrency: 3,
+  driveRangeConcurrency: 1,
+  driveRangeChunkMB: 16,
+  gdriveAutoReAuth: true, // Keep users synced during long reading sessions
+  syncIntervalMinutes: 15,
+  cloudDownloadPolicy: 'manual',
+  meteredConnection: 'auto',
+  meteredDownloadAhead: false,
+  storageEvictionEnabled: false,
+  storageCapGB: 10,
+  cloudVisionApiKey: '',
+  pageSortMode: 'natural',
+  detectDuplicatePages: false,
+  animatedPages: 'play',
+  seriesMetadataFetch: false,
+  readerStateBackupMinutes: 0,
+  readerStateBackupKeep: 10,
+  readerImageCacheMB: 0
 };
 
 const stored = browser ? window.localStorage.getItem('miscSettings') : undefined;
 
-export const miscSettings = writable<MiscSettings>(stored ? JSON.parse(stored) : defaultSettings);
+// Overlay stored values on defaults so settings added in later versions get their defaults
+export const miscSettings = writable<MiscSettings>(
+  stored ? { ...defaultSettings, ...JSON.parse(stored) } : defaultSettings
+);
 
 miscSettings.subscribe((miscSettings) => {
   if (browser) {
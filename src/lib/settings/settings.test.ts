@@ -2,9 +2,17 @@
 import { describe, expect, it, beforeEach, afterEach, vi } from 'vitest';
 import { get } from 'svelte/store';
 import {
+  activeTheme,
+  animationsDisabled,
+  currentProfile,
+  deviceSettings,
   migrateProfiles,
+  profilesWithTrash,
+  seedDeviceSettings,
+  settings,
   grayscaleActive,
   imageFilter,
+  pageToneFilter,
   updateSetting,
   updateScheduleSetting
 } from './settings';
@@ -117,3 +125,149 @@ describe('imageFilter', () => {
     expect(get(imageFilter)).toBe('invert(1) grayscale(1)');
   });
 });
+
+describe('animationsDisabled', () => {
+  const defaultMatchMedia = window.matchMedia;
+  let reduceMotion = false;
+  let onChange: ((event: { matches: boolean }) => void) | undefined;
+
+  beforeEach(() => {
+    reduceMotion = false;
+    updateSetting('disableAnimations', false);
+    window.matchMedia = vi.fn(
+      (query: string) =>
+        ({
+          matches: query === '(prefers-reduced-motion: reduce)' && reduceMotion,
+          media: query,
+          addEventListener: (_type: string, listener: typeof onChange) => (onChange = listener),
+          removeEventListener: vi.fn()
+        }) as unknown as MediaQueryList
+    );
+  });
+
+  afterEach(() => {
+    window.matchMedia = defaultMatchMedia;
+  });
+
+  it('follows the setting when the system has no motion preference', () => {
+    expect(get(animationsDisabled)).toBe(false);
+    updateSetting('disableAnimations', true);
+    expect(get(animationsDisabled)).toBe(true);
+  });
+
+  it('turns animations off when the system asks for reduced motion', () => {
+    reduceMotion = true;
+    expect(get(animationsDisabled)).toBe(true);
+  });
+
+  it('picks up a preference change while the reader is open', () => {
+    const values: boolean[] = [];
+    const unsubscribe = animationsDisabled.subscribe((value) => values.push(value));
+
+    onChange?.({ matches: true });
+    unsubscribe();
+
+    expect(values).toEqual([false, true]);
+  });
+});
+
+describe('pageToneFilter', () => {
+  beforeEach(() => {
+    updateSetting('pageBrightness', 100);
+    updateSetting('pageWarmth', 0);
+  });
+
+  it('is none at the defaults', () => {
+    expect(get(pageToneFilter)).toBe('none');
+  });
+
+  it('dims without tinting', () => {
+    updateSetting('pageBrightness', 40);
+    expect(get(pageToneFilter)).toBe('brightness(0.4)');
+  });
+
+  it('combines dimming with a warm tint', () => {
+    updateSetting('pageBrightness', 40);
+    updateSetting('pageWarmth', 50);
+    expect(get(pageToneFilter)).toBe('brightness(0.4) sepia(0.3) saturate(1.2)');
+  });
+});
+
+describe('reader background', () => {
+  beforeEach(() => {
+    updateSetting('theme', 'dark');
+    updateSetting('readerBackground', 'theme');
+  });
+
+  it('follows the theme background by default', () => {
+    expect(get(activeTheme).vars['--reader-bg']).toBe('#030712');
+  });
+
+  it('overrides only the reader background', () => {
+    updateSetting('readerBackground', 'black');
+    const vars = get(activeTheme).vars;
+    expect(vars['--reader-bg']).toBe('#000000');
+    expect(vars['--app-bg']).toBe('#030712');
+  });
+
+  it('uses the custom colour when chosen', () => {
+    updateSetting('readerBackgroundCustom', '#223344');
+    updateSetting('readerBackground', 'custom');
+    expect(get(activeTheme).vars['--reader-bg']).toBe('#223344');
+  });
+});
+
+describe('device-local settings', () => {
+  beforeEach(() => {
+    updateSetting('zoomDefault', 'zoomFitToScreen');
+    updateSetting('fontSize', 'auto');
+  });
+
+  it('keeps a device-local change out of the synced profile', () => {
+    const profileId = get(currentProfile);
+    const before = get(profilesWithTrash)[profileId];
+
+    updateSetting('zoomDefault', 'zoomFitToWidth');
+
+    expect(get(settings).zoomDefault).toBe('zoomFitToWidth');
+    expect(get(deviceSettings)[profileId].zoomDefault).toBe('zoomFitToWidth');
+    const after = get(profilesWithTrash)[profileId];
+    expect(after.zoomDefault).toBe(before.zoomDefault);
+    expect(after.lastUpdated).toBe(before.lastUpdated);
+  });
+
+  it('writes synced preferences to the profile', () => {
+    updateSetting('fontSize', '24');
+
+    const profileId = get(currentProfile);
+    expect(get(profilesWithTrash)[profileId].fontSize).toBe('24');
+    expect(get(deviceSettings)[profileId]).not.toHaveProperty('fontSize');
+  });
+
+  it("ignores another device's value arriving with a synced profile", () => {
+    const profileId = get(currentProfile);
+    updateSetting('zoomDefault', 'zoomFitToHeight');
+
+    // What a profile sync merge does with the other device's copy
+    profilesWithTrash.update((profiles) => ({
+      ...profiles,
+      [profileId]: { ...profiles[profileId], zoomDefault: 'zoomOriginal', fontSize: '32' }
+    }));
+
+    expect(get(settings).zoomDefault).toBe('zoomFitToHeight');
+    expect(get(settings).fontSize).toBe('32');
+  });
+
+  it('seeds device values from flat profiles, skipping deleted ones', () => {
+    const profiles = migrateProfiles({
+      Phone: { zoomDefault: 'zoomFitToWidth', fontSize: '20' } as any,
+      Old: { deletedOn: '2026-01-01T00:00:00.000Z' } as any
+    });
+
+    const seeded = seedDeviceSettings(profiles);
+
+    expect(seeded.Phone.zoomDefault).toBe('zoomFitToWidth');
+    expect(seeded.Phone).not.toHaveProperty('fontSize');
+    expect(seeded).not.toHaveProperty('Old');
+  });
+});
@@ -1,7 +1,13 @@
 import { browser } from '$app/environment';
 import { derived, get, readable, writable } from 'svelte/store';
 import { isMobilePlatform } from '$lib/util/platform';
-import { PRESETS, resolveTheme, type ResolvedTheme } from './theme';
+import {
+  PRESETS,
+  readerBackgroundColor,
+  resolveTheme,
+  type ReaderBackground,
+  type ResolvedTheme
+} from './theme';
 
 export type FontSize =
   | 'auto'
@@ -23,6 +29,7 @@ export type FontSize =
 export type ZoomModes =
   | 'zoomFitToScreen'
   | 'zoomFitToWidth'
+  | 'zoomFitToHeight'
   | 'zoomFillScreen'
   | 'zoomOriginal'
   | 'keepZoom';
@@ -32,7 +39,7 @@ export type ContinuousZoomMode = 'zoomFitToScreen' | 'zoomFillScreen' | 'zoomOri
 
 export type ScrollMode = 'vertical' | 'horizontal' | 'auto' | 'continuous';
 
-export type PageTransition = 'none' | 'crossfade' | 'pageTurn' | 'swipe';
+export type PageTransition = 'none' | 'crossfade' | 'pageTurn' | 'swipe' | 'slide';
 
 // AnkiConnect field mapping - template is freeform text with variables
 export type FieldMapping = {
@@ -140,24 +147,45 @@ export type Settings = {
   backgroundColor: string;
   theme: string; // preset id ('dark' | 'eink' | 'paper' | 'sepia' | 'nord' | 'custom')
   customTheme: import('./theme').CustomTheme; // edited by the Custom theme mode
+  /** Reader viewport background; 'theme' follows the theme's background token */
+  readerBackground: ReaderBackground;
+  readerBackgroundCustom: string; // used when readerBackground is 'custom'
   swipeThreshold: number;
   edgeButtonWidth: number;
   showTimer: boolean;
   quickActions: boolean;
+  /** Strip of page previews for jumping around the volume */
+  pageThumbnails: boolean;
+  /** Auto-hide reader controls after a few seconds without interaction */
+  immersiveMode: boolean;
   fontSize: FontSize;
   zoomDefault: ZoomModes;
   pageTransition: PageTransition;
+  /** Page transition length in ms; 0 uses the transition's own length */
+  pageTransitionDuration: number;
   nightMode: boolean;
   nightModeSchedule: TimeSchedule;
-  /** Skip zoom/pan/page-turn animations entirely (e-ink devices). */
+  /** Skip zoom/pan/page-turn animations entirely (e-ink devices); see animationsDisabled. */
   disableAnimations: boolean;
   invertColors: boolean;
   invertColorsSchedule: TimeSchedule;
   grayscale: boolean;
   grayscaleSchedule: TimeSchedule;
+  /** Page image brightness in percent (100 = unchanged); OCR text and UI are not dimmed */
+  pageBrightness: number;
+  /** Warm color-temperature tint over page images, 0-100 */
+  pageWarmth: number;
   inactivityTimeoutMinutes: number;
   swapWheelBehavior: boolean;
   textBoxContextMenu: boolean;
+  /** Show a JMdict popup when hovering or tapping OCR text */
+  dictionaryLookup: boolean;
+  /** Highlight words in OCR text that aren't in the known-words list */
+  unknownWordHighlight: boolean;
+  /** Unknown words ranked this common or more stay plain (0 = highlight all) */
+  unknownWordThreshold: number;
+  /** Turning past the last page opens the next volume of the series */
+  autoContinue: boolean;
   continuousScroll: boolean;
   singlePageView: PageViewMode;
   scrollMode: ScrollMode;
@@ -277,13 +305,18 @@ const defaultSettings: Settings = {
     success: '#15803d',
     danger: '#b91c1c'
   },
+  readerBackground: 'theme',
+  readerBackgroundCustom: '#808080',
   swipeThreshold: 35,
   edgeButtonWidth: 40,
   showTimer: false,
   quickActions: true,
+  pageThumbnails: false,
+  immersiveMode: false,
   fontSize: 'auto',
   zoomDefault: 'zoomFitToScreen',
   pageTransition: 'none',
+  pageTransitionDuration: 0,
   nightMode: false,
   nightModeSchedule: {
     enabled: false,
@@ -303,9 +336,15 @@ const defaultSettings: Settings = {
     startTime: '21:00',
     endTime: '06:00'
   },
+  pageBrightness: 100,
+  pageWarmth: 0,
   inactivityTimeoutMinutes: 5,
   swapWheelBehavior: false,
   textBoxContextMenu: true,
+  dictionaryLookup: false,
+  unknownWordHighlight: false,
+  unknownWordThreshold: 0,
+  autoContinue: true,
   continuousScroll: false,
   singlePageView: 'auto',
   scrollMode: 'auto',
@@ -563,17 +602,77 @@ currentProfile.subscribe((currentProfile) => {
   }
 });
 
+/**
+ * Settings that depend on this device's screen and input rather than on the
+ * reader's preferences. Profiles still carry them (as the starting values on a
+ * new device), but changes are kept in `deviceSettings` and never synced, so
+ * e.g. a phone's fit mode doesn't follow the profile onto a desktop.
+ * Everything else in a profile syncs through profiles.json.
+ */
+export const DEVICE_LOCAL_SETTINGS = [
+  'defaultFullscreen',
+  'mobile',
+  'swipeThreshold',
+  'edgeButtonWidth',
+  'zoomDefault',
+  'continuousZoomDefault',
+  'singlePageView',
+  'scrollMode',
+  'swapWheelBehavior',
+  'disableAnimations',
+  'pageBrightness'
+] as const satisfies readonly SettingsKey[];
+
+export type DeviceLocalSettingsKey = (typeof DEVICE_LOCAL_SETTINGS)[number];
+
+/** This device's values for device-local settings, per profile */
+export type DeviceSettings = Record<string, Partial<Pick<Settings, DeviceLocalSettingsKey>>>;
+
+export function isDeviceLocalSetting(key: SettingsKey): key is DeviceLocalSettingsKey {
+  return (DEVICE_LOCAL_SETTINGS as readonly SettingsKey[]).includes(key);
+}
+
+/**
+ * Take this device's current device-local values out of each profile. Run once
+ * when upgrading from flat profiles, so the values already in use here stay put
+ * when the next sync brings in another device's.
+ */
+export function seedDeviceSettings(profiles: Profiles): DeviceSettings {
+  const seeded: DeviceSettings = {};
+  for (const [name, profile] of Object.entries(profiles)) {
+    if (profile.deletedOn) continue;
+    seeded[name] = Object.fromEntries(
+      DEVICE_LOCAL_SETTINGS.map((key) => [key, profile[key]])
+    ) as DeviceSettings[string];
+  }
+  return seeded;
+}
+
+const storedDeviceSettings = browser ? window.localStorage.getItem('deviceSettings') : null;
+const initialDeviceSettings: DeviceSettings = storedDeviceSettings
+  ? JSON.parse(storedDeviceSettings)
+  : seedDeviceSettings(initialProfiles);
+
+/** Device-local settings; stays in localStorage and is never uploaded */
+export const deviceSettings = writable<DeviceSettings>(initialDeviceSettings);
+
+deviceSettings.subscribe((deviceSettings) => {
+  if (browser) {
+    window.localStorage.setItem('deviceSettings', JSON.stringify(deviceSettings));
+  }
+});
+
 export const settings = derived(
-  [profiles, currentProfile],
-  ([$profiles, $currentProfile], set: (value: Settings) => void) => {
+  [profiles, currentProfile, deviceSettings],
+  ([$profiles, $currentProfile, $deviceSettings], set: (value: Settings) => void) => {
     if ($profiles[$currentProfile]) {
-      set($profiles[$currentProfile]);
+      set({ ...$profiles[$currentProfile], ...$deviceSettings[$currentProfile] });
     } else {
       // Fall back to Desktop or Mobile profile if current profile doesn't exist
       const fallbackProfile = $profiles['Desktop'] ? 'Desktop' : 'Mobile';
       if ($profiles[fallbackProfile]) {
         currentProfile.set(fallbackProfile);
-        set($profiles[fallbackProfile]);
+        set({ ...$profiles[fallbackProfile], ...$deviceSettings[fallbackProfile] });
       } else {
         // Ultimate fallback to default settings
         set(defaultSettings);
@@ -586,11 +685,21 @@ export const settings = derived(
 export const activeTheme = derived(settings, ($settings): ResolvedTheme => {
   if (!$settings) return resolveTheme(PRESETS.dark);
   const id = $settings.theme ?? 'dark';
+  let theme: ResolvedTheme;
   if (id === 'custom' && $settings.customTheme) {
     const { base, ...tokens } = $settings.customTheme;
-    return resolveTheme({ id: 'custom', name: 'Custom', base, tokens });
+    theme = resolveTheme({ id: 'custom', name: 'Custom', base, tokens });
+  } else {
+    theme = resolveTheme(PRESETS[id] ?? PRESETS.dark);
   }
-  return resolveTheme(PRESETS[id] ?? PRESETS.dark);
+
+  // The reader background can be set independently of the app theme
+  const readerBg = readerBackgroundColor(
+    $settings.readerBackground,
+    $settings.readerBackgroundCustom
+  );
+  if (!readerBg) return theme;
+  return { ...theme, vars: { ...theme.vars, '--reader-bg': readerBg } };
 });
 
 // Derived store for easy access to catalog settings
@@ -653,6 +762,26 @@ export const grayscaleActive = derived([settings, currentMinute], ([$settings, _
   return $settings.grayscale ?? false;
 });
 
+/** The system's reduced-motion preference, kept current as it changes */
+export const prefersReducedMotion = readable(false, (set) => {
+  if (typeof window === 'undefined' || typeof window.matchMedia !== 'function') return;
+  const query = window.matchMedia('(prefers-reduced-motion: reduce)');
+  set(query.matches);
+  const onChange = (event: MediaQueryListEvent) => set(event.matches);
+  query.addEventListener('change', onChange);
+  return () => query.removeEventListener('change', onChange);
+});
+
+/**
+ * Whether reader animations (page turns, zoom, smooth scroll) are off: by the
+ * "Disable animations" setting, or because the system asks for reduced motion
+ */
+export const animationsDisabled = derived(
+  [settings, prefersReducedMotion],
+  ([$settings, $prefersReducedMotion]) =>
+    ($settings?.disableAnimations ?? false) || $prefersReducedMotion
+);
+
 /**
  * Combined CSS filter string for the manga image layer. invert() and
  * grayscale() commute, so order is irrelevant. Night mode is applied
@@ -664,6 +793,24 @@ export const imageFilter = derived(
     `invert(${$invertColorsActive ? 1 : 0}) grayscale(${$grayscaleActive ? 1 : 0})`
 );
 
+/**
+ * Backdrop filter that dims and warms the page image underneath the OCR text
+ * layer, or 'none' at the defaults so pages pay no compositing cost.
+ * Warmth is sepia for the tint plus a slight saturation boost so colored pages
+ * don't turn flat brown.
+ */
+export const pageToneFilter = derived(settings, ($settings) => {
+  const brightness = $settings?.pageBrightness ?? 100;
+  const warmth = $settings?.pageWarmth ?? 0;
+  if (brightness >= 100 && warmth <= 0) return 'none';
+
+  const parts = [`brightness(${brightness / 100})`];
+  if (warmth > 0) {
+    parts.push(`sepia(${(warmth / 100) * 0.6})`, `saturate(${1 + (warmth / 100) * 0.4})`);
+  }
+  return parts.join(' ');
+});
+
 /**
  * Helper function to update a profile's timestamp
  */
@@ -675,6 +822,16 @@ function touchProfile(profile: Settings): Settings {
 }
 
 export function updateSetting(key: SettingsKey, value: any) {
+  if (isDeviceLocalSetting(key)) {
+    // Kept on this device; the profile (and its sync timestamp) is left alone
+    const profileId = get(currentProfile);
+    deviceSettings.update((devices) => ({
+      ...devices,
+      [profileId]: { ...devices[profileId], [key]: value }
+    }));
+    return;
+  }
+
   _profilesInternal.update((profiles) => {
     const profileId = get(currentProfile);
     return {
@@ -775,6 +932,10 @@ export function resetSettings() {
       [profile]: touchProfile(defaultForProfile)
     };
   });
+  deviceSettings.update((devices) => ({
+    ...devices,
+    [profile]: seedDeviceSettings({ [profile]: defaultForProfile })[profile]
+  }));
 }
 
 export function createProfile(profileId: string) {
@@ -813,6 +974,7 @@ export function deleteProfile(profileId: string) {
       [profileId]: tombstone
     };
   });
+  deviceSettings.update(({ [profileId]: _removed, ...rest }) => rest);
 
   return true;
 }
@@ -832,6 +994,9 @@ export function renameProfile(oldName: string, newName: string) {
     delete Object.assign(profiles, { [newName]: profiles[oldName] })[oldName];
     return profiles;
   });
+  deviceSettings.update(({ [oldName]: moved, ...rest }) =>
+    moved ? { ...rest, [newName]: moved } : rest
+  );
 
   return true;
 }
@@ -846,6 +1011,7 @@ export function copyProfile(profileToCopy: string, newName: string) {
       })
     };
   });
+  deviceSettings.update((devices) => ({ ...devices, [newName]: { ...devices[profileToCopy] } }));
 }
 
 export function changeProfile(profileId: string) {
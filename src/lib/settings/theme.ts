@@ -33,6 +33,25 @@ export type ResolvedTheme = {
   vars: Record<string, string>;
 };
 
+/** Reader viewport background: follow the theme, a fixed shade, or a custom colour. */
+export type ReaderBackground = 'theme' | 'black' | 'white' | 'gray' | 'custom';
+
+export const READER_BACKGROUND_COLORS: Record<'black' | 'white' | 'gray', string> = {
+  black: '#000000',
+  white: '#ffffff',
+  gray: '#808080'
+};
+
+/** Colour to override --reader-bg with; undefined means follow the theme. */
+export function readerBackgroundColor(
+  choice: ReaderBackground | undefined,
+  custom: string | undefined
+): string | undefined {
+  if (!choice || choice === 'theme') return undefined;
+  if (choice === 'custom') return custom;
+  return READER_BACKGROUND_COLORS[choice];
+}
+
 /** Perceived brightness (0-255) — used to pick a readable on-accent label color. */
 function brightness(hex: string): number {
   const [r, g, b] = parseHex(hex);
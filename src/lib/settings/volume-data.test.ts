@@ -0,0 +1,73 @@
+import { beforeEach, describe, expect, it } from 'vitest';
+import { get } from 'svelte/store';
+import 'fake-indexeddb/auto';
+import { getSeriesProgress, isVolumeComplete } from '$lib/util/volume-helpers';
+import {
+  markVolumeAsComplete,
+  markVolumeAsUnread,
+  progress,
+  restartVolume,
+  updateVolumeChapterMarkers,
+  VolumeData,
+  volumes,
+  volumesWithTrash
+} from './volume-data';
+
+const series = [
+  { volume_uuid: 'v1', volume_title: 'Volume 1', page_count: 100 },
+  { volume_uuid: 'v2', volume_title: 'Volume 2', page_count: 120 }
+];
+
+describe('marking volumes read and unread', () => {
+  beforeEach(() => {
+    localStorage.clear();
+    volumesWithTrash.set({
+      v1: new VolumeData({ progress: 40, timeReadInMinutes: 30 }),
+      v2: new VolumeData({ progress: 120, completed: true })
+    });
+  });
+
+  it('marks a volume read without reading to the end, completing the series', () => {
+    markVolumeAsComplete('v1', 100, 5000);
+
+    const v1 = get(volumes).v1;
+    expect(isVolumeComplete(v1.progress, 100)).toBe(true);
+    expect(v1.completed).toBe(true);
+    expect(v1.lastProgressUpdate).not.toBe(new VolumeData().lastProgressUpdate);
+    expect(getSeriesProgress(series, get(progress)).isComplete).toBe(true);
+  });
+
+  it('clears progress when marked unread', () => {
+    markVolumeAsUnread('v2');
+
+    expect(get(volumes).v2.progress).toBe(0);
+    expect(get(volumes).v2.completed).toBe(false);
+    expect(getSeriesProgress(series, get(progress)).completed).toBe(0);
+  });
+
+  it('starts a volume over from page one, keeping time read', () => {
+    restartVolume('v1');
+
+    expect(get(volumes).v1.progress).toBe(1);
+    expect(get(volumes).v1.timeReadInMinutes).toBe(30);
+  });
+});
+
+describe('catalog field updates', () => {
+  beforeEach(() => {
+    localStorage.clear();
+    volumesWithTrash.set({
+      v1: new VolumeData({ progress: 40, lastProgressUpdate: '2026-01-01T00:00:00.000Z' })
+    });
+  });
+
+  it('stamps the field without touching reading progress', () => {
+    updateVolumeChapterMarkers('v1', [{ page: 3, title: 'Chapter 2' }]);
+
+    const v1 = get(volumes).v1;
+    expect(v1.chapter_markers).toEqual([{ page: 3, title: 'Chapter 2' }]);
+    expect(v1.lastProgressUpdate).toBe('2026-01-01T00:00:00.000Z');
+    expect(v1.catalog_updated?.chapter_markers).toBeDefined();
+    expect(v1.toJSON().catalog_updated).toEqual(v1.catalog_updated);
+  });
+});
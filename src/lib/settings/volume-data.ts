@@ -3,6 +3,9 @@ import { derived, writable, readable } from 'svelte/store';
 import { settings as globalSettings } from './settings';
 import { db } from '$lib/catalog/db';
 import { getEffectiveReadingTime } from '$lib/util/reading-speed';
+import { emitLibraryEvent } from '$lib/util/library-events';
+import type { ChapterMarker, PageAdjustment, SeriesMetadata, VolumeNote } from '$lib/types';
+import { stampCatalogFields, type CatalogField, type CatalogTimestamps } from './catalog-fields';
 
 // Deep equality check for settings objects
 function settingsEqual(
@@ -26,6 +29,8 @@ function settingsEqual(
 
 export type VolumeSettings = {
   rightToLeft?: boolean;
+  /** rightToLeft was detected at import (or taken from the series), not chosen */
+  autoDirection?: boolean;
   hasCover?: boolean;
   spreadBreakpoints?: number[];
 };
@@ -57,6 +62,22 @@ type VolumeDataJSON = {
   series_uuid?: string;
   series_title?: string;
   volume_title?: string;
+  // Manual catalog grouping (see VolumeMetadata.series_override)
+  series_override?: string;
+  // Manual position within the series (see VolumeMetadata.sort_index)
+  sort_index?: number;
+  // Pinned series cover (see VolumeMetadata.series_cover)
+  series_cover?: boolean;
+  // Chapter starts within the volume (see VolumeMetadata.chapter_markers)
+  chapter_markers?: ChapterMarker[];
+  // Page rotation and trim (see VolumeMetadata.page_adjustments)
+  page_adjustments?: Record<string, PageAdjustment>;
+  // Notes and highlights (see VolumeMetadata.notes)
+  notes?: VolumeNote[];
+  // Author, synopsis and external IDs of the volume's series
+  series_metadata?: SeriesMetadata;
+  // When each of the catalog fields above last changed (see catalog-fields.ts)
+  catalog_updated?: CatalogTimestamps;
   // Deletion tracking for sync (mutually exclusive)
   addedOn?: string; // ISO datetime when volume was added/created
   deletedOn?: string; // ISO datetime when metadata was deleted
@@ -74,6 +95,14 @@ export class VolumeData implements VolumeDataJSON {
   series_uuid?: string;
   series_title?: string;
   volume_title?: string;
+  series_override?: string;
+  sort_index?: number;
+  series_cover?: boolean;
+  chapter_markers?: ChapterMarker[];
+  page_adjustments?: Record<string, PageAdjustment>;
+  notes?: VolumeNote[];
+  series_metadata?: SeriesMetadata;
+  catalog_updated?: CatalogTimestamps;
   addedOn?: string; // ISO datetime when volume was added/created
   deletedOn?: string; // ISO datetime when metadata was deleted
 
@@ -93,6 +122,28 @@ export class VolumeData implements VolumeDataJSON {
     this.series_uuid = data.series_uuid;
     this.series_title = data.series_title;
     this.volume_title = data.volume_title;
+    this.series_override = data.series_override || undefined;
+    this.sort_index = typeof data.sort_index === 'number' ? data.sort_index : undefined;
+    this.series_cover = data.series_cover || undefined;
+    this.chapter_markers =
+      Array.isArray(data.chapter_markers) && data.chapter_markers.length > 0
+        ? data.chapter_markers
+        : undefined;
+    this.page_adjustments =
+      data.page_adjustments &&
+      typeof data.page_adjustments === 'object' &&
+      Object.keys(data.page_adjustments).length > 0
+        ? data.page_adjustments
+        : undefined;
+    this.notes = Array.isArray(data.notes) && data.notes.length > 0 ? data.notes : undefined;
+    this.series_metadata =
+      data.series_metadata && typeof data.series_metadata === 'object'
+        ? data.series_metadata
+        : undefined;
+    this.catalog_updated =
+      data.catalog_updated && typeof data.catalog_updated === 'object'
+        ? data.catalog_updated
+        : undefined;
 
     // Deletion tracking (optional, undefined means epoch in merge logic)
     this.addedOn = data.addedOn;
@@ -106,6 +157,9 @@ export class VolumeData implements VolumeDataJSON {
     // Only store if explicitly provided
     if (typeof data.settings?.rightToLeft === 'boolean') {
       this.settings.rightToLeft = data.settings.rightToLeft;
+      if (data.settings.autoDirection === true) {
+        this.settings.autoDirection = true;
+      }
     }
 
     if (typeof data.settings?.hasCover === 'boolean') {
@@ -144,6 +198,7 @@ export class VolumeData implements VolumeDataJSON {
     const syncableSettings: Partial<VolumeSettings> = {};
     if (typeof this.settings.rightToLeft === 'boolean') {
       syncableSettings.rightToLeft = this.settings.rightToLeft;
+      if (this.settings.autoDirection) syncableSettings.autoDirection = true;
     }
     if (typeof this.settings.hasCover === 'boolean') {
       syncableSettings.hasCover = this.settings.hasCover;
@@ -173,6 +228,30 @@ export class VolumeData implements VolumeDataJSON {
     if (this.volume_title) {
       result.volume_title = this.volume_title;
     }
+    if (this.series_override) {
+      result.series_override = this.series_override;
+    }
+    if (this.sort_index !== undefined) {
+      result.sort_index = this.sort_index;
+    }
+    if (this.series_cover) {
+      result.series_cover = true;
+    }
+    if (this.chapter_markers) {
+      result.chapter_markers = this.chapter_markers;
+    }
+    if (this.page_adjustments) {
+      result.page_adjustments = this.page_adjustments;
+    }
+    if (this.notes) {
+      result.notes = this.notes;
+    }
+    if (this.series_metadata) {
+      result.series_metadata = this.series_metadata;
+    }
+    if (this.catalog_updated && Object.keys(this.catalog_updated).length > 0) {
+      result.catalog_updated = this.catalog_updated;
+    }
 
     // Include deletion tracking timestamps if present (for sync)
     if (this.addedOn) {
@@ -278,6 +357,90 @@ export function updateVolumeSeriesTitle(volumeUuid: string, newSeriesTitle: stri
   });
 }
 
+type CatalogValues = Partial<Pick<VolumeDataJSON, CatalogField>>;
+
+/**
+ * Set catalog fields on volumes, stamping each field's own timestamp.
+ * Reading progress and its lastProgressUpdate are left alone.
+ */
+function updateCatalogFields(changes: Record<string, CatalogValues>) {
+  const now = new Date().toISOString();
+  _volumesInternal.update((prev) => {
+    const next = { ...prev };
+    for (const [volumeUuid, values] of Object.entries(changes)) {
+      const current = prev[volumeUuid] || new VolumeData();
+      next[volumeUuid] = new VolumeData({
+        ...current,
+        ...values,
+        catalog_updated: stampCatalogFields(
+          current.catalog_updated,
+          Object.keys(values) as CatalogField[],
+          now
+        )
+      });
+    }
+    return next;
+  });
+}
+
+/** Sets or clears the manual series grouping for a volume */
+export function updateVolumeSeriesOverride(volumeUuid: string, seriesOverride?: string) {
+  updateCatalogFields({
+    [volumeUuid]: { series_override: seriesOverride?.trim() || undefined }
+  });
+}
+
+/** Sets or clears the manual positions of volumes within their series */
+export function updateVolumeSortIndexes(sortIndexes: Record<string, number | undefined>) {
+  updateCatalogFields(
+    Object.fromEntries(
+      Object.entries(sortIndexes).map(([volumeUuid, sortIndex]) => [
+        volumeUuid,
+        { sort_index: sortIndex }
+      ])
+    )
+  );
+}
+
+/** Sets or clears the pinned series cover flag on volumes */
+export function updateVolumeSeriesCovers(seriesCovers: Record<string, boolean>) {
+  updateCatalogFields(
+    Object.fromEntries(
+      Object.entries(seriesCovers).map(([volumeUuid, isCover]) => [
+        volumeUuid,
+        { series_cover: isCover || undefined }
+      ])
+    )
+  );
+}
+
+/** Sets or clears a volume's chapter markers */
+export function updateVolumeChapterMarkers(volumeUuid: string, markers: ChapterMarker[]) {
+  updateCatalogFields({
+    [volumeUuid]: { chapter_markers: markers.length > 0 ? markers : undefined }
+  });
+}
+
+/** Sets or clears a volume's page rotation and trim */
+export function updateVolumePageAdjustments(
+  volumeUuid: string,
+  adjustments: Record<string, PageAdjustment> | undefined
+) {
+  updateCatalogFields({ [volumeUuid]: { page_adjustments: adjustments } });
+}
+
+/** Sets or clears a volume's notes */
+export function updateVolumeNotes(volumeUuid: string, notes: VolumeNote[]) {
+  updateCatalogFields({ [volumeUuid]: { notes: notes.length > 0 ? notes : undefined } });
+}
+
+/** Stores a series' metadata on each of its volumes */
+export function updateSeriesMetadata(volumeUuids: string[], metadata: SeriesMetadata) {
+  updateCatalogFields(
+    Object.fromEntries(volumeUuids.map((volumeUuid) => [volumeUuid, { series_metadata: metadata }]))
+  );
+}
+
 /**
  * Enriches ALL orphaned volumes (those lacking metadata) from the catalog
  * This is more aggressive than lazy enrichment and runs proactively
@@ -480,6 +643,8 @@ export function updateProgress(
       })
     };
   });
+
+  emitLibraryEvent('progress-updated', { volumeUuid: volume, page: progress, completed });
 }
 
 export function markVolumeAsComplete(volumeUuid: string, pageCount: number, totalChars?: number) {
@@ -490,6 +655,11 @@ export function markVolumeAsUnread(volumeUuid: string) {
   updateProgress(volumeUuid, 0, 0, false);
 }
 
+/** Back to page one for a reread; time read and reading history are kept */
+export function restartVolume(volumeUuid: string) {
+  updateProgress(volumeUuid, 1, 0, false);
+}
+
 export function startCount(volume: string) {
   // Guard against null/undefined/empty volume IDs
   if (!volume) {
@@ -588,7 +758,9 @@ export function updateVolumeSetting(volume: string, key: VolumeSettingsKey, valu
         ...currentVolume,
         settings: {
           ...currentVolume.settings,
-          [key]: value
+          [key]: value,
+          // Choosing a direction by hand replaces a detected one
+          ...(key === 'rightToLeft' && { autoDirection: undefined })
         }
       })
     };
@@ -597,6 +769,49 @@ export function updateVolumeSetting(volume: string, key: VolumeSettingsKey, valu
   // (rightToLeft/hasCover flow into its props).
 }
 
+/**
+ * Set a newly imported volume's reading direction without asking.
+ *
+ * A direction chosen by hand for this volume is never replaced, so
+ * re-importing keeps it. Otherwise a direction chosen for another volume of
+ * the series wins over `detected` (see detectReadingDirection). With
+ * neither, the volume keeps following the global default.
+ */
+export function applyDetectedDirection(
+  volumeUuid: string,
+  seriesUuid: string,
+  detected: boolean | undefined
+) {
+  _volumesInternal.update((prev) => {
+    const current = prev[volumeUuid];
+    const active = current && !current.deletedOn ? current : undefined;
+    if (typeof active?.settings.rightToLeft === 'boolean' && !active.settings.autoDirection) {
+      return prev;
+    }
+
+    const seriesChoice = Object.entries(prev).find(
+      ([uuid, vol]) =>
+        uuid !== volumeUuid &&
+        !vol.deletedOn &&
+        vol.series_uuid === seriesUuid &&
+        typeof vol.settings.rightToLeft === 'boolean' &&
+        !vol.settings.autoDirection
+    )?.[1].settings.rightToLeft;
+    const rightToLeft = seriesChoice ?? detected;
+    if (rightToLeft === undefined) return prev;
+
+    const base = active ?? new VolumeData({ addedOn: new Date().toISOString() });
+    return {
+      ...prev,
+      [volumeUuid]: new VolumeData({
+        ...base,
+        series_uuid: base.series_uuid ?? seriesUuid,
+        settings: { ...base.settings, rightToLeft, autoDirection: true }
+      })
+    };
+  });
+}
+
 export const totalStats = derived([volumes, globalSettings], ([$volumes, $settings]) => {
   if ($volumes) {
     const idleTimeoutMs = $settings.inactivityTimeoutMinutes * 60 * 1000;
@@ -19,8 +19,59 @@ export type Page = {
   img_path: string;
 };
 
+/**
+ * A chapter start within a volume. `page` is the 0-based page index;
+ * `img_path` pins the marker to its image so it follows the page if the
+ * volume's page order changes.
+ */
+export type ChapterMarker = {
+  page: number;
+  title: string;
+  img_path?: string;
+};
+
+/** Clockwise page rotation in degrees */
+export type PageRotation = 0 | 90 | 180 | 270;
+
+/** A crop rectangle in original image pixels */
+export type PageCrop = {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+};
+
+/** Display-time fix for one page: crop first, then rotate */
+export type PageAdjustment = {
+  rotation?: PageRotation;
+  crop?: PageCrop;
+};
+
+/**
+ * A reader's note on a volume, one of its pages, or a highlighted OCR text box.
+ * Page notes keep the page's `img_path` so they follow their page if the page
+ * order changes; highlights also keep the box's coordinates to find it again.
+ */
+export type VolumeNote = {
+  id: string;
+  text: string;
+  /** ISO datetime */
+  created: string;
+  /** ISO datetime of the last edit or removal; sync keeps the later copy of a note */
+  updated?: string;
+  /** Removed; kept without its text so the removal reaches other devices */
+  deleted?: boolean;
+  /** 0-based page index; absent for notes on the whole volume */
+  page?: number;
+  img_path?: string;
+  /** Highlighted text box as [xmin, ymin, xmax, ymax] */
+  box?: number[];
+  /** Text of the highlighted box */
+  quote?: string;
+};
+
 export interface VolumeMetadata {
-  mokuro_version: string; // Empty string '' indicates image-only volume without OCR
+  mokuro_version: string; // Detected .mokuro version; '' indicates image-only volume without OCR
   series_title: string;
   series_uuid: string;
   volume_title: string;
@@ -34,11 +85,18 @@ export interface VolumeMetadata {
   thumbnail?: File;
   thumbnail_width?: number;
   thumbnail_height?: number;
+  // THUMBNAIL_VERSION the thumbnail was generated with; 0 marks it for regeneration.
+  // Unset on thumbnails from before versioning, which count as 0
+  thumbnail_version?: number;
+  // Cover chosen by the user or taken from a sidecar; never regenerated
+  thumbnail_custom?: boolean;
 
   // Number of missing pages that were replaced with placeholders during import
   missing_pages?: number;
   // Paths of pages that were replaced with placeholders (for forced OCR visibility)
   missing_page_paths?: string[];
+  // Paths of duplicate pages skipped on import; their images stay in volume_files
+  skipped_page_paths?: string[];
 
   // Placeholder fields for cloud-only volumes (not yet downloaded locally)
   isPlaceholder?: boolean;
@@ -51,6 +109,8 @@ export interface VolumeMetadata {
   cloudPath?: string; // Full path for series extraction during download
   cloudThumbnailFileId?: string; // Provider-specific file ID for cloud thumbnail sidecar
   cloudThumbnailPath?: string; // Full path to the thumbnail sidecar (e.g. "Series/Volume.webp" or "Series/Volume.jpg")
+  cloudMokuroFileId?: string; // Provider-specific file ID for the .mokuro/.mokuro.gz sidecar
+  cloudMokuroPath?: string; // Full path to the .mokuro sidecar (page count before download)
 
   // Legacy Drive-specific fields (kept for backward compatibility)
   // When present without cloudProvider, assumed to be google-drive
@@ -60,6 +120,64 @@ export interface VolumeMetadata {
 
   // Spine width in pixels (from mokuro metadata, used for catalog stacking)
   spine_width?: number;
+
+  // Where the volume was imported from: the archive's file name (or the folder
+  // name for directory imports) and its path as picked, dropped or stored in
+  // the cloud. Downloads also record the cloud file they came from.
+  original_filename?: string;
+  source_path?: string;
+  source_provider?: ProviderType;
+  source_file_id?: string;
+
+  // Sync tracking (ISO timestamps): when the pages or text were last replaced
+  // on this device, and when this device last uploaded or downloaded the archive
+  content_modified_at?: string;
+  cloud_synced_at?: string;
+
+  // Manual catalog grouping: when set, the volume is grouped under this series
+  // title instead of series_title. Doesn't rename anything locally or in the cloud.
+  series_override?: string;
+
+  // Manual position within its series, set when the user drag-reorders volumes.
+  // Volumes without one sort after ordered ones, by title.
+  sort_index?: number;
+
+  // Pinned as its series' cover in the catalog instead of the first unread volume.
+  // The volume's own cover is changed in the volume editor.
+  series_cover?: boolean;
+
+  // Chapter starts within the volume, for chapter navigation in the reader
+  chapter_markers?: ChapterMarker[];
+
+  // Rotation and trim of individual pages, keyed by image path
+  page_adjustments?: Record<string, PageAdjustment>;
+
+  // Notes and highlights on the volume and its pages
+  notes?: VolumeNote[];
+}
+
+export type SeriesMetadataField =
+  | 'author'
+  | 'description'
+  | 'genres'
+  | 'anilist_id'
+  | 'mangadex_id';
+
+// Series details (author, synopsis, external IDs). Kept on every volume's synced
+// VolumeData so they travel with volume-data.json; copies merge field by field.
+export interface SeriesMetadata {
+  author?: string;
+  description?: string;
+  genres?: string[];
+  anilist_id?: number;
+  mangadex_id?: string;
+  // Fields edited by hand; fetching from an external source never overwrites them
+  manual_fields?: SeriesMetadataField[];
+  // ISO timestamp of the last external fetch, so it runs once per series
+  fetched_at?: string;
+  // When each field last changed, for merging copies edited on different devices
+  field_updated?: Partial<Record<SeriesMetadataField, string>>;
+  updated_at: string;
 }
 
 // v3 table: volume_ocr
@@ -0,0 +1,97 @@
+import { describe, it, expect, vi, beforeEach, afterEach } from 'vitest';
+
+vi.mock('$app/environment', () => ({ browser: true }));
+
+vi.mock('./sync/unified-cloud-manager', () => ({
+  unifiedCloudManager: {
+    getActiveProvider: () => null,
+    syncProgress: vi.fn(() => Promise.resolve())
+  }
+}));
+
+vi.mock('./sync/auto-sync', () => ({
+  isAutoSyncEnabled: () => false
+}));
+
+import { activityTracker, HEARTBEAT_MS } from './activity-tracker';
+
+const SECOND = 1000;
+const MINUTE = 60 * SECOND;
+
+describe('activityTracker', () => {
+  const onActive = vi.fn();
+  const onInactive = vi.fn();
+  let stopListening: () => void;
+
+  function isActive(): boolean {
+    let value = false;
+    activityTracker.active.subscribe((active) => (value = active))();
+    return value;
+  }
+
+  function setVisibility(state: DocumentVisibilityState) {
+    Object.defineProperty(document, 'visibilityState', { value: state, configurable: true });
+  }
+
+  beforeEach(() => {
+    vi.useFakeTimers();
+    setVisibility('visible');
+    activityTracker.initialize({ onActive, onInactive });
+    activityTracker.setTimeoutDuration(2);
+    stopListening = activityTracker.listen();
+    activityTracker.recordActivity();
+  });
+
+  afterEach(() => {
+    stopListening();
+    activityTracker.destroy();
+    vi.useRealTimers();
+    vi.clearAllMocks();
+  });
+
+  it('keeps counting while someone stares at one page within the idle timeout', () => {
+    vi.advanceTimersByTime(90 * SECOND);
+    expect(isActive()).toBe(true);
+  });
+
+  it('pauses after the idle timeout with no interaction', () => {
+    vi.advanceTimersByTime(3 * MINUTE);
+    expect(isActive()).toBe(false);
+    expect(onInactive).toHaveBeenCalled();
+  });
+
+  it('treats zoom and text selection as reading', () => {
+    vi.advanceTimersByTime(90 * SECOND);
+    window.dispatchEvent(new Event('wheel'));
+    vi.advanceTimersByTime(90 * SECOND);
+    document.dispatchEvent(new Event('selectionchange'));
+    vi.advanceTimersByTime(90 * SECOND);
+    expect(isActive()).toBe(true);
+  });
+
+  it('resumes on interaction after going idle', () => {
+    vi.advanceTimersByTime(3 * MINUTE);
+    window.dispatchEvent(new Event('pointerdown'));
+    expect(isActive()).toBe(true);
+  });
+
+  it('does not resume a timer the user paused', () => {
+    activityTracker.stop();
+    window.dispatchEvent(new Event('pointerdown'));
+    expect(isActive()).toBe(false);
+  });
+
+  it('pauses on the heartbeat once the reader is hidden', () => {
+    setVisibility('hidden');
+    vi.advanceTimersByTime(HEARTBEAT_MS);
+    expect(isActive()).toBe(false);
+  });
+
+  it('stops listening when asked', () => {
+    stopListening();
+    vi.advanceTimersByTime(3 * MINUTE);
+    window.dispatchEvent(new Event('pointerdown'));
+    expect(isActive()).toBe(false);
+    stopListening = activityTracker.listen();
+  });
+});
@@ -1,12 +1,32 @@
 import { browser } from '$app/environment';
 import { writable } from 'svelte/store';
 import { unifiedCloudManager } from './sync/unified-cloud-manager';
+import { isAutoSyncEnabled } from './sync/auto-sync';
 
 type ActivityCallback = {
   onActive: () => void;
   onInactive: () => void;
 };
 
+/**
+ * Input that shows someone is at the reader even without turning pages:
+ * taps and clicks, zoom (wheel, pinch), keys, and mouse movement. Text
+ * selection is watched separately through `selectionchange`.
+ */
+const INTERACTION_EVENTS = [
+  'pointerdown',
+  'pointermove',
+  'wheel',
+  'keydown',
+  'touchstart'
+] as const;
+
+/** Interactions closer together than this only count once */
+const INTERACTION_THROTTLE_MS = 1000;
+
+/** How often the heartbeat checks that the reader is still on screen */
+export const HEARTBEAT_MS = 15 * 1000;
+
 class ActivityTracker {
   private timeoutId: number | null = null;
   private isActive = writable(false);
@@ -19,6 +39,11 @@ class ActivityTracker {
   private readonly SYNC_DELAY_MS = 5000; // 5 seconds
   private hasPendingProgress = false;
 
+  private heartbeatId: number | null = null;
+  private lastInteraction = 0;
+  // Set by stop(): interactions don't resume a timer the user paused
+  private stopped = false;
+
   constructor() {
     if (browser) {
       // Subscribe to activity state changes
@@ -41,7 +66,7 @@ class ActivityTracker {
     this.timeoutDuration = minutes * 60 * 1000;
     // Restart the timer with new duration if currently active
     if (this.timeoutId !== null) {
-      this.recordActivity();
+      this.keepActive();
     }
   }
 
@@ -56,6 +81,74 @@ class ActivityTracker {
    * Record user activity (page turn)
    */
   recordActivity() {
+    this.stopped = false;
+    this.keepActive();
+
+    // Reset sync timer (independent of activity timer)
+    this.resetSyncTimer();
+    this.hasPendingProgress = true;
+  }
+
+  /**
+   * Record an interaction that isn't a page turn (zoom, selection, taps).
+   * Keeps the timer running for slow readers without queueing a progress sync.
+   */
+  recordInteraction() {
+    if (this.stopped) return;
+    const now = Date.now();
+    if (this.currentActiveState && now - this.lastInteraction < INTERACTION_THROTTLE_MS) return;
+    this.lastInteraction = now;
+    this.keepActive();
+  }
+
+  /**
+   * Watch the reader for interactions and start the heartbeat.
+   * Returns a function that stops listening.
+   */
+  listen(): () => void {
+    if (!browser) return () => {};
+
+    const onInteraction = () => this.recordInteraction();
+    const onVisibilityChange = () => {
+      if (document.visibilityState === 'visible') {
+        this.recordInteraction();
+      } else if (this.currentActiveState) {
+        this.pause();
+      }
+    };
+
+    for (const type of INTERACTION_EVENTS) {
+      window.addEventListener(type, onInteraction, { passive: true });
+    }
+    document.addEventListener('selectionchange', onInteraction);
+    document.addEventListener('visibilitychange', onVisibilityChange);
+
+    // Backgrounded tabs don't always get visibilitychange (e.g. the device
+    // sleeping), so also check on a heartbeat: a hidden reader stops accruing
+    // time straight away instead of running out the whole idle timeout.
+    this.heartbeatId = window.setInterval(() => {
+      if (document.visibilityState === 'hidden' && this.currentActiveState) {
+        this.pause();
+      }
+    }, HEARTBEAT_MS);
+
+    return () => {
+      for (const type of INTERACTION_EVENTS) {
+        window.removeEventListener(type, onInteraction);
+      }
+      document.removeEventListener('selectionchange', onInteraction);
+      document.removeEventListener('visibilitychange', onVisibilityChange);
+      if (this.heartbeatId !== null) {
+        clearInterval(this.heartbeatId);
+        this.heartbeatId = null;
+      }
+    };
+  }
+
+  /**
+   * Mark the user active and restart the inactivity countdown
+   */
+  private keepActive() {
     // Clear existing timeout
     if (this.timeoutId !== null) {
       clearTimeout(this.timeoutId);
@@ -70,10 +163,6 @@ class ActivityTracker {
     this.timeoutId = window.setTimeout(() => {
       this.handleInactivity();
     }, this.timeoutDuration);
-
-    // Reset sync timer (independent of activity timer)
-    this.resetSyncTimer();
-    this.hasPendingProgress = true;
   }
 
   /**
@@ -81,11 +170,20 @@ class ActivityTracker {
    */
   private handleInactivity() {
     console.log('User inactive for', this.timeoutDuration / 60000, 'minutes');
+    this.pause();
+  }
 
+  /**
+   * Go inactive now, e.g. when the reader is hidden
+   */
+  private pause() {
     // Set inactive state (will trigger timer stop via callback)
     this.isActive.set(false);
 
     // Clear timeout
+    if (this.timeoutId !== null) {
+      clearTimeout(this.timeoutId);
+    }
     this.timeoutId = null;
   }
 
@@ -109,7 +207,7 @@ class ActivityTracker {
     if (!this.hasPendingProgress) return;
 
     const hasActiveProvider = unifiedCloudManager.getActiveProvider() !== null;
-    if (hasActiveProvider) {
+    if (hasActiveProvider && isAutoSyncEnabled()) {
       console.log('Auto-syncing after 5s inactivity...');
       unifiedCloudManager.syncProgress({ silent: true }).catch((error) => {
         console.error('Auto-sync failed:', error);
@@ -125,6 +223,7 @@ class ActivityTracker {
    * NOTE: Does NOT clear sync timer - sync will still fire if pending
    */
   stop() {
+    this.stopped = true;
     if (this.timeoutId !== null) {
       clearTimeout(this.timeoutId);
       this.timeoutId = null;
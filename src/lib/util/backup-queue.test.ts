@@ -0,0 +1,168 @@
+import { beforeEach, describe, expect, it, vi } from 'vitest';
+import { get } from 'svelte/store';
+import type { VolumeMetadata } from '$lib/types';
+import type { WorkerTask } from './worker-pool';
+
+const { tasks, pool, provider, markSessionExpired } = vi.hoisted(() => {
+  const tasks: WorkerTask[] = [];
+  return {
+    tasks,
+    pool: {
+      maxConcurrentWorkers: 4,
+      addTask: (task: WorkerTask) => tasks.push(task),
+      cancelTask: vi.fn(() => true)
+    },
+    provider: {
+      type: 'google-drive',
+      name: 'Google Drive',
+      uploadConcurrencyLimit: 4,
+      supportsWorkerUpload: true
+    },
+    markSessionExpired: vi.fn()
+  };
+});
+
+vi.mock('./file-processing-pool', () => ({
+  getFileProcessingPool: vi.fn(async () => pool),
+  incrementPoolUsers: vi.fn(),
+  decrementPoolUsers: vi.fn()
+}));
+
+vi.mock('./sync/unified-cloud-manager', () => ({
+  unifiedCloudManager: {
+    getDefaultProvider: () => provider,
+    getActiveProvider: () => provider,
+    fetchAllCloudVolumes: vi.fn(async () => {})
+  }
+}));
+
+vi.mock('./sync/cache-manager', () => ({ cacheManager: { getCache: () => null } }));
+vi.mock('$lib/catalog/sync-status', () => ({ markVolumeSynced: vi.fn(async () => {}) }));
+vi.mock('./volume-sidecars', () => ({ downloadFileBlob: vi.fn() }));
+vi.mock('./sync/providers/google-drive/token-manager', () => ({
+  tokenManager: { markSessionExpired }
+}));
+
+import { backupQueue, retryFailedBackups } from './backup-queue';
+import { setBackupUiBridge } from './backup-ui';
+import { backupReport, formatTransferSummary, SIGN_IN_EXPIRED_REASON } from './transfer-report';
+
+const notify = vi.fn();
+
+function createVolume(index: number): VolumeMetadata {
+  return {
+    mokuro_version: '0.2.0',
+    series_title: 'Series',
+    series_uuid: 'series-uuid',
+    volume_title: `Volume ${String(index).padStart(2, '0')}`,
+    volume_uuid: `vol-${index}`,
+    page_count: 10,
+    character_count: 0,
+    page_char_counts: []
+  };
+}
+
+async function queueVolumes(count: number): Promise<VolumeMetadata[]> {
+  const volumes = Array.from({ length: count }, (_, i) => createVolume(i + 1));
+  backupQueue.queueSeriesVolumesForBackup(volumes);
+  await vi.waitFor(() => expect(tasks).toHaveLength(count));
+  return volumes;
+}
+
+async function succeed(task: WorkerTask) {
+  await task.onComplete!({ type: 'complete', fileId: `file-${task.id}`, size: 1 }, () => {});
+}
+
+async function fail(task: WorkerTask, error: string, status?: number) {
+  await task.onError!({ type: 'error', error, status });
+}
+
+function summary(): string {
+  return formatTransferSummary(get(backupReport.results), 'backed up');
+}
+
+describe('backup results', () => {
+  beforeEach(() => {
+    tasks.length = 0;
+    vi.clearAllMocks();
+    backupReport.clear();
+    setBackupUiBridge({
+      addProgress: vi.fn(),
+      updateProgress: vi.fn(),
+      removeProgress: vi.fn(),
+      notify
+    });
+  });
+
+  it('reports exactly the volumes that failed and retries only them', async () => {
+    await queueVolumes(20);
+
+    for (const task of tasks) {
+      if (task.id === 'vol-4') await fail(task, 'Upload failed: 500 Internal Server Error', 500);
+      else if (task.id === 'vol-17') await fail(task, 'Upload init failed: 403 Forbidden', 403);
+      else await succeed(task);
+    }
+
+    expect(get(backupQueue)).toEqual([]);
+    expect(summary()).toBe('18 backed up · 2 failed');
+    expect(
+      get(backupReport.retryable).map(({ volumeUuid, outcome, reason }) => ({
+        volumeUuid,
+        outcome,
+        reason
+      }))
+    ).toEqual([
+      {
+        volumeUuid: 'vol-4',
+        outcome: 'failed',
+        reason: 'Upload failed: 500 Internal Server Error'
+      },
+      { volumeUuid: 'vol-17', outcome: 'failed', reason: 'Upload init failed: 403 Forbidden' }
+    ]);
+
+    tasks.length = 0;
+    retryFailedBackups();
+    await vi.waitFor(() => expect(tasks).toHaveLength(2));
+    expect(tasks.map((task) => task.id).sort()).toEqual(['vol-17', 'vol-4']);
+
+    for (const task of tasks) await succeed(task);
+    expect(get(backupReport.retryable)).toEqual([]);
+    expect(summary()).toBe('20 backed up');
+  });
+
+  it('stops the batch on an auth failure instead of failing every volume', async () => {
+    await queueVolumes(20);
+
+    await succeed(tasks[0]);
+    await fail(tasks[1], 'Upload init failed: 401 Unauthorized', 401);
+    // Already in flight when the session expired
+    await fail(tasks[2], 'Upload init failed: 401 Unauthorized', 401);
+
+    expect(get(backupQueue)).toEqual([]);
+    expect(summary()).toBe('1 backed up · 1 failed · 18 skipped');
+    const skipped = get(backupReport.retryable).filter((result) => result.outcome === 'skipped');
+    expect(skipped.every((result) => result.reason === SIGN_IN_EXPIRED_REASON)).toBe(true);
+
+    await vi.waitFor(() => expect(pool.cancelTask).toHaveBeenCalledTimes(18));
+    expect(markSessionExpired).toHaveBeenCalledTimes(1);
+    expect(notify).toHaveBeenCalledTimes(2);
+    expect(notify).toHaveBeenLastCalledWith(
+      'Drive sign-in expired. Reconnect, then retry the failed backups.'
+    );
+
+    tasks.length = 0;
+    retryFailedBackups();
+    await vi.waitFor(() => expect(tasks).toHaveLength(19));
+  });
+
+  it('goes by the HTTP status, not the message, to detect an expired sign-in', async () => {
+    await queueVolumes(3);
+
+    await fail(tasks[0], 'Upload failed: file name contains "Unauthorized"', 400);
+
+    await succeed(tasks[1]);
+    await succeed(tasks[2]);
+    expect(summary()).toBe('2 backed up · 1 failed');
+    expect(markSessionExpired).not.toHaveBeenCalled();
+  });
+});
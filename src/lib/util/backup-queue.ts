@@ -3,14 +3,26 @@ import type { VolumeMetadata } from '$lib/types';
 import type { WorkerTask } from './worker-pool';
 import { getBackupUiBridge } from './backup-ui';
 import { unifiedCloudManager } from './sync/unified-cloud-manager';
-import type { BackupProviderType, SyncProvider } from './sync/provider-interface';
-import { isPseudoProvider, exportProvider } from './sync/provider-interface';
+import type { BackupProviderType, ProviderType, SyncProvider } from './sync/provider-interface';
+import {
+  ProviderError,
+  isPseudoProvider,
+  isRealProvider,
+  exportProvider
+} from './sync/provider-interface';
+import { PROVIDER_SHORT_LABELS } from './sync/provider-display';
 import {
   getFileProcessingPool,
   incrementPoolUsers,
   decrementPoolUsers
 } from './file-processing-pool';
 import { downloadFileBlob } from './volume-sidecars';
+import {
+  SIGN_IN_EXPIRED_REASON,
+  backupReport,
+  isAuthFailure,
+  type TransferOutcome
+} from './transfer-report';
 
 export interface SidecarOptions {
   includeSidecars: boolean;
@@ -69,6 +81,10 @@ let queueLock = Promise.resolve();
 // Maps "provider:seriesTitle" -> Promise that resolves when target is guaranteed to exist
 const seriesFolderLocks = new Map<string, Promise<Record<string, any> | void>>();
 
+// Providers whose session expired during the current batch. Further failures
+// to them are recorded as skipped; queueing a backup to one clears it.
+const authBlockedProviders = new Set<ProviderType>();
+
 // Subscribe to queue changes and update progress tracker
 queueStore.subscribe((queue) => {
   const totalCount = queue.length;
@@ -102,6 +118,13 @@ export function queueVolumeForBackup(
   }
 
   const queue = get(queueStore);
+  authBlockedProviders.delete(targetProvider.type);
+
+  // A new batch after a clean one starts a fresh report
+  const idle = !queue.some((item) => isRealProvider(item.provider));
+  if (get(backupReport.retryable).length === 0 && idle) {
+    backupReport.clear();
+  }
 
   // Check for duplicates by volumeUuid:provider (allows same volume to be queued for different providers)
   const isDuplicate = queue.some(
@@ -274,15 +297,91 @@ async function getUploadWorkerCredentials(
 }
 
 /**
- * Handle backup errors consistently
+ * Queue the volumes from the backup report that failed or were skipped
  */
-function handleBackupError(item: BackupQueueItem, processId: string, errorMessage: string): void {
-  getBackupUiBridge().updateProgress(processId, `Error: ${errorMessage}`, 0);
-  getBackupUiBridge().notify(`Failed to backup ${item.volumeTitle}: ${errorMessage}`);
+export function retryFailedBackups(): void {
+  // Entries stay until the retried backups replace them
+  queueSeriesVolumesForBackup(get(backupReport.retryable).map((result) => result.volume));
+}
+
+/** Cloud backups only; exports are local downloads */
+function recordResult(item: BackupQueueItem, outcome: TransferOutcome, reason?: string): void {
+  if (!isRealProvider(item.provider)) return;
+  backupReport.record({
+    volumeUuid: item.volumeUuid,
+    seriesTitle: item.seriesTitle,
+    volumeTitle: item.volumeTitle,
+    provider: item.provider,
+    outcome,
+    reason,
+    volume: item.volumeMetadata
+  });
+}
+
+/**
+ * Stop the rest of a batch after an auth failure. Every other upload to the
+ * provider would fail the same way, so they're recorded as skipped (ready for
+ * "retry failed") instead of each reporting its own error.
+ */
+async function haltBackupsForProvider(provider: ProviderType): Promise<void> {
+  authBlockedProviders.add(provider);
+
+  const remaining = get(queueStore).filter((item) => item.provider === provider);
+  queueStore.update((q) => q.filter((item) => item.provider !== provider));
+  for (const item of remaining) {
+    recordResult(item, 'skipped', SIGN_IN_EXPIRED_REASON);
+    getBackupUiBridge().removeProgress(`backup-${item.volumeUuid}`);
+  }
+
+  getBackupUiBridge().notify(
+    `${PROVIDER_SHORT_LABELS[provider]} sign-in expired. Reconnect, then retry the failed backups.`
+  );
+
+  if (provider === 'google-drive') {
+    const { tokenManager } = await import('./sync/providers/google-drive/token-manager');
+    tokenManager.markSessionExpired();
+  }
+
+  if (remaining.length > 0) {
+    const pool = await getFileProcessingPool();
+    for (const item of remaining) {
+      pool.cancelTask(item.volumeUuid);
+    }
+  }
+}
+
+/**
+ * Handle backup errors consistently. `authFailed` means the provider rejected
+ * the session, which stops the rest of the batch.
+ */
+function handleBackupError(
+  item: BackupQueueItem,
+  processId: string,
+  errorMessage: string,
+  authFailed = false
+): void {
   queueStore.update((q) =>
     q.filter((i) => !(i.volumeUuid === item.volumeUuid && i.provider === item.provider))
   );
+
+  // Caught up in a session that already expired and was reported
+  if (isRealProvider(item.provider) && authBlockedProviders.has(item.provider)) {
+    recordResult(item, 'skipped', SIGN_IN_EXPIRED_REASON);
+    getBackupUiBridge().removeProgress(processId);
+    return;
+  }
+
+  getBackupUiBridge().updateProgress(processId, `Error: ${errorMessage}`, 0);
+  recordResult(item, 'failed', errorMessage);
   setTimeout(() => getBackupUiBridge().removeProgress(processId), 3000);
+
+  if (isRealProvider(item.provider) && authFailed) {
+    haltBackupsForProvider(item.provider).catch((error) => {
+      console.error('Failed to stop backups after sign-in expired:', error);
+    });
+    return;
+  }
+  getBackupUiBridge().notify(`Failed to backup ${item.volumeTitle}: ${errorMessage}`);
 }
 
 /**
@@ -302,6 +401,17 @@ async function checkAndTerminatePool(): Promise<void> {
   }
 }
 
+/** Mark the local copy as matching the archive just uploaded */
+async function recordBackupSynced(volumeUuid: string): Promise<void> {
+  try {
+    const { markVolumeSynced } = await import('$lib/catalog/sync-status');
+    await markVolumeSynced(volumeUuid);
+  } catch (error) {
+    // Only the catalog badge depends on this; the backup itself succeeded
+    console.warn('Failed to record sync time:', error);
+  }
+}
+
 /**
  * Process backup/export using workers for all providers (including pseudo-providers)
  * Data loading is deferred until worker is ready to prevent memory pressure
@@ -473,6 +583,8 @@ async function processBackup(item: BackupQueueItem, processId: string): Promise<
               });
             }
 
+            await recordBackupSynced(item.volumeUuid);
+            recordResult(item, 'success');
             getBackupUiBridge().updateProgress(processId, 'Backup complete', 100);
             getBackupUiBridge().notify(`Backed up ${item.volumeTitle} successfully`);
             queueStore.update((q) =>
@@ -543,6 +655,8 @@ async function processBackup(item: BackupQueueItem, processId: string): Promise<
 
           const archivePath = `${item.seriesTitle}/${item.volumeTitle}.cbz`;
           addToCache(archivePath, uploadedFileId, data.size || 0);
+          await recordBackupSynced(item.volumeUuid);
+          recordResult(item, 'success');
 
           getBackupUiBridge().updateProgress(processId, 'Backup complete', 100);
           getBackupUiBridge().notify(`Backed up ${item.volumeTitle} successfully`);
@@ -573,7 +687,7 @@ async function processBackup(item: BackupQueueItem, processId: string): Promise<
       },
       onError: async (data) => {
         console.error(`Error backing up ${item.volumeTitle}:`, data.error);
-        handleBackupError(item, processId, data.error);
+        handleBackupError(item, processId, data.error, isAuthFailure(data.status));
         await checkAndTerminatePool();
       }
     };
@@ -581,7 +695,12 @@ async function processBackup(item: BackupQueueItem, processId: string): Promise<
     pool.addTask(task);
   } catch (error) {
     console.error(`Failed to prepare backup for ${item.volumeTitle}:`, error);
-    handleBackupError(item, processId, error instanceof Error ? error.message : 'Unknown error');
+    handleBackupError(
+      item,
+      processId,
+      error instanceof Error ? error.message : 'Unknown error',
+      error instanceof ProviderError && error.isAuthError
+    );
     await checkAndTerminatePool();
   }
 }
@@ -668,6 +787,7 @@ export const backupQueue = {
   queueVolumeForBackup,
   queueVolumeForExport,
   queueSeriesVolumesForBackup,
+  retryFailedBackups,
   isVolumeInBackupQueue,
   getSeriesBackupQueueStatus
 };
@@ -0,0 +1,166 @@
+import { describe, it, expect, beforeEach } from 'vitest';
+import 'fake-indexeddb/auto';
+import { get } from 'svelte/store';
+import type { Page, VolumeMetadata } from '$lib/types';
+import { db } from '$lib/catalog/db';
+import {
+  volumes as volumeDataStore,
+  volumesWithTrash,
+  VolumeData
+} from '$lib/settings/volume-data';
+import {
+  chapterAt,
+  createChapterMarker,
+  detectChapterMarkers,
+  nextChapterPage,
+  previousChapterPage,
+  reconcileChapterMarkers,
+  resolveChapterMarkers,
+  setChapterMarkers
+} from './chapter-markers';
+
+function createPage(index: number, blocks: Page['blocks'] = []): Page {
+  return {
+    version: '0.2.0',
+    img_width: 800,
+    img_height: 1200,
+    img_path: `${String(index + 1).padStart(3, '0')}.jpg`,
+    blocks
+  };
+}
+
+function speechBubble(text: string) {
+  return { box: [0, 0, 10, 10], vertical: true, font_size: 20, lines: [text] };
+}
+
+function titleBlock(text: string) {
+  return { box: [0, 0, 100, 100], vertical: true, font_size: 60, lines: [text] };
+}
+
+function createVolume(uuid: string): VolumeMetadata {
+  return {
+    mokuro_version: '0.2.0',
+    series_title: 'Series',
+    series_uuid: 'series-uuid',
+    volume_title: `Series ${uuid}`,
+    volume_uuid: uuid,
+    page_count: 30,
+    character_count: 0,
+    page_char_counts: []
+  };
+}
+
+const pages = Array.from({ length: 30 }, (_, i) =>
+  createPage(i, [speechBubble('セリフです'), speechBubble('もう一つ')])
+);
+
+describe('chapter navigation', () => {
+  const markers = [
+    createChapterMarker(pages, 2, 'Chapter 1'),
+    createChapterMarker(pages, 12, 'Chapter 2'),
+    createChapterMarker(pages, 22, 'Chapter 3')
+  ];
+
+  it('jumps to the marked pages going forward', () => {
+    let index = 0;
+    const visited: number[] = [];
+    for (let target = nextChapterPage(markers, index); target !== undefined; ) {
+      visited.push(target);
+      index = target;
+      target = nextChapterPage(markers, index);
+    }
+
+    expect(visited).toEqual([2, 12, 22]);
+  });
+
+  it('goes back to the current chapter start, then the previous one', () => {
+    expect(previousChapterPage(markers, 15)).toBe(12);
+    expect(previousChapterPage(markers, 12)).toBe(2);
+    expect(previousChapterPage(markers, 2)).toBeUndefined();
+  });
+
+  it('finds the chapter containing a page', () => {
+    expect(chapterAt(markers, 1)).toBeUndefined();
+    expect(chapterAt(markers, 12)?.title).toBe('Chapter 2');
+    expect(chapterAt(markers, 29)?.title).toBe('Chapter 3');
+  });
+});
+
+describe('resolveChapterMarkers', () => {
+  it('follows marked images when the page order changes', () => {
+    const markers = [createChapterMarker(pages, 12, 'Chapter 2')];
+    // Two pages moved in front of the marked page
+    const remapped = [pages[0], pages[28], pages[29], ...pages.slice(1, 28)];
+
+    expect(resolveChapterMarkers(markers, remapped).map((m) => m.page)).toEqual([14]);
+  });
+
+  it('keeps the stored index for markers without a matching image', () => {
+    const markers = [{ page: 5, title: 'Legacy' }, { page: 40, title: 'Out of range' }];
+
+    expect(resolveChapterMarkers(markers, pages)).toEqual([{ page: 5, title: 'Legacy' }]);
+  });
+
+  it('sorts markers and keeps one per page', () => {
+    const markers = [
+      createChapterMarker(pages, 20, 'B'),
+      createChapterMarker(pages, 4, 'A'),
+      createChapterMarker(pages, 20, 'B again')
+    ];
+
+    expect(resolveChapterMarkers(markers, pages).map((m) => m.title)).toEqual(['A', 'B']);
+  });
+});
+
+describe('detectChapterMarkers', () => {
+  it('marks sparse pages with large title text', () => {
+    const volume = pages.map((page, i) =>
+      i === 3 || i === 17 ? { ...page, blocks: [titleBlock(`第${i}話`)] } : page
+    );
+
+    expect(detectChapterMarkers(volume)).toEqual([
+      { page: 3, title: '第3話', img_path: '004.jpg' },
+      { page: 17, title: '第17話', img_path: '018.jpg' }
+    ]);
+  });
+
+  it('ignores the cover, blank pages and ordinary short dialogue', () => {
+    const volume = pages.map((page, i) => {
+      if (i === 0) return { ...page, blocks: [titleBlock('タイトル')] };
+      if (i === 5) return { ...page, blocks: [] };
+      if (i === 6) return { ...page, blocks: [speechBubble('え')] };
+      return page;
+    });
+
+    expect(detectChapterMarkers(volume)).toEqual([]);
+  });
+});
+
+describe('chapter marker storage', () => {
+  beforeEach(async () => {
+    await db.volumes.clear();
+    volumesWithTrash.set({});
+  });
+
+  it('saves markers on the volume and in synced volume data', async () => {
+    await db.volumes.add(createVolume('v1'));
+    const markers = [createChapterMarker(pages, 12, 'Chapter 2')];
+
+    await setChapterMarkers('v1', markers);
+
+    expect((await db.volumes.get('v1'))?.chapter_markers).toEqual(markers);
+    expect(get(volumeDataStore)['v1'].toJSON().chapter_markers).toEqual(markers);
+  });
+
+  it('applies markers synced from another device', async () => {
+    await db.volumes.add(createVolume('v1'));
+    const markers = [createChapterMarker(pages, 2, 'Chapter 1')];
+
+    const changed = await reconcileChapterMarkers({
+      v1: new VolumeData({ chapter_markers: markers })
+    });
+
+    expect(changed).toBe(1);
+    expect((await db.volumes.get('v1'))?.chapter_markers).toEqual(markers);
+  });
+});
@@ -0,0 +1,160 @@
+/**
+ * Chapter markers.
+ *
+ * A long volume can be split into chapters so the reader can jump between
+ * them. Markers are set by hand or detected from title pages, and live on
+ * the volume row and in volume-data (which syncs).
+ *
+ * Each marker keeps the image path of its page, so markers follow their page
+ * when the page order changes (re-import, sort mode change, skipped pages).
+ */
+
+import { db } from '$lib/catalog/db';
+import { updateVolumeChapterMarkers, type VolumeData } from '$lib/settings/volume-data';
+import type { ChapterMarker, Page, VolumeMetadata } from '$lib/types';
+
+/** Title pages carry little text... */
+const TITLE_PAGE_MAX_CHARS = 24;
+const TITLE_PAGE_MAX_BLOCKS = 3;
+/** ...set well above the volume's usual font size */
+const TITLE_FONT_RATIO = 1.8;
+/** Detected chapters closer together than this are treated as one */
+const MIN_CHAPTER_PAGES = 4;
+const MAX_TITLE_LENGTH = 40;
+
+type MarkerPage = Pick<Page, 'img_path'>;
+
+/** A marker for the page at `index` */
+export function createChapterMarker(
+  pages: MarkerPage[],
+  index: number,
+  title: string
+): ChapterMarker {
+  return { page: index, title: title.trim(), img_path: pages[index]?.img_path };
+}
+
+/**
+ * Markers mapped onto the volume's current page order, sorted, one per page.
+ * Markers whose image is gone keep their stored index; out-of-range ones are dropped.
+ */
+export function resolveChapterMarkers(
+  markers: ChapterMarker[] | undefined,
+  pages: MarkerPage[]
+): ChapterMarker[] {
+  if (!markers?.length) return [];
+  const indexByPath = new Map(pages.map((page, index) => [page.img_path, index]));
+
+  const byPage = new Map<number, ChapterMarker>();
+  for (const marker of markers) {
+    const remapped = marker.img_path ? indexByPath.get(marker.img_path) : undefined;
+    const page = remapped ?? marker.page;
+    if (page < 0 || page >= pages.length || byPage.has(page)) continue;
+    byPage.set(page, { ...marker, page });
+  }
+  return [...byPage.values()].sort((a, b) => a.page - b.page);
+}
+
+/** The chapter containing page `index`, if any */
+export function chapterAt(markers: ChapterMarker[], index: number): ChapterMarker | undefined {
+  let current: ChapterMarker | undefined;
+  for (const marker of markers) {
+    if (marker.page > index) break;
+    current = marker;
+  }
+  return current;
+}
+
+/** Index of the next chapter's first page after `index` */
+export function nextChapterPage(markers: ChapterMarker[], index: number): number | undefined {
+  return markers.find((marker) => marker.page > index)?.page;
+}
+
+/**
+ * Index of the chapter start before `index`: the current chapter's start from
+ * mid-chapter, or the previous chapter's from its first page
+ */
+export function previousChapterPage(markers: ChapterMarker[], index: number): number | undefined {
+  let previous: number | undefined;
+  for (const marker of markers) {
+    if (marker.page >= index) break;
+    previous = marker.page;
+  }
+  return previous;
+}
+
+function pageText(page: Pick<Page, 'blocks'>): string {
+  return page.blocks.map((block) => block.lines.join('')).join('');
+}
+
+function median(values: number[]): number {
+  const sorted = [...values].sort((a, b) => a - b);
+  return sorted[Math.floor(sorted.length / 2)] ?? 0;
+}
+
+/**
+ * Guess chapter starts from the OCR: mostly blank pages whose few words are
+ * set in large type (chapter title pages). The cover is never a chapter start.
+ */
+export function detectChapterMarkers(
+  pages: Pick<Page, 'img_path' | 'blocks'>[]
+): ChapterMarker[] {
+  const fontSizes = pages.flatMap((page) => page.blocks.map((block) => block.font_size));
+  if (fontSizes.length === 0) return [];
+  const titleFontSize = median(fontSizes) * TITLE_FONT_RATIO;
+
+  const markers: ChapterMarker[] = [];
+  for (const [index, page] of pages.entries()) {
+    if (index === 0 || page.blocks.length === 0) continue;
+    if (page.blocks.length > TITLE_PAGE_MAX_BLOCKS) continue;
+    if (pageText(page).length > TITLE_PAGE_MAX_CHARS) continue;
+
+    const largest = page.blocks.reduce((a, b) => (b.font_size > a.font_size ? b : a));
+    if (largest.font_size < titleFontSize) continue;
+
+    const previous = markers.at(-1);
+    if (previous && index - previous.page < MIN_CHAPTER_PAGES) continue;
+
+    const title = largest.lines.join('').slice(0, MAX_TITLE_LENGTH);
+    markers.push(createChapterMarker(pages, index, title || `Chapter ${markers.length + 1}`));
+  }
+  return markers;
+}
+
+/** Save a volume's chapter markers locally and to synced volume data */
+export async function setChapterMarkers(
+  volumeUuid: string,
+  markers: ChapterMarker[]
+): Promise<void> {
+  const sorted = [...markers].sort((a, b) => a.page - b.page);
+  await db.volumes.update(volumeUuid, {
+    chapter_markers: sorted.length > 0 ? sorted : undefined
+  });
+  updateVolumeChapterMarkers(volumeUuid, sorted);
+}
+
+/** Copy synced chapter markers onto a catalog volume; true if it changed */
+export function applySyncedChapterMarkers(volume: VolumeMetadata, data: VolumeData): boolean {
+  if (JSON.stringify(volume.chapter_markers) === JSON.stringify(data.chapter_markers)) return false;
+  volume.chapter_markers = data.chapter_markers;
+  return true;
+}
+
+/**
+ * Apply chapter markers from synced volume data to the local catalog.
+ * Called after volume-data sync so chapters set on another device show up here.
+ */
+export async function reconcileChapterMarkers(
+  volumeData: Record<string, VolumeData>
+): Promise<number> {
+  let changed = 0;
+
+  await db.transaction('rw', db.volumes, async () => {
+    await db.volumes.toCollection().modify((volume) => {
+      const data = volumeData[volume.volume_uuid];
+      if (!data || data.deletedOn) return;
+      if (applySyncedChapterMarkers(volume, data)) changed++;
+    });
+  });
+
+  return changed;
+}
@@ -0,0 +1,98 @@
+import { describe, expect, it } from 'vitest';
+import type { VolumeMetadata } from '$lib/types';
+import {
+  libraryEntries,
+  parsePageQuery,
+  searchPalette,
+  PALETTE_LIMIT,
+  type PaletteEntry
+} from './command-palette';
+
+function volume(uuid: string, series: string, title: string): VolumeMetadata {
+  return {
+    mokuro_version: '0.2.0',
+    series_title: series,
+    series_uuid: `series-${series}`,
+    volume_title: title,
+    volume_uuid: uuid,
+    page_count: 100,
+    character_count: 0,
+    page_char_counts: []
+  };
+}
+
+const library = [
+  volume('v1', 'よつばと!', 'よつばと! 01'),
+  volume('v2', 'よつばと!', 'よつばと! 02'),
+  volume('v3', 'Frieren', 'Frieren 05')
+];
+
+const lastRead = {
+  v1: '2026-01-01T00:00:00Z',
+  v3: '2026-03-01T00:00:00Z'
+};
+
+const syncAction: PaletteEntry = { id: 'action:sync', kind: 'action', title: 'Sync now' };
+
+describe('libraryEntries', () => {
+  it('lists the most recently read volumes first, then each series once', () => {
+    const entries = libraryEntries(library, lastRead);
+
+    expect(entries.map((entry) => entry.id)).toEqual([
+      'volume:v3',
+      'volume:v1',
+      'volume:v2',
+      'series:Frieren',
+      'series:よつばと!'
+    ]);
+    expect(entries[0].detail).toBe('Frieren');
+  });
+
+  it('leaves out cloud placeholders', () => {
+    const placeholder = { ...volume('v4', 'Frieren', 'Frieren 06'), isPlaceholder: true };
+
+    expect(libraryEntries([placeholder], {})).toEqual([]);
+  });
+});
+
+describe('searchPalette', () => {
+  const entries = [syncAction, ...libraryEntries(library, lastRead)];
+
+  it('shows actions and recent volumes without a query', () => {
+    expect(searchPalette(entries, '').map((entry) => entry.id)).toEqual([
+      'action:sync',
+      'volume:v3',
+      'volume:v1',
+      'volume:v2'
+    ]);
+  });
+
+  it('finds a volume from part of its title, romaji included', () => {
+    expect(searchPalette(entries, 'yotsuba 02')[0].id).toBe('volume:v2');
+    expect(searchPalette(entries, 'frieren 05')[0].id).toBe('volume:v3');
+  });
+
+  it('matches actions by name', () => {
+    expect(searchPalette(entries, 'sync')[0]).toBe(syncAction);
+  });
+
+  it('caps the number of results', () => {
+    const many = Array.from({ length: 20 }, (_, i) => volume(`x${i}`, 'Series', `Series ${i}`));
+
+    expect(searchPalette(libraryEntries(many, {}), 'series')).toHaveLength(PALETTE_LIMIT);
+  });
+});
+
+describe('parsePageQuery', () => {
+  it('reads page numbers', () => {
+    expect(parsePageQuery('12')).toBe(12);
+    expect(parsePageQuery('p12')).toBe(12);
+    expect(parsePageQuery('page 12')).toBe(12);
+  });
+
+  it('ignores anything else', () => {
+    expect(parsePageQuery('0')).toBeNull();
+    expect(parsePageQuery('frieren 5')).toBeNull();
+    expect(parsePageQuery('')).toBeNull();
+  });
+});
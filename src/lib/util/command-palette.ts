@@ -0,0 +1,74 @@
+/**
+ * Command palette search (Ctrl/Cmd+K).
+ *
+ * The palette lists volumes, series and actions as entries with a title and
+ * ranks them with the catalog's fuzzy title search, so the same width, kana
+ * and romaji folding applies. With no query it shows the actions and the
+ * most recently read volumes.
+ */
+
+import { getSeriesGroupTitle } from '$lib/catalog/catalog';
+import type { VolumeMetadata } from '$lib/types';
+import { searchByTitle } from './title-search';
+
+export type PaletteEntryKind = 'volume' | 'series' | 'action';
+
+export interface PaletteEntry {
+  id: string;
+  kind: PaletteEntryKind;
+  title: string;
+  /** Secondary line: the series of a volume, a shortcut hint... */
+  detail?: string;
+}
+
+/** Most entries shown at once */
+export const PALETTE_LIMIT = 8;
+
+/**
+ * Volume and series entries, most recently read volumes first. Series follow
+ * in the order their latest volume was read.
+ */
+export function libraryEntries(
+  volumes: VolumeMetadata[],
+  lastRead: Record<string, string | undefined>
+): PaletteEntry[] {
+  const sorted = volumes
+    .filter((volume) => !volume.isPlaceholder)
+    .map((volume) => ({ volume, time: Date.parse(lastRead[volume.volume_uuid] ?? '') || 0 }))
+    .sort((a, b) => b.time - a.time)
+    .map(({ volume }) => volume);
+
+  const entries: PaletteEntry[] = sorted.map((volume) => ({
+    id: `volume:${volume.volume_uuid}`,
+    kind: 'volume',
+    title: volume.volume_title,
+    detail: getSeriesGroupTitle(volume)
+  }));
+
+  const series = new Set(sorted.map(getSeriesGroupTitle));
+  for (const title of series) {
+    entries.push({ id: `series:${title}`, kind: 'series', title });
+  }
+  return entries;
+}
+
+/** "12", "p12" or "page 12" → 12; null for anything else */
+export function parsePageQuery(query: string): number | null {
+  const match = query.trim().match(/^(?:p|page)?\s*(\d+)$/i);
+  if (!match) return null;
+  const page = parseInt(match[1], 10);
+  return page > 0 ? page : null;
+}
+
+/**
+ * Entries matching `query`, best first. An empty query lists the actions,
+ * then the library entries in the order given.
+ */
+export function searchPalette(entries: PaletteEntry[], query: string): PaletteEntry[] {
+  if (!query.trim()) {
+    const actions = entries.filter((entry) => entry.kind === 'action');
+    const volumes = entries.filter((entry) => entry.kind === 'volume');
+    return [...actions, ...volumes].slice(0, PALETTE_LIMIT);
+  }
+  return searchByTitle(entries, query).slice(0, PALETTE_LIMIT);
+}
@@ -0,0 +1,56 @@
+import { describe, it, expect } from 'vitest';
+import { DownloadIntegrityError, Md5, md5Hex, verifyDownloadIntegrity } from './download-integrity';
+
+const encode = (text: string) => new TextEncoder().encode(text);
+
+describe('md5Hex', () => {
+  it('matches RFC 1321 test vectors', () => {
+    expect(md5Hex(encode(''))).toBe('d41d8cd98f00b204e9800998ecf8427e');
+    expect(md5Hex(encode('abc'))).toBe('900150983cd24fb0d6963f7d28e17f72');
+    expect(md5Hex(encode('message digest'))).toBe('f96b697d7cb7938d525a2f31aaf161d0');
+    expect(
+      md5Hex(
+        encode('12345678901234567890123456789012345678901234567890123456789012345678901234567890')
+      )
+    ).toBe('57edf4a22be3c955ac49da2e2107b67a');
+  });
+
+  it('gives the same digest when fed in chunks that split blocks', () => {
+    const data = Uint8Array.from({ length: 1000 }, (_, i) => (i * 31) % 256);
+    const md5 = new Md5();
+    for (let offset = 0; offset < data.length; offset += 7) {
+      md5.update(data.subarray(offset, offset + 7));
+    }
+
+    expect(md5.hex()).toBe(md5Hex(data));
+  });
+});
+
+describe('verifyDownloadIntegrity', () => {
+  const archive = encode('PK fake archive contents');
+
+  it('accepts a complete download', () => {
+    expect(() =>
+      verifyDownloadIntegrity(archive, { size: archive.byteLength, md5: md5Hex(archive) })
+    ).not.toThrow();
+  });
+
+  it('rejects a truncated download', () => {
+    const truncated = archive.slice(0, 10);
+    expect(() => verifyDownloadIntegrity(truncated, { size: archive.byteLength })).toThrow(
+      DownloadIntegrityError
+    );
+  });
+
+  it('rejects a download whose checksum does not match', () => {
+    const corrupted = archive.slice();
+    corrupted[3] ^= 0xff;
+    expect(() =>
+      verifyDownloadIntegrity(corrupted, { size: archive.byteLength, md5: md5Hex(archive) })
+    ).toThrow(/Checksum mismatch/);
+  });
+
+  it('skips checks the provider has no data for', () => {
+    expect(() => verifyDownloadIntegrity(archive, {})).not.toThrow();
+  });
+});
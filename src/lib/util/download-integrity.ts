@@ -0,0 +1,161 @@
+/**
+ * Integrity checks for downloaded archives.
+ *
+ * Downloads can end early (dropped connection, proxy timeouts) without the
+ * request failing, and a truncated CBZ may still partially decompress. The
+ * downloaded bytes are compared against the size and, when the provider
+ * reports one, the MD5 checksum recorded in the cloud before importing.
+ */
+
+export class DownloadIntegrityError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = 'DownloadIntegrityError';
+  }
+}
+
+export interface ExpectedIntegrity {
+  size?: number;
+  /** Hex MD5 digest (Google Drive's md5Checksum) */
+  md5?: string;
+}
+
+// Per-round shift amounts and sine-derived constants from RFC 1321
+const S = [
+  7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14,
+  20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10, 15, 21, 6,
+  10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21
+];
+const K = Array.from({ length: 64 }, (_, i) =>
+  Math.floor(Math.abs(Math.sin(i + 1)) * 2 ** 32) >>> 0
+);
+
+/** One 64-byte block of MD5 on `state`, read from `view` at `offset` */
+function md5Block(state: number[], view: DataView, offset: number, m: Uint32Array): void {
+  for (let j = 0; j < 16; j++) {
+    m[j] = view.getUint32(offset + j * 4, true);
+  }
+
+  let [a, b, c, d] = state;
+
+  for (let i = 0; i < 64; i++) {
+    let f: number;
+    let g: number;
+    if (i < 16) {
+      f = (b & c) | (~b & d);
+      g = i;
+    } else if (i < 32) {
+      f = (d & b) | (~d & c);
+      g = (5 * i + 1) % 16;
+    } else if (i < 48) {
+      f = b ^ c ^ d;
+      g = (3 * i + 5) % 16;
+    } else {
+      f = c ^ (b | ~d);
+      g = (7 * i) % 16;
+    }
+
+    const sum = (a + f + K[i] + m[g]) | 0;
+    a = d;
+    d = c;
+    c = b;
+    b = (b + ((sum << S[i]) | (sum >>> (32 - S[i])))) | 0;
+  }
+
+  state[0] = (state[0] + a) | 0;
+  state[1] = (state[1] + b) | 0;
+  state[2] = (state[2] + c) | 0;
+  state[3] = (state[3] + d) | 0;
+}
+
+/**
+ * Incremental MD5. Input is hashed block by block as it is fed in, so memory
+ * stays at one block however large the data is.
+ * WebCrypto has no MD5, and Drive only exposes MD5, so this is done by hand.
+ */
+export class Md5 {
+  private readonly state = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+  private readonly m = new Uint32Array(16);
+  /** Bytes of an incomplete block carried over to the next update */
+  private readonly pending = new Uint8Array(64);
+  private pendingLength = 0;
+  private length = 0;
+
+  update(chunk: ArrayBuffer | Uint8Array): this {
+    const bytes = chunk instanceof Uint8Array ? chunk : new Uint8Array(chunk);
+    this.length += bytes.length;
+    let offset = 0;
+
+    if (this.pendingLength > 0) {
+      const take = Math.min(64 - this.pendingLength, bytes.length);
+      this.pending.set(bytes.subarray(0, take), this.pendingLength);
+      this.pendingLength += take;
+      offset = take;
+      if (this.pendingLength < 64) return this;
+      md5Block(this.state, new DataView(this.pending.buffer), 0, this.m);
+      this.pendingLength = 0;
+    }
+
+    // Whole blocks are read in place, without copying the input
+    const view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+    for (; offset + 64 <= bytes.length; offset += 64) {
+      md5Block(this.state, view, offset, this.m);
+    }
+
+    this.pending.set(bytes.subarray(offset));
+    this.pendingLength = bytes.length - offset;
+    return this;
+  }
+
+  /** Lowercase hex digest of everything passed to update() */
+  hex(): string {
+    // Pad to 56 mod 64 bytes, then append the bit length as a 64-bit little-endian integer
+    const tailLength = (((this.pendingLength + 8) >>> 6) + 1) << 6;
+    const tail = new Uint8Array(tailLength);
+    tail.set(this.pending.subarray(0, this.pendingLength));
+    tail[this.pendingLength] = 0x80;
+    const view = new DataView(tail.buffer);
+    view.setUint32(tailLength - 8, (this.length << 3) >>> 0, true);
+    view.setUint32(tailLength - 4, Math.floor(this.length / 0x20000000), true);
+
+    const state = [...this.state];
+    for (let offset = 0; offset < tailLength; offset += 64) {
+      md5Block(state, view, offset, this.m);
+    }
+
+    const out = new DataView(new ArrayBuffer(16));
+    state.forEach((word, i) => out.setUint32(i * 4, word, true));
+    return Array.from(new Uint8Array(out.buffer), (byte) => byte.toString(16).padStart(2, '0')).join(
+      ''
+    );
+  }
+}
+
+/** MD5 digest of a buffer as lowercase hex */
+export function md5Hex(buffer: ArrayBuffer | Uint8Array): string {
+  return new Md5().update(buffer).hex();
+}
+
+/**
+ * Throw a DownloadIntegrityError if the downloaded bytes don't match what the
+ * cloud reported. Missing expectations are skipped (not every provider has them).
+ */
+export function verifyDownloadIntegrity(
+  data: ArrayBuffer | Uint8Array,
+  expected: ExpectedIntegrity
+): void {
+  if (expected.size && data.byteLength !== expected.size) {
+    throw new DownloadIntegrityError(
+      `Downloaded ${data.byteLength} bytes, expected ${expected.size}`
+    );
+  }
+
+  if (expected.md5) {
+    const actual = md5Hex(data);
+    if (actual !== expected.md5.toLowerCase()) {
+      throw new DownloadIntegrityError(
+        `Checksum mismatch (got ${actual}, expected ${expected.md5})`
+      );
+    }
+  }
+}
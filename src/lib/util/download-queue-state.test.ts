@@ -0,0 +1,62 @@
+import { describe, it, expect, beforeEach } from 'vitest';
+import 'fake-indexeddb/auto';
+import type { QueueItem } from './download-queue';
+import {
+  saveDownloadQueueState,
+  loadDownloadQueueState,
+  clearDownloadQueueState
+} from './download-queue-state';
+
+function createItem(uuid: string, status: QueueItem['status']): QueueItem {
+  return {
+    volumeUuid: uuid,
+    cloudFileId: `file-${uuid}`,
+    cloudProvider: 'google-drive',
+    seriesTitle: 'Series',
+    volumeTitle: `Volume ${uuid}`,
+    volumeMetadata: {
+      mokuro_version: '0.0.0',
+      series_title: 'Series',
+      series_uuid: 'series-uuid',
+      volume_title: `Volume ${uuid}`,
+      volume_uuid: uuid,
+      page_count: 0,
+      character_count: 0,
+      page_char_counts: [],
+      isPlaceholder: true
+    },
+    status
+  };
+}
+
+describe('download queue persistence', () => {
+  beforeEach(async () => {
+    await clearDownloadQueueState();
+  });
+
+  it('restores remaining items in order with the completed set', async () => {
+    await saveDownloadQueueState({
+      items: [createItem('v2', 'downloading'), createItem('v3', 'queued')],
+      completed: ['v1'],
+      paused: true
+    });
+
+    const saved = await loadDownloadQueueState();
+    expect(saved?.items.map((item) => item.volumeUuid)).toEqual(['v2', 'v3']);
+    // Interrupted downloads restart from scratch
+    expect(saved?.items.every((item) => item.status === 'queued')).toBe(true);
+    expect(saved?.completed).toEqual(['v1']);
+    expect(saved?.paused).toBe(true);
+  });
+
+  it('clears the saved queue once nothing is left', async () => {
+    await saveDownloadQueueState({
+      items: [createItem('v1', 'queued')],
+      completed: [],
+      paused: false
+    });
+    await saveDownloadQueueState({ items: [], completed: ['v1'], paused: false });
+
+    expect(await loadDownloadQueueState()).toBeUndefined();
+  });
+});
@@ -0,0 +1,71 @@
+/**
+ * Persistence for the cloud download queue.
+ *
+ * Bulk downloads can take long enough that the tab gets closed or the app
+ * restarted partway through. The remaining items and the set of finished
+ * volume IDs are written to IndexedDB so the queue can be restored (paused)
+ * on the next launch. Kept in its own database so the catalog schema and the
+ * worker-side catalog connection don't need a version bump.
+ */
+
+import Dexie, { type Table } from 'dexie';
+import type { QueueItem } from './download-queue';
+
+export interface PersistedDownloadQueue {
+  id: 'current';
+  /** Items still to download, in queue order */
+  items: QueueItem[];
+  /** Volume UUIDs finished (or skipped) since the queue was started */
+  completed: string[];
+  paused: boolean;
+  updatedAt: number;
+}
+
+class DownloadQueueDexie extends Dexie {
+  state!: Table<PersistedDownloadQueue>;
+
+  constructor() {
+    super('mokuro_download_queue');
+    this.version(1).stores({
+      state: 'id'
+    });
+  }
+}
+
+let queueDb: DownloadQueueDexie | null = null;
+
+function getQueueDb(): DownloadQueueDexie {
+  if (!queueDb) {
+    queueDb = new DownloadQueueDexie();
+  }
+  return queueDb;
+}
+
+/**
+ * Save the queue, or clear the saved copy when nothing is left to download
+ */
+export async function saveDownloadQueueState(
+  state: Omit<PersistedDownloadQueue, 'id' | 'updatedAt'>
+): Promise<void> {
+  if (state.items.length === 0) {
+    await clearDownloadQueueState();
+    return;
+  }
+
+  await getQueueDb().state.put({
+    id: 'current',
+    // Restored items always restart from the beginning of their download
+    items: state.items.map((item) => ({ ...item, status: 'queued' as const })),
+    completed: state.completed,
+    paused: state.paused,
+    updatedAt: Date.now()
+  });
+}
+
+export async function loadDownloadQueueState(): Promise<PersistedDownloadQueue | undefined> {
+  return getQueueDb().state.get('current');
+}
+
+export async function clearDownloadQueueState(): Promise<void> {
+  await getQueueDb().state.delete('current');
+}
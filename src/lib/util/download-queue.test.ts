@@ -0,0 +1,171 @@
+import { beforeEach, describe, expect, it, vi } from 'vitest';
+import { get } from 'svelte/store';
+import type { VolumeMetadata } from '$lib/types';
+import type { WorkerTask } from './worker-pool';
+
+const { tasks, pool, provider, markSessionExpired } = vi.hoisted(() => {
+  const tasks: WorkerTask[] = [];
+  return {
+    tasks,
+    pool: {
+      addTask: (task: WorkerTask) => tasks.push(task),
+      cancelTask: vi.fn(() => true)
+    },
+    provider: {
+      type: 'google-drive',
+      name: 'Google Drive',
+      downloadConcurrencyLimit: 8,
+      supportsWorkerDownload: true
+    },
+    markSessionExpired: vi.fn()
+  };
+});
+
+vi.mock('./file-processing-pool', () => ({
+  getFileProcessingPool: vi.fn(async () => pool),
+  incrementPoolUsers: vi.fn(),
+  decrementPoolUsers: vi.fn()
+}));
+
+vi.mock('./sync/unified-cloud-manager', () => ({
+  unifiedCloudManager: {
+    getActiveProvider: () => provider,
+    getAllCloudVolumes: () => [],
+    getCloudVolume: () => undefined,
+    isFetching: {
+      subscribe: (run: (value: boolean) => void) => {
+        run(false);
+        return () => {};
+      }
+    }
+  }
+}));
+
+vi.mock('$lib/catalog/db', () => ({ db: {} }));
+vi.mock('./sync/providers/google-drive/api-client', () => ({ driveApiClient: {} }));
+vi.mock('./sync/providers/google-drive/drive-files-cache', () => ({ driveFilesCache: {} }));
+vi.mock('$lib/import', () => ({}));
+vi.mock('./upload', () => ({ requestPersistentStorage: vi.fn(async () => {}) }));
+vi.mock('$lib/catalog/storage-eviction', () => ({ enforceStorageCap: vi.fn(async () => {}) }));
+vi.mock('$lib/catalog/sync-status', () => ({ markVolumeSynced: vi.fn(async () => {}) }));
+vi.mock('./snackbar', () => ({ showSnackbar: vi.fn() }));
+vi.mock('./sync/providers/google-drive/token-manager', () => ({
+  tokenManager: { markSessionExpired }
+}));
+vi.mock('./download-queue-state', () => ({
+  saveDownloadQueueState: vi.fn(async () => {}),
+  loadDownloadQueueState: vi.fn(async () => null),
+  clearDownloadQueueState: vi.fn(async () => {})
+}));
+
+import { miscSettings } from '$lib/settings/misc';
+import { cancelAllDownloads, downloadQueue, retryFailedDownloads } from './download-queue';
+import { downloadReport, formatTransferSummary, SIGN_IN_EXPIRED_REASON } from './transfer-report';
+
+function createPlaceholder(index: number): VolumeMetadata {
+  return {
+    mokuro_version: '0.0.0',
+    series_title: 'Series',
+    series_uuid: 'series-uuid',
+    volume_title: `Volume ${String(index).padStart(2, '0')}`,
+    volume_uuid: `vol-${index}`,
+    page_count: 0,
+    character_count: 0,
+    page_char_counts: [],
+    isPlaceholder: true,
+    cloudProvider: 'google-drive',
+    cloudFileId: `file-${index}`
+  };
+}
+
+function statuses(): string[] {
+  return get(downloadQueue).map((item) => `${item.volumeUuid}:${item.status}`);
+}
+
+async function fail(task: WorkerTask, status?: number) {
+  await task.onError!({ type: 'error', error: `HTTP error ${status ?? 0}`, status });
+}
+
+describe('download queue', () => {
+  beforeEach(async () => {
+    await cancelAllDownloads();
+    tasks.length = 0;
+    vi.clearAllMocks();
+    downloadReport.clear();
+    miscSettings.update((settings) => ({ ...settings, downloadConcurrency: 2 }));
+  });
+
+  it('downloads in queue order, at most the concurrency setting at a time', async () => {
+    downloadQueue.queueSeriesVolumes([3, 1, 4, 2].map(createPlaceholder));
+
+    await vi.waitFor(() => expect(tasks).toHaveLength(2));
+    expect(tasks.map((task) => task.id)).toEqual(['file-1', 'file-2']);
+    expect(statuses()).toEqual([
+      'vol-1:downloading',
+      'vol-2:downloading',
+      'vol-3:queued',
+      'vol-4:queued'
+    ]);
+
+    // A finished download frees its slot for the next queued volume
+    await fail(tasks[0]);
+    await vi.waitFor(() => expect(tasks).toHaveLength(3));
+    expect(tasks[2].id).toBe('file-3');
+    expect(statuses()).toEqual(['vol-2:downloading', 'vol-3:downloading', 'vol-4:queued']);
+  });
+
+  it('ignores a volume that is already queued', async () => {
+    downloadQueue.queueVolume(createPlaceholder(1));
+    downloadQueue.queueVolume(createPlaceholder(1));
+
+    await vi.waitFor(() => expect(tasks).toHaveLength(1));
+    expect(get(downloadQueue)).toHaveLength(1);
+  });
+
+  it('cancels queued and in-flight downloads and ignores their late callbacks', async () => {
+    downloadQueue.queueSeriesVolumes([1, 2, 3, 4].map(createPlaceholder));
+    await vi.waitFor(() => expect(tasks).toHaveLength(2));
+
+    await cancelAllDownloads();
+
+    expect(get(downloadQueue)).toEqual([]);
+    expect(pool.cancelTask).toHaveBeenCalledTimes(2);
+    expect(pool.cancelTask).toHaveBeenCalledWith('file-1');
+    expect(pool.cancelTask).toHaveBeenCalledWith('file-2');
+
+    // A worker that reports after being cancelled doesn't start anything new
+    await fail(tasks[0]);
+    await new Promise((resolve) => setTimeout(resolve, 0));
+    expect(tasks).toHaveLength(2);
+    expect(get(downloadQueue)).toEqual([]);
+  });
+
+  it('reports each failed volume, stops on an expired sign-in and retries them', async () => {
+    downloadQueue.queueSeriesVolumes([1, 2, 3, 4].map(createPlaceholder));
+    await vi.waitFor(() => expect(tasks).toHaveLength(2));
+
+    await fail(tasks[0], 500);
+    await vi.waitFor(() => expect(tasks).toHaveLength(3));
+    await fail(tasks[1], 401);
+
+    await vi.waitFor(() => expect(get(downloadQueue)).toEqual([]));
+    expect(formatTransferSummary(get(downloadReport.results), 'downloaded')).toBe(
+      '0 downloaded · 2 failed · 2 skipped'
+    );
+    const outcomes = get(downloadReport.retryable).map((r) => [r.volumeUuid, r.outcome, r.reason]);
+    expect(outcomes).toEqual([
+      ['vol-1', 'failed', 'HTTP error 500'],
+      ['vol-2', 'failed', 'HTTP error 401'],
+      ['vol-3', 'skipped', SIGN_IN_EXPIRED_REASON],
+      ['vol-4', 'skipped', SIGN_IN_EXPIRED_REASON]
+    ]);
+    // vol-3 was in flight
+    expect(pool.cancelTask).toHaveBeenCalledWith('file-3');
+    await vi.waitFor(() => expect(markSessionExpired).toHaveBeenCalledTimes(1));
+
+    tasks.length = 0;
+    retryFailedDownloads();
+    await vi.waitFor(() => expect(tasks).toHaveLength(2));
+    expect(get(downloadQueue)).toHaveLength(4);
+  });
+});
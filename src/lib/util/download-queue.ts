@@ -3,6 +3,7 @@ import type { VolumeMetadata } from '$lib/types';
 import { progressTrackerStore } from './progress-tracker';
 import type { WorkerTask } from './worker-pool';
 import type { VolumeMetadata as WorkerVolumeMetadata } from './worker-pool';
+import type { CloudCoreRangeOptions } from './sync/core/cloud-provider-core-types';
 import { db } from '$lib/catalog/db';
 import { driveApiClient } from './sync/providers/google-drive/api-client';
 import { driveFilesCache } from './sync/providers/google-drive/drive-files-cache';
@@ -13,7 +14,7 @@ import {
   getCloudSize,
   getCloudModifiedTime
 } from './cloud-fields';
-import type { ProviderType } from './sync/provider-interface';
+import type { AnyCloudFileMetadata, ProviderType } from './sync/provider-interface';
 import {
   getFileProcessingPool,
   incrementPoolUsers,
@@ -26,12 +27,30 @@ import {
   isImageExtension,
   processVolume,
   saveVolume,
+  replaceVolumeContent,
   deleteVolume as deleteStoredVolume,
   isSystemFile
 } from '$lib/import';
 import type { DecompressedVolume } from '$lib/import';
 import { extractTitlesFromPath, generateDeterministicUUID } from './series-extraction';
 import { shouldReplaceDownloadedVolume } from './download-volume-repair';
+import { enforceStorageCap } from '$lib/catalog/storage-eviction';
+import { markVolumeSynced } from '$lib/catalog/sync-status';
+import { miscSettings } from '$lib/settings/misc';
+import { showSnackbar } from './snackbar';
+import { PROVIDER_SHORT_LABELS } from './sync/provider-display';
+import {
+  SIGN_IN_EXPIRED_REASON,
+  downloadReport,
+  isAuthFailure,
+  type TransferOutcome
+} from './transfer-report';
+import { emitLibraryEvent } from './library-events';
+import {
+  saveDownloadQueueState,
+  loadDownloadQueueState,
+  clearDownloadQueueState
+} from './download-queue-state';
 
 export interface QueueItem {
   volumeUuid: string;
@@ -41,6 +60,10 @@ export interface QueueItem {
   volumeTitle: string;
   volumeMetadata: VolumeMetadata;
   status: 'queued' | 'downloading';
+  /** Low-priority download nobody is waiting on (e.g. the next volume while reading) */
+  background?: boolean;
+  /** Replace the content of the local copy once downloaded (see replaceWithCloudCopy) */
+  replaceLocal?: boolean;
 }
 
 interface SeriesQueueStatus {
@@ -87,26 +110,80 @@ const queueStore = writable<QueueItem[]>([]);
 // Track if this queue is currently using the shared pool
 let processingStarted = false;
 
-// Subscribe to queue changes and update progress tracker
-queueStore.subscribe((queue) => {
+// Paused queues keep their items but start nothing new
+const pausedStore = writable(false);
+
+// Volumes finished (succeeded, failed or skipped) since the queue was last empty,
+// so the overall bar can show "X of Y" while items are still being added
+const completedIds = new Set<string>();
+
+// Downloads cancelled while in flight; their late callbacks are ignored
+const cancelledIds = new Set<string>();
+
+// Don't write to IndexedDB until the previous session's queue has been restored,
+// otherwise the initial empty queue would wipe it
+let persistenceReady = false;
+
+function persistQueue(): void {
+  if (!persistenceReady) return;
+  saveDownloadQueueState({
+    items: get(queueStore),
+    completed: [...completedIds],
+    paused: get(pausedStore)
+  }).catch((error) => console.warn('Failed to save download queue:', error));
+}
+
+function updateOverallProgress(): void {
+  const queue = get(queueStore);
   const totalCount = queue.length;
 
   if (totalCount > 0) {
+    const paused = get(pausedStore);
+    const downloadingCount = queue.filter((item) => item.status === 'downloading').length;
+    const finished = completedIds.size;
+    const sessionTotal = finished + totalCount;
+    const activity = paused ? 'Paused' : `${downloadingCount} downloading`;
     progressTrackerStore.addProcess({
       id: 'download-queue-overall',
       description: 'Download Queue',
-      status: `${totalCount} in queue`,
-      progress: 0 // Progress bar won't show meaningful data for growing queue
+      status: `${finished} of ${sessionTotal} done · ${activity}`,
+      progress: (finished / sessionTotal) * 100,
+      state: paused ? 'paused' : 'running',
+      current: finished,
+      total: sessionTotal,
+      onPause: paused ? undefined : pauseDownloads,
+      onResume: paused ? resumeDownloads : undefined,
+      onCancel: cancelAllDownloads
     });
   } else {
+    completedIds.clear();
+    pausedStore.set(false);
     progressTrackerStore.removeProcess('download-queue-overall');
   }
+}
+
+// Subscribe to queue changes and update progress tracker
+queueStore.subscribe(() => {
+  updateOverallProgress();
+  persistQueue();
 });
 
 /**
- * Add a single volume to the download queue
+ * Remove a finished (or failed) item from the queue and count it towards overall progress
  */
-export function queueVolume(volume: VolumeMetadata): void {
+function finishQueueItem(volumeUuid: string): void {
+  completedIds.add(volumeUuid);
+  queueStore.update((q) => q.filter((i) => i.volumeUuid !== volumeUuid));
+}
+
+/**
+ * Add a single volume to the download queue. Background items start only once
+ * no regular download is waiting.
+ */
+export function queueVolume(
+  volume: VolumeMetadata,
+  options: { background?: boolean; replaceLocal?: boolean } = {}
+): void {
   // Request persistent storage from within the click that queued this download.
   // Cloud volumes are saved off the main gesture in the background worker, so
   // this synchronous enqueue is the only point in the cloud path that still
@@ -131,10 +208,24 @@ export function queueVolume(volume: VolumeMetadata): void {
   );
 
   if (isDuplicate) {
+    // Asking for a volume that was only being fetched ahead makes it a regular download
+    if (!options.background) {
+      queueStore.update((q) =>
+        q.map((i) => (i.volumeUuid === volume.volume_uuid ? { ...i, background: false } : i))
+      );
+      processQueue();
+    }
     console.log(`Volume ${volume.volume_title} already in queue`);
     return;
   }
 
+  // A new batch after a clean one starts a fresh report
+  if (queue.length === 0 && get(downloadReport.retryable).length === 0) {
+    downloadReport.clear();
+  }
+
+  cancelledIds.delete(volume.volume_uuid);
+
   const queueItem: QueueItem = {
     volumeUuid: volume.volume_uuid,
     cloudFileId,
@@ -142,7 +233,9 @@ export function queueVolume(volume: VolumeMetadata): void {
     seriesTitle: volume.series_title,
     volumeTitle: volume.volume_title,
     volumeMetadata: volume,
-    status: 'queued'
+    status: 'queued',
+    background: options.background || undefined,
+    replaceLocal: options.replaceLocal || undefined
   };
 
   queueStore.update((q) => [...q, queueItem]);
@@ -256,6 +349,41 @@ export function queueVolumesFromCloudFiles(
   queueSeriesVolumes(placeholders);
 }
 
+/**
+ * Parallel range requests for one Drive volume, when turned on in turbo mode.
+ * They write into one buffer of the file's size, so they don't add to the
+ * task's memory requirement.
+ */
+function getRangeOptions(providerType: ProviderType): CloudCoreRangeOptions | undefined {
+  const settings = get(miscSettings);
+  if (providerType !== 'google-drive' || !settings.turboMode) return undefined;
+  if (settings.driveRangeConcurrency <= 1) return undefined;
+  return {
+    concurrency: settings.driveRangeConcurrency,
+    chunkSize: settings.driveRangeChunkMB * 1024 * 1024
+  };
+}
+
+/**
+ * Remove one volume from the queue, stopping its download if it already started
+ */
+export async function cancelQueuedVolume(volumeUuid: string): Promise<void> {
+  const item = get(queueStore).find((i) => i.volumeUuid === volumeUuid);
+  if (!item) return;
+
+  queueStore.update((q) => q.filter((i) => i.volumeUuid !== volumeUuid));
+  if (item.status !== 'downloading') return;
+
+  cancelledIds.add(volumeUuid);
+  const pool = await getFileProcessingPool();
+  pool.cancelTask(item.cloudFileId);
+  void cleanupProviderDownloadCredentials(item.cloudProvider, item.cloudFileId).catch((error) =>
+    console.warn('Failed to clean up download credentials:', error)
+  );
+  progressTrackerStore.removeProcess(`download-${item.cloudFileId}`);
+  checkAndTerminatePool();
+}
+
 /**
  * Check if a specific volume is in the queue
  */
@@ -362,7 +490,8 @@ async function entriesToDecompressedVolume(
  */
 async function processVolumeData(
   entries: DecompressedEntry[],
-  placeholder: VolumeMetadata
+  placeholder: VolumeMetadata,
+  replaceLocal?: boolean
 ): Promise<void> {
   // Use the original cloud path for basePath to get proper series extraction
   // Falls back to volume_title if cloudPath not available (older placeholders)
@@ -389,6 +518,29 @@ async function processVolumeData(
     processedVolume.fileData.volume_uuid = placeholder.volume_uuid;
   }
 
+  processedVolume.metadata.origin = {
+    originalFilename: basePath.split('/').pop() || basePath,
+    sourcePath: basePath,
+    provider: getCloudProvider(placeholder) ?? undefined,
+    fileId: getCloudFileId(placeholder) ?? undefined
+  };
+
+  if (replaceLocal && (await db.volumes.get(placeholder.volume_uuid))) {
+    // Swap the content in only now, so the local copy stays readable if the
+    // download fails. Progress, notes and overrides stay on the volume row.
+    await replaceVolumeContent(placeholder.volume_uuid, processedVolume);
+    await db.volumes.update(placeholder.volume_uuid, { content_modified_at: undefined });
+    await markVolumeSynced(placeholder.volume_uuid, getCloudModifiedTime(placeholder) ?? undefined);
+    emitLibraryEvent('volume-downloaded', {
+      volumeUuid: placeholder.volume_uuid,
+      seriesUuid: placeholder.series_uuid,
+      seriesTitle: placeholder.series_title,
+      volumeTitle: placeholder.volume_title,
+      provider: getCloudProvider(placeholder)
+    });
+    return;
+  }
+
   const [existingVolume, existingOcr, existingFiles] = await Promise.all([
     db.volumes.get(processedVolume.metadata.volumeUuid),
     db.volume_ocr.get(processedVolume.metadata.volumeUuid),
@@ -412,7 +564,15 @@ async function processVolumeData(
     // the stored-title === cloud-path identity for legacy backups (volume
     // reads as un-backed-up, renames miss its files). Legacy titles are
     // sanitized at rename time instead, when the cloud files move with them.
-    await saveVolume(processedVolume, { preserveTitles: true });
+    const saved = await saveVolume(processedVolume, { preserveTitles: true });
+    await markVolumeSynced(saved.volume_uuid, getCloudModifiedTime(placeholder) ?? undefined);
+    emitLibraryEvent('volume-downloaded', {
+      volumeUuid: saved.volume_uuid,
+      seriesUuid: saved.series_uuid,
+      seriesTitle: saved.series_title,
+      volumeTitle: saved.volume_title,
+      provider: getCloudProvider(placeholder)
+    });
   }
 
   // Update cloud file description if folder name doesn't match series title
@@ -568,15 +728,69 @@ async function downloadSidecarEntries(placeholder: VolumeMetadata): Promise<Deco
 }
 
 /**
- * Handle download errors consistently
+ * MD5 the provider recorded for a cloud file, if any (only Google Drive reports one)
  */
-function handleDownloadError(item: QueueItem, processId: string, errorMessage: string): void {
-  progressTrackerStore.updateProcess(processId, {
-    progress: 0,
-    status: `Error: ${errorMessage}`
+function getCloudMd5(cloudFileId: string): string | undefined {
+  const cloudFile = unifiedCloudManager.getCloudVolume(cloudFileId) as
+    | AnyCloudFileMetadata
+    | undefined;
+  return cloudFile?.provider === 'google-drive' ? cloudFile.md5Checksum : undefined;
+}
+
+function recordResult(item: QueueItem, outcome: TransferOutcome, reason?: string): void {
+  downloadReport.record({
+    volumeUuid: item.volumeUuid,
+    seriesTitle: item.seriesTitle,
+    volumeTitle: item.volumeTitle,
+    provider: item.cloudProvider,
+    outcome,
+    reason,
+    volume: item.volumeMetadata
   });
-  queueStore.update((q) => q.filter((i) => i.volumeUuid !== item.volumeUuid));
+}
+
+/**
+ * Handle download errors consistently. `authFailed` means the provider
+ * rejected the session, which stops the rest of the queue.
+ */
+function handleDownloadError(
+  item: QueueItem,
+  processId: string,
+  errorMessage: string,
+  authFailed = false
+): void {
+  progressTrackerStore.failProcess(processId, `Error: ${errorMessage}`);
+  recordResult(item, 'failed', errorMessage);
+  finishQueueItem(item.volumeUuid);
   setTimeout(() => progressTrackerStore.removeProcess(processId), 3000);
+
+  if (authFailed) {
+    haltDownloadsForProvider(item.cloudProvider).catch((error) => {
+      console.error('Failed to stop downloads after sign-in expired:', error);
+    });
+  }
+}
+
+/**
+ * Stop the rest of the queue after an auth failure. Every other download from
+ * the provider would fail the same way, so they're recorded as skipped (ready
+ * for "retry failed") instead of each reporting its own error.
+ */
+async function haltDownloadsForProvider(provider: ProviderType): Promise<void> {
+  const remaining = get(queueStore).filter((item) => item.cloudProvider === provider);
+  for (const item of remaining) {
+    recordResult(item, 'skipped', SIGN_IN_EXPIRED_REASON);
+  }
+  await cancelDownloads(remaining);
+
+  showSnackbar(
+    `${PROVIDER_SHORT_LABELS[provider]} sign-in expired. Reconnect, then retry failed downloads.`
+  );
+
+  if (provider === 'google-drive') {
+    const { tokenManager } = await import('./sync/providers/google-drive/token-manager');
+    tokenManager.markSessionExpired();
+  }
 }
 
 /**
@@ -623,9 +837,36 @@ async function processDownload(item: QueueItem, processId: string): Promise<void
     return;
   }
 
+  // A volume deleted from the cloud while queued (e.g. a queue restored from a
+  // previous session) is skipped rather than failing with a 404. Only trust the
+  // cache's absence once it has finished loading.
+  const cacheLoaded =
+    unifiedCloudManager.getAllCloudVolumes().length > 0 && !get(unifiedCloudManager.isFetching);
+  if (cacheLoaded && !unifiedCloudManager.getCloudVolume(item.cloudFileId)) {
+    console.warn(`[Download Queue] ${item.volumeTitle} is no longer in the cloud, skipping`);
+    showSnackbar(`Skipped ${item.volumeTitle}: no longer in cloud storage`);
+    recordResult(item, 'skipped', 'No longer in cloud storage');
+    progressTrackerStore.updateProcess(processId, {
+      progress: 0,
+      status: 'Skipped: no longer in cloud storage'
+    });
+    finishQueueItem(item.volumeUuid);
+    setTimeout(() => progressTrackerStore.removeProcess(processId), 3000);
+    processQueue();
+    return;
+  }
+
   const pool = await getFileProcessingPool();
   const fileSize = getCloudSize(item.volumeMetadata) || 0;
 
+  // Make room under the user's storage cap before writing, rather than letting
+  // the browser evict the whole database under pressure
+  try {
+    await enforceStorageCap(fileSize);
+  } catch (error) {
+    console.warn('Storage cap enforcement failed:', error);
+  }
+
   const providerType = provider.type;
   const downloadConcurrencyLimit = provider.downloadConcurrencyLimit;
   const supportsWorkerDownload = provider.supportsWorkerDownload;
@@ -643,7 +884,8 @@ async function processDownload(item: QueueItem, processId: string): Promise<void
       seriesTitle: item.seriesTitle,
       volumeTitle: item.volumeTitle,
       driveModifiedTime: getCloudModifiedTime(item.volumeMetadata) ?? undefined,
-      driveSize: getCloudSize(item.volumeMetadata) ?? undefined
+      driveSize: getCloudSize(item.volumeMetadata) ?? undefined,
+      driveMd5: getCloudMd5(item.cloudFileId)
     };
 
     // Create worker task for download+decompress
@@ -664,7 +906,8 @@ async function processDownload(item: QueueItem, processId: string): Promise<void
           fileId: item.cloudFileId,
           fileName: item.volumeTitle + '.cbz',
           credentials,
-          metadata: workerMetadata
+          metadata: workerMetadata,
+          ranges: getRangeOptions(providerType)
         };
       },
       onProgress: (data) => {
@@ -675,6 +918,10 @@ async function processDownload(item: QueueItem, processId: string): Promise<void
         });
       },
       onComplete: async (data, releaseMemory) => {
+        if (cancelledIds.has(item.volumeUuid)) {
+          releaseMemory();
+          return;
+        }
         try {
           progressTrackerStore.updateProcess(processId, {
             progress: 95,
@@ -688,14 +935,12 @@ async function processDownload(item: QueueItem, processId: string): Promise<void
             '[Download Queue] Sidecar entries merged:',
             sidecarEntries.map((entry) => entry.filename)
           );
-          await processVolumeData(allEntries, item.volumeMetadata);
+          await processVolumeData(allEntries, item.volumeMetadata, item.replaceLocal);
 
-          progressTrackerStore.updateProcess(processId, {
-            progress: 100,
-            status: 'Download complete'
-          });
+          progressTrackerStore.completeProcess(processId, 'Download complete');
+          recordResult(item, 'success');
 
-          queueStore.update((q) => q.filter((i) => i.volumeUuid !== item.volumeUuid));
+          finishQueueItem(item.volumeUuid);
           setTimeout(() => progressTrackerStore.removeProcess(processId), 3000);
 
           // Process next item in queue
@@ -714,9 +959,10 @@ async function processDownload(item: QueueItem, processId: string): Promise<void
         }
       },
       onError: async (data) => {
+        if (cancelledIds.has(item.volumeUuid)) return;
         console.error(`Error downloading ${item.volumeTitle}:`, data.error);
         await cleanupProviderDownloadCredentials(provider.type, item.cloudFileId);
-        handleDownloadError(item, processId, data.error);
+        handleDownloadError(item, processId, data.error, isAuthFailure(data.status));
         checkAndTerminatePool();
 
         // Process next item in queue even after error
@@ -783,6 +1029,10 @@ async function processDownload(item: QueueItem, processId: string): Promise<void
       // decompress-only emits no progress — status already set from prepareData
     },
     onComplete: async (data, releaseMemory) => {
+      if (cancelledIds.has(item.volumeUuid)) {
+        releaseMemory();
+        return;
+      }
       try {
         progressTrackerStore.updateProcess(processId, {
           progress: 95,
@@ -791,12 +1041,10 @@ async function processDownload(item: QueueItem, processId: string): Promise<void
         const sidecarEntries = await downloadSidecarEntries(item.volumeMetadata);
         const allEntries =
           sidecarEntries.length > 0 ? [...data.entries, ...sidecarEntries] : data.entries;
-        await processVolumeData(allEntries, item.volumeMetadata);
-        progressTrackerStore.updateProcess(processId, {
-          progress: 100,
-          status: 'Download complete'
-        });
-        queueStore.update((q) => q.filter((i) => i.volumeUuid !== item.volumeUuid));
+        await processVolumeData(allEntries, item.volumeMetadata, item.replaceLocal);
+        progressTrackerStore.completeProcess(processId, 'Download complete');
+        recordResult(item, 'success');
+        finishQueueItem(item.volumeUuid);
         setTimeout(() => progressTrackerStore.removeProcess(processId), 3000);
         processQueue();
       } catch (error) {
@@ -812,8 +1060,9 @@ async function processDownload(item: QueueItem, processId: string): Promise<void
       }
     },
     onError: async (data) => {
+      if (cancelledIds.has(item.volumeUuid)) return;
       console.error(`Error downloading ${item.volumeTitle}:`, data.error);
-      handleDownloadError(item, processId, data.error);
+      handleDownloadError(item, processId, data.error, isAuthFailure(data.status));
       checkAndTerminatePool();
       processQueue();
     }
@@ -824,10 +1073,16 @@ async function processDownload(item: QueueItem, processId: string): Promise<void
 
 /**
  * Process the queue - unified download handling for all providers
- * Processes items one at a time to preserve queue ordering
- * When a download completes, processQueue() is called again to start the next item
+ * Starts queued items in queue order until the concurrency limit is reached
+ * (the user's setting, capped by the provider's own limit). The worker pool
+ * still gates on memory, so non-turbo mode effectively stays one at a time.
+ * When a download finishes, processQueue() is called again to fill the slot.
  */
 async function processQueue(): Promise<void> {
+  if (get(pausedStore)) {
+    return;
+  }
+
   // Check if there are queued items and initialize pool if needed
   // Take initial snapshot just to check if we need to initialize
   const initialQueue = get(queueStore);
@@ -842,45 +1097,174 @@ async function processQueue(): Promise<void> {
     await getFileProcessingPool();
   }
 
+  // Get active provider (single-provider architecture)
+  const provider = unifiedCloudManager.getActiveProvider();
+  if (!provider) {
+    if (hasQueuedItems) {
+      console.error('[Download Queue] No cloud provider authenticated, skipping queue');
+    }
+    return;
+  }
+
   // CRITICAL: Re-fetch queue state AFTER any await points
   // This prevents race conditions where multiple processQueue() calls interleave
   // and process the same item using stale snapshots
   const queue = get(queueStore);
-  const queuedItems = queue.filter((item) => item.status === 'queued');
+  const downloadingCount = queue.filter((item) => item.status === 'downloading').length;
+  const limit = Math.max(
+    1,
+    Math.min(get(miscSettings).downloadConcurrency, provider.downloadConcurrencyLimit || Infinity)
+  );
 
-  // Process only the FIRST queued item to preserve ordering
-  // When it completes, it will call processQueue() again to process the next item
-  // This ensures downloads complete in the order they were queued
-  const item = queuedItems[0];
-  if (!item) {
-    return; // No queued items
+  // Take items from the front so downloads still start in the order they were queued,
+  // with background items after everything else
+  const queued = queue.filter((item) => item.status === 'queued');
+  const toStart = [
+    ...queued.filter((item) => !item.background),
+    ...queued.filter((item) => item.background)
+  ].slice(0, Math.max(0, limit - downloadingCount));
+  if (toStart.length === 0) {
+    return;
   }
 
-  // Get active provider (single-provider architecture)
-  const provider = unifiedCloudManager.getActiveProvider();
-  if (!provider) {
-    console.error(`[Download Queue] No cloud provider authenticated, skipping ${item.volumeTitle}`);
-    return;
+  // Mark as downloading in one update so concurrent calls can't pick the same items
+  const startingIds = new Set(toStart.map((item) => item.volumeUuid));
+  queueStore.update((q) =>
+    q.map((i) => (startingIds.has(i.volumeUuid) ? { ...i, status: 'downloading' as const } : i))
+  );
+
+  for (const item of toStart) {
+    const processId = `download-${item.cloudFileId}`;
+
+    progressTrackerStore.addProcess({
+      id: processId,
+      description: item.background
+        ? `Preparing next volume: ${item.volumeTitle}`
+        : `Downloading ${item.volumeTitle}`,
+      progress: 0,
+      status: item.background ? 'Low priority' : 'Starting download...',
+      onCancel: item.background ? () => cancelQueuedVolume(item.volumeUuid) : undefined
+    });
+
+    processDownload(item, processId);
   }
+}
 
-  // Mark as downloading
+/**
+ * Cancel every queued and in-flight download.
+ * In-flight workers are terminated, so their partial data is never imported.
+ */
+export async function cancelAllDownloads(): Promise<void> {
+  await cancelDownloads(get(queueStore));
+}
+
+/**
+ * Retry the volumes from the download report that failed or were skipped
+ */
+export function retryFailedDownloads(): void {
+  // Entries stay until the retried downloads replace them
+  for (const result of get(downloadReport.retryable)) {
+    queueVolume(result.volume);
+  }
+}
+
+async function cancelDownloads(items: QueueItem[]): Promise<void> {
+  if (items.length === 0) return;
+
+  const ids = new Set(items.map((item) => item.volumeUuid));
+  queueStore.update((q) => q.filter((i) => !ids.has(i.volumeUuid)));
+
+  const downloading = items.filter((item) => item.status === 'downloading');
+  downloading.forEach((item) => cancelledIds.add(item.volumeUuid));
+  const pool = downloading.length > 0 ? await getFileProcessingPool() : null;
+
+  for (const item of downloading) {
+    pool?.cancelTask(item.cloudFileId);
+    void cleanupProviderDownloadCredentials(item.cloudProvider, item.cloudFileId).catch(
+      (error) => console.warn('Failed to clean up download credentials:', error)
+    );
+    progressTrackerStore.removeProcess(`download-${item.cloudFileId}`);
+  }
+
+  checkAndTerminatePool();
+}
+
+/**
+ * Pause the queue. Downloads still waiting for or transferring in a worker are
+ * stopped (terminating the worker aborts the request) and put back in the queue;
+ * volumes already past the download stage are left to finish importing.
+ */
+export async function pauseDownloads(): Promise<void> {
+  if (get(pausedStore)) return;
+  pausedStore.set(true);
+
+  const downloading = get(queueStore).filter((item) => item.status === 'downloading');
+  const pool = downloading.length > 0 ? await getFileProcessingPool() : null;
+  const requeued = new Set<string>();
+
+  for (const item of downloading) {
+    if (!pool?.cancelTask(item.cloudFileId)) continue;
+    requeued.add(item.volumeUuid);
+    void cleanupProviderDownloadCredentials(item.cloudProvider, item.cloudFileId).catch(
+      (error) => console.warn('Failed to clean up download credentials:', error)
+    );
+    progressTrackerStore.removeProcess(`download-${item.cloudFileId}`);
+  }
+
+  // Also triggers the overall progress update and persists the paused state
   queueStore.update((q) =>
-    q.map((i) => (i.volumeUuid === item.volumeUuid ? { ...i, status: 'downloading' as const } : i))
+    q.map((i) => (requeued.has(i.volumeUuid) ? { ...i, status: 'queued' as const } : i))
   );
+}
 
-  const processId = `download-${item.cloudFileId}`;
+/**
+ * Resume a paused queue from the first remaining item
+ */
+export function resumeDownloads(): void {
+  if (!get(pausedStore)) return;
+  pausedStore.set(false);
+  updateOverallProgress();
+  persistQueue();
+  processQueue();
+}
 
-  // Add progress tracker
-  progressTrackerStore.addProcess({
-    id: processId,
-    description: `Downloading ${item.volumeTitle}`,
-    progress: 0,
-    status: 'Starting download...'
-  });
+/**
+ * Restore the queue saved by a previous session. It comes back paused so the
+ * user can choose when to resume (and the cloud provider has time to sign in).
+ * Volumes that were downloaded in the meantime are dropped.
+ */
+export async function restoreDownloadQueue(): Promise<void> {
+  try {
+    const saved = await loadDownloadQueueState();
+    if (!saved || get(queueStore).length > 0) return;
+
+    const downloaded = new Set(
+      (await db.volumes.bulkGet(saved.items.map((item) => item.volumeUuid)))
+        .filter((volume) => volume !== undefined)
+        .map((volume) => volume.volume_uuid)
+    );
+    const remaining = saved.items.filter(
+      (item) => item.replaceLocal || !downloaded.has(item.volumeUuid)
+    );
 
-  // Start download - process one at a time to preserve queue ordering
-  // When this completes, onComplete will call processQueue() to start the next item
-  processDownload(item, processId);
+    if (remaining.length === 0) {
+      await clearDownloadQueueState();
+      return;
+    }
+
+    saved.completed.forEach((uuid) => completedIds.add(uuid));
+    downloaded.forEach((uuid) => completedIds.add(uuid));
+    pausedStore.set(true);
+    queueStore.set(remaining.map((item) => ({ ...item, status: 'queued' as const })));
+    showSnackbar(
+      `${remaining.length} download${remaining.length === 1 ? '' : 's'} from last session paused`
+    );
+  } catch (error) {
+    console.warn('Failed to restore download queue:', error);
+  } finally {
+    persistenceReady = true;
+    persistQueue();
+  }
 }
 
 // Export the store for reactive subscriptions
@@ -889,5 +1273,11 @@ export const downloadQueue = {
   queueVolume,
   queueSeriesVolumes,
   isVolumeInQueue,
-  getSeriesQueueStatus
+  getSeriesQueueStatus,
+  cancelQueuedVolume,
+  cancelAllDownloads,
+  retryFailedDownloads,
+  pauseDownloads,
+  resumeDownloads,
+  paused: { subscribe: pausedStore.subscribe }
 };
@@ -0,0 +1,26 @@
+import { describe, expect, it, vi } from 'vitest';
+import { emitLibraryEvent, onLibraryEvent } from './library-events';
+
+describe('library events', () => {
+  it('delivers the payload to subscribers until they unsubscribe', () => {
+    const listener = vi.fn();
+    const unsubscribe = onLibraryEvent('progress-updated', listener);
+
+    emitLibraryEvent('progress-updated', { volumeUuid: 'v1', page: 12, completed: false });
+    unsubscribe();
+    emitLibraryEvent('progress-updated', { volumeUuid: 'v1', page: 13, completed: false });
+
+    expect(listener).toHaveBeenCalledTimes(1);
+    expect(listener).toHaveBeenCalledWith({ volumeUuid: 'v1', page: 12, completed: false });
+  });
+
+  it('is visible to plain window listeners for external automation', () => {
+    const listener = vi.fn();
+    window.addEventListener('mokuro:sync-completed', listener);
+
+    emitLibraryEvent('sync-completed', { provider: 'webdav' });
+    window.removeEventListener('mokuro:sync-completed', listener);
+
+    expect((listener.mock.calls[0][0] as CustomEvent).detail).toEqual({ provider: 'webdav' });
+  });
+});
@@ -0,0 +1,67 @@
+/**
+ * Library events
+ *
+ * A typed event bus for changes to the library: imports, downloads, syncs and
+ * reading progress. Events are dispatched on `window` as CustomEvents named
+ * `mokuro:<type>`, so app code (through `onLibraryEvent`) and external
+ * automation such as userscripts or browser extensions
+ * (`window.addEventListener('mokuro:volume-imported', ...)`) receive the same
+ * events. Payload shapes are part of the public contract: add fields, but
+ * don't rename or remove them.
+ */
+
+import type { ProviderType } from './sync/provider-interface';
+import type { SyncChanges } from './sync/unified-sync-service';
+
+/** Identifies a volume in library event payloads */
+export interface LibraryVolumeRef {
+  volumeUuid: string;
+  seriesUuid: string;
+  seriesTitle: string;
+  volumeTitle: string;
+}
+
+export interface LibraryEventMap {
+  /** A volume was imported from local files */
+  'volume-imported': LibraryVolumeRef;
+  /** A cloud placeholder was downloaded and is now readable offline */
+  'volume-downloaded': LibraryVolumeRef & { provider: ProviderType | null };
+  /** A sync with one provider finished successfully */
+  'sync-completed': { provider: ProviderType; changes?: SyncChanges };
+  /** Reading progress was saved on this device (page turns, marking read/unread) */
+  'progress-updated': { volumeUuid: string; page: number; completed: boolean };
+}
+
+export type LibraryEventType = keyof LibraryEventMap;
+
+export type LibraryEvent<K extends LibraryEventType = LibraryEventType> = CustomEvent<
+  LibraryEventMap[K]
+>;
+
+const EVENT_PREFIX = 'mokuro:';
+
+/** The window event name for a library event type */
+export function libraryEventName(type: LibraryEventType): string {
+  return `${EVENT_PREFIX}${type}`;
+}
+
+export function emitLibraryEvent<K extends LibraryEventType>(
+  type: K,
+  detail: LibraryEventMap[K]
+): void {
+  if (typeof window === 'undefined') return;
+  window.dispatchEvent(new CustomEvent(libraryEventName(type), { detail }));
+}
+
+/**
+ * Subscribe to a library event. Returns a function that unsubscribes.
+ */
+export function onLibraryEvent<K extends LibraryEventType>(
+  type: K,
+  listener: (detail: LibraryEventMap[K]) => void
+): () => void {
+  if (typeof window === 'undefined') return () => {};
+  const handler = (event: Event) => listener((event as LibraryEvent<K>).detail);
+  window.addEventListener(libraryEventName(type), handler);
+  return () => window.removeEventListener(libraryEventName(type), handler);
+}
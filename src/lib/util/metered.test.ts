@@ -0,0 +1,23 @@
+import { describe, expect, it } from 'vitest';
+import { connectionLooksMetered, resolveMetered } from './metered';
+
+describe('connectionLooksMetered', () => {
+  it('treats data saver and cellular links as metered', () => {
+    expect(connectionLooksMetered({ saveData: true, type: 'wifi' })).toBe(true);
+    expect(connectionLooksMetered({ type: 'cellular' })).toBe(true);
+    expect(connectionLooksMetered({ type: 'wifi' })).toBe(false);
+  });
+
+  it('assumes unmetered when the browser has no Network Information API', () => {
+    expect(connectionLooksMetered(undefined)).toBe(false);
+  });
+});
+
+describe('resolveMetered', () => {
+  it('follows the browser unless the user forces it', () => {
+    expect(resolveMetered('auto', true)).toBe(true);
+    expect(resolveMetered('auto', false)).toBe(false);
+    expect(resolveMetered('metered', false)).toBe(true);
+    expect(resolveMetered('unmetered', true)).toBe(false);
+  });
+});
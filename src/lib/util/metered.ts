@@ -0,0 +1,57 @@
+/**
+ * Metered connection detection
+ *
+ * On mobile data the app holds back on work that spends data or battery in
+ * the background: the reader preloads fewer pages ahead, the next volume
+ * isn't downloaded while reading, and placeholder covers aren't fetched.
+ * Detection uses the Network Information API where the browser has it
+ * (Chromium); the user can force either way in the cloud settings.
+ */
+
+import { derived, readable, type Readable } from 'svelte/store';
+import { miscSettings } from '$lib/settings/misc';
+
+/** Detect from the browser, or force metered/unmetered */
+export type MeteredOverride = 'auto' | 'metered' | 'unmetered';
+
+/** The parts of the Network Information API used here */
+export interface ConnectionInfo {
+  saveData?: boolean;
+  type?: string;
+  addEventListener?: (type: 'change', listener: () => void) => void;
+  removeEventListener?: (type: 'change', listener: () => void) => void;
+}
+
+function getConnection(): ConnectionInfo | undefined {
+  if (typeof navigator === 'undefined') return undefined;
+  return (navigator as Navigator & { connection?: ConnectionInfo }).connection;
+}
+
+/** Data saver on, or a cellular link; unknown connections count as unmetered */
+export function connectionLooksMetered(connection: ConnectionInfo | undefined): boolean {
+  if (!connection) return false;
+  return connection.saveData === true || connection.type === 'cellular';
+}
+
+/** Apply the user's override to what the browser reports */
+export function resolveMetered(override: MeteredOverride, connectionMetered: boolean): boolean {
+  if (override === 'metered') return true;
+  if (override === 'unmetered') return false;
+  return connectionMetered;
+}
+
+/** Whether the browser reports a metered connection, updated as it changes */
+const connectionMetered = readable(connectionLooksMetered(getConnection()), (set) => {
+  const connection = getConnection();
+  if (!connection?.addEventListener) return;
+  const update = () => set(connectionLooksMetered(connection));
+  connection.addEventListener('change', update);
+  return () => connection.removeEventListener?.('change', update);
+});
+
+/** Whether to treat the connection as metered, after the user's override */
+export const isMetered: Readable<boolean> = derived(
+  [miscSettings, connectionMetered],
+  ([$miscSettings, $connectionMetered]) =>
+    resolveMetered($miscSettings.meteredConnection, $connectionMetered)
+);
@@ -1,4 +1,5 @@
 import { writable } from 'svelte/store';
+import type { VolumeMetadata } from '$lib/types';
 
 type CheckboxOption = {
   label: string;
@@ -81,6 +82,8 @@ type ImageOnlyImportModal = {
   totalVolumes: number;
   onConfirm?: () => void;
   onCancel?: () => void;
+  /** Offers running OCR on the volumes when set */
+  onOcr?: () => void;
 };
 
 export const imageOnlyImportModalStore = writable<ImageOnlyImportModal | undefined>(undefined);
@@ -89,14 +92,16 @@ export function promptImageOnlyImport(
   seriesList: SeriesImportInfo[],
   totalVolumes: number,
   onConfirm?: () => void,
-  onCancel?: () => void
+  onCancel?: () => void,
+  onOcr?: () => void
 ) {
   imageOnlyImportModalStore.set({
     open: true,
     seriesList,
     totalVolumes,
     onConfirm,
-    onCancel
+    onCancel,
+    onOcr
   });
 }
 
@@ -224,6 +229,19 @@ export function closeVolumeEditor() {
   volumeEditorModalStore.set(undefined);
 }
 
+// Manual series grouping modal
+type SeriesGroupModal = {
+  open: boolean;
+  volumes: VolumeMetadata[];
+  currentTitle: string;
+};
+
+export const seriesGroupModalStore = writable<SeriesGroupModal | undefined>(undefined);
+
+export function promptSeriesGroup(volumes: VolumeMetadata[], currentTitle: string) {
+  seriesGroupModalStore.set({ open: true, volumes, currentTitle });
+}
+
 // Import preparing modal - shows progress while scanning/analyzing dropped files
 export type ImportPreparingPhase = 'scanning' | 'analyzing' | 'preparing';
 
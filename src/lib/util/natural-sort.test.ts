@@ -1,5 +1,5 @@
 import { describe, expect, it } from 'vitest';
-import { naturalSort } from './natural-sort';
+import { naturalSort, pageSortComparator } from './natural-sort';
 
 describe('naturalSort', () => {
   it('sorts numbers naturally', () => {
@@ -37,4 +37,27 @@ describe('naturalSort', () => {
     const files = ['img003.jpg', 'img001.jpg', 'img002.jpg'];
     expect(files.sort(naturalSort)).toEqual(['img001.jpg', 'img002.jpg', 'img003.jpg']);
   });
+
+  it('orders unpadded page numbers numerically', () => {
+    const files = ['10.jpg', '11.jpg', '1.jpg', '2.jpg'];
+    expect(files.sort(naturalSort)).toEqual(['1.jpg', '2.jpg', '10.jpg', '11.jpg']);
+  });
+
+  it('handles inconsistent zero-padding and prefixes', () => {
+    const files = ['page_10.jpg', 'page_02.jpg', 'page_1.jpg', 'page_003.jpg'];
+    expect(files.sort(naturalSort)).toEqual([
+      'page_1.jpg',
+      'page_02.jpg',
+      'page_003.jpg',
+      'page_10.jpg'
+    ]);
+  });
+});
+
+describe('pageSortComparator', () => {
+  it('uses natural order by default and strict filename order as an override', () => {
+    const files = ['10.jpg', '2.jpg', '1.jpg'];
+    expect([...files].sort(pageSortComparator('natural'))).toEqual(['1.jpg', '2.jpg', '10.jpg']);
+    expect([...files].sort(pageSortComparator('filename'))).toEqual(['1.jpg', '10.jpg', '2.jpg']);
+  });
 });
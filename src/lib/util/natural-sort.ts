@@ -43,3 +43,18 @@ export function naturalSort(a: string, b: string): number {
 
   return a.length - b.length;
 }
+
+/**
+ * Strict code-point order, for unusual naming where numbers in filenames
+ * aren't page numbers.
+ */
+export function filenameSort(a: string, b: string): number {
+  return a < b ? -1 : a > b ? 1 : 0;
+}
+
+/** How pages without a .mokuro reading order are sorted by filename */
+export type PageSortMode = 'natural' | 'filename';
+
+export function pageSortComparator(mode: PageSortMode): (a: string, b: string) => number {
+  return mode === 'filename' ? filenameSort : naturalSort;
+}
@@ -0,0 +1,168 @@
+import { describe, it, expect, beforeEach } from 'vitest';
+import 'fake-indexeddb/auto';
+import { get } from 'svelte/store';
+import type { Page, VolumeMetadata } from '$lib/types';
+import { db } from '$lib/catalog/db';
+import {
+  volumes as volumeDataStore,
+  volumesWithTrash,
+  VolumeData
+} from '$lib/settings/volume-data';
+import {
+  applyPageAdjustment,
+  applyPageAdjustments,
+  reconcilePageAdjustments,
+  rotateBy,
+  setPageAdjustment
+} from './page-adjustments';
+
+function createPage(): Page {
+  return {
+    version: '0.2.0',
+    img_width: 800,
+    img_height: 1200,
+    img_path: '001.jpg',
+    blocks: [
+      {
+        box: [100, 200, 300, 600],
+        vertical: true,
+        font_size: 40,
+        lines: ['セリフ'],
+        lines_coords: [
+          [
+            [100, 200],
+            [300, 200],
+            [300, 600],
+            [100, 600]
+          ]
+        ]
+      }
+    ]
+  };
+}
+
+function createVolume(uuid: string): VolumeMetadata {
+  return {
+    mokuro_version: '0.2.0',
+    series_title: 'Series',
+    series_uuid: 'series-uuid',
+    volume_title: `Series ${uuid}`,
+    volume_uuid: uuid,
+    page_count: 1,
+    character_count: 0,
+    page_char_counts: []
+  };
+}
+
+describe('applyPageAdjustment', () => {
+  it('returns the page untouched without an adjustment', () => {
+    const page = createPage();
+
+    expect(applyPageAdjustment(page, undefined)).toBe(page);
+    expect(applyPageAdjustment(page, { rotation: 0 })).toBe(page);
+  });
+
+  it('swaps dimensions and maps OCR boxes for a quarter turn', () => {
+    const adjusted = applyPageAdjustment(createPage(), { rotation: 90 });
+
+    expect([adjusted.img_width, adjusted.img_height]).toEqual([1200, 800]);
+    expect(adjusted.blocks[0].box).toEqual([600, 100, 1000, 300]);
+    expect(adjusted.blocks[0].vertical).toBe(false);
+    expect(adjusted.blocks[0].lines_coords?.[0][0]).toEqual([1000, 100]);
+    expect(adjusted.imageTransform).toEqual({
+      width: 800,
+      height: 1200,
+      matrix: 'matrix(0, 1, -1, 0, 1200, 0)'
+    });
+  });
+
+  it('maps OCR boxes for half and three-quarter turns', () => {
+    const half = applyPageAdjustment(createPage(), { rotation: 180 });
+    const threeQuarter = applyPageAdjustment(createPage(), { rotation: 270 });
+
+    expect([half.img_width, half.img_height]).toEqual([800, 1200]);
+    expect(half.blocks[0].box).toEqual([500, 600, 700, 1000]);
+    expect(half.blocks[0].vertical).toBe(true);
+    expect([threeQuarter.img_width, threeQuarter.img_height]).toEqual([1200, 800]);
+    expect(threeQuarter.blocks[0].box).toEqual([200, 500, 600, 700]);
+  });
+
+  it('trims the page and drops text outside the crop', () => {
+    const page = createPage();
+    page.blocks.push({ box: [0, 0, 40, 90], vertical: true, font_size: 20, lines: ['ノイズ'] });
+
+    const adjusted = applyPageAdjustment(page, {
+      crop: { x: 50, y: 100, width: 700, height: 1000 }
+    });
+
+    expect([adjusted.img_width, adjusted.img_height]).toEqual([700, 1000]);
+    expect(adjusted.blocks).toHaveLength(1);
+    expect(adjusted.blocks[0].box).toEqual([50, 100, 250, 500]);
+  });
+
+  it('trims before rotating', () => {
+    const adjusted = applyPageAdjustment(createPage(), {
+      rotation: 90,
+      crop: { x: 50, y: 100, width: 700, height: 1000 }
+    });
+
+    expect([adjusted.img_width, adjusted.img_height]).toEqual([1000, 700]);
+    expect(adjusted.blocks[0].box).toEqual([500, 50, 900, 250]);
+  });
+
+  it('only adjusts pages with an entry', () => {
+    const pages = [createPage(), { ...createPage(), img_path: '002.jpg' }];
+
+    const adjusted = applyPageAdjustments(pages, { '002.jpg': { rotation: 90 } });
+
+    expect(adjusted[0]).toBe(pages[0]);
+    expect(adjusted[1].img_width).toBe(1200);
+  });
+});
+
+describe('rotateBy', () => {
+  it('wraps around in both directions', () => {
+    expect(rotateBy(undefined, -1)).toBe(270);
+    expect(rotateBy(270, 1)).toBe(0);
+    expect(rotateBy(90, 1)).toBe(180);
+  });
+});
+
+describe('page adjustment storage', () => {
+  beforeEach(async () => {
+    await db.volumes.clear();
+    volumesWithTrash.set({});
+  });
+
+  it('saves adjustments on the volume and in synced volume data', async () => {
+    await db.volumes.add(createVolume('v1'));
+
+    await setPageAdjustment('v1', '001.jpg', { rotation: 90 });
+
+    const expected = { '001.jpg': { rotation: 90 } };
+    expect((await db.volumes.get('v1'))?.page_adjustments).toEqual(expected);
+    expect(get(volumeDataStore)['v1'].toJSON().page_adjustments).toEqual(expected);
+  });
+
+  it('clears a page reset to its original state', async () => {
+    await db.volumes.add(createVolume('v1'));
+    await setPageAdjustment('v1', '001.jpg', { rotation: 90 });
+
+    await setPageAdjustment('v1', '001.jpg', { rotation: 0 });
+
+    expect((await db.volumes.get('v1'))?.page_adjustments).toBeUndefined();
+    expect(get(volumeDataStore)['v1'].toJSON().page_adjustments).toBeUndefined();
+  });
+
+  it('applies adjustments synced from another device', async () => {
+    await db.volumes.add(createVolume('v1'));
+    const adjustments = { '001.jpg': { rotation: 180 as const } };
+
+    const changed = await reconcilePageAdjustments({
+      v1: new VolumeData({ page_adjustments: adjustments })
+    });
+
+    expect(changed).toBe(1);
+    expect((await db.volumes.get('v1'))?.page_adjustments).toEqual(adjustments);
+  });
+});
@@ -0,0 +1,221 @@
+/**
+ * Per-page rotation and trim.
+ *
+ * Scans are sometimes sideways or carry wide scanner margins. Each page can be
+ * rotated in 90° steps and trimmed to a crop rectangle; the adjustment lives on
+ * the volume row and in volume-data (which syncs), keyed by image path.
+ *
+ * Adjustments are applied at display time only. The reader swaps in adjusted
+ * pages whose dimensions and OCR coordinates match what's on screen, so
+ * layout, zoom and page turns work unchanged. Stored OCR is never rewritten.
+ */
+
+import { db } from '$lib/catalog/db';
+import { updateVolumePageAdjustments, type VolumeData } from '$lib/settings/volume-data';
+import type {
+  Block,
+  Page,
+  PageAdjustment,
+  PageCrop,
+  PageRotation,
+  VolumeMetadata
+} from '$lib/types';
+
+/** CSS matrix(a, b, c, d, e, f): x' = a·x + c·y + e, y' = b·x + d·y + f */
+type Matrix = [number, number, number, number, number, number];
+
+/** How to draw the original image inside an adjusted page */
+export type ImageTransform = {
+  /** Original image size */
+  width: number;
+  height: number;
+  /** CSS transform mapping original pixels onto the adjusted page */
+  matrix: string;
+};
+
+/** A page as displayed, with its adjustment baked in */
+export type AdjustedPage = Page & {
+  imageTransform?: ImageTransform;
+  /** Stored OCR box of each displayed block, by index in `blocks` */
+  sourceBoxes?: number[][];
+};
+
+/** Smallest crop edge kept, in pixels, so a page never collapses */
+const MIN_CROP_SIZE = 16;
+
+export function isIdentityAdjustment(adjustment: PageAdjustment | undefined): boolean {
+  return !adjustment || (!adjustment.rotation && !adjustment.crop);
+}
+
+/** Rotation after turning `steps` quarter turns clockwise (negative = counter-clockwise) */
+export function rotateBy(rotation: PageRotation | undefined, steps: number): PageRotation {
+  const turns = ((((rotation ?? 0) / 90 + steps) % 4) + 4) % 4;
+  return (turns * 90) as PageRotation;
+}
+
+/** The crop rectangle clamped to the image, or the full image when there is none */
+export function clampCrop(page: Page, crop: PageCrop | undefined): PageCrop {
+  if (!crop) return { x: 0, y: 0, width: page.img_width, height: page.img_height };
+
+  const x = Math.min(Math.max(0, crop.x), page.img_width - MIN_CROP_SIZE);
+  const y = Math.min(Math.max(0, crop.y), page.img_height - MIN_CROP_SIZE);
+  return {
+    x,
+    y,
+    width: Math.min(Math.max(MIN_CROP_SIZE, crop.width), page.img_width - x),
+    height: Math.min(Math.max(MIN_CROP_SIZE, crop.height), page.img_height - y)
+  };
+}
+
+/** Matrix that crops, then rotates clockwise so the result starts at the origin */
+function adjustmentMatrix(crop: PageCrop, rotation: PageRotation): Matrix {
+  const { x, y, width, height } = crop;
+  switch (rotation) {
+    case 90:
+      return [0, 1, -1, 0, height + y, -x];
+    case 180:
+      return [-1, 0, 0, -1, width + x, height + y];
+    case 270:
+      return [0, -1, 1, 0, -y, width + x];
+    default:
+      return [1, 0, 0, 1, -x, -y];
+  }
+}
+
+function transformPoint([a, b, c, d, e, f]: Matrix, x: number, y: number): [number, number] {
+  return [a * x + c * y + e, b * x + d * y + f];
+}
+
+/** The block in adjusted coordinates, or null when it lies outside the crop */
+function transformBlock(
+  block: Block,
+  matrix: Matrix,
+  crop: PageCrop,
+  quarterTurn: boolean
+): Block | null {
+  const [xmin, ymin, xmax, ymax] = block.box;
+  const left = Math.max(xmin, crop.x);
+  const top = Math.max(ymin, crop.y);
+  const right = Math.min(xmax, crop.x + crop.width);
+  const bottom = Math.min(ymax, crop.y + crop.height);
+  if (right <= left || bottom <= top) return null;
+
+  const [x1, y1] = transformPoint(matrix, left, top);
+  const [x2, y2] = transformPoint(matrix, right, bottom);
+
+  return {
+    ...block,
+    box: [Math.min(x1, x2), Math.min(y1, y2), Math.max(x1, x2), Math.max(y1, y2)],
+    // Vertical text turned a quarter reads horizontally on screen, and vice versa
+    vertical: quarterTurn ? !block.vertical : block.vertical,
+    lines_coords: block.lines_coords?.map((quad) =>
+      quad.map(([px, py]) => transformPoint(matrix, px, py))
+    )
+  };
+}
+
+/**
+ * The page as displayed with `adjustment`: trimmed and rotated dimensions, OCR
+ * boxes mapped to match (blocks cropped away are dropped), and the transform
+ * for drawing the original image. Returns the page itself when unadjusted.
+ */
+export function applyPageAdjustment(
+  page: Page,
+  adjustment: PageAdjustment | undefined
+): AdjustedPage {
+  if (isIdentityAdjustment(adjustment) || !page.img_width || !page.img_height) return page;
+
+  const rotation = adjustment?.rotation ?? 0;
+  const crop = clampCrop(page, adjustment?.crop);
+  const matrix = adjustmentMatrix(crop, rotation);
+  const quarterTurn = rotation === 90 || rotation === 270;
+
+  const blocks: Block[] = [];
+  const sourceBoxes: number[][] = [];
+  for (const block of page.blocks) {
+    const transformed = transformBlock(block, matrix, crop, quarterTurn);
+    if (!transformed) continue;
+    blocks.push(transformed);
+    sourceBoxes.push(block.box);
+  }
+
+  return {
+    ...page,
+    img_width: quarterTurn ? crop.height : crop.width,
+    img_height: quarterTurn ? crop.width : crop.height,
+    blocks,
+    sourceBoxes,
+    imageTransform: {
+      width: page.img_width,
+      height: page.img_height,
+      matrix: `matrix(${matrix.join(', ')})`
+    }
+  };
+}
+
+/**
+ * The stored OCR box of a displayed block. Highlights are kept in stored
+ * coordinates so they survive a page being rotated or trimmed later.
+ */
+export function sourceBox(page: AdjustedPage, blockIndex: number): number[] {
+  return page.sourceBoxes?.[blockIndex] ?? page.blocks[blockIndex].box;
+}
+
+/** All pages of a volume with their adjustments applied */
+export function applyPageAdjustments(
+  pages: Page[],
+  adjustments: Record<string, PageAdjustment> | undefined
+): AdjustedPage[] {
+  if (!adjustments || Object.keys(adjustments).length === 0) return pages;
+  return pages.map((page) => applyPageAdjustment(page, adjustments[page.img_path]));
+}
+
+/** Save (or clear, for an identity adjustment) one page's adjustment */
+export async function setPageAdjustment(
+  volumeUuid: string,
+  imgPath: string,
+  adjustment: PageAdjustment | undefined
+): Promise<void> {
+  const volume = await db.volumes.get(volumeUuid);
+  if (!volume) return;
+
+  const adjustments = { ...volume.page_adjustments };
+  if (isIdentityAdjustment(adjustment)) {
+    delete adjustments[imgPath];
+  } else {
+    adjustments[imgPath] = adjustment!;
+  }
+
+  const next = Object.keys(adjustments).length > 0 ? adjustments : undefined;
+  await db.volumes.update(volumeUuid, { page_adjustments: next });
+  updateVolumePageAdjustments(volumeUuid, next);
+}
+
+/** Copy synced page adjustments onto a catalog volume; true if it changed */
+export function applySyncedPageAdjustments(volume: VolumeMetadata, data: VolumeData): boolean {
+  if (JSON.stringify(volume.page_adjustments) === JSON.stringify(data.page_adjustments)) {
+    return false;
+  }
+  volume.page_adjustments = data.page_adjustments;
+  return true;
+}
+
+/**
+ * Apply page adjustments from synced volume data to the local catalog.
+ * Called after volume-data sync so pages fixed on another device show up fixed here.
+ */
+export async function reconcilePageAdjustments(
+  volumeData: Record<string, VolumeData>
+): Promise<number> {
+  let changed = 0;
+
+  await db.transaction('rw', db.volumes, async () => {
+    await db.volumes.toCollection().modify((volume) => {
+      const data = volumeData[volume.volume_uuid];
+      if (!data || data.deletedOn) return;
+      if (applySyncedPageAdjustments(volume, data)) changed++;
+    });
+  });
+
+  return changed;
+}
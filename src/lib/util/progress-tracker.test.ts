@@ -0,0 +1,75 @@
+import { beforeEach, describe, expect, it } from 'vitest';
+import { get } from 'svelte/store';
+import { progressSummary, progressTrackerStore, summarizeProcesses } from './progress-tracker';
+
+describe('progressTrackerStore', () => {
+  beforeEach(() => {
+    progressTrackerStore.clearAll();
+  });
+
+  it('tracks an import and a download as separate tasks with independent progress', () => {
+    progressTrackerStore.addProcess({
+      id: 'import-1',
+      description: 'Importing Vol 1',
+      progress: 0
+    });
+    progressTrackerStore.addProcess({
+      id: 'download-abc',
+      description: 'Downloading Vol 2',
+      progress: 0
+    });
+
+    progressTrackerStore.updateProcess('import-1', { progress: 80, status: 'Saving...' });
+    progressTrackerStore.updateProcess('download-abc', { progress: 20 });
+
+    const { processes } = get(progressTrackerStore);
+    expect(processes.map((p) => [p.id, p.progress])).toEqual([
+      ['import-1', 80],
+      ['download-abc', 20]
+    ]);
+    expect(get(progressSummary)).toMatchObject({ total: 2, running: 2, progress: 50 });
+  });
+
+  it('completes and fails tasks without removing them', () => {
+    progressTrackerStore.addProcess({
+      id: 'export',
+      description: 'Exporting',
+      progress: 40,
+      onCancel: () => {}
+    });
+    progressTrackerStore.addProcess({ id: 'sync', description: 'Syncing', progress: 10 });
+
+    progressTrackerStore.failProcess('export', 'Error: disk full');
+    progressTrackerStore.completeProcess('sync');
+
+    const [exportTask, syncTask] = get(progressTrackerStore).processes;
+    expect(exportTask).toMatchObject({ state: 'error', status: 'Error: disk full' });
+    expect(exportTask.onCancel).toBeUndefined();
+    expect(syncTask).toMatchObject({ state: 'done', status: 'Complete', progress: 100 });
+    expect(get(progressSummary)).toMatchObject({ done: 1, failed: 1, progress: null });
+  });
+});
+
+describe('summarizeProcesses', () => {
+  it('averages only unfinished determinate tasks', () => {
+    const summary = summarizeProcesses([
+      { id: 'a', description: 'A', progress: 30 },
+      { id: 'b', description: 'B', progress: 70, state: 'paused' },
+      { id: 'c', description: 'C', progress: 0, indeterminate: true },
+      { id: 'd', description: 'D', progress: 100, state: 'done' }
+    ]);
+
+    expect(summary).toEqual({
+      total: 4,
+      running: 2,
+      paused: 1,
+      done: 1,
+      failed: 0,
+      progress: 50
+    });
+  });
+
+  it('reports no progress for an empty tracker', () => {
+    expect(summarizeProcesses([]).progress).toBeNull();
+  });
+});
@@ -1,18 +1,76 @@
-import { writable } from 'svelte/store';
+import { derived, writable } from 'svelte/store';
 
+/** Lifecycle of a tracked task; tasks without one are running */
+export type TaskState = 'running' | 'paused' | 'done' | 'error';
+
+/**
+ * A long-running task shown in the global progress tracker. Any feature
+ * (imports, OCR, downloads, uploads, exports, sync) registers one under a
+ * unique ID and updates it as it goes.
+ */
 export interface Process {
   id: string;
   description: string;
   status?: string;
+  /** Percent complete, 0-100; ignored while `indeterminate` */
   progress: number;
+  /** The task can't estimate how far along it is */
+  indeterminate?: boolean;
+  state?: TaskState;
+  /** Items done and in total, e.g. pages OCR'd or volumes downloaded */
+  current?: number;
+  total?: number;
   bytesLoaded?: number;
   totalBytes?: number;
+  /** Shows a cancel button in the tracker when set */
+  onCancel?: () => void;
+  /** Show pause/resume buttons in the tracker when set */
+  onPause?: () => void;
+  onResume?: () => void;
 }
 
 interface ProgressTrackerState {
   processes: Process[];
 }
 
+export interface ProgressSummary {
+  total: number;
+  running: number;
+  paused: number;
+  done: number;
+  failed: number;
+  /** Mean percent of unfinished tasks that report progress; null if none do */
+  progress: number | null;
+}
+
+export function summarizeProcesses(processes: Process[]): ProgressSummary {
+  const summary: ProgressSummary = {
+    total: processes.length,
+    running: 0,
+    paused: 0,
+    done: 0,
+    failed: 0,
+    progress: null
+  };
+  const percents: number[] = [];
+
+  for (const process of processes) {
+    const state = process.state ?? 'running';
+    if (state === 'done') summary.done++;
+    else if (state === 'error') summary.failed++;
+    else {
+      if (state === 'paused') summary.paused++;
+      else summary.running++;
+      if (!process.indeterminate) percents.push(process.progress);
+    }
+  }
+
+  if (percents.length > 0) {
+    summary.progress = percents.reduce((sum, percent) => sum + percent, 0) / percents.length;
+  }
+  return summary;
+}
+
 function createProgressTrackerStore() {
   const initialState: ProgressTrackerState = {
     processes: []
@@ -54,6 +112,35 @@ function createProgressTrackerStore() {
         return state;
       });
     },
+    /** Mark a task finished; callers still remove it once it has been seen */
+    completeProcess: (id: string, status = 'Complete') => {
+      update((state) => ({
+        ...state,
+        processes: state.processes.map((p) =>
+          p.id === id
+            ? { ...p, state: 'done' as const, status, progress: 100, indeterminate: false }
+            : p
+        )
+      }));
+    },
+    /** Mark a task failed, dropping its controls */
+    failProcess: (id: string, status: string) => {
+      update((state) => ({
+        ...state,
+        processes: state.processes.map((p) =>
+          p.id === id
+            ? {
+                ...p,
+                state: 'error' as const,
+                status,
+                onCancel: undefined,
+                onPause: undefined,
+                onResume: undefined
+              }
+            : p
+        )
+      }));
+    },
     removeProcess: (id: string) => {
       update((state) => {
         return {
@@ -69,3 +156,8 @@ function createProgressTrackerStore() {
 }
 
 export const progressTrackerStore = createProgressTrackerStore();
+
+/** Aggregate counts and progress across every tracked task */
+export const progressSummary = derived(progressTrackerStore, ($state) =>
+  summarizeProcesses($state.processes)
+);
@@ -0,0 +1,142 @@
+import { beforeEach, describe, expect, it, vi } from 'vitest';
+import 'fake-indexeddb/auto';
+
+vi.mock('$lib/util/sync/unified-sync-service', () => ({
+  reconcileCatalog: vi.fn(async () => {})
+}));
+
+import { VolumeData, volumesWithTrash } from '$lib/settings/volume-data';
+import {
+  backupFileName,
+  backupsToPrune,
+  mergeRestoredVolumes,
+  parseBackup,
+  writeBackup
+} from './reader-state-backup';
+
+/** In-memory directory handle with just what writeBackup uses */
+function makeFakeFolder() {
+  const files = new Map<string, string>();
+  const folder = {
+    kind: 'directory',
+    name: 'Backups',
+    async getFileHandle(name: string) {
+      return {
+        async createWritable() {
+          let text = '';
+          return {
+            async write(chunk: string) {
+              text += chunk;
+            },
+            async close() {
+              files.set(name, text);
+            }
+          };
+        }
+      };
+    },
+    async *values() {
+      for (const name of files.keys()) yield { kind: 'file', name };
+    },
+    async removeEntry(name: string) {
+      files.delete(name);
+    }
+  };
+  return { files, folder: folder as unknown as FileSystemDirectoryHandle };
+}
+
+describe('backup file names', () => {
+  it('are timestamped so they sort by time', () => {
+    const earlier = backupFileName(new Date('2026-10-15T09:30:00.000Z'));
+    const later = backupFileName(new Date('2026-10-15T10:00:00.000Z'));
+
+    expect(earlier).toBe('mokuro-reader-state-2026-10-15T09-30-00-000Z.json');
+    expect([later, earlier].sort()).toEqual([earlier, later]);
+  });
+
+  it('prunes all but the newest backups and leaves other files alone', () => {
+    const names = [
+      backupFileName(new Date('2026-01-01T00:00:00Z')),
+      backupFileName(new Date('2026-01-03T00:00:00Z')),
+      'notes.txt',
+      backupFileName(new Date('2026-01-02T00:00:00Z'))
+    ];
+
+    expect(backupsToPrune(names, 2)).toEqual([names[0]]);
+    // At least one backup is always kept
+    expect(backupsToPrune(names, 0)).toHaveLength(2);
+  });
+});
+
+describe('mergeRestoredVolumes', () => {
+  it('brings back progress lost in a wipe', () => {
+    const backup = {
+      v1: { progress: 42, lastProgressUpdate: '2026-10-01T00:00:00.000Z' }
+    };
+
+    const { volumes, restored } = mergeRestoredVolumes({}, backup);
+
+    expect(restored).toBe(1);
+    expect(volumes.v1.progress).toBe(42);
+  });
+
+  it('keeps local progress made after the backup', () => {
+    const local = {
+      v1: new VolumeData({ progress: 80, lastProgressUpdate: '2026-10-10T00:00:00.000Z' })
+    };
+    const backup = {
+      v1: { progress: 42, lastProgressUpdate: '2026-10-01T00:00:00.000Z' }
+    };
+
+    const { volumes, restored } = mergeRestoredVolumes(local, backup);
+
+    expect(restored).toBe(0);
+    expect(volumes.v1.progress).toBe(80);
+  });
+
+  it('restores over a local deletion but not a backed-up one', () => {
+    const local = {
+      v1: new VolumeData({ deletedOn: '2026-10-10T00:00:00.000Z' })
+    };
+    const backup = {
+      v1: { progress: 42, lastProgressUpdate: '2026-10-01T00:00:00.000Z' },
+      v2: { deletedOn: '2026-10-01T00:00:00.000Z' }
+    };
+
+    const { volumes, restored } = mergeRestoredVolumes(local, backup);
+
+    expect(restored).toBe(1);
+    expect(volumes.v1.progress).toBe(42);
+    expect(volumes.v2).toBeUndefined();
+  });
+});
+
+describe('parseBackup', () => {
+  it('rejects files that are not backups', () => {
+    expect(() => parseBackup('{"progress":1}')).toThrow();
+    expect(() => parseBackup('not json')).toThrow();
+    expect(parseBackup('{"version":1,"createdAt":"","volumes":{}}').volumes).toEqual({});
+  });
+});
+
+describe('writeBackup', () => {
+  beforeEach(() => {
+    localStorage.clear();
+    volumesWithTrash.set({
+      v1: new VolumeData({ progress: 10, lastProgressUpdate: '2026-10-01T00:00:00.000Z' })
+    });
+  });
+
+  it('writes a restorable backup and skips unchanged state', async () => {
+    const { files, folder } = makeFakeFolder();
+
+    const name = await writeBackup(folder);
+
+    expect(name).not.toBeNull();
+    expect(parseBackup(files.get(name!)!).volumes).toEqual({
+      v1: { progress: 10, lastProgressUpdate: '2026-10-01T00:00:00.000Z' }
+    });
+    expect(await writeBackup(folder)).toBeNull();
+    expect(files.size).toBe(1);
+  });
+});
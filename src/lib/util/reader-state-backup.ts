@@ -0,0 +1,208 @@
+/**
+ * Local backups of reader state.
+ *
+ * Writes the per-volume data that syncs as volume-data.json (progress, stats,
+ * notes, chapter markers...) to a folder the user picked, one timestamped
+ * file per backup, keeping the newest few. Needs the File System Access API
+ * (Chromium), like the Local Folder provider; restoring reads a plain file
+ * and works everywhere.
+ */
+
+import { get } from 'svelte/store';
+import { miscSettings } from '$lib/settings/misc';
+import { VolumeData, volumesWithTrash } from '$lib/settings/volume-data';
+import {
+  loadBackupHandle,
+  saveBackupHandle
+} from '$lib/util/sync/providers/filesystem/handle-store';
+import { reconcileCatalog } from '$lib/util/sync/unified-sync-service';
+
+export const BACKUP_PREFIX = 'mokuro-reader-state-';
+
+/** Hash of the last backup's contents, to skip backups when nothing changed */
+const LAST_BACKUP_KEY = 'readerStateBackupHash';
+
+export interface ReaderStateBackup {
+  version: 1;
+  createdAt: string;
+  volumes: Record<string, unknown>;
+}
+
+/** mokuro-reader-state-2026-10-15T09-30-00-000Z.json — sorts by time */
+export function backupFileName(date: Date): string {
+  return `${BACKUP_PREFIX}${date.toISOString().replace(/[:.]/g, '-')}.json`;
+}
+
+/** Backup files beyond the newest `keep`, oldest last */
+export function backupsToPrune(names: string[], keep: number): string[] {
+  return names
+    .filter((name) => name.startsWith(BACKUP_PREFIX) && name.endsWith('.json'))
+    .sort()
+    .reverse()
+    .slice(Math.max(1, keep));
+}
+
+/** Serialized volume data, the part of a backup that changes with reading */
+export function serializeVolumes(volumes: Record<string, VolumeData>): string {
+  return JSON.stringify(
+    Object.fromEntries(Object.entries(volumes).map(([uuid, data]) => [uuid, data.toJSON()]))
+  );
+}
+
+/** FNV-1a; only compares one backup with the next */
+function hashString(text: string): string {
+  let hash = 0x811c9dc5;
+  for (let i = 0; i < text.length; i++) {
+    hash ^= text.charCodeAt(i);
+    hash = Math.imul(hash, 0x01000193);
+  }
+  return (hash >>> 0).toString(16);
+}
+
+/**
+ * Merge a backup into local volume data. A backed-up volume replaces the
+ * local one when it is missing, deleted, or has older progress — so
+ * restoring after a wipe brings everything back without undoing reading
+ * done since the backup.
+ */
+export function mergeRestoredVolumes(
+  local: Record<string, VolumeData>,
+  restored: Record<string, unknown>
+): { volumes: Record<string, VolumeData>; restored: number } {
+  const volumes = { ...local };
+  let count = 0;
+
+  for (const [uuid, json] of Object.entries(restored)) {
+    const data = VolumeData.fromJSON(json);
+    if (data.deletedOn) continue;
+    const current = volumes[uuid];
+    if (
+      current &&
+      !current.deletedOn &&
+      Date.parse(current.lastProgressUpdate) >= Date.parse(data.lastProgressUpdate)
+    ) {
+      continue;
+    }
+    volumes[uuid] = data;
+    count++;
+  }
+
+  return { volumes, restored: count };
+}
+
+/** Parse a backup file; throws on anything that isn't one */
+export function parseBackup(text: string): ReaderStateBackup {
+  const parsed = JSON.parse(text);
+  if (parsed?.version !== 1 || typeof parsed.volumes !== 'object' || !parsed.volumes) {
+    throw new Error('Not a reader state backup');
+  }
+  return parsed as ReaderStateBackup;
+}
+
+/** Let the user pick the backup folder and remember it */
+export async function chooseBackupFolder(): Promise<FileSystemDirectoryHandle> {
+  // @ts-expect-error — File System Access API is Chromium-only, no lib.dom typing in all TS targets
+  const handle: FileSystemDirectoryHandle = await window.showDirectoryPicker({
+    mode: 'readwrite'
+  });
+  await saveBackupHandle(handle);
+  return handle;
+}
+
+/**
+ * The saved backup folder if it is still writable. With `prompt`, asks for
+ * permission again (needs a user gesture); otherwise gives up quietly.
+ */
+export async function getBackupFolder(prompt = false): Promise<FileSystemDirectoryHandle | null> {
+  const handle = await loadBackupHandle();
+  if (!handle) return null;
+  try {
+    // @ts-expect-error — queryPermission/requestPermission are Chromium-only
+    let permission = await handle.queryPermission({ mode: 'readwrite' });
+    if (permission === 'prompt' && prompt) {
+      // @ts-expect-error — see above
+      permission = await handle.requestPermission({ mode: 'readwrite' });
+    }
+    return permission === 'granted' ? handle : null;
+  } catch (error) {
+    // The folder was moved or deleted
+    console.warn('Reader state backup folder is unusable:', error);
+    return null;
+  }
+}
+
+/**
+ * Write a backup to `folder` and prune old ones. Returns the file name, or
+ * null when nothing changed since the last backup (unless `force`).
+ */
+export async function writeBackup(
+  folder: FileSystemDirectoryHandle,
+  force = false
+): Promise<string | null> {
+  const volumesJson = serializeVolumes(get(volumesWithTrash));
+  const hash = hashString(volumesJson);
+  if (!force && localStorage.getItem(LAST_BACKUP_KEY) === hash) return null;
+
+  const now = new Date();
+  const name = backupFileName(now);
+  const file = await folder.getFileHandle(name, { create: true });
+  const writable = await file.createWritable();
+  await writable.write(
+    `{"version":1,"createdAt":${JSON.stringify(now.toISOString())},"volumes":${volumesJson}}`
+  );
+  await writable.close();
+  localStorage.setItem(LAST_BACKUP_KEY, hash);
+
+  const names: string[] = [];
+  // @ts-expect-error — values() is defined on FileSystemDirectoryHandle at runtime
+  for await (const entry of folder.values()) {
+    if (entry.kind === 'file') names.push(entry.name);
+  }
+  for (const old of backupsToPrune(names, get(miscSettings).readerStateBackupKeep)) {
+    await folder.removeEntry(old).catch((error) => {
+      console.warn(`Failed to remove old backup ${old}:`, error);
+    });
+  }
+
+  return name;
+}
+
+/** Restore volume data from a backup file. Returns how many volumes changed. */
+export async function restoreBackup(file: File): Promise<number> {
+  const backup = parseBackup(await file.text());
+  const { volumes, restored } = mergeRestoredVolumes(get(volumesWithTrash), backup.volumes);
+  volumesWithTrash.set(volumes);
+  await reconcileCatalog(volumes);
+  return restored;
+}
+
+/**
+ * Back up on the interval set in settings while a folder is set up.
+ * Returns a function that stops it.
+ */
+export function startReaderStateBackups(): () => void {
+  let intervalId: ReturnType<typeof setInterval> | null = null;
+  let currentMinutes = 0;
+
+  const unsubscribe = miscSettings.subscribe(({ readerStateBackupMinutes }) => {
+    if (readerStateBackupMinutes === currentMinutes) return;
+    if (intervalId !== null) clearInterval(intervalId);
+    intervalId = null;
+    currentMinutes = readerStateBackupMinutes;
+    if (readerStateBackupMinutes <= 0) return;
+
+    intervalId = setInterval(async () => {
+      try {
+        const folder = await getBackupFolder();
+        if (folder) await writeBackup(folder);
+      } catch (error) {
+        console.error('Reader state backup failed:', error);
+      }
+    }, readerStateBackupMinutes * 60 * 1000);
+  });
+
+  return () => {
+    unsubscribe();
+    if (intervalId !== null) clearInterval(intervalId);
+  };
+}
@@ -0,0 +1,159 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest';
+import type { VolumeMetadata } from '$lib/types';
+
+const queueVolume = vi.fn();
+const cancelQueuedVolume = vi.fn();
+const queued = new Set<string>();
+
+vi.mock('./download-queue', () => ({
+  queueVolume: (...args: unknown[]) => queueVolume(...args),
+  cancelQueuedVolume: (...args: unknown[]) => cancelQueuedVolume(...args),
+  isVolumeInQueue: (uuid: string) => queued.has(uuid)
+}));
+
+import { miscSettings } from '$lib/settings/misc';
+import {
+  isNearVolumeEnd,
+  nextPlaceholderVolume,
+  prefetchNextVolume,
+  releasePrefetch
+} from './reading-prefetch';
+
+function createVolume(uuid: string, isPlaceholder = false): VolumeMetadata {
+  return {
+    mokuro_version: '0.2.0',
+    series_title: 'Series',
+    series_uuid: 'series-uuid',
+    volume_title: `Series ${uuid}`,
+    volume_uuid: uuid,
+    page_count: 10,
+    character_count: 0,
+    page_char_counts: [],
+    isPlaceholder,
+    cloudProvider: isPlaceholder ? 'google-drive' : undefined,
+    cloudFileId: isPlaceholder ? `file-${uuid}` : undefined,
+    cloudSize: isPlaceholder ? 100 * 1024 * 1024 : undefined
+  };
+}
+
+function setPolicy(cloudDownloadPolicy: 'manual' | 'reading') {
+  miscSettings.update((settings) => ({ ...settings, cloudDownloadPolicy }));
+}
+
+describe('nextPlaceholderVolume', () => {
+  it('returns the following volume only while it is a placeholder', () => {
+    const series = [createVolume('v1'), createVolume('v2', true), createVolume('v3')];
+
+    expect(nextPlaceholderVolume(series, 'v1')?.volume_uuid).toBe('v2');
+    expect(nextPlaceholderVolume(series, 'v2')).toBeUndefined();
+    expect(nextPlaceholderVolume(series, 'v3')).toBeUndefined();
+  });
+});
+
+describe('isNearVolumeEnd', () => {
+  it('starts fetching ahead at 90% of the volume', () => {
+    expect(isNearVolumeEnd(89, 100)).toBe(false);
+    expect(isNearVolumeEnd(90, 100)).toBe(true);
+    expect(isNearVolumeEnd(1, 0)).toBe(false);
+  });
+});
+
+describe('prefetchNextVolume', () => {
+  beforeEach(() => {
+    queueVolume.mockClear();
+    cancelQueuedVolume.mockClear();
+    queued.clear();
+    vi.unstubAllGlobals();
+  });
+
+  it('leaves new cloud volumes as placeholders with the metadata-only policy', async () => {
+    setPolicy('manual');
+    const series = [createVolume('a1'), createVolume('a2', true)];
+
+    expect(await prefetchNextVolume(series, 'a1')).toBeUndefined();
+    expect(queueVolume).not.toHaveBeenCalled();
+  });
+
+  it('downloads the next volume in the background with the reading policy', async () => {
+    setPolicy('reading');
+    const series = [createVolume('b1'), createVolume('b2', true), createVolume('b3', true)];
+
+    await prefetchNextVolume(series, 'b1');
+
+    expect(queueVolume).toHaveBeenCalledTimes(1);
+    expect(queueVolume.mock.calls[0][0].volume_uuid).toBe('b2');
+    expect(queueVolume.mock.calls[0][1]).toEqual({ background: true });
+  });
+
+  it('queues each volume once per session', async () => {
+    setPolicy('reading');
+    const series = [createVolume('c1'), createVolume('c2', true)];
+
+    await prefetchNextVolume(series, 'c1');
+    await prefetchNextVolume(series, 'c1');
+
+    expect(queueVolume).toHaveBeenCalledTimes(1);
+  });
+
+  it('waits for the user to opt in on a metered connection', async () => {
+    setPolicy('reading');
+    miscSettings.update((settings) => ({ ...settings, meteredConnection: 'metered' }));
+    const series = [createVolume('e1'), createVolume('e2', true)];
+
+    try {
+      expect(await prefetchNextVolume(series, 'e1')).toBeUndefined();
+      expect(queueVolume).not.toHaveBeenCalled();
+
+      miscSettings.update((settings) => ({ ...settings, meteredDownloadAhead: true }));
+      // The earlier attempt didn't count as fetched
+      expect((await prefetchNextVolume(series, 'e1'))?.volume_uuid).toBe('e2');
+    } finally {
+      miscSettings.update((settings) => ({
+        ...settings,
+        meteredConnection: 'auto',
+        meteredDownloadAhead: false
+      }));
+    }
+  });
+
+  it('skips the download when storage is nearly full', async () => {
+    setPolicy('reading');
+    vi.stubGlobal('navigator', {
+      storage: { estimate: async () => ({ quota: 1000 * 1024 * 1024, usage: 950 * 1024 * 1024 }) }
+    });
+    const series = [createVolume('d1'), createVolume('d2', true)];
+
+    expect(await prefetchNextVolume(series, 'd1')).toBeUndefined();
+    expect(queueVolume).not.toHaveBeenCalled();
+  });
+});
+
+describe('releasePrefetch', () => {
+  beforeEach(() => {
+    queueVolume.mockClear();
+    cancelQueuedVolume.mockClear();
+    queued.clear();
+    setPolicy('reading');
+  });
+
+  it('keeps the download when the reader moves on to the prefetched volume', async () => {
+    await prefetchNextVolume([createVolume('e1'), createVolume('e2', true)], 'e1');
+    queued.add('e2');
+    queueVolume.mockClear();
+
+    releasePrefetch('e2');
+
+    expect(cancelQueuedVolume).not.toHaveBeenCalled();
+    // Re-queued as a regular download so it jumps ahead of other background work
+    expect(queueVolume).toHaveBeenCalledWith(expect.objectContaining({ volume_uuid: 'e2' }));
+  });
+
+  it('cancels the download when the user stops reading', async () => {
+    await prefetchNextVolume([createVolume('f1'), createVolume('f2', true)], 'f1');
+    queued.add('f2');
+
+    releasePrefetch(undefined);
+
+    expect(cancelQueuedVolume).toHaveBeenCalledWith('f2');
+  });
+});
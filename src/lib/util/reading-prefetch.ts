@@ -0,0 +1,99 @@
+/**
+ * Download-ahead for the "currently reading" cloud download policy.
+ *
+ * Cloud volumes stay placeholders until the user downloads them. With the
+ * 'reading' policy, getting near the end of a volume queues the next volume of
+ * its series as a low-priority background download, so turning past the last
+ * page opens it without waiting. Leaving the reader for anything other than
+ * that volume cancels the download.
+ */
+
+import { get } from 'svelte/store';
+import { miscSettings } from '$lib/settings/misc';
+import { cancelQueuedVolume, isVolumeInQueue, queueVolume } from './download-queue';
+import { getCloudSize } from './cloud-fields';
+import { isMetered } from './metered';
+import type { VolumeMetadata } from '$lib/types';
+
+/** Share of a volume read before the next one is fetched */
+export const PREFETCH_PROGRESS = 0.9;
+
+// Volumes already queued ahead this session, so a cancelled download isn't re-queued
+const prefetched = new Set<string>();
+
+// The background download started for the volume being read, if any
+let pending: VolumeMetadata | null = null;
+
+/** The volume after `currentUuid` in series order, if it's still a placeholder */
+export function nextPlaceholderVolume(
+  seriesVolumes: VolumeMetadata[],
+  currentUuid: string
+): VolumeMetadata | undefined {
+  const index = seriesVolumes.findIndex((volume) => volume.volume_uuid === currentUuid);
+  if (index === -1) return undefined;
+  const next = seriesVolumes[index + 1];
+  return next?.isPlaceholder ? next : undefined;
+}
+
+/** Whether the reader is far enough into a volume to fetch the next one */
+export function isNearVolumeEnd(page: number, pageCount: number): boolean {
+  return pageCount > 0 && page / pageCount >= PREFETCH_PROGRESS;
+}
+
+/**
+ * Whether the browser's storage quota leaves room for a download. Volumes are
+ * briefly held twice (archive and extracted pages), so twice the size is asked for.
+ */
+async function hasRoomFor(bytes: number): Promise<boolean> {
+  if (!bytes || !navigator.storage?.estimate) return true;
+  try {
+    const { quota, usage } = await navigator.storage.estimate();
+    if (quota === undefined || usage === undefined) return true;
+    return quota - usage >= bytes * 2;
+  } catch {
+    return true;
+  }
+}
+
+/**
+ * Queue the next volume of the series being read in the background, when the
+ * download policy, a metered connection and storage quota allow it. Returns the queued volume.
+ */
+export async function prefetchNextVolume(
+  seriesVolumes: VolumeMetadata[],
+  currentUuid: string
+): Promise<VolumeMetadata | undefined> {
+  const { cloudDownloadPolicy, meteredDownloadAhead } = get(miscSettings);
+  if (cloudDownloadPolicy !== 'reading') return undefined;
+  // Mobile data: wait until the user opts in, or downloads it themselves
+  if (get(isMetered) && !meteredDownloadAhead) return undefined;
+
+  const next = nextPlaceholderVolume(seriesVolumes, currentUuid);
+  if (!next || prefetched.has(next.volume_uuid)) return undefined;
+
+  prefetched.add(next.volume_uuid);
+  if (!(await hasRoomFor(getCloudSize(next) ?? 0))) {
+    console.warn(`[Prefetch] Not enough storage to fetch ${next.volume_title} ahead`);
+    return undefined;
+  }
+
+  queueVolume(next, { background: true });
+  pending = next;
+  return next;
+}
+
+/**
+ * Called when the reader closes a volume. Opening the prefetched volume makes
+ * its download a regular one; going anywhere else cancels it.
+ */
+export function releasePrefetch(openingUuid: string | undefined): void {
+  const volume = pending;
+  pending = null;
+  if (!volume || !isVolumeInQueue(volume.volume_uuid)) return;
+
+  if (volume.volume_uuid === openingUuid) {
+    queueVolume(volume);
+  } else {
+    void cancelQueuedVolume(volume.volume_uuid);
+  }
+}
@@ -0,0 +1,75 @@
+import { describe, it, expect, beforeEach } from 'vitest';
+import 'fake-indexeddb/auto';
+import { get } from 'svelte/store';
+import type { VolumeMetadata } from '$lib/types';
+import { db } from '$lib/catalog/db';
+import {
+  volumes as volumeDataStore,
+  volumesWithTrash,
+  VolumeData
+} from '$lib/settings/volume-data';
+import { setSeriesCoverVolume, withSeriesCoverFirst, reconcileSeriesCovers } from './series-cover';
+
+function createVolume(uuid: string): VolumeMetadata {
+  return {
+    mokuro_version: '0.2.0',
+    series_title: 'Series',
+    series_uuid: 'series-uuid',
+    volume_title: `Series ${uuid}`,
+    volume_uuid: uuid,
+    page_count: 1,
+    character_count: 0,
+    page_char_counts: [0]
+  };
+}
+
+describe('series cover', () => {
+  beforeEach(async () => {
+    await db.volumes.clear();
+    volumesWithTrash.set({});
+  });
+
+  it('pins one volume per series and syncs the choice', async () => {
+    await db.volumes.bulkAdd([createVolume('v1'), createVolume('v2'), createVolume('v3')]);
+
+    await setSeriesCoverVolume(await db.volumes.toArray(), 'v2');
+    await setSeriesCoverVolume(await db.volumes.toArray(), 'v3');
+
+    const stored = await db.volumes.toArray();
+    expect(stored.filter((v) => v.series_cover).map((v) => v.volume_uuid)).toEqual(['v3']);
+    expect(get(volumeDataStore)['v2'].series_cover).toBeUndefined();
+    expect(get(volumeDataStore)['v3'].series_cover).toBe(true);
+  });
+
+  it('unpins when cleared', async () => {
+    await db.volumes.add({ ...createVolume('v1'), series_cover: true });
+
+    await setSeriesCoverVolume(await db.volumes.toArray(), null);
+
+    expect((await db.volumes.get('v1'))?.series_cover).toBeUndefined();
+  });
+
+  it('puts the pinned cover first without reordering the rest', () => {
+    const volumes = [
+      createVolume('v1'),
+      createVolume('v2'),
+      { ...createVolume('v3'), series_cover: true }
+    ];
+
+    expect(withSeriesCoverFirst(volumes).map((v) => v.volume_uuid)).toEqual(['v3', 'v1', 'v2']);
+    expect(withSeriesCoverFirst(volumes.slice(0, 2))).toEqual(volumes.slice(0, 2));
+  });
+
+  it('applies covers pinned on another device', async () => {
+    await db.volumes.bulkAdd([createVolume('v1'), { ...createVolume('v2'), series_cover: true }]);
+
+    const changed = await reconcileSeriesCovers({
+      v1: new VolumeData({ series_cover: true }),
+      v2: new VolumeData()
+    });
+
+    expect(changed).toBe(2);
+    expect((await db.volumes.get('v1'))?.series_cover).toBe(true);
+    expect((await db.volumes.get('v2'))?.series_cover).toBeUndefined();
+  });
+});
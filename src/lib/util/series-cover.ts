@@ -0,0 +1,76 @@
+/**
+ * Pinned series covers.
+ *
+ * The catalog shows a series with its first unread volume on top. Pinning a
+ * volume as the series cover keeps that volume's cover on top instead. The
+ * flag lives on the volume row and in volume-data (which syncs).
+ */
+
+import { db } from '$lib/catalog/db';
+import { updateVolumeSeriesCovers, type VolumeData } from '$lib/settings/volume-data';
+import type { VolumeMetadata } from '$lib/types';
+
+/**
+ * Pin `coverVolumeUuid` as the cover of the series made up of `seriesVolumes`,
+ * or unpin when null. At most one volume per series is pinned.
+ */
+export async function setSeriesCoverVolume(
+  seriesVolumes: VolumeMetadata[],
+  coverVolumeUuid: string | null
+): Promise<void> {
+  const seriesCovers: Record<string, boolean> = {};
+  for (const volume of seriesVolumes) {
+    const isCover = volume.volume_uuid === coverVolumeUuid;
+    // Only touch volumes whose flag changes, so unrelated volume data isn't re-synced
+    if (!!volume.series_cover !== isCover) {
+      seriesCovers[volume.volume_uuid] = isCover;
+    }
+  }
+
+  await db.transaction('rw', db.volumes, async () => {
+    for (const volume of seriesVolumes) {
+      if (volume.isPlaceholder || !(volume.volume_uuid in seriesCovers)) continue;
+      await db.volumes.update(volume.volume_uuid, {
+        series_cover: seriesCovers[volume.volume_uuid] || undefined
+      });
+    }
+  });
+
+  updateVolumeSeriesCovers(seriesCovers);
+}
+
+/**
+ * Order a series' volumes so the pinned cover (if any) comes first
+ */
+export function withSeriesCoverFirst<T extends { series_cover?: boolean }>(volumes: T[]): T[] {
+  const coverIndex = volumes.findIndex((volume) => volume.series_cover);
+  if (coverIndex <= 0) return volumes;
+  return [volumes[coverIndex], ...volumes.slice(0, coverIndex), ...volumes.slice(coverIndex + 1)];
+}
+
+/** Copy the synced series cover flag onto a catalog volume; true if it changed */
+export function applySyncedSeriesCover(volume: VolumeMetadata, data: VolumeData): boolean {
+  if (!!volume.series_cover === !!data.series_cover) return false;
+  volume.series_cover = data.series_cover || undefined;
+  return true;
+}
+
+/**
+ * Apply pinned series covers from synced volume data to the local catalog.
+ * Called after volume-data sync so a cover pinned on another device shows up here.
+ */
+export async function reconcileSeriesCovers(
+  volumeData: Record<string, VolumeData>
+): Promise<number> {
+  let changed = 0;
+
+  await db.transaction('rw', db.volumes, async () => {
+    await db.volumes.toCollection().modify((volume) => {
+      const data = volumeData[volume.volume_uuid];
+      if (!data || data.deletedOn) return;
+      if (applySyncedSeriesCover(volume, data)) changed++;
+    });
+  });
+
+  return changed;
+}
@@ -0,0 +1,75 @@
+import { describe, it, expect, beforeEach } from 'vitest';
+import 'fake-indexeddb/auto';
+import { get } from 'svelte/store';
+import type { VolumeMetadata } from '$lib/types';
+import { db } from '$lib/catalog/db';
+import { deriveSeriesFromVolumes } from '$lib/catalog/catalog';
+import {
+  volumes as volumeDataStore,
+  volumesWithTrash,
+  VolumeData
+} from '$lib/settings/volume-data';
+import { assignVolumesToSeries, reconcileSeriesOverrides } from './series-grouping';
+
+function createVolume(uuid: string, seriesTitle: string): VolumeMetadata {
+  return {
+    mokuro_version: '0.2.0',
+    series_title: seriesTitle,
+    series_uuid: `${seriesTitle}-uuid`,
+    volume_title: `${seriesTitle} ${uuid}`,
+    volume_uuid: uuid,
+    page_count: 1,
+    character_count: 0,
+    page_char_counts: [0]
+  };
+}
+
+describe('series grouping', () => {
+  beforeEach(async () => {
+    await db.volumes.clear();
+    volumesWithTrash.set({});
+  });
+
+  it('merges a mis-split series without renaming its volumes', async () => {
+    const main = createVolume('v1', 'Series');
+    const digital = createVolume('v2', 'Series (Digital)');
+    await db.volumes.bulkAdd([main, digital]);
+
+    await assignVolumesToSeries([digital], 'Series');
+
+    const stored = await db.volumes.toArray();
+    const series = deriveSeriesFromVolumes(stored);
+    expect(series).toHaveLength(1);
+    expect(series[0].title).toBe('Series');
+    expect(series[0].volumes.map((v) => v.volume_uuid).sort()).toEqual(['v1', 'v2']);
+    expect((await db.volumes.get('v2'))?.series_title).toBe('Series (Digital)');
+    expect(get(volumeDataStore)['v2'].series_override).toBe('Series');
+  });
+
+  it('removes a group once its last volume is moved out, and restores it on reset', async () => {
+    const a = createVolume('a', 'Lumped');
+    await db.volumes.add(a);
+
+    await assignVolumesToSeries([a], 'Other');
+    expect(deriveSeriesFromVolumes(await db.volumes.toArray()).map((s) => s.title)).toEqual([
+      'Other'
+    ]);
+
+    await assignVolumesToSeries([a], null);
+    expect(deriveSeriesFromVolumes(await db.volumes.toArray()).map((s) => s.title)).toEqual([
+      'Lumped'
+    ]);
+    expect(get(volumeDataStore)['a'].series_override).toBeUndefined();
+  });
+
+  it('applies overrides synced from another device', async () => {
+    await db.volumes.add(createVolume('x', 'Split'));
+
+    const changed = await reconcileSeriesOverrides({
+      x: new VolumeData({ series_override: 'Merged' })
+    });
+
+    expect(changed).toBe(1);
+    expect((await db.volumes.get('x'))?.series_override).toBe('Merged');
+  });
+});
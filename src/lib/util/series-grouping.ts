@@ -0,0 +1,66 @@
+/**
+ * Manual series grouping.
+ *
+ * Filename-based series extraction sometimes splits one series in two
+ * ("Series" / "Series (Digital)") or lumps unrelated volumes together.
+ * A grouping override moves volumes under another catalog title without
+ * touching series_title, so nothing is renamed locally or in the cloud.
+ * Overrides are stored on the volume row and in volume-data (which syncs).
+ */
+
+import { db } from '$lib/catalog/db';
+import { updateVolumeSeriesOverride, type VolumeData } from '$lib/settings/volume-data';
+import type { VolumeMetadata } from '$lib/types';
+
+/**
+ * Group volumes under `seriesTitle`, or restore automatic grouping when null.
+ * Assigning a volume to its own series_title clears the override. Cloud
+ * placeholders have no catalog row; their override lives only in volume data.
+ */
+export async function assignVolumesToSeries(
+  volumes: VolumeMetadata[],
+  seriesTitle: string | null
+): Promise<void> {
+  const title = seriesTitle?.trim() || null;
+  const overrideFor = (volume: VolumeMetadata) =>
+    title && title !== volume.series_title ? title : undefined;
+
+  await db.transaction('rw', db.volumes, async () => {
+    for (const volume of volumes) {
+      if (volume.isPlaceholder) continue;
+      await db.volumes.update(volume.volume_uuid, { series_override: overrideFor(volume) });
+    }
+  });
+
+  for (const volume of volumes) {
+    updateVolumeSeriesOverride(volume.volume_uuid, overrideFor(volume));
+  }
+}
+
+/** Copy the synced grouping override onto a catalog volume; true if it changed */
+export function applySyncedSeriesOverride(volume: VolumeMetadata, data: VolumeData): boolean {
+  const override = data.series_override || undefined;
+  if (volume.series_override === override) return false;
+  volume.series_override = override;
+  return true;
+}
+
+/**
+ * Apply grouping overrides from synced volume data to the local catalog.
+ * Called after volume-data sync so groupings made on another device show up here.
+ */
+export async function reconcileSeriesOverrides(
+  volumeData: Record<string, VolumeData>
+): Promise<number> {
+  let changed = 0;
+
+  await db.transaction('rw', db.volumes, async () => {
+    await db.volumes.toCollection().modify((volume) => {
+      const data = volumeData[volume.volume_uuid];
+      if (!data || data.deletedOn) return;
+      if (applySyncedSeriesOverride(volume, data)) changed++;
+    });
+  });
+
+  return changed;
+}
@@ -0,0 +1,133 @@
+import { describe, it, expect, beforeEach, vi } from 'vitest';
+import 'fake-indexeddb/auto';
+import { get } from 'svelte/store';
+import {
+  parseVolumesFromJson,
+  updateSeriesMetadata,
+  volumesWithTrash
+} from '$lib/settings/volume-data';
+import {
+  applyFetchedMetadata,
+  applyManualEdits,
+  fetchAniListMetadata,
+  getSeriesMetadata,
+  mergeSeriesMetadata
+} from './series-metadata';
+
+describe('series metadata', () => {
+  beforeEach(() => {
+    volumesWithTrash.set({});
+  });
+
+  it('persists an edited author on every volume and survives a sync round trip', () => {
+    const edited = applyManualEdits(undefined, { author: 'Kiyohiko Azuma' });
+    updateSeriesMetadata(['v1', 'v2'], edited);
+
+    const synced = parseVolumesFromJson(JSON.stringify(get(volumesWithTrash)));
+    expect(getSeriesMetadata(['v1', 'v2'], synced)?.author).toBe('Kiyohiko Azuma');
+    expect(synced.v2.series_metadata?.manual_fields).toEqual(['author']);
+  });
+
+  it('uses the most recently updated copy across the series', () => {
+    const older = { author: 'Old', updated_at: '2026-01-01T00:00:00.000Z' };
+    const newer = { author: 'New', updated_at: '2026-02-01T00:00:00.000Z' };
+
+    expect(
+      getSeriesMetadata(['a', 'b', 'c'], {
+        a: { series_metadata: older },
+        b: { series_metadata: newer },
+        c: undefined
+      })?.author
+    ).toBe('New');
+  });
+
+  it('marks only changed fields as manual, including cleared ones', () => {
+    const current = { author: 'A', description: 'D', updated_at: '' };
+    const edited = applyManualEdits(current, { author: 'A', description: '' });
+
+    expect(edited.author).toBe('A');
+    expect(edited.description).toBeUndefined();
+    expect(edited.manual_fields).toEqual(['description']);
+  });
+
+  it('never lets a later fetch overwrite manual edits', () => {
+    const edited = applyManualEdits(undefined, { author: 'My spelling' });
+    const fetched = applyFetchedMetadata(edited, {
+      author: 'AniList spelling',
+      genres: ['Comedy'],
+      anilist_id: 104
+    });
+
+    expect(fetched.author).toBe('My spelling');
+    expect(fetched.genres).toEqual(['Comedy']);
+    expect(fetched.anilist_id).toBe(104);
+    expect(fetched.fetched_at).toBeDefined();
+  });
+
+  it('keeps an edit and a fetch made on different devices', () => {
+    vi.useFakeTimers();
+    vi.setSystemTime(new Date('2026-03-01T00:00:00.000Z'));
+    const base = { author: 'Old', description: 'Old synopsis', updated_at: '' };
+    const edited = applyManualEdits(base, { description: 'My synopsis' });
+    vi.setSystemTime(new Date('2026-03-02T00:00:00.000Z'));
+    const fetched = applyFetchedMetadata(base, { author: 'Azuma', description: 'AniList' });
+    vi.useRealTimers();
+
+    const merged = mergeSeriesMetadata(edited, fetched);
+    expect(merged?.author).toBe('Azuma');
+    expect(merged?.description).toBe('My synopsis');
+    expect(merged?.manual_fields).toEqual(['description']);
+    expect(merged?.fetched_at).toBe(fetched.fetched_at);
+    expect(mergeSeriesMetadata(fetched, edited)).toEqual(merged);
+  });
+
+  it('leaves reading progress timestamps alone', () => {
+    volumesWithTrash.set(
+      parseVolumesFromJson(
+        JSON.stringify({ v1: { progress: 40, lastProgressUpdate: '2026-01-01T00:00:00.000Z' } })
+      )
+    );
+    updateSeriesMetadata(['v1'], applyManualEdits(undefined, { author: 'A' }));
+
+    const volume = get(volumesWithTrash).v1;
+    expect(volume.lastProgressUpdate).toBe('2026-01-01T00:00:00.000Z');
+    expect(volume.catalog_updated?.series_metadata).toBeDefined();
+  });
+});
+
+describe('fetchAniListMetadata', () => {
+  it('maps the AniList response to series metadata', async () => {
+    const fetchImpl = vi.fn().mockResolvedValue(
+      new Response(
+        JSON.stringify({
+          data: {
+            Media: {
+              id: 104,
+              description: 'Yotsuba is a strange little girl.<br><br>With <i>energy</i>.',
+              genres: ['Comedy', 'Slice of Life'],
+              staff: {
+                edges: [
+                  { role: 'Assistant', node: { name: { full: 'Someone Else' } } },
+                  { role: 'Story & Art', node: { name: { full: 'Kiyohiko Azuma' } } }
+                ]
+              }
+            }
+          }
+        }),
+        { status: 200 }
+      )
+    );
+
+    expect(await fetchAniListMetadata('よつばと!', fetchImpl)).toEqual({
+      author: 'Kiyohiko Azuma',
+      description: 'Yotsuba is a strange little girl.\n\nWith energy.',
+      genres: ['Comedy', 'Slice of Life'],
+      anilist_id: 104
+    });
+  });
+
+  it('returns null when AniList has no match', async () => {
+    const fetchImpl = vi.fn().mockResolvedValue(new Response('{}', { status: 404 }));
+    expect(await fetchAniListMetadata('Unknown', fetchImpl)).toBeNull();
+  });
+});
@@ -0,0 +1,212 @@
+/**
+ * Series metadata (author, synopsis, genres, external IDs)
+ *
+ * The record is stored on every volume of the series in the synced volume
+ * data. Copies are merged field by field on `field_updated`, so an edit made on
+ * one device and a fetch on another both survive. Edits made by hand are
+ * recorded in `manual_fields` and are never overwritten when details are
+ * fetched from AniList.
+ */
+
+import type { SeriesMetadata, SeriesMetadataField } from '$lib/types';
+
+export type SeriesMetadataFields = Pick<SeriesMetadata, SeriesMetadataField>;
+
+const FIELDS: SeriesMetadataField[] = [
+  'author',
+  'description',
+  'genres',
+  'anilist_id',
+  'mangadex_id'
+];
+
+const ANILIST_ENDPOINT = 'https://graphql.anilist.co';
+
+function pickFieldSource(
+  older: SeriesMetadata,
+  newer: SeriesMetadata,
+  field: SeriesMetadataField
+): SeriesMetadata {
+  const olderManual = older.manual_fields?.includes(field) ?? false;
+  const newerManual = newer.manual_fields?.includes(field) ?? false;
+  if (olderManual !== newerManual) return olderManual ? older : newer;
+  const olderChanged = older.field_updated?.[field] ?? '';
+  return olderChanged > (newer.field_updated?.[field] ?? '') ? older : newer;
+}
+
+/**
+ * Combine two copies of a series' metadata. Each field comes from the copy
+ * that changed it last, except that a manual edit always beats a fetched
+ * value; copies from before per-field timestamps fall back to the more
+ * recently updated copy.
+ */
+export function mergeSeriesMetadata(
+  a: SeriesMetadata | undefined,
+  b: SeriesMetadata | undefined
+): SeriesMetadata | undefined {
+  if (!a) return b;
+  if (!b) return a;
+  const [older, newer] = a.updated_at > b.updated_at ? [b, a] : [a, b];
+
+  const merged: SeriesMetadata = { ...newer };
+  const manual = new Set<SeriesMetadataField>();
+  const stamps: Partial<Record<SeriesMetadataField, string>> = {};
+
+  for (const field of FIELDS) {
+    const source = pickFieldSource(older, newer, field);
+    if (source[field] === undefined) {
+      delete merged[field];
+    } else {
+      (merged as Record<string, unknown>)[field] = source[field];
+    }
+    if (source.manual_fields?.includes(field)) manual.add(field);
+    const stamp = source.field_updated?.[field];
+    if (stamp) stamps[field] = stamp;
+  }
+
+  merged.manual_fields = manual.size > 0 ? [...manual] : undefined;
+  merged.field_updated = Object.keys(stamps).length > 0 ? stamps : undefined;
+  if ((older.fetched_at ?? '') > (newer.fetched_at ?? '')) merged.fetched_at = older.fetched_at;
+  return merged;
+}
+
+/**
+ * Current metadata for a series: the copies on its volumes, merged
+ */
+export function getSeriesMetadata(
+  volumeUuids: string[],
+  volumeData: Record<string, { series_metadata?: SeriesMetadata } | undefined>
+): SeriesMetadata | undefined {
+  let merged: SeriesMetadata | undefined;
+  for (const uuid of volumeUuids) {
+    merged = mergeSeriesMetadata(merged, volumeData[uuid]?.series_metadata);
+  }
+  return merged;
+}
+
+function isEmpty(value: unknown): boolean {
+  return value === undefined || value === '' || (Array.isArray(value) && value.length === 0);
+}
+
+function sameValue(a: unknown, b: unknown): boolean {
+  if (isEmpty(a) && isEmpty(b)) return true;
+  return JSON.stringify(a) === JSON.stringify(b);
+}
+
+/**
+ * Apply edits made in the UI. Every field that actually changed becomes
+ * manual, including fields cleared on purpose.
+ */
+export function applyManualEdits(
+  current: SeriesMetadata | undefined,
+  edits: SeriesMetadataFields
+): SeriesMetadata {
+  const next: SeriesMetadata = { ...current, updated_at: new Date().toISOString() };
+  const manual = new Set(current?.manual_fields ?? []);
+  const stamps = { ...current?.field_updated };
+
+  for (const field of FIELDS) {
+    if (!(field in edits) || sameValue(current?.[field], edits[field])) continue;
+    const value = edits[field];
+    if (isEmpty(value)) {
+      delete next[field];
+    } else {
+      (next as Record<string, unknown>)[field] = value;
+    }
+    manual.add(field);
+    stamps[field] = next.updated_at;
+  }
+
+  next.manual_fields = manual.size > 0 ? [...manual] : undefined;
+  next.field_updated = Object.keys(stamps).length > 0 ? stamps : undefined;
+  return next;
+}
+
+/**
+ * Fill in fetched details, leaving every manually edited field alone
+ */
+export function applyFetchedMetadata(
+  current: SeriesMetadata | undefined,
+  fetched: SeriesMetadataFields
+): SeriesMetadata {
+  const now = new Date().toISOString();
+  const next: SeriesMetadata = { ...current, fetched_at: now, updated_at: now };
+  const manual = new Set(current?.manual_fields ?? []);
+  const stamps = { ...current?.field_updated };
+
+  for (const field of FIELDS) {
+    if (manual.has(field) || isEmpty(fetched[field])) continue;
+    if (sameValue(current?.[field], fetched[field])) continue;
+    (next as Record<string, unknown>)[field] = fetched[field];
+    stamps[field] = now;
+  }
+
+  next.field_updated = Object.keys(stamps).length > 0 ? stamps : undefined;
+  return next;
+}
+
+const ANILIST_QUERY = `
+query ($search: String) {
+  Media(search: $search, type: MANGA) {
+    id
+    description(asHtml: false)
+    genres
+    staff(sort: RELEVANCE, perPage: 6) {
+      edges { role node { name { full } } }
+    }
+  }
+}`;
+
+interface AniListMedia {
+  id: number;
+  description: string | null;
+  genres: string[] | null;
+  staff?: { edges: { role: string; node: { name: { full: string } } }[] };
+}
+
+/** Prefer the story credit, then original creator, then whoever is listed first */
+function pickAuthor(media: AniListMedia): string | undefined {
+  const edges = media.staff?.edges ?? [];
+  const byRole = (pattern: RegExp) => edges.find((edge) => pattern.test(edge.role));
+  const edge = byRole(/story/i) ?? byRole(/original/i) ?? edges[0];
+  return edge?.node.name.full;
+}
+
+function cleanDescription(description: string | null): string | undefined {
+  if (!description) return undefined;
+  return description
+    .replace(/<br\s*\/?>/gi, '\n')
+    .replace(/<[^>]+>/g, '')
+    .replace(/\n{3,}/g, '\n\n')
+    .trim();
+}
+
+/**
+ * Look a series up on AniList by title. Returns null when nothing matches.
+ */
+export async function fetchAniListMetadata(
+  title: string,
+  fetchImpl: typeof fetch = fetch
+): Promise<SeriesMetadataFields | null> {
+  const response = await fetchImpl(ANILIST_ENDPOINT, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json', Accept: 'application/json' },
+    body: JSON.stringify({ query: ANILIST_QUERY, variables: { search: title } })
+  });
+
+  // AniList answers 404 when the search has no match
+  if (response.status === 404) return null;
+  if (!response.ok) {
+    throw new Error(`AniList lookup failed with status ${response.status}`);
+  }
+
+  const media: AniListMedia | null = (await response.json())?.data?.Media ?? null;
+  if (!media) return null;
+
+  return {
+    author: pickAuthor(media),
+    description: cleanDescription(media.description),
+    genres: media.genres ?? undefined,
+    anilist_id: media.id
+  };
+}
@@ -0,0 +1,51 @@
+import { get } from 'svelte/store';
+import { miscSettings } from '$lib/settings/misc';
+import { unifiedCloudManager } from './unified-cloud-manager';
+
+/**
+ * Automatic progress sync.
+ *
+ * With a non-zero `syncIntervalMinutes`, progress syncs on startup, a few
+ * seconds after reading pauses (see activity-tracker) and every interval.
+ * At 0 ("manual only") the app syncs only when the user asks.
+ */
+
+/** Whether the app may sync without being asked */
+export function isAutoSyncEnabled(): boolean {
+  return get(miscSettings).syncIntervalMinutes > 0;
+}
+
+let intervalId: ReturnType<typeof setInterval> | null = null;
+let currentMinutes = 0;
+
+/**
+ * Start the periodic sync timer, following interval changes in settings.
+ * Returns a function that stops it.
+ */
+export function startAutoSync(): () => void {
+  const unsubscribe = miscSettings.subscribe(({ syncIntervalMinutes }) => {
+    if (syncIntervalMinutes === currentMinutes && intervalId !== null) return;
+    stopTimer();
+    currentMinutes = syncIntervalMinutes;
+    if (syncIntervalMinutes <= 0) return;
+
+    intervalId = setInterval(() => {
+      if (unifiedCloudManager.getActiveProvider() === null) return;
+      unifiedCloudManager.syncProgress({ silent: true }).catch((error) => {
+        console.error('Scheduled sync failed:', error);
+      });
+    }, syncIntervalMinutes * 60 * 1000);
+  });
+
+  return () => {
+    unsubscribe();
+    stopTimer();
+  };
+}
+
+function stopTimer() {
+  if (intervalId !== null) {
+    clearInterval(intervalId);
+    intervalId = null;
+  }
+}
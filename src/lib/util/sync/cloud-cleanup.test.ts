@@ -0,0 +1,130 @@
+import { describe, expect, it, vi } from 'vitest';
+import type { VolumeMetadata } from '$lib/types';
+import type { DriveFileMetadata } from './provider-interface';
+
+const { deleteFile } = vi.hoisted(() => ({ deleteFile: vi.fn(async () => {}) }));
+vi.mock('./unified-cloud-manager', () => ({
+  unifiedCloudManager: {
+    deleteFile,
+    deleteManagedVolume: vi.fn(),
+    renameVolume: vi.fn()
+  }
+}));
+
+import { analyzeCloudFiles, removeDuplicateCopies } from './cloud-cleanup';
+
+function driveFile(
+  fileId: string,
+  path: string,
+  modifiedTime: string,
+  md5Checksum?: string
+): DriveFileMetadata {
+  return { provider: 'google-drive', fileId, path, modifiedTime, size: 100, md5Checksum };
+}
+
+function localVolume(seriesTitle: string, volumeTitle: string): VolumeMetadata {
+  return {
+    mokuro_version: '0.2.0',
+    series_title: seriesTitle,
+    series_uuid: `series-${seriesTitle}`,
+    volume_title: volumeTitle,
+    volume_uuid: `volume-${volumeTitle}`,
+    page_count: 10,
+    character_count: 0,
+    page_char_counts: []
+  };
+}
+
+describe('analyzeCloudFiles', () => {
+  it('flags two copies of the same volume and keeps the newest', () => {
+    const older = driveFile('old', 'Yotsuba/Volume 1.cbz', '2024-01-01T00:00:00Z');
+    const newer = driveFile('new', 'Yotsuba/Volume 1.cbz', '2024-06-01T00:00:00Z');
+
+    const report = analyzeCloudFiles([older, newer], [localVolume('Yotsuba', 'Volume 1')]);
+
+    expect(report.duplicates).toEqual([{ reason: 'path', keep: newer, older: [older] }]);
+  });
+
+  it('flags identical archives under different titles', () => {
+    const kept = driveFile('a', 'Yotsuba/Volume 1.cbz', '2024-01-01T00:00:00Z', 'abc');
+    const copy = driveFile('b', 'Yotsuba!/Volume 1.cbz', '2024-06-01T00:00:00Z', 'abc');
+
+    const report = analyzeCloudFiles([kept, copy], [localVolume('Yotsuba', 'Volume 1')]);
+
+    // The copy matching a local volume wins over the newer one
+    expect(report.duplicates).toEqual([{ reason: 'content', keep: kept, older: [copy] }]);
+  });
+
+  it('finds sidecars left without their archive', () => {
+    const files = [
+      driveFile('cbz', 'Yotsuba/Volume 1.cbz', 't'),
+      driveFile('ocr', 'Yotsuba/Volume 1.mokuro.gz', 't'),
+      driveFile('stray-ocr', 'Yotsuba/Volume 2.mokuro', 't'),
+      driveFile('stray-thumb', 'Yotsuba/Volume 2.webp', 't'),
+      driveFile('sync', 'volume-data.json', 't')
+    ];
+
+    const report = analyzeCloudFiles(files, []);
+
+    expect(report.orphanedFiles.map((f) => f.fileId)).toEqual(['stray-ocr', 'stray-thumb']);
+  });
+
+  it('pairs a backup with the local volume it was renamed to', () => {
+    const file = driveFile('cbz', 'Old Name/Volume 3.cbz', 't');
+    const renamed = localVolume('New Name', 'Volume 3');
+    const unrelated = localVolume('New Name', 'Volume 4');
+
+    const report = analyzeCloudFiles([file], [renamed, unrelated]);
+
+    expect(report.relinks).toEqual([{ file, volume: renamed }]);
+    expect(report.unbackedVolumes).toEqual([unrelated]);
+  });
+
+  it('matches Drive files stored under sanitized or numbered names', () => {
+    // "Fate／Zero" took the name first, so "Fate/Zero"'s upload was numbered
+    const description = 'Series: Fate/Zero';
+    const files = [
+      driveFile('own', 'Fate／Zero/Volume 1.cbz', 't'),
+      { ...driveFile('cbz', 'Fate／Zero/Volume 1 (2).cbz', 't'), description },
+      { ...driveFile('ocr', 'Fate／Zero/Volume 1 (2).mokuro', 't'), description }
+    ];
+    const volume = localVolume('Fate/Zero', 'Volume 1');
+
+    const report = analyzeCloudFiles(files, [volume]);
+
+    expect(report.unbackedVolumes).toEqual([]);
+    expect(report.orphanedFiles).toEqual([]);
+    expect(report.relinks).toEqual([]);
+  });
+
+  it('pairs a Drive backup with a renamed volume under its sanitized title', () => {
+    const file = driveFile('cbz', 'Old Name/Part 1／2.cbz', 't');
+    const renamed = localVolume('New Name', 'Part 1/2');
+
+    const report = analyzeCloudFiles([file], [renamed]);
+
+    expect(report.relinks).toEqual([{ file, volume: renamed }]);
+  });
+
+  it('ignores placeholders on the local side', () => {
+    const placeholder = { ...localVolume('Yotsuba', 'Volume 5'), isPlaceholder: true };
+
+    const report = analyzeCloudFiles([], [placeholder]);
+
+    expect(report.unbackedVolumes).toEqual([]);
+  });
+});
+
+describe('removeDuplicateCopies', () => {
+  it('deletes only the older copy', async () => {
+    const older = driveFile('old', 'Yotsuba/Volume 1.cbz', '2024-01-01T00:00:00Z');
+    const newer = driveFile('new', 'Yotsuba/Volume 1.cbz', '2024-06-01T00:00:00Z');
+    const [group] = analyzeCloudFiles([older, newer], []).duplicates;
+
+    const result = await removeDuplicateCopies(group);
+
+    expect(result).toEqual({ deleted: 1, failed: [] });
+    expect(deleteFile).toHaveBeenCalledTimes(1);
+    expect(deleteFile).toHaveBeenCalledWith(older);
+  });
+});
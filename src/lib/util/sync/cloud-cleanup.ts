@@ -0,0 +1,259 @@
+import type { VolumeMetadata } from '$lib/types';
+import type { CloudFileMetadata } from './provider-interface';
+import { unifiedCloudManager } from './unified-cloud-manager';
+import { cloudSeriesFolder, cloudVolumeName, isVolumeFile } from './volume-cloud-file';
+
+/**
+ * Cloud folder cleanup
+ *
+ * Providers like Google Drive happily keep several files with the same name in
+ * one folder, and interrupted uploads or renames done on another device can
+ * leave sidecars behind or point a backup at a title that no longer exists
+ * locally. This compares the cached cloud listing with the local library and
+ * reports what can be tidied up:
+ * - duplicates: several copies at one path, or identical archives (same MD5)
+ *   at different paths. The newest copy is kept, preferring one that matches
+ *   a local volume.
+ * - orphaned files: .mokuro and thumbnail sidecars without their archive
+ * - re-links: an archive no local volume matches, paired with the one local
+ *   volume of the same title that has no backup (typically renamed locally)
+ *
+ * Google Drive stores files under sanitized names, numbered when another
+ * series' file already took the name, so its files are matched to local
+ * volumes by the rule uploads follow rather than by path (see
+ * volume-cloud-file).
+ */
+
+const ARCHIVE_EXTENSION = '.cbz';
+const SIDECAR_EXTENSIONS = ['.mokuro.gz', '.mokuro', '.jpeg', '.webp', '.jpg'];
+
+/** Copies of one file; `older` are safe to delete */
+export interface DuplicateGroup {
+  /** 'path' for same-name copies, 'content' for identical archives at different paths */
+  reason: 'path' | 'content';
+  keep: CloudFileMetadata;
+  older: CloudFileMetadata[];
+}
+
+/** A cloud archive that belongs to a local volume under a different path */
+export interface RelinkCandidate {
+  file: CloudFileMetadata;
+  volume: VolumeMetadata;
+}
+
+export interface CloudCleanupReport {
+  duplicates: DuplicateGroup[];
+  orphanedFiles: CloudFileMetadata[];
+  relinks: RelinkCandidate[];
+  /** Local volumes with no archive in the cloud that couldn't be paired */
+  unbackedVolumes: VolumeMetadata[];
+}
+
+function normalizePath(path: string): string {
+  return path.replace(/^\/+|\/+$/g, '');
+}
+
+function volumeKey(seriesTitle: string, volumeTitle: string): string {
+  return `${seriesTitle}/${volumeTitle}`.toLowerCase();
+}
+
+interface ManagedPath {
+  key: string;
+  series: string;
+  volumeTitle: string;
+  extension: string;
+  kind: 'archive' | 'sidecar';
+}
+
+/** Split a managed file path into its volume key and kind, or null for other files */
+function parseManagedPath(path: string): ManagedPath | null {
+  const parts = normalizePath(path).split('/');
+  if (parts.length !== 2) return null;
+
+  const [series, name] = parts;
+  const lower = name.toLowerCase();
+  if (lower.endsWith(ARCHIVE_EXTENSION)) {
+    const volumeTitle = name.slice(0, -ARCHIVE_EXTENSION.length);
+    const extension = name.slice(volumeTitle.length);
+    return { key: volumeKey(series, volumeTitle), series, volumeTitle, extension, kind: 'archive' };
+  }
+  const sidecar = SIDECAR_EXTENSIONS.find((ext) => lower.endsWith(ext));
+  if (!sidecar) return null;
+  const volumeTitle = name.slice(0, -sidecar.length);
+  const extension = name.slice(volumeTitle.length);
+  return { key: volumeKey(series, volumeTitle), series, volumeTitle, extension, kind: 'sidecar' };
+}
+
+function newestFirst(a: CloudFileMetadata, b: CloudFileMetadata): number {
+  return Date.parse(b.modifiedTime || '') - Date.parse(a.modifiedTime || '') || 0;
+}
+
+function md5Of(file: CloudFileMetadata): string | undefined {
+  return (file as CloudFileMetadata & { md5Checksum?: string }).md5Checksum;
+}
+
+/**
+ * Compare the cloud listing against local volumes. Placeholders are ignored on
+ * the local side since they are generated from the cloud listing itself.
+ */
+export function analyzeCloudFiles(
+  files: CloudFileMetadata[],
+  localVolumes: VolumeMetadata[]
+): CloudCleanupReport {
+  const local = localVolumes.filter((volume) => !volume.isPlaceholder);
+  const localKeys = new Set(local.map((v) => volumeKey(v.series_title, v.volume_title)));
+
+  // One listing holds one provider's files
+  const provider = files[0]?.provider;
+  const localByFolder = new Map<string, VolumeMetadata[]>();
+  if (provider) {
+    for (const volume of local) {
+      const folder = cloudSeriesFolder(provider, volume.series_title);
+      localByFolder.set(folder, [...(localByFolder.get(folder) ?? []), volume]);
+    }
+  }
+
+  const byPath = new Map<string, CloudFileMetadata[]>();
+  const archiveKeys = new Set<string>();
+  const managed: (ManagedPath & { file: CloudFileMetadata })[] = [];
+  for (const file of files) {
+    const parsed = parseManagedPath(file.path);
+    if (!parsed) continue;
+    // Key files by the local volume they were uploaded for, when there is one
+    const owner = localByFolder
+      .get(parsed.series)
+      ?.find((v) => isVolumeFile(file, v.series_title, v.volume_title, parsed.extension));
+    if (owner) parsed.key = volumeKey(owner.series_title, owner.volume_title);
+    managed.push({ file, ...parsed });
+    if (parsed.kind === 'archive') archiveKeys.add(parsed.key);
+
+    const path = normalizePath(file.path);
+    byPath.set(path, [...(byPath.get(path) ?? []), file]);
+  }
+
+  const duplicates: DuplicateGroup[] = [];
+  const redundant = new Set<string>();
+  for (const copies of byPath.values()) {
+    if (copies.length < 2) continue;
+    const [keep, ...older] = [...copies].sort(newestFirst);
+    duplicates.push({ reason: 'path', keep, older });
+    older.forEach((file) => redundant.add(file.fileId));
+  }
+
+  // Identical archives stored under different titles
+  const byContent = new Map<string, { file: CloudFileMetadata; key: string }[]>();
+  for (const { file, key, kind } of managed) {
+    const md5 = md5Of(file);
+    if (kind !== 'archive' || !md5 || redundant.has(file.fileId)) continue;
+    const contentKey = `${md5}:${file.size}`;
+    byContent.set(contentKey, [...(byContent.get(contentKey) ?? []), { file, key }]);
+  }
+  for (const copies of byContent.values()) {
+    if (copies.length < 2) continue;
+    const [keep, ...older] = [...copies].sort(
+      (a, b) =>
+        Number(localKeys.has(b.key)) - Number(localKeys.has(a.key)) || newestFirst(a.file, b.file)
+    );
+    duplicates.push({ reason: 'content', keep: keep.file, older: older.map((c) => c.file) });
+  }
+
+  const orphanedFiles = managed
+    .filter(({ kind, key }) => kind === 'sidecar' && !archiveKeys.has(key))
+    .map(({ file }) => file);
+
+  // Pair unmatched archives with unbacked local volumes by title, only when unambiguous
+  const unbacked = local.filter((v) => !archiveKeys.has(volumeKey(v.series_title, v.volume_title)));
+  const unbackedByTitle = new Map<string, VolumeMetadata[]>();
+  for (const volume of unbacked) {
+    const title = (
+      provider ? cloudVolumeName(provider, volume.volume_title) : volume.volume_title
+    ).toLowerCase();
+    unbackedByTitle.set(title, [...(unbackedByTitle.get(title) ?? []), volume]);
+  }
+  const unmatchedByTitle = new Map<string, CloudFileMetadata[]>();
+  for (const { file, key, volumeTitle, kind } of managed) {
+    if (kind !== 'archive' || localKeys.has(key) || redundant.has(file.fileId)) continue;
+    const title = volumeTitle.toLowerCase();
+    unmatchedByTitle.set(title, [...(unmatchedByTitle.get(title) ?? []), file]);
+  }
+
+  const relinks: RelinkCandidate[] = [];
+  for (const [title, cloudFiles] of unmatchedByTitle) {
+    const candidates = unbackedByTitle.get(title);
+    if (cloudFiles.length === 1 && candidates?.length === 1) {
+      relinks.push({ file: cloudFiles[0], volume: candidates[0] });
+    }
+  }
+  const relinked = new Set(relinks.map((r) => r.volume.volume_uuid));
+
+  return {
+    duplicates,
+    orphanedFiles,
+    relinks,
+    unbackedVolumes: unbacked.filter((v) => !relinked.has(v.volume_uuid))
+  };
+}
+
+/**
+ * Delete cloud files one at a time, carrying on past failures.
+ * Returns how many were deleted and which failed.
+ */
+export async function removeCloudFiles(
+  files: CloudFileMetadata[]
+): Promise<{ deleted: number; failed: CloudFileMetadata[] }> {
+  let deleted = 0;
+  const failed: CloudFileMetadata[] = [];
+  for (const file of files) {
+    try {
+      await unifiedCloudManager.deleteFile(file);
+      deleted++;
+    } catch (error) {
+      console.error(`Failed to delete ${file.path}:`, error);
+      failed.push(file);
+    }
+  }
+  return { deleted, failed };
+}
+
+/**
+ * Delete the redundant copies of a duplicate group. Content duplicates take
+ * their sidecars with them, since the whole volume at that path is redundant.
+ */
+export async function removeDuplicateCopies(
+  group: DuplicateGroup
+): Promise<{ deleted: number; failed: CloudFileMetadata[] }> {
+  if (group.reason === 'path') {
+    return removeCloudFiles(group.older);
+  }
+
+  let deleted = 0;
+  const failed: CloudFileMetadata[] = [];
+  for (const file of group.older) {
+    const [series, name] = normalizePath(file.path).split('/');
+    const volumeTitle = name.slice(0, -ARCHIVE_EXTENSION.length);
+    try {
+      await unifiedCloudManager.deleteManagedVolume(series, volumeTitle);
+      deleted++;
+    } catch (error) {
+      console.error(`Failed to delete ${file.path}:`, error);
+      failed.push(file);
+    }
+  }
+  return { deleted, failed };
+}
+
+/**
+ * Move a cloud archive and its sidecars to the local volume's path. The
+ * volume's own titles are passed on: the provider maps them to the names it
+ * stores files under, as it does for uploads.
+ */
+export async function relinkCloudVolume(candidate: RelinkCandidate): Promise<number> {
+  const [series, name] = normalizePath(candidate.file.path).split('/');
+  return unifiedCloudManager.renameVolume(
+    series,
+    name.slice(0, -ARCHIVE_EXTENSION.length),
+    candidate.volume.series_title,
+    candidate.volume.volume_title,
+    candidate.volume.volume_uuid
+  );
+}
@@ -4,10 +4,19 @@ export type CloudCoreProviderType = ProviderType;
 
 export type CloudCoreCredentials = Record<string, unknown>;
 
+/** Split one download into byte ranges fetched in parallel (providers that support it) */
+export interface CloudCoreRangeOptions {
+  /** Bytes per range request */
+  chunkSize: number;
+  /** Range requests in flight at once */
+  concurrency: number;
+}
+
 export interface CloudCoreDownloadArgs {
   fileId: string;
   credentials: CloudCoreCredentials;
   onProgress: (loaded: number, total: number) => void;
+  ranges?: CloudCoreRangeOptions;
 }
 
 export interface CloudCoreUploadArgs {
@@ -25,6 +34,26 @@ export interface CloudProviderCore {
   uploadFile(args: CloudCoreUploadArgs): Promise<string>;
 }
 
+/**
+ * A provider request that failed with an HTTP status. Workers pass the status
+ * on with the error, so callers can tell an expired sign-in (401) from other
+ * failures without parsing the message.
+ */
+export class CloudHttpError extends Error {
+  constructor(
+    message: string,
+    readonly status: number
+  ) {
+    super(message);
+  }
+}
+
+/** HTTP status of a failed provider request, if the error carries one */
+export function errorHttpStatus(error: unknown): number | undefined {
+  const status = (error as { status?: unknown } | null)?.status;
+  return typeof status === 'number' ? status : undefined;
+}
+
 export function requireCredentialString(
   credentials: CloudCoreCredentials,
   key: string,
@@ -0,0 +1,371 @@
+import { describe, it, expect, beforeEach, afterEach, vi } from 'vitest';
+import { createHash, webcrypto } from 'node:crypto';
+import { googleDriveCore, splitByteRanges } from '../google-drive-core';
+
+const credentials = { accessToken: 'TOKEN', seriesFolderId: 'folder-1' };
+
+/** Minimal XHR stand-in that completes the resumable PUT with the given file id */
+function stubUploadXhr(fileId: string) {
+  const sent: unknown[] = [];
+  class FakeXhr {
+    status = 200;
+    statusText = 'OK';
+    responseText = JSON.stringify({ id: fileId });
+    upload: { onprogress?: unknown } = {};
+    onload?: () => void;
+    open() {}
+    setRequestHeader() {}
+    send(body: unknown) {
+      sent.push(body);
+      this.onload?.();
+    }
+  }
+  vi.stubGlobal('XMLHttpRequest', FakeXhr);
+  return sent;
+}
+
+function listResponse(
+  files: { id: string; sha256Checksum?: string; description?: string }[]
+): Response {
+  return { ok: true, json: async () => ({ files }) } as Response;
+}
+
+function initResponse(): Response {
+  return {
+    ok: true,
+    headers: new Headers({ Location: 'https://upload.example/session' })
+  } as Response;
+}
+
+function sha256Hex(bytes: Uint8Array): string {
+  return createHash('sha256').update(bytes).digest('hex');
+}
+
+describe('googleDriveCore.uploadFile', () => {
+  beforeEach(() => {
+    vi.stubGlobal('fetch', vi.fn());
+    // jsdom's crypto has no SubtleCrypto
+    vi.stubGlobal('crypto', webcrypto);
+  });
+
+  afterEach(() => {
+    vi.unstubAllGlobals();
+  });
+
+  it('creates a new file when the folder has none with that name', async () => {
+    vi.mocked(fetch).mockResolvedValueOnce(listResponse([]));
+    vi.mocked(fetch).mockResolvedValueOnce(initResponse());
+    const sent = stubUploadXhr('new-id');
+
+    const id = await googleDriveCore.uploadFile({
+      seriesTitle: 'Series',
+      filename: "Haven't v1.cbz",
+      blob: new Blob([new Uint8Array([1, 2, 3])]),
+      credentials
+    });
+
+    expect(id).toBe('new-id');
+    expect(sent).toHaveLength(1);
+    const lookupUrl = new URL(vi.mocked(fetch).mock.calls[0][0] as string);
+    expect(lookupUrl.searchParams.get('q')).toBe(
+      "name = 'Haven\\'t v1.cbz' and 'folder-1' in parents and trashed = false"
+    );
+    const [initUrl, init] = vi.mocked(fetch).mock.calls[1];
+    expect(initUrl).toBe('https://www.googleapis.com/upload/drive/v3/files?uploadType=resumable');
+    expect((init as RequestInit).method).toBe('POST');
+  });
+
+  it('updates the existing file in place instead of creating a duplicate', async () => {
+    vi.mocked(fetch).mockResolvedValueOnce(
+      listResponse([{ id: 'existing-id', sha256Checksum: 'different' }])
+    );
+    vi.mocked(fetch).mockResolvedValueOnce(initResponse());
+    stubUploadXhr('existing-id');
+
+    const id = await googleDriveCore.uploadFile({
+      seriesTitle: 'Series',
+      filename: 'v1.cbz',
+      blob: new Blob([new Uint8Array([1, 2, 3])]),
+      credentials
+    });
+
+    expect(id).toBe('existing-id');
+    const [initUrl, init] = vi.mocked(fetch).mock.calls[1];
+    expect(initUrl).toBe(
+      'https://www.googleapis.com/upload/drive/v3/files/existing-id?uploadType=resumable'
+    );
+    expect((init as RequestInit).method).toBe('PATCH');
+  });
+
+  it('skips the upload when the existing file has the same hash', async () => {
+    const bytes = new Uint8Array([1, 2, 3]);
+    vi.mocked(fetch).mockResolvedValueOnce(
+      listResponse([{ id: 'existing-id', sha256Checksum: sha256Hex(bytes) }])
+    );
+    const sent = stubUploadXhr('unused');
+    const onProgress = vi.fn();
+
+    const id = await googleDriveCore.uploadFile({
+      seriesTitle: 'Series',
+      filename: 'v1.cbz',
+      blob: new Blob([bytes]),
+      credentials,
+      onProgress
+    });
+
+    expect(id).toBe('existing-id');
+    expect(fetch).toHaveBeenCalledTimes(1);
+    expect(sent).toHaveLength(0);
+    expect(onProgress).toHaveBeenCalledWith(3, 3);
+  });
+
+  it('numbers the upload instead of replacing a file of another series', async () => {
+    // "Fate／Zero" holds a volume of the series literally named that
+    vi.mocked(fetch).mockResolvedValueOnce(listResponse([{ id: 'other-series-id' }]));
+    vi.mocked(fetch).mockResolvedValueOnce(listResponse([]));
+    vi.mocked(fetch).mockResolvedValueOnce(initResponse());
+    stubUploadXhr('new-id');
+
+    const id = await googleDriveCore.uploadFile({
+      seriesTitle: 'Fate/Zero',
+      filename: 'v1.cbz',
+      blob: new Blob([new Uint8Array([1])]),
+      credentials
+    });
+
+    expect(id).toBe('new-id');
+    const secondLookup = new URL(vi.mocked(fetch).mock.calls[1][0] as string);
+    expect(secondLookup.searchParams.get('q')).toContain("name = 'v1 (2).cbz'");
+    const init = vi.mocked(fetch).mock.calls[2][1] as RequestInit;
+    expect(init.method).toBe('POST');
+    expect(JSON.parse(init.body as string)).toMatchObject({
+      name: 'v1 (2).cbz',
+      description: 'Series: Fate/Zero'
+    });
+  });
+
+  it('replaces its own file in a folder shared with another series', async () => {
+    vi.mocked(fetch).mockResolvedValueOnce(
+      listResponse([
+        { id: 'own-id', sha256Checksum: 'different', description: 'Series: Fate/Zero' }
+      ])
+    );
+    vi.mocked(fetch).mockResolvedValueOnce(initResponse());
+    stubUploadXhr('own-id');
+
+    const id = await googleDriveCore.uploadFile({
+      seriesTitle: 'Fate/Zero',
+      filename: 'v1.cbz',
+      blob: new Blob([new Uint8Array([1])]),
+      credentials
+    });
+
+    expect(id).toBe('own-id');
+    expect((vi.mocked(fetch).mock.calls[1][1] as RequestInit).method).toBe('PATCH');
+  });
+
+  it('does not look anything up when the caller already knows the file', async () => {
+    vi.mocked(fetch).mockResolvedValueOnce(initResponse());
+    stubUploadXhr('known-id');
+
+    await googleDriveCore.uploadFile({
+      seriesTitle: 'Series',
+      filename: 'v1.cbz',
+      blob: new Blob([new Uint8Array([1])]),
+      credentials,
+      existingFileId: 'known-id'
+    });
+
+    expect(fetch).toHaveBeenCalledTimes(1);
+    expect((vi.mocked(fetch).mock.calls[0][1] as RequestInit).method).toBe('PATCH');
+  });
+});
+
+describe('splitByteRanges', () => {
+  it('covers the file with inclusive ranges', () => {
+    expect(splitByteRanges(10, 4)).toEqual([
+      [0, 3],
+      [4, 7],
+      [8, 9]
+    ]);
+    expect(splitByteRanges(8, 4)).toEqual([
+      [0, 3],
+      [4, 7]
+    ]);
+  });
+});
+
+describe('googleDriveCore.downloadFile with ranges', () => {
+  const file = Uint8Array.from({ length: 100 }, (_, i) => i);
+
+  function sizeResponse(size: number): Response {
+    return { ok: true, json: async () => ({ size: String(size) }) } as Response;
+  }
+
+  /** Serve Range requests from `file`, tracking how many are in flight */
+  function stubRangeFetch(options: { failRangeStart?: number; ignoreRange?: boolean } = {}) {
+    const stats = { inFlight: 0, maxInFlight: 0, aborted: 0, rangeRequests: 0 };
+    vi.mocked(fetch).mockImplementation(async (input, init) => {
+      const url = String(input);
+      if (url.includes('fields=size')) return sizeResponse(file.length);
+
+      const range = (init?.headers as Record<string, string>).Range;
+      const [, startText, endText] = /bytes=(\d+)-(\d+)/.exec(range)!;
+      const start = Number(startText);
+      const end = Number(endText);
+      stats.rangeRequests++;
+      stats.inFlight++;
+      stats.maxInFlight = Math.max(stats.maxInFlight, stats.inFlight);
+      init?.signal?.addEventListener('abort', () => stats.aborted++);
+
+      await new Promise((resolve) => setTimeout(resolve, 5));
+      stats.inFlight--;
+      if (options.failRangeStart === start) {
+        return new Response(null, { status: 404, statusText: 'Not Found' });
+      }
+      if (options.ignoreRange) {
+        return new Response(file, { status: 200 });
+      }
+      return new Response(file.slice(start, end + 1), { status: 206 });
+    });
+    return stats;
+  }
+
+  beforeEach(() => {
+    vi.stubGlobal('fetch', vi.fn());
+  });
+
+  afterEach(() => {
+    vi.unstubAllGlobals();
+  });
+
+  it('assembles the file from parallel ranges within the concurrency limit', async () => {
+    const stats = stubRangeFetch();
+    const onProgress = vi.fn();
+
+    const data = await googleDriveCore.downloadFile({
+      fileId: 'file-1',
+      credentials,
+      onProgress,
+      ranges: { chunkSize: 16, concurrency: 3 }
+    });
+
+    expect(new Uint8Array(data)).toEqual(file);
+    expect(stats.rangeRequests).toBe(7);
+    expect(stats.maxInFlight).toBe(3);
+    expect(onProgress).toHaveBeenLastCalledWith(100, 100);
+  });
+
+  it('aborts the other ranges when one fails', async () => {
+    const stats = stubRangeFetch({ failRangeStart: 0 });
+
+    await expect(
+      googleDriveCore.downloadFile({
+        fileId: 'file-1',
+        credentials,
+        onProgress: vi.fn(),
+        ranges: { chunkSize: 16, concurrency: 4 }
+      })
+    ).rejects.toThrow(/404/);
+    expect(stats.aborted).toBeGreaterThan(0);
+  });
+
+  it('falls back to one request when Drive ignores ranges', async () => {
+    stubRangeFetch({ ignoreRange: true });
+    class FakeXhr {
+      status = 200;
+      statusText = 'OK';
+      response = file.buffer;
+      onload?: () => void;
+      open() {}
+      setRequestHeader() {}
+      send() {
+        this.onload?.();
+      }
+    }
+    vi.stubGlobal('XMLHttpRequest', FakeXhr);
+
+    const data = await googleDriveCore.downloadFile({
+      fileId: 'file-1',
+      credentials,
+      onProgress: vi.fn(),
+      ranges: { chunkSize: 16, concurrency: 2 }
+    });
+
+    expect(new Uint8Array(data)).toEqual(file);
+  });
+});
+
+describe('googleDriveCore.downloadFile backoff', () => {
+  const file = new Uint8Array([1, 2, 3]);
+
+  function errorResponse(status: number, reason: string): Response {
+    const body = JSON.stringify({ error: { code: status, errors: [{ reason }] } });
+    return new Response(body, { status, statusText: 'Error' });
+  }
+
+  function sizeResponse(): Response {
+    return { ok: true, json: async () => ({ size: String(file.length) }) } as Response;
+  }
+
+  beforeEach(() => {
+    vi.useFakeTimers();
+    vi.stubGlobal('fetch', vi.fn());
+    vi.spyOn(console, 'warn').mockImplementation(() => {});
+    class FakeXhr {
+      status = 200;
+      statusText = 'OK';
+      response = file.buffer;
+      onload?: () => void;
+      open() {}
+      setRequestHeader() {}
+      send() {
+        this.onload?.();
+      }
+    }
+    vi.stubGlobal('XMLHttpRequest', FakeXhr);
+  });
+
+  afterEach(() => {
+    vi.useRealTimers();
+    vi.unstubAllGlobals();
+    vi.restoreAllMocks();
+  });
+
+  function download() {
+    return googleDriveCore.downloadFile({ fileId: 'file-1', credentials, onProgress: vi.fn() });
+  }
+
+  it('backs off and retries 403s that are rate limits', async () => {
+    vi.mocked(fetch)
+      .mockResolvedValueOnce(errorResponse(403, 'userRateLimitExceeded'))
+      .mockResolvedValueOnce(errorResponse(403, 'rateLimitExceeded'))
+      .mockResolvedValueOnce(sizeResponse());
+
+    const result = download();
+    await vi.runAllTimersAsync();
+
+    expect(new Uint8Array(await result)).toEqual(file);
+    expect(fetch).toHaveBeenCalledTimes(3);
+  });
+
+  it('fails other 403s without retrying', async () => {
+    vi.mocked(fetch).mockResolvedValue(errorResponse(403, 'insufficientFilePermissions'));
+
+    const result = download().catch((error) => error);
+    await vi.runAllTimersAsync();
+
+    expect(await result).toBeInstanceOf(Error);
+    expect(fetch).toHaveBeenCalledTimes(1);
+  });
+
+  it('gives up after the last retry', async () => {
+    vi.mocked(fetch).mockResolvedValue(errorResponse(429, 'rateLimitExceeded'));
+
+    const result = download().catch((error) => error);
+    await vi.runAllTimersAsync();
+
+    expect((await result).message).toContain('Failed to get file size');
+    expect(fetch).toHaveBeenCalledTimes(5);
+  });
+});
@@ -1,57 +1,382 @@
-import type { CloudProviderCore } from '../cloud-provider-core-types';
-import { requireCredentialString } from '../cloud-provider-core-types';
+import type { CloudCoreRangeOptions, CloudProviderCore } from '../cloud-provider-core-types';
+import { CloudHttpError, requireCredentialString } from '../cloud-provider-core-types';
 
-export const googleDriveCore: CloudProviderCore = {
-  async downloadFile({ fileId, credentials, onProgress }): Promise<ArrayBuffer> {
-    const accessToken = requireCredentialString(
-      credentials,
-      'accessToken',
-      'Google Drive access token'
-    );
+class DriveHttpError extends CloudHttpError {
+  constructor(
+    message: string,
+    status: number,
+    /** `reason` from Drive's error body, e.g. "rateLimitExceeded" */
+    readonly reason?: string
+  ) {
+    super(message, status);
+  }
+}
 
-    const sizeResponse = await fetch(
-      `https://www.googleapis.com/drive/v3/files/${fileId}?fields=size`,
-      {
-        headers: {
-          Authorization: `Bearer ${accessToken}`
-        }
+/** 403 reasons that mean "slow down" rather than "not allowed" */
+const RATE_LIMIT_REASONS = new Set(['rateLimitExceeded', 'userRateLimitExceeded']);
+
+/** The first error reason in a Drive JSON error body, if it has one */
+function driveErrorReason(body: string): string | undefined {
+  try {
+    const reason = JSON.parse(body)?.error?.errors?.[0]?.reason;
+    return typeof reason === 'string' ? reason : undefined;
+  } catch {
+    return undefined;
+  }
+}
+
+async function responseError(message: string, response: Response): Promise<DriveHttpError> {
+  const body = await response.text().catch(() => '');
+  return new DriveHttpError(message, response.status, driveErrorReason(body));
+}
+
+function isRateLimited(error: DriveHttpError): boolean {
+  if (error.status === 403) return RATE_LIMIT_REASONS.has(error.reason ?? '');
+  return error.status === 429 || error.status >= 500;
+}
+
+/**
+ * Retry Drive requests that were rate limited (429, or 403 with a rate-limit
+ * reason) or hit a transient server error, with exponential backoff and
+ * jitter. Other 403s (no access, quota used up) fail straight away. Parallel
+ * downloads make rate limits more likely, so each one backs off independently
+ * instead of failing the volume.
+ */
+async function withRateLimitBackoff<T>(
+  operation: () => Promise<T>,
+  maxRetries = 4,
+  baseDelay = 1000
+): Promise<T> {
+  for (let attempt = 0; ; attempt++) {
+    try {
+      return await operation();
+    } catch (error) {
+      if (!(error instanceof DriveHttpError) || !isRateLimited(error) || attempt >= maxRetries) {
+        throw error;
       }
+
+      const delay = baseDelay * Math.pow(2, attempt) + Math.random() * 1000;
+      console.warn(
+        `Drive download rate limited (HTTP ${error.status}), retrying in ${Math.round(delay)}ms`
+      );
+      await new Promise((resolve) => setTimeout(resolve, delay));
+    }
+  }
+}
+
+// Same rules as escapeNameForDriveQuery; the api-client module isn't worker-safe
+function escapeDriveQueryName(name: string): string {
+  return name.replace(/\\/g, '\\\\').replace(/'/g, "\\'");
+}
+
+/**
+ * Name to give a series folder or volume file on Drive. Uploads address files
+ * as "Series/Volume.cbz", so a slash inside a title would nest folders; it is
+ * swapped for the fullwidth look-alike, as sanitizeTitleSegment does. Control
+ * characters are dropped and names Drive would store blank become "_".
+ */
+export function toDriveName(title: string): string {
+  const name = Array.from(title, (ch) =>
+    ch.charCodeAt(0) <= 0x1f || ch.charCodeAt(0) === 0x7f ? '' : ch
+  )
+    .join('')
+    .replace(/\//g, '／')
+    .trim();
+  return name || '_';
+}
+
+/** Drive path ("Series/Volume.cbz") of an upload to `path`, with each part sanitized */
+export function toDrivePath(path: string): string {
+  const slash = path.lastIndexOf('/');
+  if (slash < 0) return toDriveName(path);
+  return `${toDriveName(path.slice(0, slash))}/${toDriveName(path.slice(slash + 1))}`;
+}
+
+const SERIES_LINE = /^\s*series:\s*(.+?)\s*$/im;
+
+/** The "Series: <title>" line placeholders read the real series title from */
+export function seriesTitleFromDescription(description: string | undefined): string | null {
+  const match = description?.match(SERIES_LINE);
+  return match ? match[1] : null;
+}
+
+/**
+ * `description` with its "Series:" line updated for a file now in
+ * `seriesTitle`'s folder: set when the folder name doesn't spell the title,
+ * dropped when it does. Other lines (user notes) are kept.
+ */
+export function withSeriesTitle(description: string | undefined, seriesTitle: string): string {
+  const notes = (description ?? '')
+    .split('\n')
+    .filter((line) => !SERIES_LINE.test(line))
+    .join('\n');
+  if (!seriesTitle || toDriveName(seriesTitle) === seriesTitle) return notes;
+  return notes ? `Series: ${seriesTitle}\n${notes}` : `Series: ${seriesTitle}`;
+}
+
+/** "v1.cbz" → ["v1", ".cbz"] */
+function splitExtension(filename: string): [string, string] {
+  const dot = filename.lastIndexOf('.');
+  return dot > 0 ? [filename.slice(0, dot), filename.slice(dot)] : [filename, ''];
+}
+
+/**
+ * Whether a listed Drive file ("Folder/Name.ext") is where an upload of
+ * `filename` for `seriesTitle` ended up: under the sanitized names, possibly
+ * numbered after a collision, and owned by that series by the same rule
+ * uploads use (see findUploadTarget).
+ */
+export function isDriveUploadOf(
+  file: { path: string; description?: string },
+  seriesTitle: string,
+  filename: string
+): boolean {
+  const slash = file.path.lastIndexOf('/');
+  const folder = slash < 0 ? '' : file.path.slice(0, slash);
+  const name = file.path.slice(slash + 1);
+  if (folder !== (seriesTitle ? toDriveName(seriesTitle) : '')) return false;
+
+  const [stem, ext] = splitExtension(toDriveName(filename));
+  if (name !== stem + ext) {
+    const numbered = name.startsWith(`${stem} (`) && name.endsWith(`)${ext}`);
+    const n = numbered ? name.slice(stem.length + 2, name.length - ext.length - 1) : '';
+    if (!/^[1-9]\d*$/.test(n) || n === '1') return false;
+  }
+
+  if (!seriesTitle) return true;
+  return (seriesTitleFromDescription(file.description) ?? folder) === seriesTitle;
+}
+
+async function sha256Hex(blob: Blob): Promise<string> {
+  const digest = await crypto.subtle.digest('SHA-256', await blob.arrayBuffer());
+  return Array.from(new Uint8Array(digest), (b) => b.toString(16).padStart(2, '0')).join('');
+}
+
+/**
+ * Look for a file that an earlier (possibly interrupted) run already put in
+ * the folder, so re-running an upload replaces it instead of creating a
+ * second file with the same name.
+ */
+async function findExistingFile(
+  accessToken: string,
+  folderId: string,
+  filename: string
+): Promise<{ id: string; sha256Checksum?: string; description?: string } | null> {
+  const name = escapeDriveQueryName(filename);
+  const query = `name = '${name}' and '${folderId}' in parents and trashed = false`;
+  const params = new URLSearchParams({
+    q: query,
+    fields: 'files(id,sha256Checksum,description)',
+    pageSize: '1'
+  });
+  const response = await fetch(`https://www.googleapis.com/drive/v3/files?${params}`, {
+    headers: { Authorization: `Bearer ${accessToken}` }
+  });
+  if (!response.ok) {
+    throw new DriveHttpError(
+      `Existing file lookup failed: ${response.status} ${response.statusText}`,
+      response.status
     );
+  }
+  const data = await response.json();
+  return data.files?.[0] ?? null;
+}
 
-    if (!sizeResponse.ok) {
-      throw new Error(`Failed to get file size: ${sizeResponse.statusText}`);
+/**
+ * Different titles can share a Drive name once sanitized ("Fate/Zero" and
+ * "Fate／Zero"), putting their volumes in one folder. A file there belongs to
+ * the series named in its description, or else to the series the folder is
+ * literally named after. Files of another series keep their name and this
+ * upload gets a numbered one ("Vol 1 (2).cbz") instead of replacing them.
+ */
+async function findUploadTarget(
+  accessToken: string,
+  folderId: string,
+  seriesTitle: string,
+  filename: string
+): Promise<{ name: string; existing: { id: string; sha256Checksum?: string } | null }> {
+  const [stem, ext] = splitExtension(filename);
+
+  for (let n = 1; ; n++) {
+    const name = n === 1 ? filename : `${stem} (${n})${ext}`;
+    const existing = await findExistingFile(accessToken, folderId, name);
+    if (!existing) return { name, existing: null };
+
+    const owner = seriesTitleFromDescription(existing.description) ?? toDriveName(seriesTitle);
+    if (!seriesTitle || owner === seriesTitle) return { name, existing };
+  }
+}
+
+/** Inclusive byte ranges covering `total` bytes in `chunkSize` pieces */
+export function splitByteRanges(total: number, chunkSize: number): Array<[number, number]> {
+  const ranges: Array<[number, number]> = [];
+  for (let start = 0; start < total; start += chunkSize) {
+    ranges.push([start, Math.min(start + chunkSize, total) - 1]);
+  }
+  return ranges;
+}
+
+/** Drive answered a range request with the whole file */
+class RangeNotSupportedError extends Error {}
+
+async function fetchFileSize(fileId: string, accessToken: string): Promise<number> {
+  const sizeResponse = await fetch(
+    `https://www.googleapis.com/drive/v3/files/${fileId}?fields=size`,
+    {
+      headers: {
+        Authorization: `Bearer ${accessToken}`
+      }
     }
+  );
+
+  if (!sizeResponse.ok) {
+    throw await responseError(`Failed to get file size: ${sizeResponse.statusText}`, sizeResponse);
+  }
 
-    const sizeData = await sizeResponse.json();
-    const totalSize = parseInt(sizeData.size, 10);
+  const sizeData = await sizeResponse.json();
+  return parseInt(sizeData.size, 10);
+}
 
-    const xhr = new XMLHttpRequest();
-    xhr.open('GET', `https://www.googleapis.com/drive/v3/files/${fileId}?alt=media`);
-    xhr.setRequestHeader('Authorization', `Bearer ${accessToken}`);
-    xhr.responseType = 'arraybuffer';
+function downloadWhole(
+  fileId: string,
+  accessToken: string,
+  totalSize: number,
+  onProgress: (loaded: number, total: number) => void
+): Promise<ArrayBuffer> {
+  const xhr = new XMLHttpRequest();
+  xhr.open('GET', `https://www.googleapis.com/drive/v3/files/${fileId}?alt=media`);
+  xhr.setRequestHeader('Authorization', `Bearer ${accessToken}`);
+  xhr.responseType = 'arraybuffer';
 
-    return await new Promise((resolve, reject) => {
-      xhr.onprogress = (event) => {
-        onProgress(event.loaded, totalSize);
-      };
+  return new Promise<ArrayBuffer>((resolve, reject) => {
+    xhr.onprogress = (event) => {
+      onProgress(event.loaded, totalSize);
+    };
 
-      xhr.onerror = () => reject(new Error('Network error during download'));
-      xhr.ontimeout = () => reject(new Error('Download timed out'));
-      xhr.onabort = () => reject(new Error('Download aborted'));
+    xhr.onerror = () => reject(new Error('Network error during download'));
+    xhr.ontimeout = () => reject(new Error('Download timed out'));
+    xhr.onabort = () => reject(new Error('Download aborted'));
 
-      xhr.onload = () => {
-        if (xhr.status >= 200 && xhr.status < 300) {
-          resolve(xhr.response as ArrayBuffer);
-        } else {
-          reject(new Error(`HTTP error ${xhr.status}: ${xhr.statusText}`));
-        }
-      };
+    xhr.onload = () => {
+      if (xhr.status >= 200 && xhr.status < 300) {
+        resolve(xhr.response as ArrayBuffer);
+      } else {
+        const body = xhr.response ? new TextDecoder().decode(xhr.response as ArrayBuffer) : '';
+        reject(
+          new DriveHttpError(
+            `HTTP error ${xhr.status}: ${xhr.statusText}`,
+            xhr.status,
+            driveErrorReason(body)
+          )
+        );
+      }
+    };
 
-      xhr.send();
+    xhr.send();
+  });
+}
+
+/**
+ * Download a file as parallel byte ranges. Each range streams straight into
+ * one buffer of the file's size, so memory stays at the file size however
+ * many ranges are in flight. The first range that fails for good aborts the
+ * others; cancelling the download terminates the worker, which aborts them too.
+ */
+async function downloadInRanges(
+  fileId: string,
+  accessToken: string,
+  totalSize: number,
+  options: CloudCoreRangeOptions,
+  onProgress: (loaded: number, total: number) => void
+): Promise<ArrayBuffer> {
+  const url = `https://www.googleapis.com/drive/v3/files/${fileId}?alt=media`;
+  const buffer = new Uint8Array(totalSize);
+  const ranges = splitByteRanges(totalSize, options.chunkSize);
+  const rangeLoaded = new Array<number>(ranges.length).fill(0);
+  const controller = new AbortController();
+  let loaded = 0;
+  let nextRange = 0;
+
+  const fetchRange = (index: number) =>
+    withRateLimitBackoff(async () => {
+      const [start, end] = ranges[index];
+      // A retry starts the range over
+      loaded -= rangeLoaded[index];
+      rangeLoaded[index] = 0;
+
+      const response = await fetch(url, {
+        headers: { Authorization: `Bearer ${accessToken}`, Range: `bytes=${start}-${end}` },
+        signal: controller.signal
+      });
+      if (response.status === 200) {
+        throw new RangeNotSupportedError('Drive ignored the range request');
+      }
+      if (response.status !== 206) {
+        const message = `HTTP error ${response.status}: ${response.statusText}`;
+        throw await responseError(message, response);
+      }
+      const reader = response.body?.getReader();
+      if (!reader) {
+        throw new Error('Response body is not readable');
+      }
+
+      let offset = start;
+      while (true) {
+        const { done, value } = await reader.read();
+        if (done) break;
+        if (offset + value.byteLength > end + 1) {
+          throw new Error('Range response is longer than requested');
+        }
+        buffer.set(value, offset);
+        offset += value.byteLength;
+        rangeLoaded[index] += value.byteLength;
+        loaded += value.byteLength;
+        onProgress(loaded, totalSize);
+      }
+      if (offset !== end + 1) {
+        throw new Error(`Range ${start}-${end} ended early at ${offset}`);
+      }
     });
+
+  const runners = Array.from({ length: Math.min(options.concurrency, ranges.length) }, async () => {
+    while (nextRange < ranges.length) {
+      await fetchRange(nextRange++);
+    }
+  });
+
+  try {
+    await Promise.all(runners);
+  } catch (error) {
+    controller.abort();
+    throw error;
+  }
+  return buffer.buffer;
+}
+
+export const googleDriveCore: CloudProviderCore = {
+  async downloadFile({ fileId, credentials, onProgress, ranges }): Promise<ArrayBuffer> {
+    const accessToken = requireCredentialString(
+      credentials,
+      'accessToken',
+      'Google Drive access token'
+    );
+
+    const totalSize = await withRateLimitBackoff(() => fetchFileSize(fileId, accessToken));
+
+    if (ranges && ranges.concurrency > 1 && totalSize > ranges.chunkSize) {
+      try {
+        return await downloadInRanges(fileId, accessToken, totalSize, ranges, onProgress);
+      } catch (error) {
+        if (!(error instanceof RangeNotSupportedError)) throw error;
+        console.warn('Drive ignored range requests; downloading in one request');
+      }
+    }
+
+    return withRateLimitBackoff(() => downloadWhole(fileId, accessToken, totalSize, onProgress));
   },
 
   async uploadFile({
+    seriesTitle,
     filename,
     blob,
     credentials,
@@ -70,10 +395,29 @@ export const googleDriveCore: CloudProviderCore = {
       'Google Drive series folder ID'
     );
     const uploadMimeType = mimeType || 'application/octet-stream';
+    let name = toDriveName(filename);
+
+    if (!existingFileId) {
+      const target = await findUploadTarget(accessToken, seriesFolderId, seriesTitle, name);
+      const existing = target.existing;
+      name = target.name;
+      if (existing) {
+        // Identical content is already on Drive: nothing to upload
+        if (existing.sha256Checksum && existing.sha256Checksum === (await sha256Hex(blob))) {
+          onProgress?.(blob.size, blob.size);
+          return existing.id;
+        }
+        existingFileId = existing.id;
+      }
+    }
 
+    // When the folder name no longer spells the series title, record it where
+    // placeholders look for it
+    const description = withSeriesTitle(undefined, seriesTitle);
     const metadata = {
-      name: filename,
+      name,
       mimeType: uploadMimeType,
+      ...(description ? { description } : {}),
       ...(existingFileId ? {} : { parents: [seriesFolderId] })
     };
 
@@ -91,7 +435,10 @@ export const googleDriveCore: CloudProviderCore = {
     });
 
     if (!initResponse.ok) {
-      throw new Error(`Upload init failed: ${initResponse.status} ${initResponse.statusText}`);
+      throw new DriveHttpError(
+        `Upload init failed: ${initResponse.status} ${initResponse.statusText}`,
+        initResponse.status
+      );
     }
 
     const uploadUrl = initResponse.headers.get('Location');
@@ -115,7 +462,7 @@ export const googleDriveCore: CloudProviderCore = {
           const result = JSON.parse(xhr.responseText);
           resolve(result.id);
         } else {
-          reject(new Error(`Upload failed: ${xhr.status} ${xhr.statusText}`));
+          reject(new DriveHttpError(`Upload failed: ${xhr.status} ${xhr.statusText}`, xhr.status));
         }
       };
 
@@ -1,5 +1,5 @@
 import type { CloudProviderCore } from '../cloud-provider-core-types';
-import { requireCredentialString } from '../cloud-provider-core-types';
+import { CloudHttpError, requireCredentialString } from '../cloud-provider-core-types';
 import { ONEDRIVE_CONFIG } from '../../providers/onedrive/constants';
 import { parseNextExpectedRange } from '../../providers/onedrive/upload-session';
 
@@ -96,7 +96,8 @@ export const onedriveCore: CloudProviderCore = {
         if (xhr.status >= 200 && xhr.status < 300) {
           resolve(xhr.response as ArrayBuffer);
         } else {
-          reject(new Error(`OneDrive download HTTP ${xhr.status}: ${xhr.statusText}`));
+          const message = `OneDrive download HTTP ${xhr.status}: ${xhr.statusText}`;
+          reject(new CloudHttpError(message, xhr.status));
         }
       };
       xhr.send();
@@ -146,7 +147,10 @@ export const onedriveCore: CloudProviderCore = {
         }
         sessionError = `${sessionResponse.status} ${sessionResponse.statusText}`;
         if (!isRetryableStatus(sessionResponse.status)) {
-          throw new Error(`Failed to create OneDrive upload session: ${sessionError}`);
+          throw new CloudHttpError(
+            `Failed to create OneDrive upload session: ${sessionError}`,
+            sessionResponse.status
+          );
         }
         if (sessionAttempt >= MAX_CHUNK_ATTEMPTS) {
           throw new Error(
@@ -242,8 +246,9 @@ export const onedriveCore: CloudProviderCore = {
         // A definitive 4xx (other than 408/429) means Graph rejected the
         // request outright — no ambiguity to resolve, unlike a dropped
         // connection or exhausted retries against a transient status.
-        throw new Error(
-          `OneDrive upload chunk failed: ${chunkResponse.status} ${chunkResponse.statusText}`
+        throw new CloudHttpError(
+          `OneDrive upload chunk failed: ${chunkResponse.status} ${chunkResponse.statusText}`,
+          chunkResponse.status
         );
       }
       await retryOrThrow(`HTTP ${chunkResponse.status} ${chunkResponse.statusText}`, waitMs);
@@ -1,5 +1,6 @@
 import { providerManager } from './provider-manager';
 import { unifiedCloudManager } from './unified-cloud-manager';
+import { isAutoSyncEnabled } from './auto-sync';
 import { driveApiClient } from '$lib/util/sync/providers/google-drive/api-client';
 import { tokenManager } from '$lib/util/sync/providers/google-drive/token-manager';
 import { GOOGLE_DRIVE_CONFIG } from '$lib/util/sync/providers/google-drive/constants';
@@ -145,10 +146,12 @@ export async function initializeProviders(): Promise<void> {
         await unifiedCloudManager.fetchAllCloudVolumes();
         console.log('✅ Cloud cache populated on app startup');
 
-        // Sync progress after cache is populated
-        console.log('🔄 Syncing progress on app startup...');
-        await unifiedCloudManager.syncProgress({ silent: true });
-        console.log('✅ Initial sync completed');
+        // Sync progress after cache is populated, unless the user syncs manually
+        if (isAutoSyncEnabled()) {
+          console.log('🔄 Syncing progress on app startup...');
+          await unifiedCloudManager.syncProgress({ silent: true });
+          console.log('✅ Initial sync completed');
+        }
       } catch (error) {
         console.warn('⚠️ Failed to populate cloud cache or sync on startup:', error);
       }
@@ -168,6 +168,8 @@ export interface DriveFileMetadata extends CloudFileMetadata {
   parentId?: string;
   /** Original file name from Drive */
   name?: string;
+  /** Hex MD5 of the content, used to verify downloads */
+  md5Checksum?: string;
 }
 
 /**
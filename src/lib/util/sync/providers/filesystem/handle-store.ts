@@ -47,3 +47,19 @@ export async function loadRootHandle(): Promise<FileSystemDirectoryHandle | null
 export async function clearRootHandle(): Promise<void> {
   await withStore('readwrite', (store) => store.delete(ROOT_KEY));
 }
+
+/** Folder for local reader-state backups (see reader-state-backup) */
+const BACKUP_KEY = 'reader-state-backup';
+
+export async function saveBackupHandle(handle: FileSystemDirectoryHandle): Promise<void> {
+  await withStore('readwrite', (store) => store.put(handle, BACKUP_KEY));
+}
+
+export async function loadBackupHandle(): Promise<FileSystemDirectoryHandle | null> {
+  const result = await withStore('readonly', (store) => store.get(BACKUP_KEY));
+  return (result as FileSystemDirectoryHandle | undefined) ?? null;
+}
+
+export async function clearBackupHandle(): Promise<void> {
+  await withStore('readwrite', (store) => store.delete(BACKUP_KEY));
+}
@@ -1,5 +1,5 @@
 import { describe, it, expect } from 'vitest';
-import { escapeNameForDriveQuery } from './api-client';
+import { driveErrorReason, escapeNameForDriveQuery, isDriveAuthFailure } from './api-client';
 
 describe('escapeNameForDriveQuery', () => {
   it('should return string unchanged when no special characters', () => {
@@ -46,3 +46,24 @@ describe('escapeNameForDriveQuery', () => {
     expect(escapeNameForDriveQuery("The King's Avatar")).toBe("The King\\'s Avatar");
   });
 });
+
+describe('isDriveAuthFailure', () => {
+  const reason = (reason: string) => driveErrorReason({ error: { errors: [{ reason }] } });
+
+  it('treats 401 and scope/permission 403s as auth failures', () => {
+    expect(isDriveAuthFailure(401)).toBe(true);
+    expect(isDriveAuthFailure(403)).toBe(true);
+    expect(isDriveAuthFailure(403, reason('insufficientPermissions'))).toBe(true);
+  });
+
+  it('leaves rate limits and file sharing errors to the caller', () => {
+    expect(isDriveAuthFailure(403, reason('userRateLimitExceeded'))).toBe(false);
+    expect(isDriveAuthFailure(403, reason('insufficientFilePermissions'))).toBe(false);
+    expect(isDriveAuthFailure(404)).toBe(false);
+  });
+
+  it('reads no reason from bodies without one', () => {
+    expect(driveErrorReason(undefined)).toBeUndefined();
+    expect(driveErrorReason({ error: { message: 'Forbidden' } })).toBeUndefined();
+  });
+});
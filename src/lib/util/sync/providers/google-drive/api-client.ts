@@ -1,4 +1,5 @@
 import { GOOGLE_DRIVE_CONFIG, type DriveFile } from './constants';
+import { getGoogleClient } from './client-config';
 import { tokenManager } from './token-manager';
 
 /**
@@ -19,7 +20,7 @@ export function escapeNameForDriveQuery(name: string): string {
   return name.replace(/\\/g, '\\\\').replace(/'/g, "\\'");
 }
 
-class DriveApiError extends Error {
+export class DriveApiError extends Error {
   constructor(
     message: string,
     public readonly status?: number,
@@ -30,6 +31,43 @@ class DriveApiError extends Error {
   }
 }
 
+/**
+ * 403 reasons that are about quotas or one file's sharing rather than the
+ * token — signing in again can't fix these, so they must not trigger re-auth
+ */
+const NON_AUTH_FORBIDDEN_REASONS = new Set([
+  'rateLimitExceeded',
+  'userRateLimitExceeded',
+  'dailyLimitExceeded',
+  'storageQuotaExceeded',
+  'quotaExceeded',
+  'insufficientFilePermissions',
+  'domainPolicy'
+]);
+
+/**
+ * The `reason` of a Drive error response, from a gapi error (`result`) or a
+ * raw JSON body (XHR uploads/downloads)
+ */
+export function driveErrorReason(body: unknown): string | undefined {
+  const error = (body as { error?: { errors?: { reason?: string }[] } } | null)?.error;
+  return error?.errors?.[0]?.reason;
+}
+
+/** Whether a failed Drive call means the token is expired or lacks access */
+export function isDriveAuthFailure(status: number | undefined, reason?: string): boolean {
+  if (status === 401) return true;
+  return status === 403 && !(reason && NON_AUTH_FORBIDDEN_REASONS.has(reason));
+}
+
+function missingScopesError(): DriveApiError {
+  return new DriveApiError(
+    'Google Drive access was not granted. Reconnect and allow access to your files.',
+    403,
+    false
+  );
+}
+
 /**
  * Handle authentication errors (401/403) with auto re-auth support
  * Extracted to be reusable across api-client and provider
@@ -84,6 +122,33 @@ async function handleAuthError(status: number, retryOnAuth = true): Promise<neve
   throw new DriveApiError('Authentication error', status, false);
 }
 
+/**
+ * A 403 can mean Drive access was never granted or was revoked from the
+ * Google account page. Re-authenticating silently returns a token with the
+ * same scopes, so when scopes are missing stop here: the token manager has
+ * flagged the account and Drive calls fail fast until the user reconnects.
+ */
+async function handleAuthFailure(status: number, retryOnAuth = true): Promise<never> {
+  if (status === 403) {
+    const missing = await tokenManager.verifyGrantedScopes();
+    if (missing.length > 0) throw missingScopesError();
+  }
+  return handleAuthError(status, retryOnAuth);
+}
+
+/** Reason from an XHR error response, whatever its responseType */
+function xhrErrorReason(xhr: XMLHttpRequest): string | undefined {
+  if (xhr.responseType === 'json') return driveErrorReason(xhr.response);
+  if (xhr.responseType === '' || xhr.responseType === 'text') {
+    try {
+      return driveErrorReason(JSON.parse(xhr.responseText));
+    } catch {
+      return undefined;
+    }
+  }
+  return undefined;
+}
+
 class DriveApiClient {
   private isInitialized = false;
 
@@ -116,7 +181,7 @@ class DriveApiClient {
       gapi.load('client:picker', async () => {
         try {
           await gapi.client.init({
-            apiKey: GOOGLE_DRIVE_CONFIG.API_KEY,
+            apiKey: getGoogleClient().apiKey,
             discoveryDocs: [GOOGLE_DRIVE_CONFIG.DISCOVERY_DOC]
           });
 
@@ -131,6 +196,9 @@ class DriveApiClient {
   }
 
   private async handleApiCall<T>(apiCall: () => Promise<T>, retryOnAuth = true): Promise<T> {
+    // Fail fast with a clear message rather than an opaque 403 from Drive
+    if (tokenManager.getMissingScopes().length > 0) throw missingScopesError();
+
     try {
       return await apiCall();
     } catch (error: any) {
@@ -143,8 +211,8 @@ class DriveApiClient {
         error.status === 0;
 
       // Handle authentication errors
-      if (!isNetworkError && (error.status === 401 || error.status === 403)) {
-        await handleAuthError(error.status, retryOnAuth);
+      if (!isNetworkError && isDriveAuthFailure(error.status, driveErrorReason(error.result))) {
+        await handleAuthFailure(error.status, retryOnAuth);
       }
 
       throw new DriveApiError(error.message || 'Unknown API error', error.status, isNetworkError);
@@ -203,6 +271,8 @@ class DriveApiClient {
     fileId: string,
     onProgress?: (loaded: number, total: number) => void
   ): Promise<Blob> {
+    if (tokenManager.getMissingScopes().length > 0) throw missingScopesError();
+
     // Get current access token
     const token = this.getCurrentToken();
     if (!token) {
@@ -237,10 +307,10 @@ class DriveApiClient {
       xhr.onload = async () => {
         if (xhr.status >= 200 && xhr.status < 300) {
           resolve(xhr.response as Blob);
-        } else if (xhr.status === 401 || xhr.status === 403) {
+        } else if (isDriveAuthFailure(xhr.status, xhrErrorReason(xhr))) {
           // Handle authentication errors using shared helper
           try {
-            await handleAuthError(xhr.status);
+            await handleAuthFailure(xhr.status);
           } catch (error) {
             reject(error);
           }
@@ -271,6 +341,8 @@ class DriveApiClient {
     form.append('metadata', new Blob([JSON.stringify(metadata)], { type: 'application/json' }));
     form.append('file', blob);
 
+    if (tokenManager.getMissingScopes().length > 0) throw missingScopesError();
+
     const url = `https://www.googleapis.com/upload/drive/v3/files${fileId ? `/${fileId}` : ''}?uploadType=multipart`;
     const token = tokenManager.isAuthenticated() ? this.getCurrentToken() : null;
 
@@ -312,9 +384,9 @@ class DriveApiClient {
           } catch {
             reject(new DriveApiError('Upload response parsing failed', xhr.status));
           }
-        } else if (xhr.status === 401 || xhr.status === 403) {
+        } else if (isDriveAuthFailure(xhr.status, xhrErrorReason(xhr))) {
           try {
-            await handleAuthError(xhr.status);
+            await handleAuthFailure(xhr.status);
           } catch (error) {
             reject(error);
           }
@@ -0,0 +1,27 @@
+import { describe, it, expect } from 'vitest';
+import { classifyAuthError } from './auth-errors';
+
+describe('classifyAuthError', () => {
+  it('classifies a denied consent as user-denied', () => {
+    expect(classifyAuthError('access_denied')).toMatchObject({
+      kind: 'user-denied',
+      code: 'access_denied'
+    });
+  });
+
+  it('classifies popup errors from both callbacks', () => {
+    expect(classifyAuthError('popup_closed').kind).toBe('cancelled');
+    expect(classifyAuthError('popup_failed_to_open').kind).toBe('popup-blocked');
+    expect(classifyAuthError('popup_blocked').kind).toBe('popup-blocked');
+  });
+
+  it('marks temporary Google failures as transient', () => {
+    expect(classifyAuthError('server_error').kind).toBe('transient');
+    expect(classifyAuthError('temporarily_unavailable').kind).toBe('transient');
+  });
+
+  it('treats unknown and missing codes as fatal', () => {
+    expect(classifyAuthError('invalid_client').kind).toBe('fatal');
+    expect(classifyAuthError(undefined)).toMatchObject({ kind: 'fatal', code: 'unknown' });
+  });
+});
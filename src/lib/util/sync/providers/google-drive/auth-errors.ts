@@ -0,0 +1,61 @@
+/**
+ * Google sign-in failures, classified so callers can react by kind instead
+ * of matching raw GIS error codes.
+ */
+
+export type GoogleAuthErrorKind =
+  /** The user clicked "Deny" on the consent screen */
+  | 'user-denied'
+  /** The user closed the sign-in popup */
+  | 'cancelled'
+  /** The browser blocked the popup (no user activation) */
+  | 'popup-blocked'
+  /** Google had a temporary problem issuing the token; worth retrying */
+  | 'transient'
+  /** Misconfiguration or an unexpected error; retrying won't help */
+  | 'fatal';
+
+export interface GoogleAuthError {
+  kind: GoogleAuthErrorKind;
+  /** Raw code from Google Identity Services, kept for logging */
+  code: string;
+  /** Human-readable message for the snackbar */
+  message: string;
+}
+
+/**
+ * Window event fired when the user declines Google's consent screen. It is a
+ * choice rather than a failure, so listeners return to the signed-out state
+ * without showing an error.
+ */
+export const OAUTH_CANCELLED_EVENT = 'oauth-cancelled';
+
+export type OAuthCancelledEvent = CustomEvent<{ provider: 'google-drive'; code: string }>;
+
+const KIND_BY_CODE: Record<string, GoogleAuthErrorKind> = {
+  access_denied: 'user-denied',
+  popup_closed: 'cancelled',
+  popup_closed_by_user: 'cancelled',
+  popup_failed_to_open: 'popup-blocked',
+  popup_blocked: 'popup-blocked',
+  server_error: 'transient',
+  temporarily_unavailable: 'transient'
+};
+
+const MESSAGES: Record<GoogleAuthErrorKind, string> = {
+  'user-denied': 'Google sign-in cancelled. Google Drive was not connected.',
+  cancelled: 'Sign-in cancelled. Please try again when ready.',
+  'popup-blocked': 'Google Drive session expired — click or tap anywhere to reconnect.',
+  transient: 'Google sign-in is temporarily unavailable. Retrying...',
+  fatal: 'Authentication failed. Please try signing in again.'
+};
+
+/**
+ * Classify an error from the token client's `callback` (OAuth errors) or
+ * `error_callback` (popup errors, reported as `type`)
+ */
+export function classifyAuthError(code: string | undefined): GoogleAuthError {
+  const normalized = code || 'unknown';
+  const kind = KIND_BY_CODE[normalized] ?? 'fatal';
+  return { kind, code: normalized, message: MESSAGES[kind] };
+}
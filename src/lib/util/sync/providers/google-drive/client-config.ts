@@ -0,0 +1,87 @@
+/**
+ * Google Cloud client used for Drive
+ *
+ * Builds ship with the app's own OAuth client and API key
+ * (VITE_GDRIVE_CLIENT_ID / VITE_GDRIVE_API_KEY). Self-hosters, or anyone who
+ * wants Drive access to go through their own Google Cloud project, can set
+ * their own here. It's kept in this browser's localStorage; the API key is
+ * sent with every Picker request anyway, and the token flow has no client
+ * secret to store.
+ */
+
+import { GOOGLE_DRIVE_CONFIG } from './constants';
+
+export interface GoogleClientConfig {
+  clientId: string;
+  apiKey: string;
+}
+
+const CLIENT_ID_PATTERN = /^\d+-[a-z0-9]+\.apps\.googleusercontent\.com$/;
+const API_KEY_PATTERN = /^AIza[0-9A-Za-z_-]{35}$/;
+
+/** Why a client ID can't be used, or null if it looks right */
+export function validateClientId(clientId: string): string | null {
+  if (!clientId.trim()) return 'Enter a client ID';
+  if (!CLIENT_ID_PATTERN.test(clientId.trim())) {
+    return 'Client IDs look like 1234567890-abc123.apps.googleusercontent.com';
+  }
+  return null;
+}
+
+/** Why an API key can't be used, or null if it looks right */
+export function validateApiKey(apiKey: string): string | null {
+  if (!apiKey.trim()) return 'Enter an API key';
+  if (!API_KEY_PATTERN.test(apiKey.trim())) {
+    return 'API keys start with "AIza" and are 39 characters long';
+  }
+  return null;
+}
+
+/** The user's own client, or null when using the app's */
+export function getCustomGoogleClient(): GoogleClientConfig | null {
+  if (typeof localStorage === 'undefined') return null;
+  const stored = localStorage.getItem(GOOGLE_DRIVE_CONFIG.STORAGE_KEYS.CUSTOM_CLIENT);
+  if (!stored) return null;
+  try {
+    const config = JSON.parse(stored) as Partial<GoogleClientConfig>;
+    if (!config.clientId || !config.apiKey) return null;
+    return { clientId: config.clientId, apiKey: config.apiKey };
+  } catch {
+    return null;
+  }
+}
+
+/** The client the Drive sign-in, API and Picker use */
+export function getGoogleClient(): GoogleClientConfig {
+  return (
+    getCustomGoogleClient() ?? {
+      clientId: GOOGLE_DRIVE_CONFIG.CLIENT_ID,
+      apiKey: GOOGLE_DRIVE_CONFIG.API_KEY
+    }
+  );
+}
+
+/**
+ * Use the user's own client, or the app's again when `config` is null.
+ * Tokens issued to the old client don't work with the new one, so the
+ * caller signs out of Drive when this returns true.
+ *
+ * @returns Whether the client ID changed
+ * @throws If the client ID or API key is malformed
+ */
+export function setCustomGoogleClient(config: GoogleClientConfig | null): boolean {
+  const previousClientId = getGoogleClient().clientId;
+
+  if (config) {
+    const error = validateClientId(config.clientId) ?? validateApiKey(config.apiKey);
+    if (error) throw new Error(error);
+    localStorage.setItem(
+      GOOGLE_DRIVE_CONFIG.STORAGE_KEYS.CUSTOM_CLIENT,
+      JSON.stringify({ clientId: config.clientId.trim(), apiKey: config.apiKey.trim() })
+    );
+  } else {
+    localStorage.removeItem(GOOGLE_DRIVE_CONFIG.STORAGE_KEYS.CUSTOM_CLIENT);
+  }
+
+  return getGoogleClient().clientId !== previousClientId;
+}
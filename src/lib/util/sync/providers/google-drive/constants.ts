@@ -7,6 +7,7 @@ export interface DriveFile {
   createdTime?: string;
   parents?: string[];
   description?: string;
+  md5Checksum?: string;
 }
 
 export const GOOGLE_DRIVE_CONFIG = {
@@ -15,6 +16,7 @@ export const GOOGLE_DRIVE_CONFIG = {
   DISCOVERY_DOC: 'https://www.googleapis.com/discovery/v1/apis/drive/v3/rest',
   SCOPES: 'https://www.googleapis.com/auth/drive.file',
   OAUTH_ENDPOINT: 'https://oauth2.googleapis.com/revoke',
+  TOKENINFO_ENDPOINT: 'https://oauth2.googleapis.com/tokeninfo',
 
   FOLDER_NAMES: {
     READER: 'mokuro-reader'
@@ -22,7 +24,8 @@ export const GOOGLE_DRIVE_CONFIG = {
 
   FILE_NAMES: {
     VOLUME_DATA: 'volume-data.json',
-    PROFILES: 'profiles.json'
+    PROFILES: 'profiles.json',
+    KNOWN_WORDS: 'known-words.json'
   },
 
   MIME_TYPES: {
@@ -35,12 +38,17 @@ export const GOOGLE_DRIVE_CONFIG = {
     TOKEN_EXPIRES: 'gdrive_token_expires',
     SYNC_AFTER_LOGIN: 'sync_after_login',
     LAST_AUTH_TIME: 'gdrive_last_auth_time',
-    HAS_AUTHENTICATED: 'gdrive_has_authenticated' // Track if user has ever authenticated
+    HAS_AUTHENTICATED: 'gdrive_has_authenticated', // Track if user has ever authenticated
+    GRANTED_SCOPES: 'gdrive_granted_scopes', // Scopes of the current token (space-separated)
+    ROOT_FOLDER: 'gdrive_root_folder', // User-chosen sync root ({ id, name } JSON)
+    CUSTOM_CLIENT: 'gdrive_custom_client' // User's own OAuth client ({ clientId, apiKey } JSON)
   },
 
   // Token expiry monitoring
   TOKEN_WARNING_BUFFER_MS: 10 * 60 * 1000, // Warn 10 minutes before expiry
   TOKEN_REFRESH_CHECK_INTERVAL_MS: 60 * 1000, // Check every minute
+  TOKEN_PROACTIVE_REFRESH_MS: 5 * 60 * 1000, // Refresh on the next gesture 5 minutes before expiry
+  TOKEN_PROACTIVE_BACKOFF_MS: 60 * 1000, // First backoff after a failed proactive refresh (doubles)
 
   // Debug mode: Set to true to use short-lived tokens for testing (30 seconds)
   DEBUG_SHORT_TOKEN_EXPIRY: false
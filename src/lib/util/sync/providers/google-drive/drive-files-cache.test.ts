@@ -2,8 +2,10 @@ import { beforeEach, describe, expect, it, vi } from 'vitest';
 
 vi.mock('./api-client', () => ({
   driveApiClient: {
-    listFiles: vi.fn()
-  }
+    listFiles: vi.fn(),
+    getFileMetadata: vi.fn()
+  },
+  DriveApiError: class extends Error {}
 }));
 
 vi.mock('../../folder-deduplicator', () => ({
@@ -20,10 +22,12 @@ vi.mock('./google-drive-provider', () => ({
 
 import { driveApiClient } from './api-client';
 import { driveFilesCache } from './drive-files-cache';
+import { getCustomRootFolder, setCustomRootFolder } from './root-folder';
 
 describe('driveFilesCache', () => {
   beforeEach(() => {
     vi.clearAllMocks();
+    setCustomRootFolder(null);
     driveFilesCache.clear();
   });
 
@@ -82,4 +86,71 @@ describe('driveFilesCache', () => {
       'Series/Volume 1.webp'
     ]);
   });
+
+  it('builds placeholders from a pre-existing custom root folder', async () => {
+    setCustomRootFolder({ id: 'collection', name: 'My Manga' });
+    vi.mocked(driveApiClient.getFileMetadata).mockResolvedValue({
+      id: 'collection',
+      mimeType: 'application/vnd.google-apps.folder',
+      trashed: false,
+      capabilities: { canAddChildren: true }
+    });
+    vi.mocked(driveApiClient.listFiles).mockImplementation(async (query: string) => {
+      if (query.startsWith("'collection' in parents")) {
+        return [
+          {
+            id: 'series-folder',
+            name: 'Existing Series',
+            mimeType: 'application/vnd.google-apps.folder',
+            parents: ['collection']
+          }
+        ];
+      }
+      if (query.startsWith("'series-folder' in parents")) {
+        return [
+          {
+            id: 'cbz-1',
+            name: 'Volume 1.cbz',
+            mimeType: 'application/x-cbz',
+            parents: ['series-folder'],
+            size: '100'
+          }
+        ];
+      }
+      // Volumes outside the chosen folder must not show up
+      return [
+        {
+          id: 'outside',
+          name: 'Other.cbz',
+          mimeType: 'application/x-cbz',
+          parents: ['elsewhere']
+        }
+      ];
+    });
+
+    await driveFilesCache.fetch();
+
+    expect(driveFilesCache.getAllFiles().map((file) => file.path)).toEqual([
+      'Existing Series/Volume 1.cbz'
+    ]);
+    expect(await driveFilesCache.getReaderFolderId()).toBe('collection');
+  });
+
+  it('falls back to the default folder when the custom root was deleted', async () => {
+    setCustomRootFolder({ id: 'gone', name: 'Deleted' });
+    vi.mocked(driveApiClient.getFileMetadata).mockResolvedValue({
+      id: 'gone',
+      mimeType: 'application/vnd.google-apps.folder',
+      trashed: true
+    });
+    vi.mocked(driveApiClient.listFiles).mockResolvedValue([]);
+
+    await driveFilesCache.fetch();
+
+    expect(getCustomRootFolder()).toBeNull();
+    expect(driveApiClient.listFiles).toHaveBeenCalledWith(
+      `'me' in owners and trashed=false`,
+      expect.any(String)
+    );
+  });
 });
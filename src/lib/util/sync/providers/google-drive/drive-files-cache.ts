@@ -1,6 +1,7 @@
 import { writable } from 'svelte/store';
 import { driveApiClient } from './api-client';
-import { GOOGLE_DRIVE_CONFIG } from './constants';
+import { GOOGLE_DRIVE_CONFIG, type DriveFile } from './constants';
+import { resolveCustomRootFolder } from './root-folder';
 import { unifiedCloudManager } from '../../unified-cloud-manager';
 import type { CloudCache } from '../../cloud-cache-interface';
 import type { DriveFileMetadata } from '../../provider-interface';
@@ -75,12 +76,20 @@ class DriveFilesCacheManager implements CloudCache<DriveFileMetadata> {
       try {
         console.log('Fetching all Drive file metadata...');
 
+        // A user-chosen sync root limits the listing to that folder's tree
+        const customRoot = await resolveCustomRootFolder();
+        if (customRoot) {
+          this.readerFolderId = customRoot.id;
+        }
+
         // Get only files owned by the user (guarantees edit permissions)
         // This filters out viewer-only shared files while keeping shared files with edit access that user owns
-        const allItems = await driveApiClient.listFiles(
-          `'me' in owners and trashed=false`,
-          'files(id,name,mimeType,modifiedTime,size,parents,description)'
-        );
+        const allItems = customRoot
+          ? await this.listFolderTree(customRoot.id)
+          : await driveApiClient.listFiles(
+              `'me' in owners and trashed=false`,
+              'files(id,name,mimeType,modifiedTime,size,parents,description,md5Checksum)'
+            );
         console.log('Found items:', allItems);
 
         // Count by file type
@@ -89,6 +98,7 @@ class DriveFilesCacheManager implements CloudCache<DriveFileMetadata> {
         const sidecarFiles: any[] = [];
         const volumeDataFiles: any[] = [];
         const profilesFiles: any[] = [];
+        const knownWordsFiles: any[] = [];
         const folderNames = new Map<string, string>();
         const foundFolderNames: string[] = [];
 
@@ -104,7 +114,7 @@ class DriveFilesCacheManager implements CloudCache<DriveFileMetadata> {
             foundFolderNames.push(item.name);
 
             // Capture mokuro-reader folder ID
-            if (item.name === GOOGLE_DRIVE_CONFIG.FOLDER_NAMES.READER) {
+            if (!customRoot && item.name === GOOGLE_DRIVE_CONFIG.FOLDER_NAMES.READER) {
               this.readerFolderId = item.id;
               console.log('Found mokuro-reader folder ID:', item.id);
             }
@@ -120,6 +130,8 @@ class DriveFilesCacheManager implements CloudCache<DriveFileMetadata> {
             volumeDataFiles.push(item);
           } else if (item.name === GOOGLE_DRIVE_CONFIG.FILE_NAMES.PROFILES) {
             profilesFiles.push(item);
+          } else if (item.name === GOOGLE_DRIVE_CONFIG.FILE_NAMES.KNOWN_WORDS) {
+            knownWordsFiles.push(item);
           }
         }
 
@@ -155,7 +167,8 @@ class DriveFilesCacheManager implements CloudCache<DriveFileMetadata> {
               size: file.size ? parseInt(file.size) : 0,
               path: path,
               description: file.description,
-              parentId: parentId
+              parentId: parentId,
+              md5Checksum: file.md5Checksum
             };
 
             // Group by series title (parentName) instead of full path
@@ -206,6 +219,20 @@ class DriveFilesCacheManager implements CloudCache<DriveFileMetadata> {
           cacheMap.set(GOOGLE_DRIVE_CONFIG.FILE_NAMES.PROFILES, profilesMetadata);
         }
 
+        if (knownWordsFiles.length > 0) {
+          const knownWordsMetadata = knownWordsFiles.map(
+            (file): DriveFileMetadata => ({
+              provider: 'google-drive',
+              fileId: file.id,
+              name: file.name,
+              modifiedTime: file.modifiedTime || new Date().toISOString(),
+              size: file.size ? parseInt(file.size) : 0,
+              path: file.name
+            })
+          );
+          cacheMap.set(GOOGLE_DRIVE_CONFIG.FILE_NAMES.KNOWN_WORDS, knownWordsMetadata);
+        }
+
         console.log(
           `Cached ${cbzFiles.length} .cbz files, ${sidecarFiles.length} sidecar files, ${volumeDataFiles.length} volume-data.json file(s), and ${profilesFiles.length} profiles.json file(s)`
         );
@@ -247,6 +274,32 @@ class DriveFilesCacheManager implements CloudCache<DriveFileMetadata> {
     return this.fetchPromise;
   }
 
+  /**
+   * List every file and folder under a folder, breadth-first.
+   * Used for a custom sync root, where a global listing would also pick up
+   * volumes outside the chosen folder.
+   */
+  private async listFolderTree(rootId: string): Promise<DriveFile[]> {
+    const items: DriveFile[] = [];
+    const folders = [rootId];
+
+    // `folders` grows while iterating: each subfolder is queued for its own listing
+    for (let i = 0; i < folders.length; i++) {
+      const children = await driveApiClient.listFiles(
+        `'${folders[i]}' in parents and trashed=false`,
+        'files(id,name,mimeType,modifiedTime,size,parents,description,md5Checksum)'
+      );
+      for (const child of children) {
+        items.push(child);
+        if (child.mimeType === GOOGLE_DRIVE_CONFIG.MIME_TYPES.FOLDER) {
+          folders.push(child.id);
+        }
+      }
+    }
+
+    return items;
+  }
+
   /**
    * Get the mokuro-reader folder ID from cache
    * Waits for ongoing fetch if needed
@@ -1,9 +1,15 @@
 import { beforeEach, describe, expect, it, vi } from 'vitest';
 
+const { coreUploadFile } = vi.hoisted(() => ({
+  coreUploadFile: vi.fn(async () => 'uploaded-id')
+}));
+
 vi.mock('$app/environment', () => ({ browser: true }));
 vi.mock('$lib/util/sync/providers/google-drive/token-manager', () => ({
   tokenManager: {
     isAuthenticated: vi.fn(() => true),
+    getMissingScopes: vi.fn(() => []),
+    requestNewToken: vi.fn(),
     token: { subscribe: (cb: (v: string) => void) => (cb('TOKEN'), () => {}) },
     needsAttention: { subscribe: (cb: (v: boolean) => void) => (cb(false), () => {}) }
   }
@@ -12,7 +18,13 @@ vi.mock('$lib/util/sync/providers/google-drive/api-client', () => ({
   driveApiClient: {
     initialize: vi.fn(async () => {}),
     listFiles: vi.fn(async () => []),
-    deleteFile: vi.fn(async () => {})
+    createFolder: vi.fn(async () => 'created-folder'),
+    deleteFile: vi.fn(async () => {}),
+    updateFileMetadata: vi.fn(async (id: string, resource: { name: string }) => ({
+      id,
+      ...resource
+    })),
+    updateFileDescription: vi.fn(async () => {})
   }
 }));
 vi.mock('$lib/util/sync/providers/google-drive/drive-files-cache', () => ({
@@ -20,7 +32,8 @@ vi.mock('$lib/util/sync/providers/google-drive/drive-files-cache', () => ({
     getReaderFolderId: vi.fn(async () => 'reader-root'),
     setReaderFolderId: vi.fn(),
     getDriveFilesBySeries: vi.fn(() => []),
-    removeById: vi.fn()
+    removeById: vi.fn(),
+    fetch: vi.fn(async () => {})
   }
 }));
 vi.mock('$lib/util/backup', () => ({ findFile: vi.fn() }));
@@ -32,12 +45,16 @@ vi.mock('../../provider-detection', () => ({
   clearActiveProviderKey: vi.fn()
 }));
 vi.mock('../../core/cloud-provider-core-registry', () => ({
-  getCloudProviderCore: vi.fn(() => ({}))
+  getCloudProviderCore: vi.fn(() => ({ uploadFile: coreUploadFile }))
 }));
 
 import { googleDriveProvider } from './google-drive-provider';
 import { driveApiClient } from '$lib/util/sync/providers/google-drive/api-client';
+import { driveFilesCache } from '$lib/util/sync/providers/google-drive/drive-files-cache';
+import { findFile } from '$lib/util/backup';
 import { tokenManager } from '$lib/util/sync/providers/google-drive/token-manager';
+import { OAUTH_CANCELLED_EVENT } from './auth-errors';
+import { setActiveProviderKey } from '../../provider-detection';
 
 const FOLDER_MIME = 'application/vnd.google-apps.folder';
 
@@ -71,6 +88,32 @@ beforeEach(() => {
   vi.mocked(tokenManager.isAuthenticated).mockReturnValue(true);
 });
 
+describe('GoogleDriveProvider.login()', () => {
+  it('settles as cancelled, not failed, when the user denies consent', async () => {
+    const originalToken = tokenManager.token;
+    // No token arrives: the consent screen is still open
+    Object.assign(tokenManager, {
+      token: { subscribe: (cb: (v: string) => void) => (cb(''), () => {}) }
+    });
+
+    try {
+      const login = googleDriveProvider.login();
+      // Let login() reach the wait before Google reports the denial
+      await vi.waitFor(() => expect(tokenManager.requestNewToken).toHaveBeenCalled());
+      window.dispatchEvent(
+        new CustomEvent(OAUTH_CANCELLED_EVENT, {
+          detail: { provider: 'google-drive', code: 'access_denied' }
+        })
+      );
+
+      await expect(login).rejects.toMatchObject({ code: 'LOGIN_CANCELLED' });
+      expect(setActiveProviderKey).not.toHaveBeenCalled();
+    } finally {
+      Object.assign(tokenManager, { token: originalToken });
+    }
+  });
+});
+
 describe('GoogleDriveProvider.removeDirectoryIfEmpty()', () => {
   it('deletes the folder when the SERVER reports it empty', async () => {
     mockDriveQueries({ seriesFolderExists: true, children: [] });
@@ -113,3 +156,100 @@ describe('GoogleDriveProvider.removeDirectoryIfEmpty()', () => {
     expect(driveApiClient.deleteFile).not.toHaveBeenCalled();
   });
 });
+
+describe('GoogleDriveProvider.uploadFile()', () => {
+  it('puts two volumes of a series under one shared series folder', async () => {
+    // Folders "exist" on Drive only once createFolder has resolved
+    const created = new Map<string, string>();
+    vi.mocked(driveApiClient.listFiles).mockImplementation(async (query: string) => {
+      const name = query.match(/^name='(.+?)'/)?.[1];
+      const id = name ? created.get(name) : undefined;
+      return id ? [{ id, name, mimeType: FOLDER_MIME }] : [];
+    });
+    vi.mocked(driveApiClient.createFolder).mockImplementation(async (name: string) => {
+      await new Promise((resolve) => setTimeout(resolve, 5));
+      const id = `folder-${created.size + 1}`;
+      created.set(name, id);
+      return id;
+    });
+
+    // Uploaded in parallel, like the backup queue does
+    await Promise.all([
+      googleDriveProvider.uploadFile('Series/Volume 1.cbz', new Blob(['1'])),
+      googleDriveProvider.uploadFile('Series/Volume 2.cbz', new Blob(['2']))
+    ]);
+
+    expect(driveApiClient.createFolder).toHaveBeenCalledTimes(1);
+    expect(driveApiClient.createFolder).toHaveBeenCalledWith('Series', 'reader-root');
+    const targets = coreUploadFile.mock.calls.map(
+      (call) => (call as any[])[0].credentials.seriesFolderId
+    );
+    expect(targets).toEqual(['folder-1', 'folder-1']);
+  });
+
+  it('keeps a series title with a slash in one sanitized folder', async () => {
+    vi.mocked(driveApiClient.listFiles).mockResolvedValue([]);
+    vi.mocked(driveApiClient.createFolder).mockResolvedValue('series-folder');
+
+    await googleDriveProvider.uploadFile('Fate/Zero/Volume 1.cbz', new Blob(['1']));
+
+    expect(driveApiClient.createFolder).toHaveBeenCalledTimes(1);
+    expect(driveApiClient.createFolder).toHaveBeenCalledWith('Fate／Zero', 'reader-root');
+    expect(coreUploadFile).toHaveBeenCalledWith(
+      expect.objectContaining({ seriesTitle: 'Fate/Zero', filename: 'Volume 1.cbz' })
+    );
+  });
+});
+
+describe('GoogleDriveProvider renames', () => {
+  const volume = {
+    provider: 'google-drive' as const,
+    fileId: 'file-1',
+    path: 'Old/Volume 1.cbz',
+    modifiedTime: '2024-01-01T00:00:00Z',
+    size: 1,
+    parentId: 'series-1'
+  };
+
+  it('renames a series to a title with a slash as one sanitized folder', async () => {
+    vi.mocked(driveFilesCache.getDriveFilesBySeries).mockReturnValue([volume] as never);
+    vi.mocked(driveApiClient.listFiles).mockImplementation(async (query: string) =>
+      query.startsWith("name='Old'")
+        ? [{ id: 'series-1', name: 'Old', mimeType: FOLDER_MIME, parents: ['reader-root'] }]
+        : []
+    );
+
+    const renamed = await googleDriveProvider.renameFolder('Old', 'Fate/Zero');
+
+    expect(driveFilesCache.getDriveFilesBySeries).toHaveBeenCalledWith('Old');
+    expect(driveApiClient.updateFileMetadata).toHaveBeenCalledWith(
+      'series-1',
+      { name: 'Fate／Zero' },
+      expect.objectContaining({ addParents: undefined, removeParents: undefined })
+    );
+    expect(driveApiClient.createFolder).not.toHaveBeenCalled();
+    // Placeholders read the real title back from the description
+    expect(driveApiClient.updateFileDescription).toHaveBeenCalledWith(
+      'file-1',
+      'Series: Fate/Zero'
+    );
+    expect(renamed.map((file) => file.path)).toEqual(['Fate／Zero/Volume 1.cbz']);
+  });
+
+  it('moves a volume into the sanitized series folder uploads use', async () => {
+    vi.mocked(driveApiClient.listFiles).mockResolvedValue([]);
+    vi.mocked(driveApiClient.createFolder).mockResolvedValue('series-2');
+    vi.mocked(findFile).mockResolvedValue(null);
+
+    const renamed = await googleDriveProvider.renameFile(volume, 'Fate/Zero/Volume 1.cbz');
+
+    expect(driveApiClient.createFolder).toHaveBeenCalledWith('Fate／Zero', 'reader-root');
+    expect(findFile).toHaveBeenCalledWith('Volume 1.cbz', 'series-2');
+    expect(driveApiClient.updateFileMetadata).toHaveBeenCalledWith(
+      'file-1',
+      { name: 'Volume 1.cbz', description: 'Series: Fate/Zero' },
+      expect.objectContaining({ addParents: 'series-2', removeParents: 'series-1' })
+    );
+    expect(renamed.path).toBe('Fate／Zero/Volume 1.cbz');
+  });
+});
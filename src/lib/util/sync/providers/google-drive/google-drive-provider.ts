@@ -12,11 +12,24 @@ import { tokenManager } from '$lib/util/sync/providers/google-drive/token-manage
 import { driveApiClient } from '$lib/util/sync/providers/google-drive/api-client';
 import { driveFilesCache } from '$lib/util/sync/providers/google-drive/drive-files-cache';
 import { GOOGLE_DRIVE_CONFIG } from '$lib/util/sync/providers/google-drive/constants';
+import { getGoogleClient } from '$lib/util/sync/providers/google-drive/client-config';
+import { OAUTH_CANCELLED_EVENT } from '$lib/util/sync/providers/google-drive/auth-errors';
+import {
+  getCustomRootFolder,
+  setCustomRootFolder,
+  verifyRootFolder,
+  type DriveRootFolder
+} from '$lib/util/sync/providers/google-drive/root-folder';
 import { findFile } from '$lib/util/backup';
 import { cacheManager } from '../../cache-manager';
 import { setActiveProviderKey, clearActiveProviderKey } from '../../provider-detection';
 import type { FolderOperations, FolderInfo, FolderItem } from '../../folder-deduplicator';
 import { getCloudProviderCore } from '../../core/cloud-provider-core-registry';
+import {
+  toDriveName,
+  toDrivePath,
+  withSeriesTitle
+} from '../../core/providers/google-drive-core';
 
 /**
  * Metadata for a file selected from the Google Drive file picker
@@ -47,6 +60,8 @@ class GoogleDriveProvider implements SyncProvider {
   private readerFolderId: string | null = null;
   private initializePromise: Promise<void> | null = null;
   private readerFolderPromise: Promise<string> | null = null; // Mutex for folder creation
+  // In-flight folder lookups keyed by parent + name, so parallel uploads share one folder
+  private pendingFolders = new Map<string, Promise<string>>();
   private cloudCore = getCloudProviderCore('google-drive');
 
   private getAccessToken(): string {
@@ -112,9 +127,13 @@ class GoogleDriveProvider implements SyncProvider {
 
     let statusMessage = 'Not connected';
     if (authenticated) {
-      statusMessage = needsAttention
-        ? 'Session expired - re-authentication required'
-        : 'Connected to Google Drive';
+      if (tokenManager.getMissingScopes().length > 0) {
+        statusMessage = 'Reconnect Google Drive and allow access to your files';
+      } else {
+        statusMessage = needsAttention
+          ? 'Session expired - re-authentication required'
+          : 'Connected to Google Drive';
+      }
     } else if (hasCredentials) {
       statusMessage = 'Configured (not connected)';
     } else {
@@ -141,25 +160,43 @@ class GoogleDriveProvider implements SyncProvider {
       // Request OAuth token with full consent screen (initial login)
       tokenManager.requestNewToken(true);
 
-      // Wait for token to be set
+      // Wait for token to be set, or for the user to decline consent
       await new Promise<void>((resolve, reject) => {
+        let unsubscribe: (() => void) | null = null;
+        let done = false;
+        const finish = () => {
+          done = true;
+          clearTimeout(timeout);
+          unsubscribe?.();
+          window.removeEventListener(OAUTH_CANCELLED_EVENT, onCancelled);
+        };
+        const onCancelled = () => {
+          finish();
+          reject(
+            new ProviderError('Google sign-in cancelled', 'google-drive', 'LOGIN_CANCELLED')
+          );
+        };
         const timeout = setTimeout(() => {
+          finish();
           reject(new Error('Login timeout'));
         }, 60000); // 60 second timeout
 
-        const unsubscribe = tokenManager.token.subscribe((token) => {
-          if (token) {
-            clearTimeout(timeout);
-            unsubscribe();
+        window.addEventListener(OAUTH_CANCELLED_EVENT, onCancelled);
+        unsubscribe = tokenManager.token.subscribe((token) => {
+          if (token && !done) {
+            finish();
             resolve();
           }
         });
+        // The store calls back synchronously when a token is already set
+        if (done) unsubscribe();
       });
 
       // Set the active provider key for lazy loading on next startup
       setActiveProviderKey('google-drive');
       console.log('✅ Google Drive login successful');
     } catch (error) {
+      if (error instanceof ProviderError && error.code === 'LOGIN_CANCELLED') throw error;
       throw new ProviderError(
         `Google Drive login failed: ${error instanceof Error ? error.message : 'Unknown error'}`,
         'google-drive',
@@ -310,7 +347,8 @@ class GoogleDriveProvider implements SyncProvider {
     await this.ensureInitialized();
 
     try {
-      // Parse path: "SeriesTitle/VolumeTitle.cbz" or "volume-data.json"
+      // Parse path: "SeriesTitle/VolumeTitle.cbz" or "volume-data.json".
+      // Everything before the last slash is the series title, slashes included.
       const pathParts = path.split('/');
       const fileName = pathParts.pop() || path;
       const seriesTitle = pathParts.join('/');
@@ -333,10 +371,8 @@ class GoogleDriveProvider implements SyncProvider {
         targetFolderId = await this.ensureSeriesFolder(seriesTitle);
       }
 
-      // Find existing file for replacement
-      const existingFileId = await findFile(fileName, targetFolderId);
-
-      // Upload (create or update) via shared provider core.
+      // Upload (create or update) via shared provider core, which finds the
+      // file to replace under its sanitized name.
       const token = this.getAccessToken();
       if (!token) {
         throw new Error('No access token available');
@@ -350,7 +386,6 @@ class GoogleDriveProvider implements SyncProvider {
           seriesFolderId: targetFolderId
         },
         mimeType,
-        existingFileId: existingFileId || undefined,
         onProgress
       });
 
@@ -459,15 +494,18 @@ class GoogleDriveProvider implements SyncProvider {
 
     await this.ensureInitialized();
 
+    // Named like uploadFile names it: everything before the last slash is the
+    // series title, and both parts are sanitized for Drive
     const normalizedNewPath = newPath.replace(/^\/+|\/+$/g, '');
-    if (file.path === normalizedNewPath) {
+    const lastSlash = normalizedNewPath.lastIndexOf('/');
+    const newSeriesTitle = lastSlash < 0 ? '' : normalizedNewPath.slice(0, lastSlash);
+    const newFileName = toDriveName(normalizedNewPath.slice(lastSlash + 1));
+    const newDrivePath = toDrivePath(normalizedNewPath);
+    if (file.path === newDrivePath) {
       return file as DriveFileMetadata;
     }
 
     try {
-      const pathParts = normalizedNewPath.split('/');
-      const newFileName = pathParts.pop() || normalizedNewPath;
-      const newSeriesTitle = pathParts.join('/');
       const targetFolderId = newSeriesTitle
         ? await this.ensureSeriesFolder(newSeriesTitle)
         : await this.ensureReaderFolder();
@@ -475,7 +513,7 @@ class GoogleDriveProvider implements SyncProvider {
       const existingTargetId = await findFile(newFileName, targetFolderId);
       if (existingTargetId && existingTargetId !== file.fileId) {
         throw new ProviderError(
-          `Target file already exists at '${normalizedNewPath}'`,
+          `Target file already exists at '${newDrivePath}'`,
           'google-drive',
           'TARGET_EXISTS'
         );
@@ -488,9 +526,12 @@ class GoogleDriveProvider implements SyncProvider {
           ? (await this.findFolderByPath(file.path.split('/').slice(0, -1).join('/')))?.id
           : null);
 
+      // Keep the "Series:" line naming the series the file now belongs to
+      const description = withSeriesTitle(file.description, newSeriesTitle);
+      const descriptionChanged = description !== (file.description ?? '');
       const updated = await driveApiClient.updateFileMetadata(
         file.fileId,
-        { name: newFileName },
+        { name: newFileName, ...(descriptionChanged ? { description } : {}) },
         {
           addParents: oldParentId && oldParentId !== targetFolderId ? targetFolderId : undefined,
           removeParents: oldParentId && oldParentId !== targetFolderId ? oldParentId : undefined,
@@ -501,7 +542,7 @@ class GoogleDriveProvider implements SyncProvider {
       return {
         provider: 'google-drive',
         fileId: updated.id || file.fileId,
-        path: normalizedNewPath,
+        path: newDrivePath,
         modifiedTime: updated.modifiedTime || new Date().toISOString(),
         size: updated.size ? parseInt(updated.size, 10) : file.size,
         description: updated.description ?? file.description,
@@ -535,59 +576,64 @@ class GoogleDriveProvider implements SyncProvider {
 
     await this.ensureInitialized();
 
-    const normalizedOldPath = oldPath.replace(/^\/+|\/+$/g, '');
-    const normalizedNewPath = newPath.replace(/^\/+|\/+$/g, '');
-    if (normalizedOldPath === normalizedNewPath) {
-      return driveFilesCache.getDriveFilesBySeries(normalizedOldPath);
-    }
+    // Series folders are one level deep and named like ensureSeriesFolder names them
+    const newSeriesTitle = newPath.replace(/^\/+|\/+$/g, '');
+    const oldFolderName = toDriveName(oldPath.replace(/^\/+|\/+$/g, ''));
+    const newFolderName = toDriveName(newSeriesTitle);
 
     try {
-      const existingFiles = driveFilesCache.getDriveFilesBySeries(normalizedOldPath);
+      const existingFiles = driveFilesCache.getDriveFilesBySeries(oldFolderName);
       if (existingFiles.length === 0) {
         return [];
       }
 
-      const folder = await this.findFolderByPath(normalizedOldPath);
+      const rootFolderId = await this.ensureReaderFolder();
+      const folder = await this.findFolder(rootFolderId, oldFolderName);
       if (!folder) {
         throw new ProviderError(
-          `Series folder '${normalizedOldPath}' not found`,
+          `Series folder '${oldFolderName}' not found`,
           'google-drive',
           'FOLDER_NOT_FOUND'
         );
       }
 
-      const targetFolder = await this.findFolderByPath(normalizedNewPath);
-      if (targetFolder && targetFolder.id !== folder.id) {
-        throw new ProviderError(
-          `Target series folder already exists at '${normalizedNewPath}'`,
-          'google-drive',
-          'TARGET_EXISTS'
+      if (newFolderName !== oldFolderName) {
+        const targetFolder = await this.findFolder(rootFolderId, newFolderName);
+        if (targetFolder && targetFolder.id !== folder.id) {
+          throw new ProviderError(
+            `Target series folder already exists at '${newFolderName}'`,
+            'google-drive',
+            'TARGET_EXISTS'
+          );
+        }
+
+        await driveApiClient.updateFileMetadata(
+          folder.id,
+          { name: newFolderName },
+          {
+            addParents:
+              folder.parentId && folder.parentId !== rootFolderId ? rootFolderId : undefined,
+            removeParents:
+              folder.parentId && folder.parentId !== rootFolderId ? folder.parentId : undefined,
+            fields: 'id,name,parents'
+          }
         );
       }
 
-      const pathParts = normalizedNewPath.split('/');
-      const newFolderName = pathParts.pop() || normalizedNewPath;
-      const newParentPath = pathParts.join('/');
-      const targetParentId = newParentPath
-        ? await this.ensureSeriesFolder(newParentPath)
-        : await this.ensureReaderFolder();
-
-      await driveApiClient.updateFileMetadata(
-        folder.id,
-        { name: newFolderName },
-        {
-          addParents:
-            folder.parentId && folder.parentId !== targetParentId ? targetParentId : undefined,
-          removeParents:
-            folder.parentId && folder.parentId !== targetParentId ? folder.parentId : undefined,
-          fields: 'id,name,parents'
+      // The files' "Series:" lines still name the old title
+      const renamed: DriveFileMetadata[] = [];
+      for (const existingFile of existingFiles) {
+        const description = withSeriesTitle(existingFile.description, newSeriesTitle);
+        if (description !== (existingFile.description ?? '')) {
+          await driveApiClient.updateFileDescription(existingFile.fileId, description);
         }
-      );
-
-      return existingFiles.map((existingFile) => ({
-        ...existingFile,
-        path: `${normalizedNewPath}${existingFile.path.slice(normalizedOldPath.length)}`
-      }));
+        renamed.push({
+          ...existingFile,
+          description,
+          path: `${newFolderName}${existingFile.path.slice(oldFolderName.length)}`
+        });
+      }
+      return renamed;
     } catch (error) {
       if (error instanceof ProviderError) {
         throw error;
@@ -743,8 +789,8 @@ class GoogleDriveProvider implements SyncProvider {
             .addView(docsView)
             .addView(folderView)
             .setOAuthToken(token)
-            .setAppId(GOOGLE_DRIVE_CONFIG.CLIENT_ID)
-            .setDeveloperKey(GOOGLE_DRIVE_CONFIG.API_KEY)
+            .setAppId(getGoogleClient().clientId)
+            .setDeveloperKey(getGoogleClient().apiKey)
             .enableFeature(google.picker.Feature.MULTISELECT_ENABLED)
             .setCallback(async (data: google.picker.ResponseObject) => {
               try {
@@ -814,6 +860,82 @@ class GoogleDriveProvider implements SyncProvider {
     });
   }
 
+  /**
+   * Let the user choose an existing Drive folder as the sync root.
+   * Picking through the Google Picker also grants this app access to the
+   * folder under the drive.file scope.
+   * @returns The chosen folder, or null if cancelled
+   */
+  async showRootFolderPicker(): Promise<DriveRootFolder | null> {
+    if (!this.isAuthenticated()) {
+      throw new ProviderError('Not authenticated', 'google-drive', 'NOT_AUTHENTICATED', true);
+    }
+
+    await this.ensureInitialized();
+
+    const token = this.getAccessToken();
+    if (!token) {
+      throw new ProviderError('No access token available', 'google-drive', 'PICKER_FAILED');
+    }
+
+    const picked = await new Promise<DriveRootFolder | null>((resolve) => {
+      const folderView = new google.picker.DocsView(google.picker.ViewId.FOLDERS)
+        .setSelectFolderEnabled(true)
+        .setMimeTypes(GOOGLE_DRIVE_CONFIG.MIME_TYPES.FOLDER);
+
+      const picker = new google.picker.PickerBuilder()
+        .addView(folderView)
+        .setTitle('Choose a sync folder')
+        .setOAuthToken(token)
+        .setAppId(getGoogleClient().clientId)
+        .setDeveloperKey(getGoogleClient().apiKey)
+        .setCallback((data: google.picker.ResponseObject) => {
+          const action = data[google.picker.Response.ACTION];
+          if (action === google.picker.Action.PICKED) {
+            const doc = data[google.picker.Response.DOCUMENTS]?.[0];
+            resolve(
+              doc
+                ? {
+                    id: doc[google.picker.Document.ID],
+                    name: doc[google.picker.Document.NAME] || 'Untitled folder'
+                  }
+                : null
+            );
+          } else if (action === google.picker.Action.CANCEL) {
+            resolve(null);
+          }
+        })
+        .build();
+
+      picker.setVisible(true);
+    });
+
+    if (!picked) return null;
+
+    if ((await verifyRootFolder(picked.id)) !== true) {
+      throw new ProviderError(
+        `You don't have permission to add files to "${picked.name}"`,
+        'google-drive',
+        'ROOT_FOLDER_INACCESSIBLE'
+      );
+    }
+
+    await this.setRootFolder(picked);
+    return picked;
+  }
+
+  /**
+   * Switch the sync root (null restores the default mokuro-reader folder)
+   * and reload the file listing so placeholders reflect the new folder.
+   */
+  async setRootFolder(folder: DriveRootFolder | null): Promise<void> {
+    setCustomRootFolder(folder);
+    this.readerFolderId = null;
+    this.pendingFolders.clear();
+    driveFilesCache.clearCache();
+    await driveFilesCache.fetch();
+  }
+
   /**
    * List all files in a folder recursively
    * Expands subfolders and returns all ZIP/CBZ files
@@ -943,6 +1065,27 @@ class GoogleDriveProvider implements SyncProvider {
       throw new ProviderError('Not authenticated', 'google-drive', 'NOT_AUTHENTICATED', true);
     }
 
+    // Concurrent uploads into a new series would otherwise each see "no folder"
+    // and create their own, splitting the series across duplicate folders
+    const key = `${parentId}/${folderName}`;
+    const pending = this.pendingFolders.get(key);
+    if (pending) {
+      return pending;
+    }
+
+    const promise = this.findOrCreateFolderUncoalesced(parentId, folderName);
+    this.pendingFolders.set(key, promise);
+    try {
+      return await promise;
+    } finally {
+      this.pendingFolders.delete(key);
+    }
+  }
+
+  private async findOrCreateFolderUncoalesced(
+    parentId: string,
+    folderName: string
+  ): Promise<string> {
     await this.ensureInitialized();
 
     const escapedName = folderName.replace(/\\/g, '\\\\').replace(/'/g, "\\'");
@@ -1038,21 +1181,6 @@ class GoogleDriveProvider implements SyncProvider {
     return currentFolder;
   }
 
-  private async ensureFolderPath(folderPath: string): Promise<string> {
-    const normalizedPath = folderPath.replace(/^\/+|\/+$/g, '');
-    const rootFolderId = await this.ensureReaderFolder();
-    if (!normalizedPath) {
-      return rootFolderId;
-    }
-
-    let currentParentId = rootFolderId;
-    for (const part of normalizedPath.split('/').filter(Boolean)) {
-      currentParentId = await this.findOrCreateFolder(currentParentId, part);
-    }
-
-    return currentParentId;
-  }
-
   /**
    * Get folder operations interface for the FolderDeduplicator
    * Returns an object that implements FolderOperations
@@ -1116,6 +1244,13 @@ class GoogleDriveProvider implements SyncProvider {
    * Public so backup-queue can use it instead of duplicating folder creation logic
    */
   async ensureReaderFolder(): Promise<string> {
+    // A user-chosen sync root replaces the mokuro-reader folder entirely.
+    // It is re-validated on every cache fetch (see resolveCustomRootFolder).
+    const customRoot = getCustomRootFolder();
+    if (customRoot) {
+      return customRoot.id;
+    }
+
     // Check local cache first (fast path for repeated calls within this provider)
     if (this.readerFolderId) {
       return this.readerFolderId;
@@ -1167,11 +1302,13 @@ class GoogleDriveProvider implements SyncProvider {
 
   /**
    * Ensure a series folder exists uniquely within the mokuro-reader folder
-   * Handles deduplication if multiple folders with the same name exist
+   * Handles deduplication if multiple folders with the same name exist.
+   * The title is one folder name, sanitized for Drive: a slash in it doesn't nest.
    * Public so backup-queue can use it instead of duplicating logic
    */
   async ensureSeriesFolder(seriesTitle: string): Promise<string> {
-    return this.ensureFolderPath(seriesTitle);
+    const rootFolderId = await this.ensureReaderFolder();
+    return this.findOrCreateFolder(rootFolderId, toDriveName(seriesTitle));
   }
 
   async getWorkerUploadCredentials(): Promise<Record<string, any>> {
@@ -0,0 +1,90 @@
+import { writable, get } from 'svelte/store';
+import { browser } from '$app/environment';
+import { GOOGLE_DRIVE_CONFIG } from './constants';
+import { driveApiClient, DriveApiError } from './api-client';
+
+/**
+ * A user-chosen Drive folder used as the sync root instead of the default
+ * mokuro-reader folder, e.g. an existing mokuro collection.
+ */
+export interface DriveRootFolder {
+  id: string;
+  name: string;
+}
+
+function loadRootFolder(): DriveRootFolder | null {
+  if (!browser) return null;
+  try {
+    const stored = localStorage.getItem(GOOGLE_DRIVE_CONFIG.STORAGE_KEYS.ROOT_FOLDER);
+    const parsed = stored ? JSON.parse(stored) : null;
+    return parsed && typeof parsed.id === 'string' ? parsed : null;
+  } catch {
+    return null;
+  }
+}
+
+export const driveRootFolder = writable<DriveRootFolder | null>(loadRootFolder());
+
+driveRootFolder.subscribe((folder) => {
+  if (!browser) return;
+  if (folder) {
+    localStorage.setItem(GOOGLE_DRIVE_CONFIG.STORAGE_KEYS.ROOT_FOLDER, JSON.stringify(folder));
+  } else {
+    localStorage.removeItem(GOOGLE_DRIVE_CONFIG.STORAGE_KEYS.ROOT_FOLDER);
+  }
+});
+
+/** The custom sync root, or null when using the default mokuro-reader folder */
+export function getCustomRootFolder(): DriveRootFolder | null {
+  return get(driveRootFolder);
+}
+
+export function setCustomRootFolder(folder: DriveRootFolder | null): void {
+  driveRootFolder.set(folder);
+}
+
+/**
+ * Check that the folder still exists, isn't trashed, and accepts new files.
+ * Returns null when Drive can't be reached, so a flaky network never
+ * silently resets the user's choice.
+ */
+export async function verifyRootFolder(folderId: string): Promise<boolean | null> {
+  try {
+    const folder = await driveApiClient.getFileMetadata(
+      folderId,
+      'id,name,mimeType,trashed,capabilities(canAddChildren)'
+    );
+    return (
+      folder?.mimeType === GOOGLE_DRIVE_CONFIG.MIME_TYPES.FOLDER &&
+      !folder.trashed &&
+      folder.capabilities?.canAddChildren !== false
+    );
+  } catch (error) {
+    if (error instanceof DriveApiError && (error.status === 404 || error.status === 403)) {
+      return false;
+    }
+    return null;
+  }
+}
+
+/**
+ * Drop a custom root that has been deleted or lost access, falling back to
+ * the default folder. Returns the root to use, or null for the default.
+ */
+export async function resolveCustomRootFolder(): Promise<DriveRootFolder | null> {
+  const folder = getCustomRootFolder();
+  if (!folder) return null;
+
+  const valid = await verifyRootFolder(folder.id);
+  if (valid === false) {
+    console.warn(`Drive sync folder "${folder.name}" is no longer accessible, using default`);
+    setCustomRootFolder(null);
+    const { showSnackbar } = await import('$lib/util/snackbar');
+    showSnackbar(
+      `Drive folder "${folder.name}" is no longer available. ` +
+        `Using ${GOOGLE_DRIVE_CONFIG.FOLDER_NAMES.READER}.`
+    );
+    return null;
+  }
+  return folder;
+}
@@ -0,0 +1,29 @@
+import { describe, it, expect } from 'vitest';
+import { parseScopes, findMissingScopes } from './scopes';
+
+const DRIVE_FILE = 'https://www.googleapis.com/auth/drive.file';
+
+describe('parseScopes', () => {
+  it('splits a space-separated scope string', () => {
+    expect(parseScopes(`openid  ${DRIVE_FILE}`)).toEqual(['openid', DRIVE_FILE]);
+  });
+
+  it('returns an empty list for missing scopes', () => {
+    expect(parseScopes(undefined)).toEqual([]);
+    expect(parseScopes('')).toEqual([]);
+  });
+});
+
+describe('findMissingScopes', () => {
+  it('reports drive.file when the user unticked it on the consent screen', () => {
+    expect(findMissingScopes(['openid'])).toEqual([DRIVE_FILE]);
+  });
+
+  it('reports nothing when every required scope was granted', () => {
+    expect(findMissingScopes(['openid', DRIVE_FILE])).toEqual([]);
+  });
+
+  it('treats unknown grants as complete', () => {
+    expect(findMissingScopes(null)).toEqual([]);
+  });
+});
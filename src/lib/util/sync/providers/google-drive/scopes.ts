@@ -0,0 +1,21 @@
+import { GOOGLE_DRIVE_CONFIG } from './constants';
+
+/**
+ * Split an OAuth `scope` string (space-separated, as returned by the token
+ * client and the tokeninfo endpoint) into a list
+ */
+export function parseScopes(scope: string | undefined | null): string[] {
+  return (scope ?? '').split(/\s+/).filter(Boolean);
+}
+
+/**
+ * Scopes the app needs that are not in `granted`. Unknown grants (null) are
+ * treated as complete so tokens from before scopes were tracked keep working.
+ */
+export function findMissingScopes(
+  granted: string[] | null,
+  required: string[] = parseScopes(GOOGLE_DRIVE_CONFIG.SCOPES)
+): string[] {
+  if (!granted) return [];
+  return required.filter((scope) => !granted.includes(scope));
+}
@@ -0,0 +1,119 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+
+vi.mock('$app/environment', () => ({ browser: true }));
+vi.mock('$lib/util/snackbar', () => ({ showSnackbar: vi.fn() }));
+vi.mock('./client-config', () => ({
+  getGoogleClient: () => ({ clientId: 'client-id', apiKey: 'api-key' })
+}));
+vi.mock('../../provider-manager', () => ({ providerManager: { updateStatus: vi.fn() } }));
+
+import { GOOGLE_DRIVE_CONFIG } from './constants';
+
+const MINUTE = 60 * 1000;
+
+const requestAccessToken = vi.fn();
+let clientConfig: {
+  callback: (response: Record<string, unknown>) => void;
+  error_callback: (error: { type?: string }) => void;
+};
+
+/** A signed-in token manager whose token expires in `expiresInMs` */
+async function freshManager(expiresInMs: number) {
+  vi.resetModules();
+  const { STORAGE_KEYS } = GOOGLE_DRIVE_CONFIG;
+  localStorage.setItem(STORAGE_KEYS.TOKEN, 'old-token');
+  localStorage.setItem(STORAGE_KEYS.TOKEN_EXPIRES, String(Date.now() + expiresInMs));
+  localStorage.setItem(STORAGE_KEYS.HAS_AUTHENTICATED, 'true');
+
+  const { tokenManager } = await import('./token-manager');
+  await tokenManager.initTokenClient();
+  return tokenManager;
+}
+
+function gesture() {
+  window.dispatchEvent(new Event('pointerdown'));
+}
+
+describe('tokenManager proactive refresh', () => {
+  beforeEach(() => {
+    vi.useFakeTimers();
+    localStorage.clear();
+    requestAccessToken.mockClear();
+    vi.stubGlobal('gapi', { client: { setToken: vi.fn() } });
+    vi.stubGlobal('fetch', vi.fn(async () => ({ ok: false })));
+    vi.stubGlobal('google', {
+      accounts: {
+        oauth2: {
+          initTokenClient: (config: typeof clientConfig) => {
+            clientConfig = config;
+            return { requestAccessToken };
+          }
+        }
+      }
+    });
+  });
+
+  afterEach(async () => {
+    const { tokenManager } = await import('./token-manager');
+    await tokenManager.logout();
+    vi.unstubAllGlobals();
+    vi.useRealTimers();
+  });
+
+  it('waits for the next gesture once the token is close to expiry', async () => {
+    await freshManager(4 * MINUTE);
+
+    // Nothing happens on its own: the popup needs a user gesture
+    vi.advanceTimersByTime(GOOGLE_DRIVE_CONFIG.TOKEN_REFRESH_CHECK_INTERVAL_MS);
+    expect(requestAccessToken).not.toHaveBeenCalled();
+
+    gesture();
+    expect(requestAccessToken).toHaveBeenCalledTimes(1);
+    expect(requestAccessToken).toHaveBeenCalledWith({});
+
+    // One-shot: later gestures don't request again
+    gesture();
+    expect(requestAccessToken).toHaveBeenCalledTimes(1);
+  });
+
+  it('does not arm while the token has plenty of time left', async () => {
+    await freshManager(30 * MINUTE);
+
+    vi.advanceTimersByTime(GOOGLE_DRIVE_CONFIG.TOKEN_REFRESH_CHECK_INTERVAL_MS);
+    gesture();
+
+    expect(requestAccessToken).not.toHaveBeenCalled();
+  });
+
+  it('re-arms for the next token once a refresh succeeds', async () => {
+    await freshManager(4 * MINUTE);
+    vi.advanceTimersByTime(GOOGLE_DRIVE_CONFIG.TOKEN_REFRESH_CHECK_INTERVAL_MS);
+    gesture();
+
+    // The new token is itself short-lived, so the next check arms again
+    clientConfig.callback({ access_token: 'new-token', expires_in: 4 * 60 });
+    gesture();
+    expect(requestAccessToken).toHaveBeenCalledTimes(1);
+
+    vi.advanceTimersByTime(GOOGLE_DRIVE_CONFIG.TOKEN_REFRESH_CHECK_INTERVAL_MS);
+    gesture();
+    expect(requestAccessToken).toHaveBeenCalledTimes(2);
+  });
+
+  it('backs off before re-arming after a declined refresh', async () => {
+    await freshManager(4 * MINUTE);
+    vi.advanceTimersByTime(GOOGLE_DRIVE_CONFIG.TOKEN_REFRESH_CHECK_INTERVAL_MS);
+    gesture();
+
+    clientConfig.error_callback({ type: 'popup_closed' });
+
+    // Checks inside the backoff window don't arm
+    vi.advanceTimersByTime(GOOGLE_DRIVE_CONFIG.TOKEN_PROACTIVE_BACKOFF_MS / 2);
+    gesture();
+    expect(requestAccessToken).toHaveBeenCalledTimes(1);
+
+    vi.advanceTimersByTime(GOOGLE_DRIVE_CONFIG.TOKEN_PROACTIVE_BACKOFF_MS);
+    gesture();
+    expect(requestAccessToken).toHaveBeenCalledTimes(2);
+  });
+});
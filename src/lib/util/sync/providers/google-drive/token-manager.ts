@@ -1,17 +1,32 @@
 import { writable } from 'svelte/store';
 import { browser } from '$app/environment';
 import { GOOGLE_DRIVE_CONFIG } from './constants';
+import { getGoogleClient } from './client-config';
 import { showSnackbar } from '$lib/util/snackbar';
 import { onNextUserGesture } from '$lib/util/user-gesture';
+import { findMissingScopes, parseScopes } from './scopes';
+import {
+  classifyAuthError,
+  OAUTH_CANCELLED_EVENT,
+  type GoogleAuthError,
+  type OAuthCancelledEvent
+} from './auth-errors';
 
 class TokenManager {
   private tokenStore = writable<string>('');
   private tokenClientStore = writable<any>(null);
   private needsAttentionStore = writable<boolean>(false);
+  private lastErrorStore = writable<GoogleAuthError | null>(null);
+  private transientRetryUsed = false;
+  // Scopes granted to the current token; null when unknown
+  private grantedScopesStore = writable<string[] | null>(null);
   private refreshIntervalId: number | null = null;
   private isRefreshing = false;
   private gestureRetryCancel: (() => void) | null = null;
   private pendingPostReauthSync = false;
+  private proactiveRefreshInFlight = false;
+  private proactiveRefreshFailures = 0;
+  private proactiveRefreshNotBefore = 0;
 
   constructor() {
     if (browser) {
@@ -28,9 +43,84 @@ class TokenManager {
     return this.needsAttentionStore;
   }
 
+  /** The most recent sign-in failure, cleared when a token is issued */
+  get lastError() {
+    return this.lastErrorStore;
+  }
+
+  get grantedScopes() {
+    return this.grantedScopesStore;
+  }
+
+  /**
+   * Required scopes the current token was not granted. Drive calls made
+   * without them fail with a 403, so callers check this up front.
+   */
+  getMissingScopes(): string[] {
+    let granted: string[] | null = null;
+    this.grantedScopesStore.subscribe((value) => {
+      granted = value;
+    })();
+    return findMissingScopes(granted);
+  }
+
+  private setGrantedScopes(scopes: string[] | null): void {
+    this.grantedScopesStore.set(scopes);
+    if (!browser) return;
+    if (scopes) {
+      localStorage.setItem(GOOGLE_DRIVE_CONFIG.STORAGE_KEYS.GRANTED_SCOPES, scopes.join(' '));
+    } else {
+      localStorage.removeItem(GOOGLE_DRIVE_CONFIG.STORAGE_KEYS.GRANTED_SCOPES);
+    }
+  }
+
+  /**
+   * Ask Google which scopes the current token really has. Catches a
+   * permission revoked from the Google account page since the token was issued.
+   * Returns the missing scopes (empty when the check couldn't be made).
+   */
+  async verifyGrantedScopes(): Promise<string[]> {
+    const token = this.getCurrentToken();
+    if (!token) return [];
+
+    try {
+      const response = await fetch(
+        `${GOOGLE_DRIVE_CONFIG.TOKENINFO_ENDPOINT}?access_token=${encodeURIComponent(token)}`
+      );
+      // Expired or revoked tokens are handled by the normal re-auth path
+      if (!response.ok) return [];
+      const info = await response.json();
+      this.setGrantedScopes(parseScopes(info.scope));
+    } catch (error) {
+      console.warn('Could not verify granted Drive scopes:', error);
+      return [];
+    }
+
+    const missing = this.getMissingScopes();
+    if (missing.length > 0) this.promptForMissingScopes(missing);
+    return missing;
+  }
+
+  /**
+   * The user unticked (or later revoked) Drive access on the consent screen.
+   * Flag the account so the reconnect button asks for consent again.
+   */
+  private promptForMissingScopes(missing: string[]): void {
+    console.warn('Google Drive token is missing scopes:', missing);
+    this.needsAttentionStore.set(true);
+    showSnackbar(
+      'Google Drive access to your files was not granted. Reconnect and allow access to continue.'
+    );
+    import('../../provider-manager').then(({ providerManager }) => {
+      providerManager.updateStatus();
+    });
+  }
+
   private loadPersistedToken(): void {
     const token = localStorage.getItem(GOOGLE_DRIVE_CONFIG.STORAGE_KEYS.TOKEN);
     const expiresAt = localStorage.getItem(GOOGLE_DRIVE_CONFIG.STORAGE_KEYS.TOKEN_EXPIRES);
+    const scopes = localStorage.getItem(GOOGLE_DRIVE_CONFIG.STORAGE_KEYS.GRANTED_SCOPES);
+    this.grantedScopesStore.set(scopes !== null ? parseScopes(scopes) : null);
 
     if (token && expiresAt) {
       const now = Date.now();
@@ -82,6 +172,10 @@ class TokenManager {
         return;
       }
 
+      if (timeUntilExpiry <= GOOGLE_DRIVE_CONFIG.TOKEN_PROACTIVE_REFRESH_MS) {
+        this.armProactiveRefresh();
+      }
+
       // Show warning when getting close to expiry
       if (timeUntilExpiry <= GOOGLE_DRIVE_CONFIG.TOKEN_WARNING_BUFFER_MS) {
         const minutesLeft = Math.round(timeUntilExpiry / 60000);
@@ -114,6 +208,94 @@ class TokenManager {
     });
   }
 
+  /**
+   * React to a failed token request by kind: transient failures are retried
+   * once automatically, blocked popups wait for the next user gesture, and
+   * a denied consent signs out quietly, clearing the auth history so the next
+   * attempt shows full consent.
+   */
+  private handleAuthError(error: GoogleAuthError): void {
+    this.lastErrorStore.set(error);
+    this.isRefreshing = false;
+
+    if (this.proactiveRefreshInFlight) {
+      this.proactiveRefreshInFlight = false;
+      this.proactiveRefreshFailures++;
+      this.proactiveRefreshNotBefore =
+        Date.now() +
+        GOOGLE_DRIVE_CONFIG.TOKEN_PROACTIVE_BACKOFF_MS * 2 ** (this.proactiveRefreshFailures - 1);
+      // The current token is still valid; nothing else to do until it expires
+      if (error.kind === 'cancelled' || error.kind === 'popup-blocked') return;
+    }
+
+    switch (error.kind) {
+      case 'user-denied':
+        // Declining consent is the user's choice, not something to fix: back
+        // to signed out, with no attention badge or pending retry left behind
+        this.disarmGestureRetry();
+        this.pendingPostReauthSync = false;
+        this.clearToken(false);
+        this.needsAttentionStore.set(false);
+        showSnackbar(error.message);
+        window.dispatchEvent(
+          new CustomEvent(OAUTH_CANCELLED_EVENT, {
+            detail: { provider: 'google-drive', code: error.code }
+          }) as OAuthCancelledEvent
+        );
+        break;
+      case 'cancelled':
+        // Preserve all state so they can try again immediately
+        showSnackbar(error.message);
+        break;
+      case 'popup-blocked':
+        // Retry inside the next real click/tap, where blockers always allow it
+        console.log('Popup was blocked by browser — arming gesture retry');
+        this.armGestureRetry();
+        break;
+      case 'transient':
+        if (!this.transientRetryUsed) {
+          this.transientRetryUsed = true;
+          showSnackbar(error.message);
+          setTimeout(() => this.requestNewToken(false), 2000);
+          break;
+        }
+      // Retried once already: give up like any other failure
+      // falls through
+      case 'fatal':
+        // Keep auth history but clear token; permissions weren't explicitly denied
+        this.transientRetryUsed = false;
+        this.clearToken(true);
+        this.needsAttentionStore.set(true);
+        showSnackbar('Authentication failed. Please try signing in again.');
+        break;
+    }
+  }
+
+  /**
+   * GIS tokens can't be refreshed in the background (no refresh token, and the
+   * popup needs user activation), so shortly before expiry quietly refresh
+   * inside the user's next click or key press. A Drive call right at expiry
+   * then finds a fresh token instead of failing once first. Failed attempts
+   * back off exponentially so a declining user isn't prompted on every click.
+   */
+  private armProactiveRefresh(): void {
+    if (this.gestureRetryCancel || this.isRefreshing) return;
+    if (Date.now() < this.proactiveRefreshNotBefore) return;
+    if (this.getMissingScopes().length > 0) return; // Needs explicit re-consent
+
+    this.gestureRetryCancel = onNextUserGesture(() => {
+      this.gestureRetryCancel = null;
+      try {
+        // Synchronous, like armGestureRetry, to keep the activation window
+        this.proactiveRefreshInFlight = true;
+        this.requestNewToken(false);
+      } catch (error) {
+        this.proactiveRefreshInFlight = false;
+        console.warn('Proactive token refresh failed:', error);
+      }
+    });
+  }
+
   private disarmGestureRetry(): void {
     if (this.gestureRetryCancel) {
       this.gestureRetryCancel();
@@ -125,6 +307,11 @@ class TokenManager {
     this.tokenStore.set(token);
     this.isRefreshing = false;
     this.needsAttentionStore.set(false); // Clear attention flag when token is set
+    this.lastErrorStore.set(null);
+    this.transientRetryUsed = false;
+    this.proactiveRefreshInFlight = false;
+    this.proactiveRefreshFailures = 0;
+    this.proactiveRefreshNotBefore = 0;
     this.disarmGestureRetry(); // A valid token makes any pending retry moot
 
     if (browser) {
@@ -153,12 +340,14 @@ class TokenManager {
 
   clearToken(keepAuthHistory = true): void {
     this.tokenStore.set('');
+    this.grantedScopesStore.set(null);
     this.isRefreshing = false;
 
     if (browser) {
       localStorage.removeItem(GOOGLE_DRIVE_CONFIG.STORAGE_KEYS.TOKEN);
       localStorage.removeItem(GOOGLE_DRIVE_CONFIG.STORAGE_KEYS.TOKEN_EXPIRES);
       localStorage.removeItem(GOOGLE_DRIVE_CONFIG.STORAGE_KEYS.LAST_AUTH_TIME);
+      localStorage.removeItem(GOOGLE_DRIVE_CONFIG.STORAGE_KEYS.GRANTED_SCOPES);
 
       // Only clear auth history on explicit logout
       if (!keepAuthHistory) {
@@ -214,50 +403,29 @@ class TokenManager {
     await this.waitForGoogleIdentity();
 
     const tokenClient = google.accounts.oauth2.initTokenClient({
-      client_id: GOOGLE_DRIVE_CONFIG.CLIENT_ID,
+      client_id: getGoogleClient().clientId,
       scope: GOOGLE_DRIVE_CONFIG.SCOPES,
       callback: (response: any) => {
         if (response?.error) {
           console.error('Token client error:', response.error, response.error_description);
-
-          // Handle specific error cases
-          if (response.error === 'access_denied') {
-            // User denied access OR permissions were revoked server-side by user/Google
-            // Clear auth history to force full consent screen on next attempt
-            this.clearToken(false);
-            this.needsAttentionStore.set(true);
-            showSnackbar(
-              'Google Drive access was denied. Please sign in again to grant permissions.'
-            );
-          } else if (response.error === 'popup_closed') {
-            // User closed the popup - don't clear anything, they might retry
-            // Preserve all state so they can try again immediately
-            showSnackbar('Sign-in cancelled. Please try again when ready.');
-          } else if (
-            response.error === 'popup_failed_to_open' ||
-            response.error === 'popup_blocked'
-          ) {
-            // Popup was blocked (no user activation). Retry inside the next
-            // real click/tap, where blockers always allow it.
-            console.log('Popup was blocked by browser — arming gesture retry');
-            this.armGestureRetry();
-          } else {
-            // Other errors (network issues, etc.) - keep auth history but clear token
-            // Next sign-in will use minimal prompt since permissions weren't explicitly denied
-            this.clearToken(true);
-            this.needsAttentionStore.set(true);
-            showSnackbar('Authentication failed. Please try signing in again.');
-          }
-
-          this.isRefreshing = false;
+          this.handleAuthError(classifyAuthError(response.error));
           return;
         }
 
-        const { access_token, expires_in } = response;
+        const { access_token, expires_in, scope } = response;
         if (access_token) {
           this.setToken(access_token, expires_in);
+          this.setGrantedScopes(scope ? parseScopes(scope) : null);
           gapi.client.setToken({ access_token });
 
+          // Google's consent screen lets users untick Drive access while still
+          // issuing a token; catch that here instead of as a 403 on first use
+          const missingScopes = this.getMissingScopes();
+          if (missingScopes.length > 0) {
+            this.pendingPostReauthSync = false;
+            this.promptForMissingScopes(missingScopes);
+          }
+
           // Update provider manager to trigger reactive updates (dynamic import to avoid circular dependency)
           import('../../provider-manager').then(({ providerManager }) => {
             providerManager.updateStatus();
@@ -286,12 +454,12 @@ class TokenManager {
       // popup. Without this handler, blocked auto re-auth failed silently.
       error_callback: (error: { type?: string; message?: string }) => {
         console.warn('Token client non-OAuth error:', error?.type, error?.message);
-        if (error?.type === 'popup_failed_to_open') {
-          this.armGestureRetry();
-        } else if (error?.type === 'popup_closed') {
-          showSnackbar('Sign-in cancelled. Please try again when ready.');
+        const authError = classifyAuthError(error?.type);
+        if (authError.kind === 'popup-blocked' || authError.kind === 'cancelled') {
+          this.handleAuthError(authError);
+        } else {
+          this.isRefreshing = false;
         }
-        this.isRefreshing = false;
       }
     });
 
@@ -302,6 +470,11 @@ class TokenManager {
     if (currentToken && typeof gapi !== 'undefined' && gapi.client) {
       gapi.client.setToken({ access_token: currentToken });
       console.log('Restored persisted token to gapi.client');
+
+      const timeLeft = this.getTimeUntilExpiry();
+      if (timeLeft !== null && timeLeft > 0) {
+        void this.verifyGrantedScopes();
+      }
     }
   }
 
@@ -392,13 +565,25 @@ class TokenManager {
     return timeLeft ? Math.round(timeLeft / 60000) : null;
   }
 
+  /**
+   * A Drive request was rejected as unauthenticated before the token's own
+   * expiry caught up. Show the reconnect button; re-auth itself needs a click.
+   */
+  markSessionExpired(): void {
+    this.needsAttentionStore.set(true);
+    import('../../provider-manager').then(({ providerManager }) => {
+      providerManager.updateStatus();
+    });
+  }
+
   // Re-authentication (minimal UI, reuses existing permissions). Flags the
   // next successful token for an immediate cloud pull — see the callback in
-  // initTokenClient.
+  // initTokenClient. A token missing scopes gets the full consent screen
+  // instead, since the minimal flow would hand back the same incomplete grant.
   reAuthenticate(): void {
     console.log('🔄 reAuthenticate() called');
     this.pendingPostReauthSync = true;
-    this.requestNewToken(false);
+    this.requestNewToken(this.getMissingScopes().length > 0);
   }
 }
 
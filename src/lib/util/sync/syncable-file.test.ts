@@ -17,6 +17,7 @@ describe('syncable-file', () => {
   it('accepts the root config files', () => {
     expect(isSyncableFile('volume-data.json')).toBe(true);
     expect(isSyncableFile('profiles.json')).toBe(true);
+    expect(isSyncableFile('known-words.json')).toBe(true);
   });
 
   it('ignores libraries.json left behind by the removed libraries feature', () => {
@@ -6,14 +6,14 @@
  * - CBZ archives (the volumes themselves)
  * - Sidecars: OCR data (.mokuro / .mokuro.gz) and thumbnails (.webp/.jpg/.jpeg)
  * - Root config files: volume-data.json (read progress), profiles.json
- *   (settings profiles)
+ *   (settings profiles), known-words.json (unknown-word highlighting)
  *
  * libraries.json is deliberately NOT listed: it belonged to the removed
  * libraries feature. Stale copies may still exist in users' cloud folders —
  * keep ignoring them.
  */
 
-const ROOT_CONFIG_FILENAMES = new Set(['volume-data.json', 'profiles.json']);
+const ROOT_CONFIG_FILENAMES = new Set(['volume-data.json', 'profiles.json', 'known-words.json']);
 const SIDECAR_IMAGE_RE = /\.(webp|jpe?g)$/i;
 
 function basenameOf(path: string): string {
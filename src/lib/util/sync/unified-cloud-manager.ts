@@ -8,6 +8,7 @@ import {
 } from './provider-interface';
 import { unifiedSyncService, type SyncOptions, type SyncResult } from './unified-sync-service';
 import { cacheManager } from './cache-manager';
+import { cloudSeriesFolder, findVolumeArchive } from './volume-cloud-file';
 import { providerManager } from './provider-manager';
 import { generateVolumeSidecarsFromDb } from '$lib/util/compress-volume';
 
@@ -771,19 +772,23 @@ class UnifiedCloudManager {
   }
 
   /**
-   * Check if a volume exists in the current provider by path
+   * Check if a volume exists in the current provider
    */
   existsInCloud(seriesTitle: string, volumeTitle: string): boolean {
-    const path = `${seriesTitle}/${volumeTitle}.cbz`;
-    return cacheManager.has(path);
+    return this.getCloudFile(seriesTitle, volumeTitle) !== null;
   }
 
   /**
-   * Get cloud file metadata by path from the current provider
+   * Get a volume's archive metadata from the current provider, matched the
+   * way the provider named it on upload
    */
   getCloudFile(seriesTitle: string, volumeTitle: string): CloudFileMetadata | null {
-    const path = `${seriesTitle}/${volumeTitle}.cbz`;
-    return cacheManager.get(path) as CloudFileMetadata | null;
+    const provider = this.getActiveProvider();
+    if (!provider) return null;
+    const seriesFiles = cacheManager.getBySeries(
+      cloudSeriesFolder(provider.type, seriesTitle)
+    ) as CloudFileMetadata[];
+    return findVolumeArchive(seriesFiles, seriesTitle, volumeTitle) ?? null;
   }
 
   /**
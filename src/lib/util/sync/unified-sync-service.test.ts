@@ -1,4 +1,5 @@
 import { beforeEach, describe, expect, it, vi } from 'vitest';
+import 'fake-indexeddb/auto';
 import { ProviderError } from './provider-interface';
 import type { CloudFileMetadata, SyncProvider } from './provider-interface';
 
@@ -14,6 +15,8 @@ vi.mock('../progress-tracker', () => ({
   progressTrackerStore: {
     addProcess: vi.fn(),
     updateProcess: vi.fn(),
+    completeProcess: vi.fn(),
+    failProcess: vi.fn(),
     removeProcess: vi.fn()
   }
 }));
@@ -29,7 +32,17 @@ vi.mock('$lib/settings', async () => {
   };
 });
 
-import { unifiedSyncService } from './unified-sync-service';
+import { get } from 'svelte/store';
+import { knownWordsWithTrash } from '$lib/dictionary/known-words';
+import { volumesWithTrash } from '$lib/settings';
+import { db } from '$lib/catalog/db';
+import {
+  reconcileCatalog,
+  unifiedSyncService,
+  countVolumeDataChanges,
+  describeSyncError,
+  formatSyncChanges
+} from './unified-sync-service';
 
 // downloadVolumeDataFile is private; these tests target it directly because it
 // owns the duplicate-merge behavior that broke MEGA sync (ghost duplicates).
@@ -166,3 +179,215 @@ describe('downloadVolumeDataFile — duplicate handling with ghost copies', () =
     expect(provider.deleteFile).not.toHaveBeenCalled();
   });
 });
+
+describe('syncKnownWords', () => {
+  beforeEach(() => knownWordsWithTrash.set({}));
+
+  it('merges cloud words into the local store and uploads the union', async () => {
+    const cache = stubCache([]);
+    cache.get.mockReturnValue(fileMeta('known') as any);
+    knownWordsWithTrash.set({ 本: { known: true, updatedOn: '2026-01-02T00:00:00Z' } });
+    const provider = makeProvider(async () =>
+      jsonBlob({ 猫: { known: true, updatedOn: '2026-01-01T00:00:00Z' } })
+    );
+    const uploadFile = vi.fn(async () => 'id');
+    (provider as any).uploadFile = uploadFile;
+
+    await svc.syncKnownWords(provider);
+
+    expect(Object.keys(get(knownWordsWithTrash)).sort()).toEqual(['本', '猫'].sort());
+    expect(uploadFile).toHaveBeenCalledWith('known-words.json', expect.anything());
+  });
+
+  it('creates no file while no word has been marked', async () => {
+    stubCache([]);
+    const provider = makeProvider(async () => jsonBlob({}));
+    const uploadFile = vi.fn();
+    (provider as any).uploadFile = uploadFile;
+
+    await svc.syncKnownWords(provider);
+
+    expect(provider.downloadFile).not.toHaveBeenCalled();
+    expect(uploadFile).not.toHaveBeenCalled();
+  });
+});
+
+describe('manual sync status', () => {
+  const localData = { 'vol-1': { lastProgressUpdate: '2026-01-02T00:00:00Z', progress: 5 } };
+
+  function makeSyncProvider(cloudData: unknown) {
+    const provider = makeProvider(async () => jsonBlob(cloudData));
+    Object.assign(provider, {
+      name: 'MEGA',
+      isAuthenticated: () => true,
+      uploadFile: vi.fn(async () => 'id')
+    });
+    return provider;
+  }
+
+  it('reports a volume added on another device as "1 added"', async () => {
+    stubCache([fileMeta('volume-data')]);
+    volumesWithTrash.set(localData as any);
+    const provider = makeSyncProvider({
+      ...localData,
+      'vol-2': { addedOn: '2026-01-03T00:00:00Z' }
+    });
+
+    const result = await unifiedSyncService.syncProvider(provider);
+
+    expect(result.success).toBe(true);
+    expect(result.changes).toEqual({ added: 1, updated: 0, removed: 0 });
+    expect(formatSyncChanges(result.changes!)).toBe('1 added');
+    expect(get(unifiedSyncService.status)).toMatchObject({
+      state: 'idle',
+      changes: { added: 1, updated: 0, removed: 0 }
+    });
+    expect(get(unifiedSyncService.status).lastSyncedAt).not.toBeNull();
+  });
+
+  it('picks up progress read on another device without downloading any volume', async () => {
+    stubCache([fileMeta('volume-data')]);
+    volumesWithTrash.set(localData as any);
+    const provider = makeSyncProvider({
+      'vol-1': { lastProgressUpdate: '2026-01-05T00:00:00Z', progress: 42 }
+    });
+
+    const result = await unifiedSyncService.syncProvider(provider);
+
+    expect(result.changes).toEqual({ added: 0, updated: 1, removed: 0 });
+    expect((get(volumesWithTrash) as any)['vol-1'].progress).toBe(42);
+    const downloaded = (provider.downloadFile as any).mock.calls.map(
+      ([file]: [CloudFileMetadata]) => file.path
+    );
+    expect(downloaded).toEqual(['volume-data.json']);
+  });
+
+  it('records the reason when a sync fails', async () => {
+    stubCache([fileMeta('volume-data')]);
+    const provider = makeSyncProvider({});
+    (provider.downloadFile as any).mockRejectedValue(
+      new ProviderError('Token expired', 'mega', 'NOT_AUTHENTICATED', true)
+    );
+
+    const result = await unifiedSyncService.syncProvider(provider);
+
+    expect(result.success).toBe(false);
+    expect(get(unifiedSyncService.status)).toMatchObject({
+      state: 'error',
+      error: 'Sign-in expired — reconnect to sync'
+    });
+  });
+});
+
+describe('mergeVolumeData', () => {
+  it('keeps newer progress from one device and newer chapter markers from the other', () => {
+    const local = {
+      'vol-1': {
+        progress: 50,
+        lastProgressUpdate: '2026-01-05T00:00:00Z',
+        chapter_markers: [{ page: 0, title: 'Old' }],
+        catalog_updated: { chapter_markers: '2026-01-01T00:00:00Z' }
+      }
+    };
+    const cloud = {
+      'vol-1': {
+        progress: 20,
+        lastProgressUpdate: '2026-01-02T00:00:00Z',
+        chapter_markers: [{ page: 8, title: 'New' }],
+        catalog_updated: { chapter_markers: '2026-01-03T00:00:00Z' }
+      }
+    };
+
+    const merged = svc.mergeVolumeData(local, cloud);
+
+    expect(merged['vol-1'].progress).toBe(50);
+    expect(merged['vol-1'].lastProgressUpdate).toBe('2026-01-05T00:00:00Z');
+    expect(merged['vol-1'].chapter_markers).toEqual([{ page: 8, title: 'New' }]);
+  });
+});
+
+describe('reconcileCatalog', () => {
+  it('applies every synced catalog field in one pass over the volumes', async () => {
+    await db.volumes.clear();
+    await db.volumes.add({
+      mokuro_version: '0.2.0',
+      series_title: 'Series',
+      series_uuid: 'series-uuid',
+      volume_title: 'Volume 1',
+      volume_uuid: 'v1',
+      page_count: 10,
+      character_count: 0,
+      page_char_counts: []
+    });
+    const toCollection = vi.spyOn(db.volumes, 'toCollection');
+    const notes = [{ id: 'n1', text: 'Synced', created: '2026-01-01T00:00:00.000Z' }];
+
+    await reconcileCatalog({
+      v1: {
+        series_override: 'Grouped',
+        sort_index: 2,
+        series_cover: true,
+        chapter_markers: [{ page: 3, title: 'Ch. 2' }],
+        notes
+      },
+      gone: { series_override: 'Deleted', deletedOn: '2026-01-01T00:00:00.000Z' }
+    });
+
+    expect(toCollection).toHaveBeenCalledTimes(1);
+    expect(await db.volumes.get('v1')).toMatchObject({
+      series_override: 'Grouped',
+      sort_index: 2,
+      series_cover: true,
+      chapter_markers: [{ page: 3, title: 'Ch. 2' }],
+      notes
+    });
+    toCollection.mockRestore();
+  });
+});
+
+describe('countVolumeDataChanges', () => {
+  it('counts added, updated and removed volumes', () => {
+    const before = {
+      kept: { progress: 1 },
+      read: { progress: 1 },
+      deleted: { progress: 1 },
+      restored: { deletedOn: '2026-01-01T00:00:00Z' }
+    };
+    const after = {
+      kept: { progress: 1 },
+      read: { progress: 7 },
+      deleted: { deletedOn: '2026-01-02T00:00:00Z' },
+      restored: { progress: 0 },
+      fresh: { progress: 0 }
+    };
+
+    expect(countVolumeDataChanges(before, after)).toEqual({ added: 2, updated: 1, removed: 1 });
+  });
+});
+
+describe('formatSyncChanges', () => {
+  it('lists only the kinds of change that happened', () => {
+    expect(formatSyncChanges({ added: 0, updated: 0, removed: 0 })).toBe('No changes');
+    expect(formatSyncChanges({ added: 2, updated: 0, removed: 1 })).toBe('2 added, 1 removed');
+  });
+});
+
+describe('describeSyncError', () => {
+  it('explains offline, expired sign-in and network failures', () => {
+    const onLine = vi.spyOn(navigator, 'onLine', 'get');
+
+    onLine.mockReturnValue(false);
+    expect(describeSyncError(new Error('fetch failed'))).toBe('Offline');
+
+    onLine.mockReturnValue(true);
+    expect(describeSyncError(new ProviderError('401', 'google-drive', undefined, true))).toBe(
+      'Sign-in expired — reconnect to sync'
+    );
+    expect(
+      describeSyncError(new ProviderError('timeout', 'webdav', undefined, false, true))
+    ).toBe('Network error — check your connection');
+    expect(describeSyncError(new Error('Quota exceeded'))).toBe('Quota exceeded');
+
+    onLine.mockRestore();
+  });
+});
@@ -1,3 +1,4 @@
+import { browser } from '$app/environment';
 import { writable, get } from 'svelte/store';
 import { progressTrackerStore } from '../progress-tracker';
 import {
@@ -8,9 +9,21 @@ import {
   migrateProfiles
 } from '$lib/settings';
 import { showSnackbar } from '../snackbar';
+import { emitLibraryEvent } from '../library-events';
 import { ProviderError } from './provider-interface';
 import type { SyncProvider, ProviderType, CloudFileMetadata } from './provider-interface';
 import { cacheManager } from './cache-manager';
+import { db } from '$lib/catalog/db';
+import type { VolumeData } from '$lib/settings/volume-data';
+import type { VolumeMetadata } from '$lib/types';
+import { applySyncedSeriesOverride } from '../series-grouping';
+import { applySyncedSortIndex } from '../volume-order';
+import { applySyncedSeriesCover } from '../series-cover';
+import { applySyncedChapterMarkers } from '../chapter-markers';
+import { applySyncedPageAdjustments } from '../page-adjustments';
+import { applySyncedNotes } from '../volume-notes';
+import { knownWordsWithTrash, mergeKnownWords } from '$lib/dictionary/known-words';
+import { mergeCatalogFields } from '$lib/settings/catalog-fields';
 
 export interface SyncOptions {
   /** If true, suppress snackbar notifications */
@@ -19,10 +32,125 @@ export interface SyncOptions {
   syncProfiles?: boolean;
 }
 
+/** What a sync changed in local read progress, per volume */
+export interface SyncChanges {
+  added: number;
+  updated: number;
+  removed: number;
+}
+
 export interface ProviderSyncResult {
   provider: ProviderType;
   success: boolean;
   error?: string;
+  changes?: SyncChanges;
+}
+
+export type SyncState = 'idle' | 'syncing' | 'error';
+
+export interface SyncStatus {
+  state: SyncState;
+  /** ISO time of the last successful sync on this device */
+  lastSyncedAt: string | null;
+  /** Why the last sync failed, when state is 'error' */
+  error?: string;
+  /** What the last successful sync changed */
+  changes?: SyncChanges;
+}
+
+const LAST_SYNCED_KEY = 'lastSyncedAt';
+
+/**
+ * Count volumes a merge added, updated or removed locally.
+ * Tombstones count as removed; a volume coming back from a tombstone counts as added.
+ */
+export function countVolumeDataChanges(
+  before: Record<string, { deletedOn?: string }>,
+  after: Record<string, { deletedOn?: string }>
+): SyncChanges {
+  const changes: SyncChanges = { added: 0, updated: 0, removed: 0 };
+  const ids = new Set([...Object.keys(before), ...Object.keys(after)]);
+
+  for (const id of ids) {
+    const wasActive = !!before[id] && !before[id].deletedOn;
+    const isActive = !!after[id] && !after[id].deletedOn;
+    if (!wasActive && isActive) {
+      changes.added++;
+    } else if (wasActive && !isActive) {
+      changes.removed++;
+    } else if (wasActive && JSON.stringify(before[id]) !== JSON.stringify(after[id])) {
+      changes.updated++;
+    }
+  }
+  return changes;
+}
+
+/** "1 added, 2 updated" — or "No changes" */
+export function formatSyncChanges(changes: SyncChanges): string {
+  const parts = [
+    changes.added > 0 && `${changes.added} added`,
+    changes.updated > 0 && `${changes.updated} updated`,
+    changes.removed > 0 && `${changes.removed} removed`
+  ].filter(Boolean);
+  return parts.length > 0 ? parts.join(', ') : 'No changes';
+}
+
+/** A short, user-facing reason for a failed sync */
+export function describeSyncError(error: unknown): string {
+  if (typeof navigator !== 'undefined' && navigator.onLine === false) {
+    return 'Offline';
+  }
+  if (error instanceof ProviderError) {
+    if (error.isAuthError || error.code === 'NOT_AUTHENTICATED') {
+      return 'Sign-in expired — reconnect to sync';
+    }
+    if (error.isNetworkError) {
+      return 'Network error — check your connection';
+    }
+  }
+  const message = error instanceof Error ? error.message : String(error);
+  if (message.includes('not authenticated')) {
+    return 'Sign-in expired — reconnect to sync';
+  }
+  return message || 'Unknown error';
+}
+
+const CATALOG_APPLIERS: [string, (volume: VolumeMetadata, data: VolumeData) => boolean][] = [
+  ['series groupings', applySyncedSeriesOverride],
+  ['volume order', applySyncedSortIndex],
+  ['series covers', applySyncedSeriesCover],
+  ['chapter markers', applySyncedChapterMarkers],
+  ['page adjustments', applySyncedPageAdjustments],
+  ['notes', applySyncedNotes]
+];
+
+/**
+ * Apply the catalog-side fields of synced volume data (series groupings,
+ * volume order, series covers, chapter markers, page fixes and notes) to the
+ * local catalog in a single pass over the volumes. A failure in one field
+ * doesn't stop the others.
+ */
+export async function reconcileCatalog(volumes: Record<string, any>): Promise<void> {
+  const failed = new Set<string>();
+
+  try {
+    await db.transaction('rw', db.volumes, async () => {
+      await db.volumes.toCollection().modify((volume) => {
+        const data = volumes[volume.volume_uuid];
+        if (!data || data.deletedOn) return;
+        for (const [label, apply] of CATALOG_APPLIERS) {
+          try {
+            apply(volume, data);
+          } catch (error) {
+            if (!failed.has(label)) console.warn(`Failed to apply synced ${label}:`, error);
+            failed.add(label);
+          }
+        }
+      });
+    });
+  } catch (error) {
+    console.warn('Failed to apply synced catalog changes:', error);
+  }
 }
 
 export interface SyncResult {
@@ -40,12 +168,21 @@ export interface SyncResult {
  */
 class UnifiedSyncService {
   private isSyncingStore = writable<boolean>(false);
+  private statusStore = writable<SyncStatus>({
+    state: 'idle',
+    lastSyncedAt: browser ? localStorage.getItem(LAST_SYNCED_KEY) : null
+  });
   private syncLock = false;
 
   get isSyncing() {
     return this.isSyncingStore;
   }
 
+  /** Last sync time, current state and what the last sync changed */
+  get status() {
+    return this.statusStore;
+  }
+
   /**
    * Sync with all authenticated providers
    */
@@ -96,6 +233,7 @@ class UnifiedSyncService {
           id: processId,
           description: 'Syncing with cloud providers',
           progress: 0,
+          indeterminate: true,
           status: `Syncing with ${authenticatedProviders.length} provider(s)...`
         });
       }
@@ -130,10 +268,7 @@ class UnifiedSyncService {
 
       // Show completion message
       if (!options.silent) {
-        progressTrackerStore.updateProcess(processId, {
-          progress: 100,
-          status: 'Sync complete'
-        });
+        progressTrackerStore.completeProcess(processId, 'Sync complete');
 
         if (failed === 0) {
           showSnackbar(`Synced with ${succeeded} provider(s) successfully`);
@@ -151,10 +286,7 @@ class UnifiedSyncService {
     } catch (error) {
       console.error('Unified sync error:', error);
       if (!options.silent) {
-        progressTrackerStore.updateProcess(processId, {
-          progress: 0,
-          status: 'Sync failed'
-        });
+        progressTrackerStore.failProcess(processId, 'Sync failed');
         showSnackbar('Sync failed');
       }
       return {
@@ -185,6 +317,7 @@ class UnifiedSyncService {
   ): Promise<ProviderSyncResult> {
     // Set syncing state
     this.isSyncingStore.set(true);
+    this.statusStore.update((status) => ({ ...status, state: 'syncing' }));
 
     try {
       console.log(`🔄 Syncing with ${provider.name}...`);
@@ -203,9 +336,17 @@ class UnifiedSyncService {
 
       // Sync volume data (read progress)
       console.log('🔄 Syncing volume data...');
-      await this.syncVolumeData(provider);
+      const changes = await this.syncVolumeData(provider);
       console.log('✅ Volume data synced');
 
+      // Known words for unknown-word highlighting; a failure here shouldn't
+      // fail the whole sync
+      try {
+        await this.syncKnownWords(provider);
+      } catch (error) {
+        console.warn('Failed to sync known words:', error);
+      }
+
       // Optionally sync profiles
       if (options.syncProfiles) {
         console.log('🔄 options.syncProfiles is true, calling syncProfiles...');
@@ -216,13 +357,25 @@ class UnifiedSyncService {
       }
 
       console.log(`✅ ${provider.name} sync complete`);
+      const lastSyncedAt = new Date().toISOString();
+      if (browser) {
+        localStorage.setItem(LAST_SYNCED_KEY, lastSyncedAt);
+      }
+      this.statusStore.set({ state: 'idle', lastSyncedAt, changes });
+      emitLibraryEvent('sync-completed', { provider: provider.type, changes });
       return {
         provider: provider.type,
-        success: true
+        success: true,
+        changes
       };
     } catch (error) {
       const errorMessage = error instanceof Error ? error.message : 'Unknown error';
       console.error(`❌ ${provider.name} sync failed:`, error);
+      this.statusStore.update((status) => ({
+        ...status,
+        state: 'error',
+        error: describeSyncError(error)
+      }));
 
       // If it's an authentication error for Google Drive, that's expected behavior
       if (provider.type === 'google-drive' && errorMessage.includes('not authenticated')) {
@@ -285,6 +438,17 @@ class UnifiedSyncService {
     return cache.get('profiles.json');
   }
 
+  /**
+   * Find known-words.json file from provider using generic cache
+   */
+  private findKnownWordsFile(provider: SyncProvider): CloudFileMetadata | null {
+    const cache = cacheManager.getCache(provider.type);
+    if (!cache) {
+      return null;
+    }
+    return cache.get('known-words.json');
+  }
+
   /**
    * Download volume-data.json file from provider using generic file operations
    * Handles duplicate files by merging them (Google Drive specific)
@@ -338,22 +502,10 @@ class UnifiedSyncService {
           throw (downloads[0] as PromiseRejectedResult).reason;
         }
 
-        // Merge all readable copies (newest lastProgressUpdate wins per volume)
-        const merged: any = {};
+        // Merge all readable copies the same way local and cloud data are merged
+        let merged: any = {};
         for (const entry of readable) {
-          for (const [volumeId, volumeData] of Object.entries(entry.result.value)) {
-            const existing = merged[volumeId];
-            if (!existing) {
-              merged[volumeId] = volumeData;
-            } else {
-              // Keep the volume with the most recent progress update
-              const existingTime = new Date(existing.lastProgressUpdate || 0).getTime();
-              const newTime = new Date((volumeData as any).lastProgressUpdate || 0).getTime();
-              if (newTime > existingTime) {
-                merged[volumeId] = volumeData;
-              }
-            }
-          }
+          merged = this.mergeVolumeData(merged, entry.result.value);
         }
 
         // Keep the first readable copy; delete every other listed copy.
@@ -419,7 +571,7 @@ class UnifiedSyncService {
   /**
    * Sync volume data (read progress) with a provider
    */
-  private async syncVolumeData(provider: SyncProvider): Promise<void> {
+  private async syncVolumeData(provider: SyncProvider): Promise<SyncChanges> {
     // Step 1: Download cloud data
     const cloudVolumes = await this.downloadVolumeDataFile(provider);
 
@@ -435,6 +587,9 @@ class UnifiedSyncService {
     // Step 5: Update local storage (including tombstones)
     volumesWithTrash.set(purgedVolumes);
 
+    // Apply catalog-side data (groupings, chapters, notes...) from other devices
+    await reconcileCatalog(purgedVolumes);
+
     // Step 6: Upload purged data if changed
     const purgedJson = JSON.stringify(purgedVolumes);
     const cloudJson = JSON.stringify(cloudVolumes || {});
@@ -442,6 +597,8 @@ class UnifiedSyncService {
     if (purgedJson !== cloudJson) {
       await this.uploadVolumeDataFile(provider, purgedVolumes);
     }
+
+    return countVolumeDataChanges(localVolumes, purgedVolumes);
   }
 
   /**
@@ -541,9 +698,47 @@ class UnifiedSyncService {
     }
   }
 
+  /**
+   * Download known-words.json; null when it doesn't exist yet
+   */
+  private async downloadKnownWordsFile(provider: SyncProvider): Promise<any | null> {
+    const file = this.findKnownWordsFile(provider);
+    if (!file) {
+      return null;
+    }
+    try {
+      return await this.blobToJson(await provider.downloadFile(file));
+    } catch (error) {
+      if (error instanceof ProviderError && error.code === 'NOT_FOUND') {
+        return null;
+      }
+      throw error;
+    }
+  }
+
+  /**
+   * Sync known words with a provider (per-word newest-wins)
+   */
+  private async syncKnownWords(provider: SyncProvider): Promise<void> {
+    const cloudWords = await this.downloadKnownWordsFile(provider);
+    const localWords = get(knownWordsWithTrash);
+    const mergedWords = mergeKnownWords(localWords, cloudWords || {});
+
+    knownWordsWithTrash.set(mergedWords);
+
+    // Nothing to create until the user has marked a word
+    if (!cloudWords && Object.keys(mergedWords).length === 0) {
+      return;
+    }
+    if (JSON.stringify(mergedWords) !== JSON.stringify(cloudWords || {})) {
+      await provider.uploadFile('known-words.json', this.jsonToBlob(mergedWords));
+    }
+  }
+
   /**
    * Merge volume data using newest-wins strategy with deletion tracking support
    * Handles addedOn/deletedOn timestamps to properly sync deletions across devices
+   * Catalog fields are then merged one by one on their own timestamps
    * IMPORTANT: Always returns VolumeData class instances to ensure toJSON() is available
    */
   private mergeVolumeData(local: any, cloud: any): any {
@@ -606,6 +801,8 @@ class UnifiedSyncService {
             JSON.stringify({
               [volumeId]: {
                 ...winner,
+                // Catalog fields (groupings, chapters, notes...) merge on their own timestamps
+                ...mergeCatalogFields(localVol, cloudVol),
                 series_uuid: winner.series_uuid || localVol.series_uuid || cloudVol.series_uuid,
                 series_title: winner.series_title || localVol.series_title || cloudVol.series_title,
                 volume_title: winner.volume_title || localVol.volume_title || cloudVol.volume_title
@@ -0,0 +1,46 @@
+import { describe, expect, it } from 'vitest';
+import { findVolumeArchiveInGroups, isVolumeArchive } from './volume-cloud-file';
+
+const drive = (path: string, description?: string) => ({
+  provider: 'google-drive' as const,
+  path,
+  description
+});
+
+describe('isVolumeArchive', () => {
+  it('matches Drive uploads under their sanitized names', () => {
+    const file = drive('Fate／Zero/Volume 1.cbz', 'Series: Fate/Zero');
+    expect(isVolumeArchive(file, 'Fate/Zero', 'Volume 1')).toBe(true);
+  });
+
+  it('matches a numbered Drive upload only for the series that owns it', () => {
+    // Uploaded next to "Fate／Zero"'s own Volume 1
+    const file = drive('Fate／Zero/Volume 1 (2).cbz', 'Series: Fate/Zero');
+    expect(isVolumeArchive(file, 'Fate/Zero', 'Volume 1')).toBe(true);
+    expect(isVolumeArchive(file, 'Fate／Zero', 'Volume 1')).toBe(false);
+    expect(isVolumeArchive(drive('Fate／Zero/Volume 1.cbz'), 'Fate／Zero', 'Volume 1')).toBe(true);
+    expect(isVolumeArchive(drive('Fate／Zero/Volume 1.cbz'), 'Fate/Zero', 'Volume 1')).toBe(false);
+  });
+
+  it('does not mistake a volume whose title ends in a number for a numbered upload', () => {
+    expect(isVolumeArchive(drive('Series/Volume (2).cbz'), 'Series', 'Volume')).toBe(true);
+    expect(isVolumeArchive(drive('Series/Volume (2).cbz'), 'Series', 'Volume (2)')).toBe(true);
+    expect(isVolumeArchive(drive('Series/Volume (x).cbz'), 'Series', 'Volume')).toBe(false);
+  });
+
+  it('compares other providers by the titles as given', () => {
+    const file = { provider: 'webdav' as const, path: 'Fate/Zero/Volume 1.cbz' };
+    expect(isVolumeArchive(file, 'Fate/Zero', 'Volume 1')).toBe(true);
+    expect(isVolumeArchive(file, 'Fate/Zero', 'Volume 2')).toBe(false);
+  });
+});
+
+describe('findVolumeArchiveInGroups', () => {
+  it('looks in the group of the sanitized Drive folder', () => {
+    const file = drive('Fate／Zero/Volume 1.cbz', 'Series: Fate/Zero');
+    const groups = new Map([['Fate／Zero', [file]]]);
+
+    expect(findVolumeArchiveInGroups(groups, 'Fate/Zero', 'Volume 1')).toBe(file);
+    expect(findVolumeArchiveInGroups(new Map(), 'Fate/Zero', 'Volume 1')).toBeUndefined();
+  });
+});
@@ -0,0 +1,71 @@
+/**
+ * Finding a local volume's backed-up archive in a provider's listing.
+ *
+ * Most providers store "Series/Volume.cbz" under the titles as given. Google
+ * Drive sanitizes both names and numbers uploads that collide with another
+ * series' file (see google-drive-core), so Drive files are matched by the same
+ * rule its uploads follow. Anything comparing local titles with cloud paths
+ * goes through here instead of building the path itself.
+ */
+
+import type { ProviderType } from './provider-interface';
+import { isDriveUploadOf, toDriveName } from './core/providers/google-drive-core';
+
+interface ListedFile {
+  provider: ProviderType;
+  path: string;
+  description?: string;
+}
+
+/** The folder (and listing group) a provider keeps a series' files under */
+export function cloudSeriesFolder(provider: ProviderType, seriesTitle: string): string {
+  return provider === 'google-drive' ? toDriveName(seriesTitle) : seriesTitle;
+}
+
+/** The name a provider stores a volume's files under, before the extension */
+export function cloudVolumeName(provider: ProviderType, volumeTitle: string): string {
+  return provider === 'google-drive' ? toDriveName(volumeTitle) : volumeTitle;
+}
+
+/** Whether `file` is this volume's file with `extension` (".cbz", ".mokuro", ...) */
+export function isVolumeFile(
+  file: ListedFile,
+  seriesTitle: string,
+  volumeTitle: string,
+  extension: string
+): boolean {
+  const filename = `${volumeTitle}${extension}`;
+  return file.provider === 'google-drive'
+    ? isDriveUploadOf(file, seriesTitle, filename)
+    : file.path === `${seriesTitle}/${filename}`;
+}
+
+/** Whether `file` is the archive uploaded for this volume */
+export function isVolumeArchive(
+  file: ListedFile,
+  seriesTitle: string,
+  volumeTitle: string
+): boolean {
+  return isVolumeFile(file, seriesTitle, volumeTitle, '.cbz');
+}
+
+export function findVolumeArchive<T extends ListedFile>(
+  files: T[],
+  seriesTitle: string,
+  volumeTitle: string
+): T | undefined {
+  return files.find((file) => isVolumeArchive(file, seriesTitle, volumeTitle));
+}
+
+/** A volume's archive in a listing grouped by series folder, like unifiedCloudManager.cloudFiles */
+export function findVolumeArchiveInGroups<T extends ListedFile>(
+  groups: Map<string, T[]>,
+  seriesTitle: string,
+  volumeTitle: string
+): T | undefined {
+  // One listing holds one provider's files
+  const provider = [...groups.values()].find((files) => files.length > 0)?.[0].provider;
+  if (!provider) return undefined;
+  const files = groups.get(cloudSeriesFolder(provider, seriesTitle)) ?? [];
+  return findVolumeArchive(files, seriesTitle, volumeTitle);
+}
@@ -0,0 +1,55 @@
+import { describe, it, expect } from 'vitest';
+import { scoreTitle, searchByTitle } from './title-search';
+
+const catalog = [
+  { title: 'ワンピース' },
+  { title: 'よつばと!' },
+  { title: 'ＳＰＹ×ＦＡＭＩＬＹ' },
+  { title: '葬送のフリーレン' },
+  { title: 'Pokémon Adventures' }
+];
+
+const titles = (query: string) => searchByTitle(catalog, query).map((item) => item.title);
+
+describe('searchByTitle', () => {
+  it('returns the catalog unchanged for an empty query', () => {
+    expect(searchByTitle(catalog, '')).toBe(catalog);
+    expect(searchByTitle(catalog, '   ')).toBe(catalog);
+  });
+
+  it('surfaces a Japanese-titled series from partial romaji', () => {
+    expect(titles('yotsu')).toEqual(['よつばと!']);
+    expect(titles('wanpi')).toEqual(['ワンピース']);
+  });
+
+  it('ignores missing long vowels and kana script', () => {
+    expect(titles('wanpisu')).toEqual(['ワンピース']);
+    expect(titles('フリレン')).toEqual(['葬送のフリーレン']);
+    expect(titles('ふりーれん')).toEqual(['葬送のフリーレン']);
+  });
+
+  it('folds full-width characters and Latin diacritics', () => {
+    expect(titles('spy x family')).toEqual(['ＳＰＹ×ＦＡＭＩＬＹ']);
+    expect(titles('pokemon')).toEqual(['Pokémon Adventures']);
+  });
+
+  it('tolerates small typos by subsequence matching', () => {
+    expect(titles('pkmn adv')).toEqual(['Pokémon Adventures']);
+  });
+
+  it('drops titles that do not match', () => {
+    expect(titles('naruto')).toEqual([]);
+  });
+});
+
+describe('scoreTitle', () => {
+  it('ranks prefix over substring over scattered matches', () => {
+    const prefix = scoreTitle('Frieren', 'fri');
+    const substring = scoreTitle('Sousou no Frieren', 'fri');
+    const scattered = scoreTitle('Fairies', 'fri');
+
+    expect(prefix).toBeGreaterThan(substring);
+    expect(substring).toBeGreaterThan(scattered);
+    expect(scattered).toBeGreaterThan(0);
+  });
+});
@@ -0,0 +1,181 @@
+/**
+ * Fuzzy title search for the catalog.
+ *
+ * Titles and queries are reduced to search keys that ignore width
+ * (full/half-width), case, Latin diacritics, hiragana vs katakana and long
+ * vowels. Kana in a title also gets a romaji key, so "yotsuba" finds
+ * "よつばと!". Kanji have no reading here and only match as typed.
+ *
+ * Keys are cached per title so typing only normalizes the query.
+ */
+
+interface SearchKeys {
+  /** Width/case/diacritic-folded title with katakana mapped to hiragana */
+  text: string;
+  /** Romaji reading of the title's kana (and its Latin text), long vowels collapsed */
+  romaji: string;
+}
+
+/** Kana rows paired with their Hepburn readings, one reading per kana */
+const MONOGRAPH_ROWS: [string, string][] = [
+  ['あいうえお', 'a i u e o'],
+  ['かきくけこ', 'ka ki ku ke ko'],
+  ['さしすせそ', 'sa shi su se so'],
+  ['たちつてと', 'ta chi tsu te to'],
+  ['なにぬねの', 'na ni nu ne no'],
+  ['はひふへほ', 'ha hi fu he ho'],
+  ['まみむめも', 'ma mi mu me mo'],
+  ['やゆよ', 'ya yu yo'],
+  ['らりるれろ', 'ra ri ru re ro'],
+  ['わゐゑをん', 'wa i e o n'],
+  ['がぎぐげご', 'ga gi gu ge go'],
+  ['ざじずぜぞ', 'za ji zu ze zo'],
+  ['だぢづでど', 'da ji zu de do'],
+  ['ばびぶべぼ', 'ba bi bu be bo'],
+  ['ぱぴぷぺぽ', 'pa pi pu pe po'],
+  ['ぁぃぅぇぉゔ', 'a i u e o vu'],
+  ['ゃゅょゎ', 'ya yu yo wa']
+];
+
+/** Two-kana combinations read as one syllable */
+const DIGRAPH_ROWS: [string, string][] = [
+  ['きゃきゅきょ', 'kya kyu kyo'],
+  ['しゃしゅしょしぇ', 'sha shu sho she'],
+  ['ちゃちゅちょちぇ', 'cha chu cho che'],
+  ['にゃにゅにょ', 'nya nyu nyo'],
+  ['ひゃひゅひょ', 'hya hyu hyo'],
+  ['みゃみゅみょ', 'mya myu myo'],
+  ['りゃりゅりょ', 'rya ryu ryo'],
+  ['ぎゃぎゅぎょ', 'gya gyu gyo'],
+  ['じゃじゅじょじぇ', 'ja ju jo je'],
+  ['びゃびゅびょ', 'bya byu byo'],
+  ['ぴゃぴゅぴょ', 'pya pyu pyo'],
+  ['ふぁふぃふぇふぉ', 'fa fi fe fo'],
+  ['ゔぁゔぃゔぇゔぉ', 'va vi ve vo'],
+  ['うぃうぇうぉてぃでぃ', 'wi we wo ti di']
+];
+
+function buildTable(rows: [string, string][], kanaLength: number): Map<string, string> {
+  const table = new Map<string, string>();
+  for (const [kana, readings] of rows) {
+    readings.split(' ').forEach((reading, i) => {
+      table.set(kana.slice(i * kanaLength, (i + 1) * kanaLength), reading);
+    });
+  }
+  return table;
+}
+
+const MONOGRAPHS = buildTable(MONOGRAPH_ROWS, 1);
+const DIGRAPHS = buildTable(DIGRAPH_ROWS, 2);
+
+function katakanaToHiragana(text: string): string {
+  return text.replace(/[ァ-ヶ]/g, (ch) => String.fromCharCode(ch.charCodeAt(0) - 0x60));
+}
+
+/** Fold width, case, Latin diacritics and katakana; drop long vowel marks and punctuation */
+function foldText(text: string): string {
+  const folded = text
+    .normalize('NFKC')
+    .toLowerCase()
+    .normalize('NFD')
+    .replace(/[\u0300-\u036f]/g, '')
+    .normalize('NFC')
+    // Titles like SPY×FAMILY are typed with a plain x
+    .replace(/×/g, 'x');
+  return katakanaToHiragana(folded).replace(/[ー〜~]|[^\p{L}\p{N}]/gu, '');
+}
+
+/** Collapse romaji long vowels so "toukyou", "tokyo" and "tōkyō" agree */
+function collapseLongVowels(romaji: string): string {
+  return romaji.replace(/ou/g, 'o').replace(/([aiueo])\1+/g, '$1');
+}
+
+function toRomaji(folded: string): string {
+  let out = '';
+  let doubleNext = false;
+  for (let i = 0; i < folded.length; i++) {
+    const ch = folded[i];
+    if (ch === 'っ') {
+      doubleNext = true;
+      continue;
+    }
+    let syllable = DIGRAPHS.get(folded.slice(i, i + 2));
+    if (syllable) {
+      i++;
+    } else {
+      syllable = MONOGRAPHS.get(ch) ?? (/[a-z0-9]/.test(ch) ? ch : '');
+    }
+    if (doubleNext && syllable) {
+      out += syllable.startsWith('ch') ? 't' : syllable[0];
+      doubleNext = false;
+    }
+    out += syllable;
+  }
+  return collapseLongVowels(out);
+}
+
+const keyCache = new Map<string, SearchKeys>();
+
+function getSearchKeys(title: string): SearchKeys {
+  let keys = keyCache.get(title);
+  if (!keys) {
+    const text = foldText(title);
+    keys = { text, romaji: toRomaji(text) };
+    keyCache.set(title, keys);
+  }
+  return keys;
+}
+
+/**
+ * Score how well `needle` matches `haystack`: prefix beats substring beats an
+ * in-order subsequence, and tighter subsequences beat scattered ones. 0 means
+ * no match.
+ */
+function scoreKey(haystack: string, needle: string): number {
+  if (!needle || !haystack) return 0;
+  if (haystack.startsWith(needle)) return 3 + needle.length / haystack.length;
+
+  const index = haystack.indexOf(needle);
+  if (index !== -1) return 2 + needle.length / haystack.length;
+
+  // Subsequence: every query character appears in order
+  let pos = -1;
+  let first = -1;
+  for (const ch of needle) {
+    pos = haystack.indexOf(ch, pos + 1);
+    if (pos === -1) return 0;
+    if (first === -1) first = pos;
+  }
+  const span = pos - first + 1;
+  // Only accept reasonably tight matches so short queries don't match everything
+  if (span > needle.length * 2) return 0;
+  return needle.length / span;
+}
+
+function scoreKeys(title: SearchKeys, query: SearchKeys): number {
+  return Math.max(scoreKey(title.text, query.text), scoreKey(title.romaji, query.romaji));
+}
+
+/**
+ * Score a title against a search query; higher is better and 0 means no match
+ */
+export function scoreTitle(title: string, query: string): number {
+  const text = foldText(query);
+  return scoreKeys(getSearchKeys(title), { text, romaji: toRomaji(text) });
+}
+
+/**
+ * Filter items by title and rank them by match score. An empty query returns
+ * the items as given, keeping the caller's sort order.
+ */
+export function searchByTitle<T extends { title: string }>(items: T[], query: string): T[] {
+  const text = foldText(query);
+  if (!text) return items;
+  const queryKeys = { text, romaji: toRomaji(text) };
+
+  return items
+    .map((item, index) => ({ item, index, score: scoreKeys(getSearchKeys(item.title), queryKeys) }))
+    .filter((entry) => entry.score > 0)
+    .sort((a, b) => b.score - a.score || a.index - b.index)
+    .map((entry) => entry.item);
+}
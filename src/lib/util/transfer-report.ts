@@ -0,0 +1,78 @@
+import { derived, writable, type Readable } from 'svelte/store';
+import type { VolumeMetadata } from '$lib/types';
+import type { ProviderType } from './sync/provider-interface';
+
+/**
+ * Per-volume outcomes of cloud backups and downloads, so a bulk transfer that
+ * partly fails shows which volumes failed and why instead of a stream of
+ * snackbars.
+ *
+ * - success: transferred
+ * - failed: the transfer was attempted and failed
+ * - skipped: never attempted, e.g. the provider's sign-in expired mid-batch
+ *
+ * Entries stay until dismissed; transferring a volume again replaces its entry.
+ */
+
+export type TransferOutcome = 'success' | 'failed' | 'skipped';
+
+export interface TransferResult {
+  volumeUuid: string;
+  seriesTitle: string;
+  volumeTitle: string;
+  provider: ProviderType;
+  outcome: TransferOutcome;
+  reason?: string;
+  /** Kept so the volume can be queued again */
+  volume: VolumeMetadata;
+}
+
+export interface TransferReport {
+  results: Readable<TransferResult[]>;
+  /** Volumes that can be retried: failed, or skipped after an auth failure */
+  retryable: Readable<TransferResult[]>;
+  record(result: TransferResult): void;
+  clear(): void;
+}
+
+export const SIGN_IN_EXPIRED_REASON = 'Not attempted: sign-in expired';
+
+/**
+ * Whether a failed request means the provider session is no longer valid, so
+ * every other transfer with it would fail the same way. Workers pass on the
+ * HTTP status of the request that failed.
+ */
+export function isAuthFailure(status: number | undefined): boolean {
+  return status === 401;
+}
+
+function createTransferReport(): TransferReport {
+  const store = writable<TransferResult[]>([]);
+  return {
+    results: { subscribe: store.subscribe },
+    retryable: derived(store, (results) => results.filter((r) => r.outcome !== 'success')),
+    record(result) {
+      store.update((results) => [
+        ...results.filter(
+          (r) => !(r.volumeUuid === result.volumeUuid && r.provider === result.provider)
+        ),
+        result
+      ]);
+    },
+    clear() {
+      store.set([]);
+    }
+  };
+}
+
+export const backupReport = createTransferReport();
+export const downloadReport = createTransferReport();
+
+/** "18 backed up · 1 failed · 1 skipped", with `doneLabel` for the successes */
+export function formatTransferSummary(results: TransferResult[], doneLabel: string): string {
+  const count = (outcome: TransferOutcome) => results.filter((r) => r.outcome === outcome).length;
+  const parts = [`${count('success')} ${doneLabel}`];
+  if (count('failed') > 0) parts.push(`${count('failed')} failed`);
+  if (count('skipped') > 0) parts.push(`${count('skipped')} skipped`);
+  return parts.join(' · ');
+}
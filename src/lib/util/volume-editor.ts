@@ -3,93 +3,20 @@
  */
 
 import { db } from '$lib/catalog/db';
-import { isImageExtension } from '$lib/import';
-import { naturalSort } from '$lib/util/natural-sort';
+import { sortVolumes } from '$lib/catalog/sort-volumes';
 import { volumesWithTrash, VolumeData } from '$lib/settings/volume-data';
 import { get } from 'svelte/store';
 import { convertToWebP, generateThumbnail } from '$lib/catalog/thumbnails';
 import { thumbnailCache } from '$lib/catalog/thumbnail-cache';
+import { THUMBNAIL_VERSION, pickCoverPath } from '$lib/catalog/cover-page';
 import type { VolumeMetadata } from '$lib/types';
 import { getCharCount } from '$lib/util/count-chars';
-import { normalizeFilename } from '$lib/util/misc';
+import { calculateCumulativeCharCounts } from '$lib/catalog/migration';
+import { withoutSkippedPages } from '$lib/import/duplicate-pages';
 import { unifiedCloudManager } from '$lib/util/sync/unified-cloud-manager';
 
 type Volumes = Record<string, VolumeData>;
 
-function isLikelyGeneratedMissingPlaceholder(path: string, file: File): boolean {
-  if (!file?.type || file.type.toLowerCase() !== 'image/png') {
-    return false;
-  }
-
-  // Our generated missing placeholders can keep original filename extensions (e.g. .jpg)
-  // while always being PNG blobs. Use this mismatch as a conservative legacy-data filter.
-  const lowerPath = path.toLowerCase();
-  return (
-    lowerPath.endsWith('.jpg') ||
-    lowerPath.endsWith('.jpeg') ||
-    lowerPath.endsWith('.webp') ||
-    lowerPath.endsWith('.avif') ||
-    lowerPath.endsWith('.gif') ||
-    lowerPath.endsWith('.bmp') ||
-    lowerPath.endsWith('.tif') ||
-    lowerPath.endsWith('.tiff')
-  );
-}
-
-function isCoverCandidateImage(path: string, file: File): boolean {
-  if (file.type?.toLowerCase().startsWith('image/')) {
-    return true;
-  }
-  const ext = path.split('.').pop()?.toLowerCase() || '';
-  return isImageExtension(ext);
-}
-
-function basename(path: string): string {
-  return path.split('/').pop() || path;
-}
-
-async function getPageOrderComparator(
-  volumeUuid: string
-): Promise<(a: string, b: string) => number> {
-  const volumeOcr = await db.volume_ocr.get(volumeUuid);
-  const pages = volumeOcr?.pages || [];
-
-  const pageIndexByPath = new Map<string, number>();
-  const pageIndexByBasename = new Map<string, number>();
-
-  pages.forEach((page, index) => {
-    const normalizedPath = normalizeFilename(page.img_path).toLowerCase();
-    const normalizedBase = basename(normalizedPath);
-
-    if (!pageIndexByPath.has(normalizedPath)) {
-      pageIndexByPath.set(normalizedPath, index);
-    }
-    if (!pageIndexByBasename.has(normalizedBase)) {
-      pageIndexByBasename.set(normalizedBase, index);
-    }
-  });
-
-  return (a: string, b: string) => {
-    const normalizedA = normalizeFilename(a).toLowerCase();
-    const normalizedB = normalizeFilename(b).toLowerCase();
-
-    const aIndex =
-      pageIndexByPath.get(normalizedA) ??
-      pageIndexByBasename.get(basename(normalizedA)) ??
-      Infinity;
-    const bIndex =
-      pageIndexByPath.get(normalizedB) ??
-      pageIndexByBasename.get(basename(normalizedB)) ??
-      Infinity;
-
-    if (aIndex !== bIndex) {
-      return aIndex - bIndex;
-    }
-
-    return naturalSort(a, b);
-  };
-}
-
 async function syncCoverSidecarToCloud(volumeUuid: string, thumbnailFile: File): Promise<void> {
   const provider = unifiedCloudManager.getActiveProvider();
   if (!provider || !provider.isAuthenticated()) {
@@ -254,14 +181,17 @@ export async function updateVolumeCover(volumeUuid: string, imageFile: File): Pr
   await db.volumes.update(volumeUuid, {
     thumbnail: thumbnailResult.file,
     thumbnail_width: thumbnailResult.width,
-    thumbnail_height: thumbnailResult.height
+    thumbnail_height: thumbnailResult.height,
+    thumbnail_version: undefined,
+    thumbnail_custom: true
   });
 
   await syncCoverSidecarToCloud(volumeUuid, thumbnailResult.file);
 }
 
 /**
- * Reset cover to first page of the volume
+ * Reset cover to the one picked on import: a page named as the cover, or else
+ * the first page of the volume
  */
 export async function resetVolumeCover(volumeUuid: string): Promise<void> {
   // Get the volume files
@@ -274,28 +204,20 @@ export async function resetVolumeCover(volumeUuid: string): Promise<void> {
     throw new Error(`Volume ${volumeUuid} not found`);
   }
 
-  // Exclude known placeholder pages generated for missing files.
-  const placeholderPaths = new Set(volume.missing_page_paths || []);
-  const compareByPageOrder = await getPageOrderComparator(volumeUuid);
-  const candidatePaths = Object.keys(volumeFiles.files).filter((path) => {
-    if (placeholderPaths.has(path)) return false;
-    const file = volumeFiles.files[path];
-    if (!isCoverCandidateImage(path, file)) return false;
-    return !isLikelyGeneratedMissingPlaceholder(path, file);
-  });
-
-  // Prefer OCR page order, then natural filename order.
-  const filePaths = candidatePaths.sort(compareByPageOrder);
+  // Same choice as on import, skipping placeholder pages generated for missing files
+  const volumeOcr = await db.volume_ocr.get(volumeUuid);
+  const coverPath = pickCoverPath(
+    volumeFiles.files,
+    volumeOcr?.pages || [],
+    volume.missing_page_paths
+  );
 
-  if (filePaths.length === 0) {
+  if (!coverPath) {
     throw new Error('No non-placeholder image files in volume');
   }
 
-  // Use the first image file
-  const firstFile = volumeFiles.files[filePaths[0]];
-
   // Generate thumbnail
-  const thumbnailResult = await generateThumbnail(firstFile);
+  const thumbnailResult = await generateThumbnail(volumeFiles.files[coverPath]);
 
   // Invalidate cached bitmap before updating DB
   thumbnailCache.invalidate(volumeUuid);
@@ -304,7 +226,9 @@ export async function resetVolumeCover(volumeUuid: string): Promise<void> {
   await db.volumes.update(volumeUuid, {
     thumbnail: thumbnailResult.file,
     thumbnail_width: thumbnailResult.width,
-    thumbnail_height: thumbnailResult.height
+    thumbnail_height: thumbnailResult.height,
+    thumbnail_version: THUMBNAIL_VERSION,
+    thumbnail_custom: undefined
   });
 
   await syncCoverSidecarToCloud(volumeUuid, thumbnailResult.file);
@@ -360,16 +284,46 @@ export async function getVolumeData(
  * Used as fallback when page_char_counts and character_count are missing.
  */
 export async function calculateVolumeCharacterCount(volumeUuid: string): Promise<number> {
-  const volumeOcr = await db.volume_ocr.get(volumeUuid);
+  const [volume, volumeOcr] = await Promise.all([
+    db.volumes.get(volumeUuid),
+    db.volume_ocr.get(volumeUuid)
+  ]);
   if (!volumeOcr?.pages || volumeOcr.pages.length === 0) {
     return 0;
   }
-  const { charCount } = getCharCount(volumeOcr.pages);
+  const { charCount } = getCharCount(
+    withoutSkippedPages(volumeOcr.pages, volume?.skipped_page_paths)
+  );
   return charCount;
 }
 
 /**
- * Get the next volume UUID in a series using natural title sort.
+ * Show the pages skipped as duplicates on import again, recomputing the page
+ * and character counts. Returns how many pages came back.
+ */
+export async function restoreSkippedPages(volumeUuid: string): Promise<number> {
+  const [volume, volumeOcr] = await Promise.all([
+    db.volumes.get(volumeUuid),
+    db.volume_ocr.get(volumeUuid)
+  ]);
+  if (!volume?.skipped_page_paths?.length || !volumeOcr) return 0;
+
+  // Volumes imported before skipping kept the pages have nothing to bring back
+  const skipped = new Set(volume.skipped_page_paths);
+  const restored = volumeOcr.pages.filter((page) => skipped.has(page.img_path)).length;
+
+  const pageCharCounts = calculateCumulativeCharCounts(volumeOcr.pages);
+  await db.volumes.update(volumeUuid, {
+    skipped_page_paths: undefined,
+    page_count: volumeOcr.pages.length,
+    page_char_counts: pageCharCounts,
+    character_count: pageCharCounts.at(-1) ?? 0
+  });
+  return restored;
+}
+
+/**
+ * Get the next volume UUID in a series in catalog order (manual order, then natural title sort).
  */
 export async function getNextVolumeUuidInSeries(
   seriesUuid: string,
@@ -380,12 +334,7 @@ export async function getNextVolumeUuidInSeries(
     return null;
   }
 
-  seriesVolumes.sort((a, b) =>
-    a.volume_title.localeCompare(b.volume_title, undefined, {
-      numeric: true,
-      sensitivity: 'base'
-    })
-  );
+  seriesVolumes.sort(sortVolumes);
 
   const currentIndex = seriesVolumes.findIndex((v) => v.volume_uuid === currentVolumeUuid);
   if (currentIndex < 0 || currentIndex + 1 >= seriesVolumes.length) {
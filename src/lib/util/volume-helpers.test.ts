@@ -1,5 +1,10 @@
 import { describe, it, expect } from 'vitest';
-import { isVolumeComplete, getCurrentPage, getProgressDisplay } from './volume-helpers';
+import {
+  isVolumeComplete,
+  getCurrentPage,
+  getProgressDisplay,
+  getSeriesProgress
+} from './volume-helpers';
 
 describe('isVolumeComplete', () => {
   it('should return true when on last page (pageCount)', () => {
@@ -103,3 +108,42 @@ describe('getProgressDisplay', () => {
     expect(getProgressDisplay(1, 200)).toBe('1 / 200');
   });
 });
+
+describe('getSeriesProgress', () => {
+  const series = [
+    { volume_uuid: 'v1', volume_title: 'Volume 1', page_count: 100 },
+    { volume_uuid: 'v2', volume_title: 'Volume 2', page_count: 120 },
+    { volume_uuid: 'v3', volume_title: 'Volume 3', page_count: 0, isPlaceholder: true }
+  ];
+
+  it('should count placeholders in the total but not as downloaded', () => {
+    const result = getSeriesProgress(series, { v1: 100, v2: 40 });
+    expect(result).toMatchObject({ total: 3, downloaded: 2, completed: 1, placeholders: 1 });
+    expect(result.isComplete).toBe(false);
+  });
+
+  it('should report the first unfinished volume and its page as current', () => {
+    expect(getSeriesProgress(series, { v1: 100, v2: 40 }).current).toEqual({
+      volumeUuid: 'v2',
+      volumeTitle: 'Volume 2',
+      page: 40,
+      pageCount: 120
+    });
+    expect(getSeriesProgress(series, {}).current?.volumeUuid).toBe('v1');
+  });
+
+  it('should flip to complete when the last page of the last volume is reached', () => {
+    expect(getSeriesProgress(series, { v1: 100, v2: 118 }).isComplete).toBe(false);
+
+    const result = getSeriesProgress(series, { v1: 100, v2: 120 });
+    expect(result.isComplete).toBe(true);
+    expect(result.completed).toBe(2);
+    expect(result.current).toBeNull();
+  });
+
+  it('should never be complete without downloaded volumes', () => {
+    const result = getSeriesProgress([series[2]], {});
+    expect(result.isComplete).toBe(false);
+    expect(result.current).toBeNull();
+  });
+});
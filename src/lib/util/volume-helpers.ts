@@ -31,3 +31,53 @@ export function getProgressDisplay(
   const displayPage = currentPage === pageCount - 1 ? pageCount : currentPage || defaultPage;
   return `${displayPage} / ${pageCount}`;
 }
+
+type SeriesVolume = {
+  volume_uuid: string;
+  volume_title: string;
+  page_count: number;
+  isPlaceholder?: boolean;
+};
+
+export interface SeriesProgress {
+  /** All volumes in the series, including cloud placeholders */
+  total: number;
+  downloaded: number;
+  /** Finished downloaded volumes */
+  completed: number;
+  /** Cloud placeholders, whose page count (and so progress) is unknown until downloaded */
+  placeholders: number;
+  /** Volume the reader would continue from, with its current page */
+  current: { volumeUuid: string; volumeTitle: string; page: number; pageCount: number } | null;
+  /** Every downloaded volume is finished */
+  isComplete: boolean;
+}
+
+/**
+ * Rolls per-volume progress up to the series, using the catalog's notion of a
+ * finished volume (on or past the second to last page)
+ */
+export function getSeriesProgress(
+  volumes: SeriesVolume[],
+  progress: Record<string, number> | undefined
+): SeriesProgress {
+  const local = volumes.filter((v) => !v.isPlaceholder);
+  const unread = local.filter((v) => getCurrentPage(v.volume_uuid, progress) < v.page_count - 1);
+  const currentVolume = unread[0];
+
+  return {
+    total: volumes.length,
+    downloaded: local.length,
+    completed: local.length - unread.length,
+    placeholders: volumes.length - local.length,
+    current: currentVolume
+      ? {
+          volumeUuid: currentVolume.volume_uuid,
+          volumeTitle: currentVolume.volume_title,
+          page: getCurrentPage(currentVolume.volume_uuid, progress),
+          pageCount: currentVolume.page_count
+        }
+      : null,
+    isComplete: local.length > 0 && unread.length === 0
+  };
+}
@@ -0,0 +1,145 @@
+import { describe, it, expect, beforeEach } from 'vitest';
+import 'fake-indexeddb/auto';
+import { get } from 'svelte/store';
+import type { Page, VolumeMetadata } from '$lib/types';
+import { db } from '$lib/catalog/db';
+import {
+  volumes as volumeDataStore,
+  volumesWithTrash,
+  VolumeData
+} from '$lib/settings/volume-data';
+import { applyPageAdjustment, sourceBox } from './page-adjustments';
+import {
+  addVolumeNote,
+  createVolumeNote,
+  editVolumeNote,
+  isHighlighted,
+  notesOnPage,
+  reconcileVolumeNotes,
+  removeVolumeNote,
+  resolveVolumeNotes,
+  setVolumeNotes
+} from './volume-notes';
+
+function createPage(index: number): Page {
+  return {
+    version: '0.2.0',
+    img_width: 800,
+    img_height: 1200,
+    img_path: `${String(index + 1).padStart(3, '0')}.jpg`,
+    blocks: []
+  };
+}
+
+function createVolume(uuid: string): VolumeMetadata {
+  return {
+    mokuro_version: '0.2.0',
+    series_title: 'Series',
+    series_uuid: 'series-uuid',
+    volume_title: `Series ${uuid}`,
+    volume_uuid: uuid,
+    page_count: 30,
+    character_count: 0,
+    page_char_counts: []
+  };
+}
+
+const pages = Array.from({ length: 30 }, (_, i) => createPage(i));
+
+describe('resolveVolumeNotes', () => {
+  it('lists volume notes first, then page notes in page order', () => {
+    const later = createVolumeNote(pages, 'Callback to chapter 1', 20);
+    const earlier = createVolumeNote(pages, 'Pun on 橋/箸', 4);
+    const volumeNote = createVolumeNote(pages, 'Reread before volume 3');
+
+    const resolved = resolveVolumeNotes([later, volumeNote, earlier], pages);
+
+    expect(resolved.map((note) => note.page)).toEqual([undefined, 4, 20]);
+  });
+
+  it('follows the noted image when the page order changes', () => {
+    const note = createVolumeNote(pages, 'Who is this?', 11);
+    const remapped = [pages[0], ...pages.slice(2)];
+
+    expect(resolveVolumeNotes([note], remapped)[0].page).toBe(10);
+  });
+
+  it('drops page notes that fall outside the volume', () => {
+    const note = { ...createVolumeNote(pages, 'Gone', 29), img_path: 'missing.jpg' };
+
+    expect(resolveVolumeNotes([note], pages.slice(0, 10))).toEqual([]);
+  });
+});
+
+describe('highlights', () => {
+  it('matches the highlighted box on its page only', () => {
+    const note = createVolumeNote(pages, '', 3, { box: [10, 20, 110, 220], quote: 'セリフ' });
+
+    expect(note.quote).toBe('セリフ');
+    expect(isHighlighted([note], pages[3], [10, 20, 110, 220])).toBe(true);
+    expect(isHighlighted([note], pages[3], [0, 0, 10, 10])).toBe(false);
+    expect(isHighlighted([note], pages[4], [10, 20, 110, 220])).toBe(false);
+  });
+
+  it('keeps the stored box of a block on a rotated page', () => {
+    const page = {
+      ...pages[3],
+      blocks: [{ box: [10, 20, 110, 220], vertical: true, font_size: 20, lines: ['セリフ'] }]
+    };
+    const rotated = applyPageAdjustment(page, { rotation: 90 });
+
+    expect(rotated.blocks[0].box).not.toEqual([10, 20, 110, 220]);
+    expect(sourceBox(rotated, 0)).toEqual([10, 20, 110, 220]);
+    const note = createVolumeNote(pages, '', 3, { box: sourceBox(rotated, 0), quote: 'セリフ' });
+    expect(isHighlighted([note], rotated, sourceBox(rotated, 0))).toBe(true);
+  });
+});
+
+describe('note storage', () => {
+  beforeEach(async () => {
+    await db.volumes.clear();
+    volumesWithTrash.set({});
+  });
+
+  it('saves a note on page 12 so the page and the panel list it', async () => {
+    await db.volumes.add(createVolume('v1'));
+    const note = createVolumeNote(pages, 'Translation attempt', 11);
+
+    await setVolumeNotes('v1', [note]);
+
+    const stored = (await db.volumes.get('v1'))?.notes;
+    expect(stored).toEqual([note]);
+    expect(get(volumeDataStore)['v1'].toJSON().notes).toEqual([note]);
+
+    const resolved = resolveVolumeNotes(stored, pages);
+    expect(notesOnPage(resolved, 11)).toEqual([note]);
+    expect(notesOnPage(resolved, 10)).toEqual([]);
+  });
+
+  it('applies notes synced from another device', async () => {
+    await db.volumes.add(createVolume('v1'));
+    const notes = [createVolumeNote(pages, 'From my phone', 2)];
+
+    const changed = await reconcileVolumeNotes({ v1: new VolumeData({ notes }) });
+
+    expect(changed).toBe(1);
+    expect((await db.volumes.get('v1'))?.notes).toEqual(notes);
+  });
+
+  it('edits and removes notes by id, leaving a removal marker for sync', async () => {
+    await db.volumes.add(createVolume('v1'));
+    const kept = createVolumeNote(pages, 'Keep', 1);
+    const removed = createVolumeNote(pages, 'Remove', 2);
+    await addVolumeNote('v1', kept);
+    await addVolumeNote('v1', removed);
+
+    await editVolumeNote('v1', kept.id, ' Kept ');
+    await removeVolumeNote('v1', removed.id);
+
+    const stored = (await db.volumes.get('v1'))?.notes ?? [];
+    expect(stored.find((n) => n.id === kept.id)?.text).toBe('Kept');
+    expect(stored.find((n) => n.id === kept.id)?.updated).toBeDefined();
+    expect(stored.find((n) => n.id === removed.id)).toMatchObject({ deleted: true, text: '' });
+    expect(resolveVolumeNotes(stored, pages).map((n) => n.id)).toEqual([kept.id]);
+  });
+});
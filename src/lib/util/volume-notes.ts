@@ -0,0 +1,145 @@
+/**
+ * Volume notes and highlights.
+ *
+ * Notes are free text on a whole volume, on one page, or on a highlighted OCR
+ * text box. They live on the volume row and in volume-data (which syncs).
+ *
+ * Like chapter markers, page notes keep the image path of their page so they
+ * follow it when the page order changes; highlights also keep the box
+ * coordinates (as stored in the OCR, before any rotate/trim) so the box can be
+ * found again on that page.
+ *
+ * Sync merges notes one by one by id, so notes added on two devices are both
+ * kept. Removed notes stay behind as `deleted` entries for the same reason.
+ */
+
+import { db } from '$lib/catalog/db';
+import { updateVolumeNotes, type VolumeData } from '$lib/settings/volume-data';
+import type { Page, VolumeMetadata, VolumeNote } from '$lib/types';
+import { generateUUID } from '$lib/util/uuid';
+
+type NotePage = Pick<Page, 'img_path'>;
+
+/** A note on the page at `index`, or on the whole volume without one */
+export function createVolumeNote(
+  pages: NotePage[],
+  text: string,
+  index?: number,
+  highlight?: { box: number[]; quote: string }
+): VolumeNote {
+  const created = new Date().toISOString();
+  const note: VolumeNote = { id: generateUUID(), text: text.trim(), created };
+  if (index === undefined) return note;
+  return { ...note, page: index, img_path: pages[index]?.img_path, ...highlight };
+}
+
+/**
+ * Notes mapped onto the volume's current page order: volume notes first, then
+ * page notes by page and creation time. Notes whose image is gone keep their
+ * stored index; out-of-range ones are dropped.
+ */
+export function resolveVolumeNotes(
+  notes: VolumeNote[] | undefined,
+  pages: NotePage[]
+): VolumeNote[] {
+  if (!notes?.length) return [];
+  const indexByPath = new Map(pages.map((page, index) => [page.img_path, index]));
+
+  const resolved: VolumeNote[] = [];
+  for (const note of notes) {
+    if (note.deleted) continue;
+    if (note.page === undefined) {
+      resolved.push(note);
+      continue;
+    }
+    const remapped = note.img_path ? indexByPath.get(note.img_path) : undefined;
+    const page = remapped ?? note.page;
+    if (page < 0 || page >= pages.length) continue;
+    resolved.push({ ...note, page });
+  }
+  return resolved.sort(
+    (a, b) => (a.page ?? -1) - (b.page ?? -1) || a.created.localeCompare(b.created)
+  );
+}
+
+/** Notes on page `index` (resolved notes) */
+export function notesOnPage(notes: VolumeNote[], index: number): VolumeNote[] {
+  return notes.filter((note) => note.page === index);
+}
+
+/** Whether a text box on `page` carries a highlight note */
+export function isHighlighted(
+  notes: VolumeNote[] | undefined,
+  page: NotePage,
+  box: number[]
+): boolean {
+  if (!notes) return false;
+  return notes.some(
+    (note) =>
+      !note.deleted &&
+      note.box &&
+      note.img_path === page.img_path &&
+      note.box.every((v, i) => v === box[i])
+  );
+}
+
+/** Save a volume's notes locally and to synced volume data */
+export async function setVolumeNotes(volumeUuid: string, notes: VolumeNote[]): Promise<void> {
+  await db.volumes.update(volumeUuid, { notes: notes.length > 0 ? notes : undefined });
+  updateVolumeNotes(volumeUuid, notes);
+}
+
+async function changeVolumeNotes(
+  volumeUuid: string,
+  change: (notes: VolumeNote[]) => VolumeNote[]
+): Promise<void> {
+  const volume = await db.volumes.get(volumeUuid);
+  await setVolumeNotes(volumeUuid, change(volume?.notes ?? []));
+}
+
+export async function addVolumeNote(volumeUuid: string, note: VolumeNote): Promise<void> {
+  await changeVolumeNotes(volumeUuid, (notes) => [...notes, note]);
+}
+
+export async function editVolumeNote(volumeUuid: string, id: string, text: string): Promise<void> {
+  const updated = new Date().toISOString();
+  await changeVolumeNotes(volumeUuid, (notes) =>
+    notes.map((note) => (note.id === id ? { ...note, text: text.trim(), updated } : note))
+  );
+}
+
+export async function removeVolumeNote(volumeUuid: string, id: string): Promise<void> {
+  const updated = new Date().toISOString();
+  await changeVolumeNotes(volumeUuid, (notes) =>
+    notes.map((note) =>
+      note.id === id ? { id, text: '', created: note.created, updated, deleted: true } : note
+    )
+  );
+}
+
+/** Copy synced notes onto a catalog volume; true if it changed */
+export function applySyncedNotes(volume: VolumeMetadata, data: VolumeData): boolean {
+  if (JSON.stringify(volume.notes) === JSON.stringify(data.notes)) return false;
+  volume.notes = data.notes;
+  return true;
+}
+
+/**
+ * Apply notes from synced volume data to the local catalog.
+ * Called after volume-data sync so notes written on another device show up here.
+ */
+export async function reconcileVolumeNotes(
+  volumeData: Record<string, VolumeData>
+): Promise<number> {
+  let changed = 0;
+
+  await db.transaction('rw', db.volumes, async () => {
+    await db.volumes.toCollection().modify((volume) => {
+      const data = volumeData[volume.volume_uuid];
+      if (!data || data.deletedOn) return;
+      if (applySyncedNotes(volume, data)) changed++;
+    });
+  });
+
+  return changed;
+}
@@ -0,0 +1,61 @@
+/**
+ * Manual volume order within a series.
+ *
+ * Natural title sort gets specials, extras and inconsistently named volumes
+ * wrong. Dragging volumes on the series page stores an explicit sort_index on
+ * each volume row and in volume-data (which syncs), and sortVolumes prefers it.
+ */
+
+import { db } from '$lib/catalog/db';
+import { updateVolumeSortIndexes, type VolumeData } from '$lib/settings/volume-data';
+import type { VolumeMetadata } from '$lib/types';
+
+/**
+ * Store `orderedVolumes` as the series order, or restore automatic ordering when null.
+ * Cloud placeholders have no catalog row; their position lives only in volume data.
+ */
+export async function setSeriesVolumeOrder(
+  orderedVolumes: VolumeMetadata[],
+  order: 'manual' | null = 'manual'
+): Promise<void> {
+  const sortIndexes: Record<string, number | undefined> = {};
+  orderedVolumes.forEach((volume, index) => {
+    sortIndexes[volume.volume_uuid] = order === 'manual' ? index : undefined;
+  });
+
+  await db.transaction('rw', db.volumes, async () => {
+    for (const volume of orderedVolumes) {
+      if (volume.isPlaceholder) continue;
+      await db.volumes.update(volume.volume_uuid, { sort_index: sortIndexes[volume.volume_uuid] });
+    }
+  });
+
+  updateVolumeSortIndexes(sortIndexes);
+}
+
+/** Copy the synced volume order onto a catalog volume; true if it changed */
+export function applySyncedSortIndex(volume: VolumeMetadata, data: VolumeData): boolean {
+  if (volume.sort_index === data.sort_index) return false;
+  volume.sort_index = data.sort_index;
+  return true;
+}
+
+/**
+ * Apply volume orders from synced volume data to the local catalog.
+ * Called after volume-data sync so a reorder made on another device shows up here.
+ */
+export async function reconcileVolumeOrder(
+  volumeData: Record<string, VolumeData>
+): Promise<number> {
+  let changed = 0;
+
+  await db.transaction('rw', db.volumes, async () => {
+    await db.volumes.toCollection().modify((volume) => {
+      const data = volumeData[volume.volume_uuid];
+      if (!data || data.deletedOn) return;
+      if (applySyncedSortIndex(volume, data)) changed++;
+    });
+  });
+
+  return changed;
+}
@@ -0,0 +1,61 @@
+import { describe, it, expect } from 'vitest';
+import { clampToScreens, parseWindowState, type WindowState } from './window-state';
+
+const primary = { left: 0, top: 0, width: 1920, height: 1040 };
+const right = { left: 1920, top: 0, width: 2560, height: 1400 };
+
+function windowAt(overrides: Partial<WindowState> = {}): WindowState {
+  return { x: 100, y: 80, width: 1200, height: 800, maximized: false, ...overrides };
+}
+
+describe('clampToScreens', () => {
+  it('keeps a window that fits where it was', () => {
+    const state = windowAt({ x: 2200, y: 100 });
+    expect(clampToScreens(state, [primary, right], primary)).toEqual(state);
+  });
+
+  it('moves a window from a disconnected monitor onto the primary screen', () => {
+    const restored = clampToScreens(windowAt({ x: 2200, y: 100 }), [primary], primary);
+    expect(restored).toEqual({ x: 360, y: 120, width: 1200, height: 800, maximized: false });
+  });
+
+  it('shrinks a window larger than the fallback screen', () => {
+    const restored = clampToScreens(
+      windowAt({ x: 2000, width: 2400, height: 1300 }),
+      [primary],
+      primary
+    );
+    expect(restored.width).toBe(1920);
+    expect(restored.height).toBe(1040);
+    expect(restored.x).toBe(0);
+    expect(restored.y).toBe(0);
+  });
+
+  it('pulls a partly off-screen window fully onto its screen', () => {
+    const restored = clampToScreens(windowAt({ x: 1500, y: 500 }), [primary], primary);
+    expect(restored).toEqual({ x: 720, y: 240, width: 1200, height: 800, maximized: false });
+  });
+
+  it('re-maximizes on the screen the window was on', () => {
+    const restored = clampToScreens(
+      windowAt({ x: 1920, y: 0, width: 2560, height: 1400, maximized: true }),
+      [primary, right],
+      primary
+    );
+    expect(restored).toEqual({ x: 1920, y: 0, width: 2560, height: 1400, maximized: true });
+  });
+});
+
+describe('parseWindowState', () => {
+  it('reads a saved state', () => {
+    const state = windowAt();
+    expect(parseWindowState(JSON.stringify(state))).toEqual(state);
+  });
+
+  it('ignores missing or malformed data', () => {
+    expect(parseWindowState(null)).toBeNull();
+    expect(parseWindowState('not json')).toBeNull();
+    expect(parseWindowState(JSON.stringify({ x: 0, y: 0, width: 0, height: 10 }))).toBeNull();
+    expect(parseWindowState(JSON.stringify({ x: 'a', y: 0, width: 10, height: 10 }))).toBeNull();
+  });
+});
@@ -0,0 +1,227 @@
+import { browser } from '$app/environment';
+
+/**
+ * Window geometry persistence for the installed app.
+ *
+ * Browsers only let a page move or resize its own window when it runs as an
+ * installed app (standalone / window-controls-overlay), so regular tabs are
+ * left alone. The saved position is clamped to the screens available now, so
+ * a window last used on a disconnected monitor comes back on the primary one.
+ * Fullscreen can't be entered without a user gesture; the reader's
+ * "default fullscreen" setting covers that case.
+ */
+
+const WINDOW_STATE_KEY = 'window-state';
+const SAVE_DEBOUNCE_MS = 500;
+/** How much of the window must stay on a screen to count as visible there */
+const MIN_VISIBLE_PX = 100;
+
+export interface WindowState {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  maximized: boolean;
+}
+
+/** A screen's usable area (excluding taskbars/docks), in global coordinates */
+export interface ScreenArea {
+  left: number;
+  top: number;
+  width: number;
+  height: number;
+}
+
+function overlap(state: WindowState, screen: ScreenArea): { x: number; y: number } {
+  const x =
+    Math.min(state.x + state.width, screen.left + screen.width) - Math.max(state.x, screen.left);
+  const y =
+    Math.min(state.y + state.height, screen.top + screen.height) - Math.max(state.y, screen.top);
+  return { x: Math.max(0, x), y: Math.max(0, y) };
+}
+
+/**
+ * Fit a saved window onto the current screens. Keeps it on the screen it
+ * overlaps most, or moves it to `primary` if it's no longer visible anywhere,
+ * shrinking it to fit and pulling it fully on-screen.
+ */
+export function clampToScreens(
+  state: WindowState,
+  screens: ScreenArea[],
+  primary: ScreenArea
+): WindowState {
+  let target: ScreenArea | null = null;
+  let best = 0;
+  for (const screen of screens) {
+    const { x, y } = overlap(state, screen);
+    if (x >= MIN_VISIBLE_PX && y >= MIN_VISIBLE_PX && x * y > best) {
+      best = x * y;
+      target = screen;
+    }
+  }
+  const screen = target ?? primary;
+
+  if (state.maximized) {
+    return {
+      x: screen.left,
+      y: screen.top,
+      width: screen.width,
+      height: screen.height,
+      maximized: true
+    };
+  }
+
+  const width = Math.min(state.width, screen.width);
+  const height = Math.min(state.height, screen.height);
+  if (!target) {
+    // Center on the fallback screen
+    return {
+      x: screen.left + Math.round((screen.width - width) / 2),
+      y: screen.top + Math.round((screen.height - height) / 2),
+      width,
+      height,
+      maximized: false
+    };
+  }
+  return {
+    x: Math.min(Math.max(state.x, screen.left), screen.left + screen.width - width),
+    y: Math.min(Math.max(state.y, screen.top), screen.top + screen.height - height),
+    width,
+    height,
+    maximized: false
+  };
+}
+
+export function parseWindowState(raw: string | null): WindowState | null {
+  if (!raw) return null;
+  try {
+    const value = JSON.parse(raw);
+    const numbers = [value.x, value.y, value.width, value.height];
+    if (!numbers.every((n) => typeof n === 'number' && Number.isFinite(n))) return null;
+    if (value.width <= 0 || value.height <= 0) return null;
+    return {
+      x: value.x,
+      y: value.y,
+      width: value.width,
+      height: value.height,
+      maximized: value.maximized === true
+    };
+  } catch {
+    return null;
+  }
+}
+
+function isInstalledWindow(): boolean {
+  return (
+    window.matchMedia('(display-mode: standalone)').matches ||
+    window.matchMedia('(display-mode: window-controls-overlay)').matches
+  );
+}
+
+function currentScreenArea(): ScreenArea {
+  const screen = window.screen as Screen & { availLeft?: number; availTop?: number };
+  return {
+    left: screen.availLeft ?? 0,
+    top: screen.availTop ?? 0,
+    width: screen.availWidth,
+    height: screen.availHeight
+  };
+}
+
+type ScreenDetailed = ScreenArea & { isPrimary: boolean };
+
+/**
+ * All connected screens when the Window Management permission was already
+ * granted; otherwise just the one this window is on. Never prompts.
+ */
+async function availableScreens(): Promise<{ screens: ScreenArea[]; primary: ScreenArea }> {
+  const current = currentScreenArea();
+  const getScreenDetails = (
+    window as Window & {
+      getScreenDetails?: () => Promise<{
+        screens: Array<{
+          availLeft: number;
+          availTop: number;
+          availWidth: number;
+          availHeight: number;
+          isPrimary: boolean;
+        }>;
+      }>;
+    }
+  ).getScreenDetails;
+  if (!getScreenDetails) return { screens: [current], primary: current };
+
+  try {
+    const permission = await navigator.permissions.query({
+      name: 'window-management' as PermissionName
+    });
+    if (permission.state !== 'granted') return { screens: [current], primary: current };
+
+    const details = await getScreenDetails.call(window);
+    const screens: ScreenDetailed[] = details.screens.map((s) => ({
+      left: s.availLeft,
+      top: s.availTop,
+      width: s.availWidth,
+      height: s.availHeight,
+      isPrimary: s.isPrimary
+    }));
+    if (screens.length === 0) return { screens: [current], primary: current };
+    return { screens, primary: screens.find((s) => s.isPrimary) ?? screens[0] };
+  } catch {
+    return { screens: [current], primary: current };
+  }
+}
+
+function readWindowState(): WindowState {
+  const area = currentScreenArea();
+  return {
+    x: window.screenX,
+    y: window.screenY,
+    width: window.outerWidth,
+    height: window.outerHeight,
+    maximized: window.outerWidth >= area.width && window.outerHeight >= area.height
+  };
+}
+
+function saveWindowState(): void {
+  // Fullscreen geometry is the whole screen; keep the last windowed one
+  if (document.fullscreenElement) return;
+  localStorage.setItem(WINDOW_STATE_KEY, JSON.stringify(readWindowState()));
+}
+
+async function restoreWindowState(): Promise<void> {
+  const saved = parseWindowState(localStorage.getItem(WINDOW_STATE_KEY));
+  if (!saved) return;
+
+  const { screens, primary } = await availableScreens();
+  const target = clampToScreens(saved, screens, primary);
+  try {
+    window.resizeTo(target.width, target.height);
+    window.moveTo(target.x, target.y);
+  } catch (error) {
+    console.warn('Failed to restore window position:', error);
+  }
+}
+
+/**
+ * Restore the installed app's window geometry and keep saving it.
+ * Does nothing in a browser tab.
+ */
+export function initWindowStatePersistence(): void {
+  if (!browser || !isInstalledWindow()) return;
+
+  restoreWindowState().catch((error) => {
+    console.warn('Failed to restore window state:', error);
+  });
+
+  // Moving a window fires no event, so also save whenever the app is hidden or closed
+  let saveTimer: ReturnType<typeof setTimeout> | null = null;
+  window.addEventListener('resize', () => {
+    if (saveTimer) clearTimeout(saveTimer);
+    saveTimer = setTimeout(saveWindowState, SAVE_DEBOUNCE_MS);
+  });
+  window.addEventListener('pagehide', saveWindowState);
+  document.addEventListener('visibilitychange', () => {
+    if (document.visibilityState === 'hidden') saveWindowState();
+  });
+}
@@ -1,6 +1,7 @@
 // Worker pool for managing parallel downloads and decompression
 
 import { sharedMemoryManager } from './shared-memory-manager';
+import { errorHttpStatus } from './sync/core/cloud-provider-core-types';
 
 export interface VolumeMetadata {
   volumeUuid: string;
@@ -9,6 +10,8 @@ export interface VolumeMetadata {
   volumeTitle: string;
   driveModifiedTime?: string;
   driveSize?: number;
+  /** Hex MD5 reported by the provider, when it has one (Google Drive) */
+  driveMd5?: string;
 }
 
 export interface WorkerTask {
@@ -127,6 +130,16 @@ export class WorkerPool {
   }
 
   private completeTask(worker: Worker) {
+    this.releaseTask(worker);
+
+    // Process the queue to assign next tasks
+    this.processQueue();
+  }
+
+  /**
+   * Free the memory reservation and provider slot held by a worker's task
+   */
+  private releaseTask(worker: Worker) {
     const taskId = this.workerTaskMap.get(worker);
     if (taskId) {
       const task = this.activeTasks.get(taskId);
@@ -149,9 +162,6 @@ export class WorkerPool {
       this.activeTasks.delete(taskId);
       this.workerTaskMap.set(worker, null);
     }
-
-    // Process the queue to assign next tasks
-    this.processQueue();
   }
 
   private processQueue() {
@@ -256,7 +266,8 @@ export class WorkerPool {
         task.onError({
           type: 'error',
           fileId: task.id,
-          error: error instanceof Error ? error.message : 'Failed to prepare task data'
+          error: error instanceof Error ? error.message : 'Failed to prepare task data',
+          status: errorHttpStatus(error)
         });
       }
 
@@ -279,6 +290,35 @@ export class WorkerPool {
     this.processQueue();
   }
 
+  /**
+   * Cancel a queued or running task without invoking its callbacks.
+   * A running task's worker is terminated (the only way to abort its in-flight
+   * request) and replaced with a fresh one.
+   * @returns true if the task was found
+   */
+  public cancelTask(taskId: string): boolean {
+    const queuedIndex = this.taskQueue.findIndex((task) => task.id === taskId);
+    if (queuedIndex >= 0) {
+      this.taskQueue.splice(queuedIndex, 1);
+      return true;
+    }
+
+    const worker = this.workers.find((w) => this.workerTaskMap.get(w) === taskId);
+    if (!worker) {
+      return false;
+    }
+
+    worker.terminate();
+    this.releaseTask(worker);
+    this.workers = this.workers.filter((w) => w !== worker);
+    this.workerTaskMap.delete(worker);
+    this.addWorker();
+    console.log(`[${this.poolId}] Cancelled task ${taskId}`);
+
+    this.processQueue();
+    return true;
+  }
+
   public terminate() {
     // Terminate all workers
     for (const worker of this.workers) {
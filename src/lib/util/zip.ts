@@ -320,20 +320,17 @@ export async function createArchiveBlob(
     const blob = await zipWriter.close();
 
     if (processId) {
-      progressTrackerStore.updateProcess(processId, {
-        progress: 100,
-        status: 'Download ready'
-      });
+      progressTrackerStore.completeProcess(processId, 'Download ready');
       setTimeout(() => progressTrackerStore.removeProcess(processId), 3000);
     }
 
     return blob;
   } catch (error) {
     if (processId) {
-      progressTrackerStore.updateProcess(processId, {
-        progress: 0,
-        status: `Error: ${error instanceof Error ? error.message : 'Unknown error'}`
-      });
+      progressTrackerStore.failProcess(
+        processId,
+        `Error: ${error instanceof Error ? error.message : 'Unknown error'}`
+      );
       setTimeout(() => progressTrackerStore.removeProcess(processId), 5000);
     }
     throw error;
@@ -2,6 +2,7 @@ export type {
   CloudCoreProviderType as WorkerProviderType,
   CloudCoreCredentials as WorkerProviderCredentials,
   CloudCoreDownloadArgs as WorkerCloudDownloadArgs,
+  CloudCoreRangeOptions as WorkerRangeOptions,
   CloudCoreUploadArgs as WorkerCloudUploadArgs,
   CloudProviderCore as WorkerCloudProviderAdapter
 } from '$lib/util/sync/core/cloud-provider-core-types';
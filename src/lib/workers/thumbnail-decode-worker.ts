@@ -6,6 +6,8 @@
 export interface DecodeRequest {
   id: number;
   file: File;
+  /** Scale down to this height while decoding, keeping the aspect ratio */
+  resizeHeight?: number;
 }
 
 export interface DecodeResponse {
@@ -15,10 +17,12 @@ export interface DecodeResponse {
 }
 
 self.onmessage = async (event: MessageEvent<DecodeRequest>) => {
-  const { id, file } = event.data;
+  const { id, file, resizeHeight } = event.data;
 
   try {
-    const bitmap = await createImageBitmap(file);
+    const bitmap = resizeHeight
+      ? await createImageBitmap(file, { resizeHeight, resizeQuality: 'medium' })
+      : await createImageBitmap(file);
 
     // Transfer the bitmap back (zero-copy)
     self.postMessage({ id, bitmap } satisfies DecodeResponse, { transfer: [bitmap] });
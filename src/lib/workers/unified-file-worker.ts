@@ -16,8 +16,14 @@ import {
   type MokuroMetadata
 } from '$lib/util/compress-volume';
 import { matchFileToVolume } from '$lib/import/archive-extraction';
+import { DownloadIntegrityError, verifyDownloadIntegrity } from '$lib/util/download-integrity';
+import { errorHttpStatus } from '$lib/util/sync/core/cloud-provider-core-types';
 import { getWorkerCloudProvider } from './cloud-providers';
-import type { WorkerProviderCredentials, WorkerProviderType } from './cloud-providers/types';
+import type {
+  WorkerProviderCredentials,
+  WorkerProviderType,
+  WorkerRangeOptions
+} from './cloud-providers/types';
 
 // Define the worker context
 const ctx: Worker = self as any;
@@ -33,6 +39,9 @@ interface VolumeMetadata {
   volumeTitle: string;
   cloudModifiedTime?: string;
   cloudSize?: number;
+  // Sent by the download queue for integrity checking
+  driveSize?: number;
+  driveMd5?: string;
 }
 
 type ProviderCredentials = WorkerProviderCredentials;
@@ -45,6 +54,8 @@ interface DownloadAndDecompressMessage {
   fileName: string;
   credentials: ProviderCredentials;
   metadata?: VolumeMetadata;
+  /** Fetch the archive as parallel byte ranges (Google Drive) */
+  ranges?: WorkerRangeOptions;
 }
 
 interface DecompressOnlyMessage {
@@ -176,6 +187,7 @@ interface ErrorMessage {
   type: 'error';
   fileId?: string;
   error: string;
+  status?: number; // HTTP status when a provider request failed
 }
 
 interface DecompressedEntry {
@@ -419,23 +431,43 @@ ctx.addEventListener('message', async (event) => {
   try {
     if (message.mode === 'download-and-decompress') {
       // ========== DOWNLOAD AND DECOMPRESS MODE ==========
-      const { provider, fileId, fileName, credentials, metadata } = message;
+      const { provider, fileId, fileName, credentials, metadata, ranges } = message;
       console.log(`Worker: Starting download for ${fileName} (${fileId})`);
 
       const cloudProvider = getWorkerCloudProvider(provider);
-      const arrayBuffer = await cloudProvider.downloadFile({
-        fileId,
-        credentials,
-        onProgress: (loaded, total) => {
-          const progressMessage: DownloadProgressMessage = {
-            type: 'progress',
-            fileId,
-            loaded,
-            total
-          };
-          ctx.postMessage(progressMessage);
+      const download = () =>
+        cloudProvider.downloadFile({
+          fileId,
+          credentials,
+          ranges,
+          onProgress: (loaded, total) => {
+            const progressMessage: DownloadProgressMessage = {
+              type: 'progress',
+              fileId,
+              loaded,
+              total
+            };
+            ctx.postMessage(progressMessage);
+          }
+        });
+      const expected = { size: metadata?.driveSize, md5: metadata?.driveMd5 };
+
+      // A truncated or corrupted download is retried once, then rejected
+      // rather than imported as a volume with missing pages
+      let arrayBuffer = await download();
+      try {
+        verifyDownloadIntegrity(arrayBuffer, expected);
+      } catch (error) {
+        if (!(error instanceof DownloadIntegrityError)) throw error;
+        console.warn(`Worker: ${fileName} failed integrity check (${error.message}), retrying`);
+        arrayBuffer = await download();
+        try {
+          verifyDownloadIntegrity(arrayBuffer, expected);
+        } catch (retryError) {
+          if (!(retryError instanceof DownloadIntegrityError)) throw retryError;
+          throw new Error(`Download corrupted: ${retryError.message}`);
         }
-      });
+      }
 
       console.log(`Worker: Download complete for ${fileName}`);
 
@@ -884,7 +916,8 @@ ctx.addEventListener('message', async (event) => {
         (message.mode === 'download-and-decompress' || message.mode === 'decompress-only')
           ? message.fileId
           : undefined,
-      error: error instanceof Error ? error.message : String(error)
+      error: error instanceof Error ? error.message : String(error),
+      status: errorHttpStatus(error)
     };
     ctx.postMessage(errorMessage);
   }
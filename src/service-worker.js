@@ -6,7 +6,9 @@ const CACHE = `cache-${version}`;
 
 const ASSETS = [
   ...build, // the app itself
-  ...files // everything in `static`
+  // everything in `static` except the dictionary: it's tens of MB, fetched
+  // only once the popup is turned on, and kept in IndexedDB after that
+  ...files.filter((file) => !file.includes('/dictionary/'))
 ];
 
 self.addEventListener('install', (event) => {